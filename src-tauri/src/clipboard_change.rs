@@ -0,0 +1,104 @@
+// clipboard_change.rs：读取系统提供的剪贴板变化序号，让后台 watcher 只在剪贴板真正变化后才读取内容。
+// 说明：
+// 1. Windows 通过 GetClipboardSequenceNumber 读取剪贴板序号，每次写入剪贴板都会递增；
+// 2. macOS 通过 NSPasteboard 的 changeCount 读取通用剪贴板的变化计数；
+// 3. Linux 没有无需额外依赖即可查询的变化计数（X11 需要 XFixes 事件，Wayland 取决于合成器），
+//    返回 None 由 watcher 退回按轮询间隔定时读取；
+// 序号只用于判断是否需要读取，去重、跳过自身写入等逻辑仍以读取到的内容为准。
+
+use std::time::Duration;
+
+// 支持变化序号时的检查间隔：查询序号的开销远小于读取剪贴板内容，可以更频繁地检查以降低延迟
+const CHANGE_CHECK_INTERVAL: Duration = Duration::from_millis(100);
+
+// 剪贴板变化检测器，只在后台 watcher 线程中使用
+pub(crate) struct ClipboardChangeCounter {
+    // 最近一次检查时读到的序号，平台不支持或读取失败时为 None
+    current: Option<u64>,
+    // 最近一次成功读取剪贴板内容时对应的序号
+    last_read: Option<u64>,
+}
+
+impl ClipboardChangeCounter {
+    pub(crate) fn new() -> Self {
+        Self {
+            current: query_change_count(),
+            last_read: None,
+        }
+    }
+
+    // 下一次检查前的等待时间：能读到序号时按较短间隔检查，否则按轮询间隔定时读取内容
+    pub(crate) fn next_wait(&self, poll_interval: Duration) -> Duration {
+        if self.current.is_some() {
+            CHANGE_CHECK_INTERVAL
+        } else {
+            poll_interval
+        }
+    }
+
+    // 返回自上次读取内容后剪贴板是否可能发生了变化；读不到序号时一律视为已变化，交给内容去重判断
+    pub(crate) fn has_changed(&mut self) -> bool {
+        self.current = query_change_count();
+        match (self.current, self.last_read) {
+            (Some(current), Some(last_read)) => current != last_read,
+            _ => true,
+        }
+    }
+
+    // 成功读取内容后调用：记录检查时的序号，读取期间发生的变化会在下一次检查时被发现
+    pub(crate) fn mark_read(&mut self) {
+        self.last_read = self.current;
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn query_change_count() -> Option<u64> {
+    #[link(name = "user32")]
+    extern "system" {
+        fn GetClipboardSequenceNumber() -> u32;
+    }
+
+    // SAFETY：无参数调用，只返回序号；没有访问剪贴板的权限时返回 0
+    let sequence = unsafe { GetClipboardSequenceNumber() };
+    (sequence != 0).then_some(u64::from(sequence))
+}
+
+#[cfg(target_os = "macos")]
+fn query_change_count() -> Option<u64> {
+    use std::ffi::{c_char, c_void};
+
+    #[link(name = "AppKit", kind = "framework")]
+    extern "C" {}
+
+    #[link(name = "objc")]
+    extern "C" {
+        fn objc_getClass(name: *const c_char) -> *mut c_void;
+        fn sel_registerName(name: *const c_char) -> *mut c_void;
+        fn objc_msgSend();
+    }
+
+    // SAFETY：objc_msgSend 需要按实际方法签名转换后调用：generalPasteboard 返回共享的剪贴板对象
+    // （不需要释放），changeCount 返回 NSInteger；类或对象为空时直接返回，不向空指针发送消息
+    unsafe {
+        let class = objc_getClass(c"NSPasteboard".as_ptr());
+        if class.is_null() {
+            return None;
+        }
+        let send_object: unsafe extern "C" fn(*mut c_void, *mut c_void) -> *mut c_void =
+            std::mem::transmute(objc_msgSend as unsafe extern "C" fn());
+        let pasteboard = send_object(class, sel_registerName(c"generalPasteboard".as_ptr()));
+        if pasteboard.is_null() {
+            return None;
+        }
+        let send_integer: unsafe extern "C" fn(*mut c_void, *mut c_void) -> isize =
+            std::mem::transmute(objc_msgSend as unsafe extern "C" fn());
+        let count = send_integer(pasteboard, sel_registerName(c"changeCount".as_ptr()));
+        Some(count as u64)
+    }
+}
+
+// 其他平台暂未实现变化序号，watcher 按轮询间隔定时读取
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+fn query_change_count() -> Option<u64> {
+    None
+}
@@ -0,0 +1,146 @@
+// clipboard_image.rs：剪贴板图片的编码、缩略图与内容哈希。
+// 说明：
+// 1. 从剪贴板读到的是未压缩的 RGBA 像素，保存时统一编码为 PNG，并另存一份列表展示用的缩略图；
+// 2. 去重使用像素内容的哈希，与 PNG 编码参数无关，同一张截图重复复制时只累加次数；
+// 这里只处理像素数据，不访问数据库与剪贴板。
+
+use crate::error::{AppError, Reason};
+use crate::models::{ClipboardImage, IMAGE_THUMBNAIL_MAX_SIDE};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use sha2::{Digest, Sha256};
+
+// 计算图片内容的哈希（十六进制 SHA-256），宽高一并参与计算，避免像素字节相同但尺寸不同的图片被当作同一张
+pub(crate) fn image_content_hash(image: &ClipboardImage) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update((image.width as u64).to_le_bytes());
+    hasher.update((image.height as u64).to_le_bytes());
+    hasher.update(&image.rgba);
+    hex::encode(hasher.finalize())
+}
+
+// watcher 读取图片时使用的内容哈希缓存：Linux 没有剪贴板变化序号，剪贴板中只有图片时每次轮询都会读到同一张图。
+// 保留上一次读到的像素与哈希，像素完全相同时直接沿用哈希，逐字节比较远比重新计算 SHA-256 便宜；
+// 只缓存最近一张，图片变化时用新图替换
+#[derive(Default)]
+pub(crate) struct ImageHashCache {
+    last: Option<(ClipboardImage, String)>,
+}
+
+impl ImageHashCache {
+    // 返回图片内容的哈希，与 image_content_hash 的结果一致
+    pub(crate) fn content_hash(&mut self, image: &ClipboardImage) -> String {
+        if let Some((cached, hash)) = &self.last {
+            if cached.width == image.width
+                && cached.height == image.height
+                && cached.rgba == image.rgba
+            {
+                return hash.clone();
+            }
+        }
+        let hash = image_content_hash(image);
+        self.last = Some((image.clone(), hash.clone()));
+        hash
+    }
+}
+
+// watcher 去重基准与跳过标记中表示图片的键：与文本共用同一个基准，加前缀与文本区分
+pub(crate) fn image_dedup_key(hash: &str) -> String {
+    format!("image:{hash}")
+}
+
+// 图片条目的正文：只作为列表标题的说明，不参与搜索与导出，图片本身保存在 image_png 列
+pub(crate) fn image_item_label(image: &ClipboardImage) -> String {
+    format!("图片 {}×{}", image.width, image.height)
+}
+
+// 把 RGBA 像素编码为 PNG
+pub(crate) fn encode_png(image: &ClipboardImage) -> Result<Vec<u8>, AppError> {
+    let too_large = |_| AppError::Unsupported(Reason::ImageTooLarge);
+    let width = u32::try_from(image.width).map_err(too_large)?;
+    let height = u32::try_from(image.height).map_err(too_large)?;
+    let mut output = Vec::new();
+    let mut encoder = png::Encoder::new(&mut output, width, height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    let encoding_failed =
+        |err: png::EncodingError| AppError::Other(Reason::Detail(err.to_string()));
+    let mut writer = encoder.write_header().map_err(encoding_failed)?;
+    writer
+        .write_image_data(&image.rgba)
+        .map_err(encoding_failed)?;
+    writer.finish().map_err(encoding_failed)?;
+    Ok(output)
+}
+
+// 按比例缩小到最长边不超过 IMAGE_THUMBNAIL_MAX_SIDE，目标像素取源图对应区域的平均值；
+// 原图已经足够小时直接复制
+pub(crate) fn build_thumbnail(image: &ClipboardImage) -> ClipboardImage {
+    let longest = image.width.max(image.height);
+    if longest <= IMAGE_THUMBNAIL_MAX_SIDE {
+        return image.clone();
+    }
+    let width = (image.width * IMAGE_THUMBNAIL_MAX_SIDE / longest).max(1);
+    let height = (image.height * IMAGE_THUMBNAIL_MAX_SIDE / longest).max(1);
+    let mut rgba = Vec::with_capacity(width * height * 4);
+    for y in 0..height {
+        let top = y * image.height / height;
+        let bottom = ((y + 1) * image.height / height).max(top + 1);
+        for x in 0..width {
+            let left = x * image.width / width;
+            let right = ((x + 1) * image.width / width).max(left + 1);
+            let mut totals = [0u64; 4];
+            for source_y in top..bottom {
+                for source_x in left..right {
+                    let offset = (source_y * image.width + source_x) * 4;
+                    for (total, value) in totals.iter_mut().zip(&image.rgba[offset..offset + 4]) {
+                        *total += u64::from(*value);
+                    }
+                }
+            }
+            let count = ((bottom - top) * (right - left)) as u64;
+            rgba.extend(totals.iter().map(|total| (total / count) as u8));
+        }
+    }
+    ClipboardImage {
+        width,
+        height,
+        rgba,
+    }
+}
+
+// 把 PNG 数据转换为前端 <img> 可直接使用的 data URL
+pub(crate) fn png_data_url(png: &[u8]) -> String {
+    format!("data:image/png;base64,{}", STANDARD.encode(png))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn image(width: usize, height: usize, fill: u8) -> ClipboardImage {
+        ClipboardImage {
+            width,
+            height,
+            rgba: vec![fill; width * height * 4],
+        }
+    }
+
+    #[test]
+    fn hash_cache_reuses_hash_only_for_identical_pixels() {
+        let mut cache = ImageHashCache::default();
+        let first = image(2, 2, 7);
+        let hash = cache.content_hash(&first);
+        assert_eq!(hash, image_content_hash(&first));
+        assert_eq!(cache.content_hash(&first.clone()), hash);
+
+        // 同尺寸但有一个像素不同、像素字节相同但尺寸不同，都必须重新计算
+        let mut changed = first.clone();
+        changed.rgba[5] = 8;
+        assert_eq!(cache.content_hash(&changed), image_content_hash(&changed));
+        assert_ne!(cache.content_hash(&changed), hash);
+        let reshaped = image(4, 1, 7);
+        assert_eq!(cache.content_hash(&reshaped), image_content_hash(&reshaped));
+        assert_ne!(image_content_hash(&reshaped), hash);
+    }
+}
@@ -1,80 +1,584 @@
+use crate::clipboard_image::{image_dedup_key, png_data_url};
 use crate::db::{
-    get_app_setting, map_row, prune_history_by_days, set_app_setting,
-    update_clipboard_item_text_internal, upsert_clipboard_item_internal,
+    archive_items_before_internal, auto_categorize_internal, compile_exclusion_patterns,
+    compile_sensitive_path_rules, compile_user_regex, delete_expired_items, export_redacted_copy,
+    get_app_setting, history_order_by, item_expires_at, list_app_settings,
+    load_capture_filter_settings, load_category_tags, load_compiled_autotag_rules,
+    load_exclusion_patterns, load_filter_shortcuts, load_history_sort_mode, load_items,
+    load_paste_wrapper, load_remember_window_placement, load_runtime_settings,
+    load_sensitive_path_patterns, load_shortcut_bindings, load_source_blocklist, load_tray_menu,
+    load_tray_recent_count, map_row, merge_whitespace_variants_internal, move_db_internal,
+    prune_history_by_days, query_clipboard_item, query_items, reorder_items_internal,
+    reorder_tags_internal, search_archive_items, search_items_fts, set_app_setting,
+    swap_item_positions_internal, tag_query_results_internal, text_hash, unpin_stale_items,
+    update_clipboard_item_text_internal, upsert_clipboard_item_internal, CLIPBOARD_ITEM_COLUMNS,
 };
+use crate::detect::KNOWN_SCRIPTS;
+use crate::error::{active_locale, set_active_locale, AppError, Reason};
+use crate::export::{
+    build_history_markdown, build_items_atom, build_items_csv, build_items_from_template,
+    build_items_json, write_items_csv, write_items_json,
+};
+use crate::format::{build_item_preview, item_title, pretty_json, pretty_xml};
 use crate::models::{
-    AppState, ClipboardItem, ClipboardUpdateResult, ClipboardUpsertPayload,
-    HISTORY_RETENTION_DAYS, OPEN_WINDOW_SHORTCUT_KEY,
+    build_clipboard_payload, local_day_range, lock_or_recover, now_iso_string,
+    skip_next_text_ttl_ms, AppState, AutotagRule, CaptureFilter, CaptureFilterMode,
+    CaptureFilterSettings, ClipboardBroadcastPayload, ClipboardHistoryPage, ClipboardItem,
+    ClipboardItemNeighborhood, ClipboardPrunedPayload, ClipboardUpdateResult,
+    ClipboardUpsertPayload, ContentCategory, ContentType, DayActivity, EffectiveConfig,
+    ExportFormat, FilterShortcut, HistoryCursor, HistoryPage, HistorySortMode, ItemChunk, ItemKind,
+    ItemPreview, ItemTemplate, Locale, MarkdownExportOptions, MergeTextWinner, PasteTransform,
+    PasteWrapper, PinnedSortMode, QueryParams, RegexSearchField, SettingsExport, SettingsImport,
+    SettingsImportReport, ShortcutBindings, SkippedCapture, StorageLocation, TagInfo,
+    TagLatestItem, TrayLeftClickAction, TrayMenuAction, VersionInfo, WhitespaceMergeReport,
+    ARCHIVE_PATH_KEY, ARCHIVE_SEARCH_LIMIT_MAX, AUTO_UNPIN_DAYS_KEY, AUTO_UNPIN_DAYS_RANGE,
+    BLOCK_RISKY_CAPTURES_KEY, BROADCAST_BATCH_THRESHOLD_KEY, BROADCAST_BATCH_THRESHOLD_RANGE,
+    BROADCAST_RATE_WINDOW, CAPTURE_BURST_THRESHOLD_KEY, CAPTURE_BURST_THRESHOLD_RANGE,
+    CAPTURE_BURST_WINDOW, CAPTURE_FILTER_KEY, CAPTURE_FILTER_PATTERN_KEY, CATEGORY_TAGS_KEY,
+    CLIPBOARD_CLEAR_SECONDS_KEY, CLIPBOARD_CLEAR_SECONDS_RANGE, CLIPBOARD_POLL_INTERVAL_MS,
+    DB_FILE_NAME, EXCLUSION_PATTERNS_KEY, FILTER_SHORTCUTS_KEY, HISTORY_PAGE_LIMIT_MAX,
+    HISTORY_RETENTION_DAYS, HISTORY_SORT_KEY, IGNORE_OWN_WINDOW_COPIES_KEY,
+    IMPORTABLE_SETTING_KEYS, ITEM_CHUNK_MAX_CHARS, ITEM_PREVIEW_MAX_CHARS, ITEM_TTL_MINUTES_KEY,
+    ITEM_TTL_MINUTES_RANGE, ITEM_URI_PREFIX, LAST_SEEN_AT_KEY, LOCALE_KEY, MERGE_TEXT_WINNER_KEY,
+    MIN_CREATED_AT_MS, MONITORING_ENABLED_KEY, NEIGHBOR_RADIUS_MAX, OPEN_WINDOW_SHORTCUT_KEY,
+    PASTE_PREFIX_KEY, PASTE_SUFFIX_KEY, PAUSE_ON_LOCK_KEY, PINNED_SORT_KEY, POLL_INTERVAL_MS_KEY,
+    POLL_INTERVAL_MS_RANGE, REMEMBER_WINDOW_PLACEMENT_KEY, RESET_DEDUP_ON_EMPTY_KEY,
+    SANITIZE_ON_CAPTURE_KEY, SENSITIVE_PATH_PATTERNS_KEY, SETTINGS_EXPORT_VERSION,
+    SHORTCUT_TOGGLES_WINDOW_KEY, SOURCE_BLOCKLIST_KEY, TOGGLE_MONITORING_SHORTCUT_KEY,
+    TRANSIENT_RESTORE_MS_RANGE, TRAY_LEFT_CLICK_KEY, TRAY_MENU_KEY, TRAY_RECENT_COUNT_KEY,
+    TRAY_RECENT_COUNT_RANGE,
 };
-use rusqlite::params;
+use crate::normalize::{normalize_for_storage, NormalizePolicy};
+use rusqlite::types::Value;
+use rusqlite::{params, OptionalExtension};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 use std::sync::atomic::Ordering;
-use tauri::State;
+use std::time::Instant;
+use tauri::{Emitter, State};
 use tauri_plugin_autostart::ManagerExt;
+use tauri_plugin_clipboard_manager::ClipboardExt;
 
 // 命令层（commands.rs）：这里只做“参数校验 + 状态读写 + 调用 db/desktop 模块”。
 // 这样可以避免所有逻辑都挤在 lib.rs 里，同时也让未来新增命令更直观。
 
+// 广播被过期清理的条目 id：所有窗口据此同步移除，避免列表残留已不存在的数据
+pub(crate) fn emit_clipboard_pruned(app: &tauri::AppHandle, pruned_ids: Vec<String>) {
+    if pruned_ids.is_empty() {
+        return;
+    }
+    let _ = app.emit(
+        "clipboard-pruned",
+        ClipboardPrunedPayload { ids: pruned_ids },
+    );
+}
+
+// 广播到期被删除的条目 id：与过期清理一样由各窗口同步移除
+pub(crate) fn emit_clipboard_expired(app: &tauri::AppHandle, expired_ids: Vec<String>) {
+    if expired_ids.is_empty() {
+        return;
+    }
+    let _ = app.emit(
+        "clipboard-expired",
+        ClipboardPrunedPayload { ids: expired_ids },
+    );
+}
+
+// 广播被自动取消固定的条目：各窗口按更新后的条目替换本地记录，固定区随之移除
+pub(crate) fn emit_clipboard_unpinned(app: &tauri::AppHandle, items: Vec<ClipboardItem>) {
+    for item in items {
+        let _ = app.emit("clipboard-pinned", item);
+    }
+}
+
 // 启动时读取历史记录，供前端渲染并恢复状态
 #[tauri::command]
 pub fn load_clipboard_history(
+    app: tauri::AppHandle,
     state: State<AppState>,
     limit: i64,
-) -> Result<Vec<ClipboardItem>, String> {
-    let conn = state
-        .db
-        .lock()
-        .map_err(|_| "数据库连接被占用，无法读取历史记录".to_string())?;
+) -> Result<Vec<ClipboardItem>, AppError> {
+    let conn = lock_or_recover(&state.db);
     // 每次加载前先执行一次按时间清理，避免久不写入时残留过期数据
-    prune_history_by_days(&conn, HISTORY_RETENTION_DAYS).map_err(|err| err.to_string())?;
+    let pruned_ids = prune_history_by_days(&conn, HISTORY_RETENTION_DAYS)?;
+    emit_clipboard_pruned(&app, pruned_ids);
+    let expired_ids = delete_expired_items(&conn)?;
+    emit_clipboard_expired(&app, expired_ids);
+    let unpinned = unpin_stale_items(
+        &conn,
+        state.auto_unpin_days.load(Ordering::Relaxed),
+        state.item_ttl_minutes.load(Ordering::Relaxed),
+    )?;
+    emit_clipboard_unpinned(&app, unpinned);
     let cutoff = {
         // 使用与数据库一致的 RFC3339 格式作为截止时间，确保字符串比较可用
         let now = chrono::Utc::now() - chrono::Duration::days(HISTORY_RETENTION_DAYS);
         now.to_rfc3339()
     };
     let limit = limit.clamp(0, 500);
-    let mut stmt = if limit > 0 {
-        conn.prepare(
-            "
-            SELECT id, text, created_at, updated_at, pinned, count
-            FROM clipboard_items
-            WHERE pinned = 1 OR updated_at >= ?1
-            ORDER BY pinned DESC, updated_at DESC
-            LIMIT ?2
-            ",
-        )
+    let order_by = history_order_by(&conn)?;
+    load_items(
+        &conn,
+        "pinned = 1 OR updated_at >= ?",
+        &order_by,
+        vec![Value::Text(cutoff)],
+        if limit > 0 { limit } else { -1 },
+        0,
+    )
+    .map_err(AppError::from)
+}
+
+// 按偏移量分页读取历史记录，供前端无限滚动：排序与保留范围和 load_clipboard_history 一致，
+// 额外按 id 排序保证相同更新时间的条目在各页之间不重复也不遗漏；limit 为 0 时返回偏移之后的全部条目。
+// 总数与本页在同一次加锁内查询，两者对应同一份数据
+#[tauri::command]
+pub fn load_clipboard_history_page(
+    state: State<AppState>,
+    limit: i64,
+    offset: i64,
+) -> Result<ClipboardHistoryPage, AppError> {
+    let limit = match limit.clamp(0, HISTORY_PAGE_LIMIT_MAX) {
+        0 => -1,
+        value => value,
+    };
+    let offset = offset.max(0);
+    let cutoff = (chrono::Utc::now() - chrono::Duration::days(HISTORY_RETENTION_DAYS)).to_rfc3339();
+    let conn = lock_or_recover(&state.db);
+    let order_by = history_order_by(&conn)?;
+    let items = load_items(
+        &conn,
+        "pinned = 1 OR updated_at >= ?",
+        &format!("{order_by}, id DESC"),
+        vec![Value::Text(cutoff.clone())],
+        limit,
+        offset,
+    )?;
+    let total = conn.query_row(
+        "SELECT COUNT(*) FROM clipboard_items WHERE pinned = 1 OR updated_at >= ?1",
+        params![cutoff],
+        |row| row.get(0),
+    )?;
+    Ok(ClipboardHistoryPage { items, total })
+}
+
+// 以游标分页的方式按更新时间倒序读取历史记录（不区分固定条目），适合历史很长时逐页浏览：
+// 使用 (updated_at, id) 作为键集条件，翻页深度不影响查询速度；after 为空时从最新一条开始
+#[tauri::command]
+pub fn load_history_cursor(
+    state: State<AppState>,
+    after: Option<HistoryCursor>,
+    limit: i64,
+) -> Result<HistoryPage, AppError> {
+    load_history_cursor_internal(&state, after, limit)
+}
+
+pub(crate) fn load_history_cursor_internal(
+    state: &AppState,
+    after: Option<HistoryCursor>,
+    limit: i64,
+) -> Result<HistoryPage, AppError> {
+    let limit = limit.clamp(1, HISTORY_PAGE_LIMIT_MAX);
+    let conn = lock_or_recover(&state.db);
+    // 首页不加游标条件，避免 “? IS NULL OR ...” 这类条件让 SQLite 放弃走索引
+    let (condition, values) = match after {
+        Some(cursor) => (
+            "(updated_at, id) < (?, ?)",
+            vec![Value::Text(cursor.updated_at), Value::Text(cursor.id)],
+        ),
+        None => ("", Vec::new()),
+    };
+    let items = load_items(
+        &conn,
+        condition,
+        "updated_at DESC, id DESC",
+        values,
+        limit,
+        0,
+    )?;
+    // 取满一页时才可能还有下一页；恰好取完时下一次请求会返回空页
+    let next_cursor = if items.len() as i64 == limit {
+        items.last().map(|item| HistoryCursor {
+            updated_at: item.updated_at.clone(),
+            id: item.id.clone(),
+        })
     } else {
-        conn.prepare(
-            "
-            SELECT id, text, created_at, updated_at, pinned, count
+        None
+    };
+    Ok(HistoryPage { items, next_cursor })
+}
+
+// 与 load_history_cursor 方向相反：读取比游标 (updated_at_ms, id) 更新的至多 limit 条记录，按更新时间正序返回，
+// 便于列表向上滚动时依次插到顶部。游标条目仍存在时使用其完整精度的更新时间，避免毫秒截断后把它自己也算作“更新”
+#[tauri::command]
+pub fn load_history_before(
+    state: State<AppState>,
+    updated_at_ms: i64,
+    id: String,
+    limit: i64,
+) -> Result<Vec<ClipboardItem>, AppError> {
+    let limit = limit.clamp(1, HISTORY_PAGE_LIMIT_MAX);
+    let cursor_time = chrono::DateTime::from_timestamp_millis(updated_at_ms)
+        .ok_or(AppError::InvalidInput(Reason::InvalidCursor))?;
+    let conn = lock_or_recover(&state.db);
+    let stored_updated_at = conn
+        .query_row(
+            "SELECT updated_at FROM clipboard_items WHERE id = ?1",
+            params![id],
+            |row| row.get::<_, String>(0),
+        )
+        .optional()?
+        .filter(|value| {
+            chrono::DateTime::parse_from_rfc3339(value)
+                .is_ok_and(|time| time.timestamp_millis() == updated_at_ms)
+        });
+    let cursor_updated_at = stored_updated_at.unwrap_or_else(|| cursor_time.to_rfc3339());
+    load_items(
+        &conn,
+        "(updated_at, id) > (?, ?)",
+        "updated_at ASC, id ASC",
+        vec![Value::Text(cursor_updated_at), Value::Text(id)],
+        limit,
+        0,
+    )
+    .map_err(AppError::from)
+}
+
+// 读取最近一次复制发生在指定本地日期（YYYY-MM-DD）的全部条目及数量，按更新时间倒序；
+// 存储的时间可能带不同的时区写法，用 julianday 统一换算后再比较
+#[tauri::command]
+pub fn get_activity_for_day(state: State<AppState>, date: String) -> Result<DayActivity, AppError> {
+    let (start, end) = local_day_range(&date)?;
+    let conn = lock_or_recover(&state.db);
+    let mut stmt = conn.prepare(&format!(
+        "
+            SELECT {CLIPBOARD_ITEM_COLUMNS}
             FROM clipboard_items
-            WHERE pinned = 1 OR updated_at >= ?1
-            ORDER BY pinned DESC, updated_at DESC
-            ",
+            WHERE julianday(updated_at) >= julianday(?1)
+              AND julianday(updated_at) < julianday(?2)
+            ORDER BY updated_at DESC
+            "
+    ))?;
+    let rows = stmt.query_map(params![start, end], map_row)?;
+    let items = rows.collect::<Result<Vec<_>, _>>()?;
+    Ok(DayActivity {
+        date: date.trim().to_string(),
+        count: items.len(),
+        items,
+    })
+}
+
+// 统计首次捕获时间落在 [from_ms, to_ms) 内的条目数量（毫秒时间戳），用于“本周 / 本月新增”等汇总；
+// 按 created_at 统计，重复复制同一文本只会刷新 updated_at，不会被重复计入
+#[tauri::command]
+pub fn count_captured_between(
+    state: State<AppState>,
+    from_ms: i64,
+    to_ms: i64,
+) -> Result<i64, AppError> {
+    if from_ms > to_ms {
+        return Err(AppError::InvalidInput(Reason::StatsRangeReversed));
+    }
+    let [from, to] = [from_ms, to_ms].map(|ms| {
+        chrono::DateTime::from_timestamp_millis(ms)
+            .map(|time| time.to_rfc3339())
+            .ok_or(AppError::InvalidInput(Reason::InvalidStatsRange))
+    });
+    let (from, to) = (from?, to?);
+    let conn = lock_or_recover(&state.db);
+    // 与其他按时间筛选的查询一致使用 julianday 比较，兼容不同时区偏移写入的时间文本
+    conn.query_row(
+        "
+        SELECT COUNT(*)
+        FROM clipboard_items
+        WHERE julianday(created_at) >= julianday(?1)
+          AND julianday(created_at) < julianday(?2)
+        ",
+        params![from, to],
+        |row| row.get::<_, i64>(0),
+    )
+    .map_err(AppError::from)
+}
+
+// 获取条目的单行预览（最多 max_chars 个字符）及类型提示，列表渲染统一使用后端生成的结果
+#[tauri::command]
+pub fn get_item_preview(
+    state: State<AppState>,
+    id: String,
+    max_chars: usize,
+) -> Result<ItemPreview, AppError> {
+    let (text, content_type) = {
+        let conn = lock_or_recover(&state.db);
+        conn.query_row(
+            "SELECT text, content_type FROM clipboard_items WHERE id = ?1",
+            params![id],
+            |row| Ok((row.get::<_, String>(0)?, row.get::<_, Option<String>>(1)?)),
+        )
+        .optional()?
+        .ok_or(AppError::NotFound)?
+    };
+    Ok(build_item_preview(
+        &text,
+        content_type.as_deref(),
+        max_chars.min(ITEM_PREVIEW_MAX_CHARS),
+    ))
+}
+
+// 分段读取条目文本，供查看器按滚动逐步加载几 MB 的大条目，避免一次 IPC 传输整段文本造成界面卡顿。
+// offset 与 len 按字符计算，下一段的 offset 为本段起点加上返回的字符数
+#[tauri::command]
+pub fn read_item_chunk(
+    state: State<AppState>,
+    id: String,
+    offset: i64,
+    len: i64,
+) -> Result<ItemChunk, AppError> {
+    if offset < 0 {
+        return Err(AppError::InvalidInput(Reason::NegativeOffset));
+    }
+    let len = len.clamp(1, ITEM_CHUNK_MAX_CHARS);
+    let conn = lock_or_recover(&state.db);
+    // SQLite 的 substr / length 对 TEXT 按字符计算，只把需要的一段取出，且不会截断多字节字符
+    let (chunk, total) = conn
+        .query_row(
+            "SELECT substr(text, ?2 + 1, ?3), length(text) FROM clipboard_items WHERE id = ?1",
+            params![id, offset, len],
+            |row| Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?)),
+        )
+        .optional()?
+        .ok_or(AppError::NotFound)?;
+    Ok(ItemChunk {
+        eof: offset + len >= total,
+        chunk,
+    })
+}
+
+// 按指定格式美化条目文本（JSON / XML）并返回结果，不修改已保存的内容，由前端决定是否复制
+#[tauri::command]
+pub fn format_item(
+    state: State<AppState>,
+    id: String,
+    kind: ContentType,
+) -> Result<String, AppError> {
+    let text = {
+        let conn = lock_or_recover(&state.db);
+        conn.query_row(
+            "SELECT text FROM clipboard_items WHERE id = ?1",
+            params![id],
+            |row| row.get::<_, String>(0),
         )
+        .optional()?
+        .ok_or(AppError::NotFound)?
+    };
+    match kind {
+        ContentType::Json => pretty_json(&text),
+        ContentType::Xml => pretty_xml(&text),
     }
-    .map_err(|err| err.to_string())?;
-    let rows = if limit > 0 {
-        stmt.query_map(params![cutoff, limit], map_row)
-    } else {
-        stmt.query_map(params![cutoff], map_row)
+}
+
+// 按主要文字体系筛选条目（如只看中文或只看英文），limit 为 0 表示不限制条数
+#[tauri::command]
+pub fn load_by_script(
+    state: State<AppState>,
+    script: String,
+    limit: i64,
+) -> Result<Vec<ClipboardItem>, AppError> {
+    if !KNOWN_SCRIPTS.contains(&script.as_str()) {
+        return Err(AppError::InvalidInput(Reason::UnknownScript(script)));
     }
-    .map_err(|err| err.to_string())?;
-    let mut items = Vec::new();
+    let limit = limit.clamp(0, 500);
+    let conn = lock_or_recover(&state.db);
+    let order_by = history_order_by(&conn)?;
+    load_items(
+        &conn,
+        "script = ?",
+        &order_by,
+        vec![Value::Text(script)],
+        if limit > 0 { limit } else { -1 },
+        0,
+    )
+    .map_err(AppError::from)
+}
+
+// 只读取包含换行的多行条目（代码片段、段落等），与按内容类型筛选互补；limit 为 0 表示不限制条数
+#[tauri::command]
+pub fn load_multiline_items(
+    state: State<AppState>,
+    limit: i64,
+) -> Result<Vec<ClipboardItem>, AppError> {
+    let limit = limit.clamp(0, 500);
+    let conn = lock_or_recover(&state.db);
+    let order_by = history_order_by(&conn)?;
+    // Windows 换行 \r\n 同样包含 \n，只需匹配 char(10)
+    load_items(
+        &conn,
+        "text LIKE '%' || char(10) || '%'",
+        &order_by,
+        Vec::new(),
+        if limit > 0 { limit } else { -1 },
+        0,
+    )
+    .map_err(AppError::from)
+}
+
+// 只读取带有指定标签的条目，供标签栏作为快捷筛选；标签不存在时返回空列表，limit 为 0 表示不限制条数
+#[tauri::command]
+pub fn load_history_by_tag(
+    state: State<AppState>,
+    tag: String,
+    limit: i64,
+) -> Result<Vec<ClipboardItem>, AppError> {
+    let limit = limit.clamp(0, 500);
+    let conn = lock_or_recover(&state.db);
+    let order_by = history_order_by(&conn)?;
+    load_items(
+        &conn,
+        "EXISTS (
+            SELECT 1 FROM item_tags
+            WHERE item_tags.item_id = clipboard_items.id AND item_tags.tag = ?
+        )",
+        &order_by,
+        vec![Value::Text(tag.trim().to_string())],
+        if limit > 0 { limit } else { -1 },
+        0,
+    )
+    .map_err(AppError::from)
+}
+
+// 读取本次启动后复制过的条目（含再次复制的旧内容），供“本次会话”视图使用，避免前端自行做时间换算
+#[tauri::command]
+pub fn load_session_items(state: State<AppState>) -> Result<Vec<ClipboardItem>, AppError> {
+    let conn = lock_or_recover(&state.db);
+    let order_by = history_order_by(&conn)?;
+    load_items(
+        &conn,
+        "updated_at >= ?",
+        &order_by,
+        vec![Value::Text(state.session_started_at.clone())],
+        -1,
+        0,
+    )
+    .map_err(AppError::from)
+}
+
+// 读取指定条目及其前后各 radius 条记录（按当前历史排序），靠近列表两端时对应一侧会少于 radius 条
+#[tauri::command]
+pub fn load_around(
+    state: State<AppState>,
+    id: String,
+    radius: i64,
+) -> Result<ClipboardItemNeighborhood, AppError> {
+    let radius = radius.clamp(0, NEIGHBOR_RADIUS_MAX);
+    let conn = lock_or_recover(&state.db);
+    let order_by = history_order_by(&conn)?;
+    // 先按历史排序为每条记录编号，再取目标编号前后 radius 范围内的记录
+    let mut stmt = conn
+        .prepare(&format!(
+            "
+            WITH ordered AS (
+                SELECT {CLIPBOARD_ITEM_COLUMNS}, ROW_NUMBER() OVER (ORDER BY {order_by}) AS row_index
+                FROM clipboard_items
+            ),
+            target AS (
+                SELECT row_index FROM ordered WHERE id = ?1
+            )
+            SELECT {CLIPBOARD_ITEM_COLUMNS}
+            FROM ordered
+            WHERE row_index BETWEEN (SELECT row_index FROM target) - ?2
+                AND (SELECT row_index FROM target) + ?2
+            ORDER BY row_index
+            "
+        ))?;
+    let rows = stmt.query_map(params![id, radius], map_row)?;
+    let mut before = Vec::new();
+    let mut item = None;
+    let mut after = Vec::new();
     for row in rows {
-        items.push(row.map_err(|err| err.to_string())?);
+        let entry = row?;
+        if item.is_some() {
+            after.push(entry);
+        } else if entry.id == id {
+            item = Some(entry);
+        } else {
+            before.push(entry);
+        }
     }
-    Ok(items)
+    let item = item.ok_or(AppError::NotFound)?;
+    Ok(ClipboardItemNeighborhood {
+        before,
+        item,
+        after,
+    })
+}
+
+// 查找与当前系统剪贴板内容相同的已保存条目：按与写入相同的去重规则（首尾空白忽略）匹配，找不到返回 None
+#[tauri::command]
+pub fn find_by_current_clipboard(
+    state: State<AppState>,
+) -> Result<Option<ClipboardItem>, AppError> {
+    #[cfg(desktop)]
+    let content = crate::desktop::read_clipboard_text()?;
+    #[cfg(not(desktop))]
+    let content = String::new();
+    let normalized = normalize_for_storage(&content, NormalizePolicy::capture(&state));
+    if normalized.is_empty() {
+        return Ok(None);
+    }
+    let conn = lock_or_recover(&state.db);
+    conn.query_row(
+        &format!("SELECT {CLIPBOARD_ITEM_COLUMNS} FROM clipboard_items WHERE text_hash = ?1"),
+        params![text_hash(&normalized.display_text)],
+        map_row,
+    )
+    .optional()
+    .map_err(AppError::from)
+}
+
+// 立即记录当前系统剪贴板内容（手动快照）：不受监听开关与捕获过滤的限制，剪贴板为空时返回 None。
+// 写入后同步更新去重基准，避免监听开启时 watcher 把同一内容再记录一次
+#[tauri::command]
+pub fn capture_now(
+    app: tauri::AppHandle,
+    state: State<AppState>,
+) -> Result<Option<ClipboardItem>, AppError> {
+    #[cfg(desktop)]
+    let content = crate::desktop::read_clipboard_text()?;
+    #[cfg(not(desktop))]
+    let content = String::new();
+    let normalized = normalize_for_storage(&content, NormalizePolicy::capture(&state));
+    if normalized.is_empty() {
+        return Ok(None);
+    }
+    let outcome = upsert_clipboard_item_internal(
+        &state,
+        build_clipboard_payload(content),
+        HISTORY_RETENTION_DAYS,
+    )?;
+    *lock_or_recover(&state.last_clipboard_text) = Some(normalized.dedup_key);
+    let _ = app.emit(
+        "clipboard-updated",
+        ClipboardBroadcastPayload {
+            item: outcome.item.clone(),
+            merged_id: None,
+        },
+    );
+    emit_clipboard_pruned(&app, outcome.pruned_ids);
+    emit_clipboard_unpinned(&app, outcome.unpinned);
+    Ok(Some(outcome.item))
 }
 
 // 前端或后台调用的命令入口，实际逻辑由内部函数统一处理
 #[tauri::command]
 pub fn upsert_clipboard_item(
+    app: tauri::AppHandle,
     state: State<AppState>,
     item: ClipboardUpsertPayload,
     max_items: i64,
-) -> Result<ClipboardItem, String> {
+) -> Result<ClipboardItem, AppError> {
     // 兼容旧参数名 max_items，但实际含义已改为“保留天数”
-    upsert_clipboard_item_internal(&state, item, max_items)
+    let outcome = upsert_clipboard_item_internal(&state, item, max_items)?;
+    emit_clipboard_pruned(&app, outcome.pruned_ids);
+    emit_clipboard_unpinned(&app, outcome.unpinned);
+    Ok(outcome.item)
 }
 
 // 更新条目文本，若文本重复则合并计数并删除旧条目
@@ -84,7 +588,7 @@ pub fn update_clipboard_item_text(
     id: String,
     text: String,
     updated_at: String,
-) -> Result<ClipboardUpdateResult, String> {
+) -> Result<ClipboardUpdateResult, AppError> {
     update_clipboard_item_text_internal(&state, id, text, updated_at)
 }
 
@@ -94,161 +598,2326 @@ pub fn set_clipboard_item_pinned(
     state: State<AppState>,
     id: String,
     pinned: bool,
-) -> Result<ClipboardItem, String> {
-    let conn = state
-        .db
-        .lock()
-        .map_err(|_| "数据库连接被占用，无法更新固定状态".to_string())?;
+) -> Result<ClipboardItem, AppError> {
+    let conn = lock_or_recover(&state.db);
+    // 新固定的条目排在已手动排序的固定条目最前面并记录固定时间，同时清除到期时间；
+    // 取消固定时清除位置与固定时间，避免残留影响下次固定，并按默认存活时间重新计时；
+    // 固定状态未变化时保持原值
     conn.execute(
-        "UPDATE clipboard_items SET pinned = ?1 WHERE id = ?2",
-        params![if pinned { 1 } else { 0 }, id],
-    )
-    .map_err(|err| err.to_string())?;
-    let persisted = conn
-        .query_row(
-            "
-            SELECT id, text, created_at, updated_at, pinned, count
-            FROM clipboard_items
-            WHERE id = ?1
-            ",
-            params![id],
-            map_row,
-        )
-        .map_err(|err| err.to_string())?;
+        "
+        UPDATE clipboard_items
+        SET pinned = ?1,
+            position = CASE
+                WHEN ?1 = 0 THEN NULL
+                WHEN pinned = 1 THEN position
+                ELSE (SELECT MIN(position) - 1 FROM clipboard_items WHERE pinned = 1)
+            END,
+            pinned_at = CASE
+                WHEN ?1 = 0 THEN NULL
+                WHEN pinned = 1 THEN pinned_at
+                ELSE ?3
+            END,
+            expires_at = CASE
+                WHEN ?1 = 1 THEN NULL
+                WHEN pinned = 1 THEN ?4
+                ELSE expires_at
+            END
+        WHERE id = ?2
+        ",
+        params![
+            if pinned { 1 } else { 0 },
+            id,
+            now_iso_string(),
+            item_expires_at(state.item_ttl_minutes.load(Ordering::Relaxed))
+        ],
+    )?;
+    let persisted = query_clipboard_item(&conn, &id)?;
     Ok(persisted)
 }
 
+// 交换两个固定条目的位置并返回更新后的两条记录，供“上移/下移”这类增量操作使用
+#[tauri::command]
+pub fn swap_item_positions(
+    state: State<AppState>,
+    id_a: String,
+    id_b: String,
+) -> Result<Vec<ClipboardItem>, AppError> {
+    swap_item_positions_internal(&state, &id_a, &id_b)
+}
+
+// 管理性修正条目的创建时间：用于修复导入数据后错误的时间线，与普通文本编辑路径分离
+#[tauri::command]
+pub fn set_item_created_at(
+    state: State<AppState>,
+    id: String,
+    created_at_ms: i64,
+) -> Result<ClipboardItem, AppError> {
+    // 只接受 2000 年之后且不晚于当前时间的时间戳，避免误传秒级时间戳或未来时间破坏排序与清理
+    let created_at = chrono::DateTime::from_timestamp_millis(created_at_ms)
+        .filter(|value| value.timestamp_millis() >= MIN_CREATED_AT_MS)
+        .filter(|value| *value <= chrono::Utc::now())
+        .ok_or(AppError::InvalidInput(Reason::InvalidCreatedAt))?;
+    let conn = lock_or_recover(&state.db);
+    let updated = conn.execute(
+        "UPDATE clipboard_items SET created_at = ?1 WHERE id = ?2",
+        params![created_at.to_rfc3339(), id],
+    )?;
+    if updated == 0 {
+        return Err(AppError::NotFound);
+    }
+    query_clipboard_item(&conn, &id).map_err(AppError::from)
+}
+
 // 删除单条记录：后端删除后不返回数据，前端只需同步移除即可
 #[tauri::command]
-pub fn delete_clipboard_item(state: State<AppState>, id: String) -> Result<(), String> {
-    let conn = state
-        .db
-        .lock()
-        .map_err(|_| "数据库连接被占用，无法删除条目".to_string())?;
-    conn.execute("DELETE FROM clipboard_items WHERE id = ?1", params![id])
-        .map_err(|err| err.to_string())?;
+pub fn delete_clipboard_item(state: State<AppState>, id: String) -> Result<(), AppError> {
+    let conn = lock_or_recover(&state.db);
+    conn.execute("DELETE FROM clipboard_items WHERE id = ?1", params![id])?;
     Ok(())
 }
 
 // 清空全部历史记录：用于“清空历史”按钮对应操作
 #[tauri::command]
-pub fn clear_clipboard_history(state: State<AppState>) -> Result<(), String> {
-    let conn = state
-        .db
-        .lock()
-        .map_err(|_| "数据库连接被占用，无法清空历史记录".to_string())?;
-    conn.execute("DELETE FROM clipboard_items", [])
-        .map_err(|err| err.to_string())?;
+pub fn clear_clipboard_history(state: State<AppState>) -> Result<(), AppError> {
+    let conn = lock_or_recover(&state.db);
+    conn.execute("DELETE FROM clipboard_items", [])?;
     Ok(())
 }
 
-// 切换后台剪贴板监听开关：该开关只影响 watcher 是否持续轮询剪贴板，不影响已保存的历史记录
+// 导出脱敏后的数据库副本，便于反馈问题时附带：正文只保留长度，备注、图片、来源应用与含原文的设置被清除，
+// 时间、计数等元数据保持不变；
+// 返回被脱敏的条目数量
 #[tauri::command]
-pub fn set_clipboard_monitoring(state: State<AppState>, enabled: bool) -> Result<(), String> {
-    state.monitoring_enabled.store(enabled, Ordering::Relaxed);
-    Ok(())
+pub fn export_support_bundle(state: State<AppState>, path: String) -> Result<usize, AppError> {
+    let target = path.trim();
+    if target.is_empty() {
+        return Err(AppError::InvalidInput(Reason::EmptyExportPath));
+    }
+    // VACUUM INTO 要求目标文件不存在，这里提前检查以给出更明确的提示，也避免覆盖用户文件
+    if Path::new(target).exists() {
+        return Err(AppError::InvalidInput(Reason::TargetExists(
+            target.to_string(),
+        )));
+    }
+    let conn = lock_or_recover(&state.db);
+    export_redacted_copy(&conn, target).map_err(|err| {
+        // 导出中途失败时删除不完整的副本，避免留下含有原文的文件
+        let _ = std::fs::remove_file(target);
+        AppError::from(err)
+    })
 }
 
-// 获取当前监听状态：供设置页初始化时对齐开关状态
+// 把完整历史（含固定状态、次数与时间）导出为 JSON 文件，便于在应用之外备份；
+// 按历史列表的顺序逐条写入，不会把全部条目一次性读入内存。返回写入的条目数量
 #[tauri::command]
-pub fn get_clipboard_monitoring(state: State<AppState>) -> Result<bool, String> {
-    Ok(state.monitoring_enabled.load(Ordering::Relaxed))
+pub fn export_history(state: State<AppState>, path: String) -> Result<usize, AppError> {
+    export_items_to_file(&state, &path, None, |writer, items| {
+        write_items_json(writer, items)
+    })
 }
 
-// 标记下一次要跳过的剪贴板文本：防止应用自身写入导致后台 watcher 重复计数
+// 把完整历史导出为 CSV 文件，便于用表格软件分析：列为 id,text,created_at,updated_at,pinned,count,tags，
+// 按复制次数从多到少排列，最常用的片段在最前面。返回写入的行数（不含表头）
 #[tauri::command]
-pub fn mark_clipboard_skip(state: State<AppState>, text: String) -> Result<(), String> {
-    let mut skip_lock = state
-        .skip_next_text
-        .lock()
-        .map_err(|_| "监听状态被占用，无法更新跳过内容".to_string())?;
-    let mut last_lock = state
-        .last_clipboard_text
-        .lock()
-        .map_err(|_| "监听状态被占用，无法更新最近内容".to_string())?;
-    let trimmed = text.trim();
-    if trimmed.is_empty() {
-        return Ok(());
+pub fn export_history_csv(state: State<AppState>, path: String) -> Result<usize, AppError> {
+    export_items_to_file(
+        &state,
+        &path,
+        Some("count DESC, updated_at DESC, id"),
+        |writer, items| write_items_csv(writer, items),
+    )
+}
+
+// 按给定排序（未指定时与历史列表一致）把全部文本条目逐条写入新建的导出文件，不会把全部条目一次性读入内存；
+// 图片条目的正文只是尺寸说明，不写入导出文本。
+// 与支持包一致不覆盖已有文件，避免误选路径时冲掉之前的备份，写入中途失败时删除不完整的文件
+fn export_items_to_file<F>(
+    state: &AppState,
+    path: &str,
+    order_by: Option<&str>,
+    write: F,
+) -> Result<usize, AppError>
+where
+    F: FnOnce(
+        &mut std::io::BufWriter<std::fs::File>,
+        &mut dyn Iterator<Item = Result<ClipboardItem, AppError>>,
+    ) -> Result<usize, AppError>,
+{
+    let target = path.trim();
+    if target.is_empty() {
+        return Err(AppError::InvalidInput(Reason::EmptyExportPath));
     }
-    *skip_lock = Some(trimmed.to_string());
-    *last_lock = Some(trimmed.to_string());
-    Ok(())
+    if Path::new(target).exists() {
+        return Err(AppError::InvalidInput(Reason::TargetExists(
+            target.to_string(),
+        )));
+    }
+    let conn = lock_or_recover(&state.db);
+    let order_by = match order_by {
+        Some(order_by) => order_by.to_string(),
+        None => history_order_by(&conn)?,
+    };
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {CLIPBOARD_ITEM_COLUMNS} FROM clipboard_items WHERE kind = 'text' ORDER BY {order_by}"
+    ))?;
+    let mut rows = stmt
+        .query_map([], map_row)?
+        .map(|row| row.map_err(AppError::from));
+    let file = std::fs::File::create(target)?;
+    let mut writer = std::io::BufWriter::new(file);
+    let result = write(&mut writer, &mut rows);
+    drop(writer);
+    if result.is_err() {
+        let _ = std::fs::remove_file(target);
+    }
+    result
 }
 
-// 获取当前系统开机自启动状态：供设置页初始化使用
+// 把指定时间之前（毫秒时间戳）的未固定条目移入归档库，保持在线历史表精简；
+// 归档路径会被记住，供 search_archive 使用。返回被归档的条目数量，并通知前端移除这些条目
 #[tauri::command]
-pub fn get_autostart_status(app: tauri::AppHandle) -> Result<bool, String> {
-    let manager = app.autolaunch();
-    manager.is_enabled().map_err(|err| err.to_string())
+pub fn archive_items_before(
+    app: tauri::AppHandle,
+    state: State<AppState>,
+    before_ms: i64,
+    archive_path: String,
+) -> Result<usize, AppError> {
+    let cutoff = chrono::DateTime::from_timestamp_millis(before_ms)
+        .ok_or(AppError::InvalidInput(Reason::InvalidArchiveCutoff))?
+        .to_rfc3339();
+    let archive_path = archive_path.trim().to_string();
+    if archive_path.is_empty() {
+        return Err(AppError::InvalidInput(Reason::EmptyArchivePath));
+    }
+    // 归档到当前数据库自身会在同一文件里重复建表，直接拦截
+    let live_path = lock_or_recover(&state.db_path).clone();
+    let same_file = match (
+        std::fs::canonicalize(&archive_path),
+        std::fs::canonicalize(&live_path),
+    ) {
+        (Ok(archive), Ok(live)) => archive == live,
+        _ => false,
+    };
+    if same_file {
+        return Err(AppError::InvalidInput(Reason::ArchiveIsLiveDb));
+    }
+    let archived_ids = {
+        let mut conn = lock_or_recover(&state.db);
+        let archived_ids = archive_items_before_internal(&mut conn, &cutoff, &archive_path)?;
+        set_app_setting(&conn, ARCHIVE_PATH_KEY, Some(archive_path))?;
+        archived_ids
+    };
+    let count = archived_ids.len();
+    emit_clipboard_pruned(&app, archived_ids);
+    Ok(count)
 }
 
-// 切换系统开机自启动状态：返回实际结果，避免前端显示与系统真实状态不一致
+// 全文检索在线历史：走 FTS5 索引，不必把整段历史加载到前端再过滤；空关键词返回空列表
 #[tauri::command]
-pub fn set_autostart_enabled(app: tauri::AppHandle, enabled: bool) -> Result<bool, String> {
-    let manager = app.autolaunch();
-    if enabled {
-        manager.enable().map_err(|err| err.to_string())?;
-    } else {
-        manager.disable().map_err(|err| err.to_string())?;
+pub fn search_clipboard_history(
+    state: State<AppState>,
+    query: String,
+    limit: i64,
+) -> Result<Vec<ClipboardItem>, AppError> {
+    let conn = lock_or_recover(&state.db);
+    search_items_fts(&conn, &query, limit.clamp(1, HISTORY_PAGE_LIMIT_MAX)).map_err(AppError::from)
+}
+
+// 在归档库中搜索条目（只读），尚未归档过时返回空列表；结果不会出现在在线历史中，需由前端单独展示
+#[tauri::command]
+pub fn search_archive(
+    state: State<AppState>,
+    query: String,
+    limit: i64,
+) -> Result<Vec<ClipboardItem>, AppError> {
+    let query = query.trim();
+    if query.is_empty() {
+        return Ok(Vec::new());
     }
-    manager.is_enabled().map_err(|err| err.to_string())
+    let archive_path = {
+        let conn = lock_or_recover(&state.db);
+        get_app_setting(&conn, ARCHIVE_PATH_KEY)?
+    };
+    let Some(archive_path) = archive_path else {
+        return Ok(Vec::new());
+    };
+    search_archive_items(
+        &archive_path,
+        query,
+        limit.clamp(1, ARCHIVE_SEARCH_LIMIT_MAX),
+    )
+    .map_err(AppError::from)
 }
 
-// 读取打开主窗口的快捷键设置：供设置页初始化展示
+// 按正则搜索历史记录，只在指定字段上匹配（正文、标题或备注），按更新时间倒序返回至多 limit 条；
+// 正则与自动打标签规则一样限制编译体积，避免过于复杂的表达式拖慢搜索
 #[tauri::command]
-pub fn get_open_window_shortcut(state: State<AppState>) -> Result<Option<String>, String> {
-    let conn = state
-        .db
-        .lock()
-        .map_err(|_| "数据库连接被占用，无法读取快捷键设置".to_string())?;
-    get_app_setting(&conn, OPEN_WINDOW_SHORTCUT_KEY).map_err(|err| err.to_string())
+pub fn regex_search_field(
+    state: State<AppState>,
+    field: RegexSearchField,
+    pattern: String,
+    limit: i64,
+) -> Result<Vec<ClipboardItem>, AppError> {
+    regex_search_field_internal(&state, field, &pattern, limit)
 }
 
-// 更新打开主窗口的快捷键设置：同步更新数据库并注册/取消全局快捷键（desktop 下生效）
+pub(crate) fn regex_search_field_internal(
+    state: &AppState,
+    field: RegexSearchField,
+    pattern: &str,
+    limit: i64,
+) -> Result<Vec<ClipboardItem>, AppError> {
+    if pattern.is_empty() {
+        return Err(AppError::InvalidInput(Reason::EmptyRegex));
+    }
+    let regex = compile_user_regex(pattern)?;
+    let limit = limit.clamp(1, HISTORY_PAGE_LIMIT_MAX) as usize;
+    let conn = lock_or_recover(&state.db);
+    // 图片条目的正文只是尺寸说明，按正文与标题搜索时排除；没有备注的条目不可能命中，按备注搜索时同样直接排除
+    let condition = match field {
+        RegexSearchField::Text | RegexSearchField::Title => "WHERE kind = 'text'",
+        RegexSearchField::Note => "WHERE note IS NOT NULL",
+    };
+    // 正则无法交给 SQLite 过滤，逐行匹配并在取满后停止读取
+    let mut stmt = conn.prepare(&format!(
+        "
+        SELECT {CLIPBOARD_ITEM_COLUMNS}
+        FROM clipboard_items
+        {condition}
+        ORDER BY updated_at DESC, id DESC
+        "
+    ))?;
+    let rows = stmt.query_map([], map_row)?;
+    let mut items = Vec::new();
+    for row in rows {
+        let item = row?;
+        let target = match field {
+            RegexSearchField::Text => item.text.as_str(),
+            RegexSearchField::Title => item_title(&item.text),
+            RegexSearchField::Note => item.note.as_deref().unwrap_or_default(),
+        };
+        if regex.is_match(target) {
+            items.push(item);
+            if items.len() == limit {
+                break;
+            }
+        }
+    }
+    Ok(items)
+}
+
+// 设置条目的备注：去掉首尾空白后为空（或传 None）时清除备注，返回更新后的条目
 #[tauri::command]
-pub fn set_open_window_shortcut(
+pub fn set_item_note(
+    state: State<AppState>,
+    id: String,
+    note: Option<String>,
+) -> Result<ClipboardItem, AppError> {
+    set_item_note_internal(&state, &id, note)
+}
+
+pub(crate) fn set_item_note_internal(
+    state: &AppState,
+    id: &str,
+    note: Option<String>,
+) -> Result<ClipboardItem, AppError> {
+    let note = note
+        .map(|note| note.trim().to_string())
+        .filter(|note| !note.is_empty());
+    let conn = lock_or_recover(&state.db);
+    let updated = conn.execute(
+        "UPDATE clipboard_items SET note = ?1 WHERE id = ?2",
+        params![note, id],
+    )?;
+    if updated == 0 {
+        return Err(AppError::NotFound);
+    }
+    Ok(query_clipboard_item(&conn, id)?)
+}
+
+// 一次性合并仅空白不同的重复条目，适合清理旧版本严格去重时积累下来的变体；返回合并报告，
+// 并通过已有事件让各窗口移除被合并的条目、刷新保留条目的计数与固定状态
+#[tauri::command]
+pub fn merge_whitespace_variants(
     app: tauri::AppHandle,
     state: State<AppState>,
-    shortcut: Option<String>,
-) -> Result<Option<String>, String> {
-    let normalized = shortcut
-        .map(|value| value.trim().to_string())
-        .filter(|value| !value.is_empty());
-    let previous = {
-        let conn = state
-            .db
-            .lock()
-            .map_err(|_| "数据库连接被占用，无法读取快捷键设置".to_string())?;
-        get_app_setting(&conn, OPEN_WINDOW_SHORTCUT_KEY).map_err(|err| err.to_string())?
-    };
-    if previous == normalized {
-        return Ok(normalized);
+) -> Result<WhitespaceMergeReport, AppError> {
+    let report = merge_whitespace_variants_internal(&state)?;
+    emit_clipboard_pruned(
+        &app,
+        report
+            .groups
+            .iter()
+            .flat_map(|group| group.merged_ids.iter().cloned())
+            .collect(),
+    );
+    for item in &report.items {
+        let _ = app.emit(
+            "clipboard-updated",
+            ClipboardBroadcastPayload {
+                item: item.clone(),
+                merged_id: None,
+            },
+        );
     }
-    #[cfg(desktop)]
-    {
-        crate::desktop::update_open_window_shortcut(
-            &app,
-            previous.as_deref(),
-            normalized.as_deref(),
-        )?;
+    Ok(report)
+}
+
+// 把历史记录导出为 Markdown 文档，便于整批粘贴到笔记或 wiki；指定 ids 时只导出这些条目，图片条目不导出
+#[tauri::command]
+pub fn export_history_markdown(
+    state: State<AppState>,
+    options: MarkdownExportOptions,
+) -> Result<String, AppError> {
+    let conn = lock_or_recover(&state.db);
+    let order_by = history_order_by(&conn)?;
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {CLIPBOARD_ITEM_COLUMNS} FROM clipboard_items WHERE kind = 'text' ORDER BY {order_by}"
+    ))?;
+    let rows = stmt.query_map([], map_row)?;
+    let mut items = Vec::new();
+    for row in rows {
+        let item = row?;
+        let selected = match &options.ids {
+            Some(ids) => ids.contains(&item.id),
+            None => true,
+        };
+        if selected {
+            items.push(item);
+        }
     }
-    {
-        let conn = state
-            .db
-            .lock()
-            .map_err(|_| "数据库连接被占用，无法写入快捷键设置".to_string())?;
-        set_app_setting(&conn, OPEN_WINDOW_SHORTCUT_KEY, normalized.clone())
-            .map_err(|err| err.to_string())?;
+    Ok(build_history_markdown(&items, &options, &now_iso_string()))
+}
+
+// 按组合条件导出查询结果（如某个标签下上个月的条目），支持 JSON / CSV / Markdown，返回导出文本；
+// 与其他导出一致不含图片条目
+#[tauri::command]
+pub fn export_query_results(
+    state: State<AppState>,
+    query: QueryParams,
+    format: ExportFormat,
+) -> Result<String, AppError> {
+    let conn = lock_or_recover(&state.db);
+    let mut items = query_items(&conn, &query)?;
+    items.retain(|item| item.kind == ItemKind::Text);
+    match format {
+        ExportFormat::Json => build_items_json(&items),
+        ExportFormat::Csv => build_items_csv(&items),
+        ExportFormat::Markdown => {
+            let options = MarkdownExportOptions {
+                include_titles: true,
+                include_tags: true,
+                include_timestamps: true,
+                ..MarkdownExportOptions::default()
+            };
+            Ok(build_history_markdown(&items, &options, &now_iso_string()))
+        }
     }
-    Ok(normalized)
 }
 
-// 打开设置窗口：由后端统一创建/复用窗口，避免前端重复实现多窗口逻辑
+// 按模板拼接选中的条目（如生成列表字面量、switch 分支），按传入 id 的顺序输出，返回可直接复制的文本；
+// 选中的图片条目没有可拼接的正文，直接跳过
 #[tauri::command]
-pub fn open_settings_window_command(app: tauri::AppHandle) -> Result<(), String> {
-    #[cfg(desktop)]
-    {
-        crate::desktop::open_settings_window(&app);
+pub fn render_items_template(
+    state: State<AppState>,
+    ids: Vec<String>,
+    template: ItemTemplate,
+) -> Result<String, AppError> {
+    if ids.is_empty() {
+        return Err(AppError::InvalidInput(Reason::NoItemsSelected));
+    }
+    if template.body.is_empty() {
+        return Err(AppError::InvalidInput(Reason::EmptyTemplate));
+    }
+    let conn = lock_or_recover(&state.db);
+    let mut items = Vec::with_capacity(ids.len());
+    for id in &ids {
+        let item = query_clipboard_item(&conn, id)
+            .optional()?
+            .ok_or(AppError::NotFound)?;
+        if item.kind == ItemKind::Text {
+            items.push(item);
+        }
+    }
+    Ok(build_items_from_template(&items, &template))
+}
+
+// 给符合组合条件的全部条目批量加上标签（如把所有包含 localhost 的条目标记为 dev），返回新加上标签的条目数
+#[tauri::command]
+pub fn tag_query_results(
+    state: State<AppState>,
+    query: QueryParams,
+    tag: String,
+) -> Result<usize, AppError> {
+    let tag = tag.trim().to_string();
+    if tag.is_empty() {
+        return Err(AppError::InvalidInput(Reason::EmptyTag));
+    }
+    let conn = lock_or_recover(&state.db);
+    tag_query_results_internal(&conn, &query, &tag)
+}
+
+// 导出最近更新的文本条目为 Atom 订阅文档，供阅读器或自动化工具读取；按更新时间倒序，不区分固定
+#[tauri::command]
+pub fn export_recent_feed(state: State<AppState>, limit: i64) -> Result<String, AppError> {
+    let limit = limit.clamp(1, HISTORY_PAGE_LIMIT_MAX);
+    let conn = lock_or_recover(&state.db);
+    let mut stmt = conn.prepare(&format!(
+        "
+            SELECT {CLIPBOARD_ITEM_COLUMNS}
+            FROM clipboard_items
+            WHERE kind = 'text'
+            ORDER BY updated_at DESC, id DESC
+            LIMIT ?1
+            "
+    ))?;
+    let items = stmt
+        .query_map(params![limit], map_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(build_items_atom(&items, &now_iso_string()))
+}
+
+// 把当前全部条目标记为已读：记录查看时间，主窗口获得焦点时调用
+#[tauri::command]
+pub fn mark_all_seen(state: State<AppState>) -> Result<(), AppError> {
+    let conn = lock_or_recover(&state.db);
+    set_app_setting(&conn, LAST_SEEN_AT_KEY, Some(now_iso_string())).map_err(AppError::from)
+}
+
+// 统计上次查看之后新增或再次复制的条目数量，供未读角标使用；从未查看过时视为没有未读
+#[tauri::command]
+pub fn count_unseen(state: State<AppState>) -> Result<i64, AppError> {
+    let conn = lock_or_recover(&state.db);
+    let Some(last_seen_at) = get_app_setting(&conn, LAST_SEEN_AT_KEY)? else {
+        return Ok(0);
+    };
+    conn.query_row(
+        "SELECT COUNT(*) FROM clipboard_items WHERE updated_at > ?1",
+        params![last_seen_at],
+        |row| row.get(0),
+    )
+    .map_err(AppError::from)
+}
+
+// 切换后台剪贴板监听开关：该开关只影响 watcher 是否持续轮询剪贴板，不影响已保存的历史记录
+#[tauri::command]
+pub fn set_clipboard_monitoring(
+    app: tauri::AppHandle,
+    state: State<AppState>,
+    enabled: bool,
+) -> Result<(), AppError> {
+    {
+        let conn = lock_or_recover(&state.db);
+        set_app_setting(&conn, MONITORING_ENABLED_KEY, Some(enabled.to_string()))?;
+    }
+    state.monitoring_enabled.store(enabled, Ordering::Relaxed);
+    // 托盘菜单中的监听开关文字、托盘图标与提示文字随状态变化，刷新失败不影响开关本身
+    #[cfg(desktop)]
+    {
+        let _ = crate::desktop::refresh_tray_menu(&app);
+        let _ = crate::desktop::apply_tray_monitoring_state(&app, enabled);
+    }
+    #[cfg(not(desktop))]
+    let _ = app;
+    Ok(())
+}
+
+// 获取当前监听状态：供设置页初始化时对齐开关状态
+#[tauri::command]
+pub fn get_clipboard_monitoring(state: State<AppState>) -> Result<bool, AppError> {
+    Ok(state.monitoring_enabled.load(Ordering::Relaxed))
+}
+
+// 把条目写回系统剪贴板：默认先执行条目自身的粘贴变换，再按设置添加前缀 / 后缀；raw 为 true 时写入原文，
+// 存储的内容保持不变。返回实际写入剪贴板的文本
+#[tauri::command]
+pub fn copy_item_to_clipboard(
+    app: tauri::AppHandle,
+    state: State<AppState>,
+    id: String,
+    raw: Option<bool>,
+) -> Result<String, AppError> {
+    let output = item_clipboard_text(&state, &id, raw.unwrap_or(false))?;
+    write_text_to_clipboard(&app, &state, &output)?;
+    Ok(output)
+}
+
+// 把图片条目保存的原图写回系统剪贴板，便于再次粘贴历史中的截图；条目不是图片时返回错误
+#[tauri::command]
+pub fn copy_image_item_to_clipboard(
+    app: tauri::AppHandle,
+    state: State<AppState>,
+    id: String,
+) -> Result<(), AppError> {
+    let (kind, content_hash, png) = {
+        let conn = lock_or_recover(&state.db);
+        conn.query_row(
+            "SELECT kind, text_hash, image_png FROM clipboard_items WHERE id = ?1",
+            params![id],
+            |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, Option<Vec<u8>>>(2)?,
+                ))
+            },
+        )
+        .optional()?
+        .ok_or(AppError::NotFound)?
+    };
+    let (ItemKind::Image, Some(png)) = (ItemKind::from_column(&kind), png) else {
+        return Err(AppError::Unsupported(Reason::NotAnImage));
+    };
+    write_image_to_clipboard(&app, &state, &png, &content_hash)
+}
+
+// 读取图片条目的缩略图（PNG 的 data URL），文本条目返回 None。缩略图不随列表返回，
+// 避免每次读取历史都对全部图片做 base64 编码，由前端在条目显示时按需加载
+#[tauri::command]
+pub fn get_item_thumbnail(state: State<AppState>, id: String) -> Result<Option<String>, AppError> {
+    let conn = lock_or_recover(&state.db);
+    let thumbnail = conn
+        .query_row(
+            "SELECT thumbnail_png FROM clipboard_items WHERE id = ?1",
+            params![id],
+            |row| row.get::<_, Option<Vec<u8>>>(0),
+        )
+        .optional()?
+        .ok_or(AppError::NotFound)?;
+    Ok(thumbnail.map(|png| png_data_url(&png)))
+}
+
+// 临时粘贴：把条目写入剪贴板，延迟 restore_after_ms 后恢复写入前的剪贴板内容，
+// 期间用户复制了其他内容则不恢复。返回实际写入剪贴板的文本
+#[tauri::command]
+pub fn paste_transient(
+    app: tauri::AppHandle,
+    state: State<AppState>,
+    id: String,
+    restore_after_ms: u64,
+) -> Result<String, AppError> {
+    let restore_after_ms = restore_after_ms.clamp(
+        *TRANSIENT_RESTORE_MS_RANGE.start(),
+        *TRANSIENT_RESTORE_MS_RANGE.end(),
+    );
+    let output = item_clipboard_text(&state, &id, false)?;
+    #[cfg(desktop)]
+    {
+        crate::desktop::write_transient_text(
+            &app,
+            &state,
+            &output,
+            std::time::Duration::from_millis(restore_after_ms),
+        )?;
+    }
+    #[cfg(not(desktop))]
+    {
+        let _ = restore_after_ms;
+        write_text_to_clipboard(&app, &state, &output)?;
+    }
+    Ok(output)
+}
+
+// 生成条目写回剪贴板时的文本：默认先执行条目自身的粘贴变换，再按设置添加前缀 / 后缀，raw 为 true 时返回原文
+fn item_clipboard_text(state: &AppState, id: &str, raw: bool) -> Result<String, AppError> {
+    let (text, transform, wrapper) = {
+        let conn = lock_or_recover(&state.db);
+        let (text, transform, kind) = conn
+            .query_row(
+                "SELECT text, transform, kind FROM clipboard_items WHERE id = ?1",
+                params![id],
+                |row| {
+                    Ok((
+                        row.get::<_, String>(0)?,
+                        row.get::<_, Option<String>>(1)?,
+                        row.get::<_, String>(2)?,
+                    ))
+                },
+            )
+            .optional()?
+            .ok_or(AppError::NotFound)?;
+        if ItemKind::from_column(&kind) == ItemKind::Image {
+            return Err(AppError::Unsupported(Reason::ImageNeedsImageCopy));
+        }
+        let wrapper = load_paste_wrapper(&conn)?;
+        (text, transform, wrapper)
+    };
+    if raw {
+        return Ok(text);
+    }
+    let transformed = match transform {
+        Some(spec) => serde_json::from_str::<PasteTransform>(&spec)
+            .map_err(|_| AppError::InvalidInput(Reason::InvalidPasteTransform))?
+            .apply(&text, chrono::Local::now()),
+        None => text,
+    };
+    Ok(wrapper.apply(&transformed))
+}
+
+// 设置条目的粘贴变换（传 None 或不含任何变换时清除），返回实际保存的设置
+#[tauri::command]
+pub fn set_item_transform(
+    state: State<AppState>,
+    id: String,
+    spec: Option<PasteTransform>,
+) -> Result<Option<PasteTransform>, AppError> {
+    let spec = spec.filter(|spec| !spec.is_noop());
+    let value = spec.as_ref().map(serde_json::to_string).transpose()?;
+    let conn = lock_or_recover(&state.db);
+    let updated = conn.execute(
+        "UPDATE clipboard_items SET transform = ?1 WHERE id = ?2",
+        params![value, id],
+    )?;
+    if updated == 0 {
+        return Err(AppError::NotFound);
+    }
+    Ok(spec)
+}
+
+// 由后端直接写入剪贴板：先标记跳过再写入，避免 watcher 把应用自身的写入当作新复制，并按设置安排自动清空
+pub(crate) fn write_text_to_clipboard(
+    app: &tauri::AppHandle,
+    state: &AppState,
+    text: &str,
+) -> Result<(), AppError> {
+    mark_skip_text(state, text)?;
+    app.clipboard().write_text(text)?;
+    #[cfg(desktop)]
+    {
+        crate::desktop::schedule_clipboard_clear(app, text);
+    }
+    Ok(())
+}
+
+// 把图片条目保存的 PNG 写入剪贴板，并以内容哈希标记跳过，watcher 读到这张图片时不会重复计数；
+// 自动清空剪贴板只针对文本，写入图片不会安排清空
+fn write_image_to_clipboard(
+    app: &tauri::AppHandle,
+    state: &AppState,
+    png: &[u8],
+    content_hash: &str,
+) -> Result<(), AppError> {
+    let image = tauri::image::Image::from_bytes(png)?;
+    let key = image_dedup_key(content_hash);
+    *lock_or_recover(&state.skip_next_text) = Some((key.clone(), Instant::now()));
+    *lock_or_recover(&state.last_clipboard_text) = Some(key);
+    app.clipboard().write_image(&image).map_err(AppError::from)
+}
+
+// 清空系统剪贴板：部分平台（如 Linux 的部分剪贴板管理器）不支持直接清空，失败时退回写入空字符串；
+// 两种结果在 watcher 中都读到空内容而被忽略。同时重置最近内容与跳过标记，
+// 让用户清空后再次复制与之前相同的文本时仍能被正常记录
+#[tauri::command]
+pub fn clear_system_clipboard(
+    app: tauri::AppHandle,
+    state: State<AppState>,
+) -> Result<(), AppError> {
+    {
+        let mut skip_lock = lock_or_recover(&state.skip_next_text);
+        let mut last_lock = lock_or_recover(&state.last_clipboard_text);
+        *skip_lock = None;
+        *last_lock = None;
+    }
+    // 使尚未到期的自动清空任务失效，剪贴板已经是空的，无需再次处理
+    state
+        .clipboard_clear_generation
+        .fetch_add(1, Ordering::Relaxed);
+    if app.clipboard().clear().is_err() {
+        app.clipboard()
+            .write_text("")
+            .map_err(|err| AppError::Other(Reason::ClipboardClearFailed(err.to_string())))?;
+    }
+    Ok(())
+}
+
+// 获取写回剪贴板时使用的前缀 / 后缀：供设置页初始化使用
+#[tauri::command]
+pub fn get_paste_wrapper(state: State<AppState>) -> Result<PasteWrapper, AppError> {
+    let conn = lock_or_recover(&state.db);
+    load_paste_wrapper(&conn).map_err(AppError::from)
+}
+
+// 设置写回剪贴板时使用的前缀 / 后缀：保留用户输入的空白与换行，空字符串表示不添加
+#[tauri::command]
+pub fn set_paste_wrapper(
+    state: State<AppState>,
+    prefix: String,
+    suffix: String,
+) -> Result<PasteWrapper, AppError> {
+    let conn = lock_or_recover(&state.db);
+    set_app_setting(
+        &conn,
+        PASTE_PREFIX_KEY,
+        Some(prefix.clone()).filter(|value| !value.is_empty()),
+    )?;
+    set_app_setting(
+        &conn,
+        PASTE_SUFFIX_KEY,
+        Some(suffix.clone()).filter(|value| !value.is_empty()),
+    )?;
+    Ok(PasteWrapper { prefix, suffix })
+}
+
+// 标记下一次要跳过的剪贴板文本：防止应用自身写入导致后台 watcher 重复计数；
+// 前端每次写入剪贴板后都会调用，因此也在这里安排自动清空（desktop 下生效）
+#[tauri::command]
+pub fn mark_clipboard_skip(
+    app: tauri::AppHandle,
+    state: State<AppState>,
+    text: String,
+) -> Result<(), AppError> {
+    mark_skip_text(&state, &text)?;
+    #[cfg(desktop)]
+    {
+        crate::desktop::schedule_clipboard_clear(&app, &text);
+    }
+    Ok(())
+}
+
+// 记录应用自身即将写入剪贴板的文本，前端命令与后端直接写入（如托盘动作）共用
+pub(crate) fn mark_skip_text(state: &AppState, text: &str) -> Result<(), AppError> {
+    let mut skip_lock = lock_or_recover(&state.skip_next_text);
+    let mut last_lock = lock_or_recover(&state.last_clipboard_text);
+    let normalized = normalize_for_storage(text, NormalizePolicy::capture(state));
+    if normalized.is_empty() {
+        return Ok(());
+    }
+    *skip_lock = Some((normalized.dedup_key.clone(), Instant::now()));
+    *last_lock = Some(normalized.dedup_key);
+    Ok(())
+}
+
+// 读取最近被 watcher 跳过的捕获及原因（最新的在前），用于解释“复制了却没有出现在历史中”
+#[tauri::command]
+pub fn get_recent_skips(state: State<AppState>) -> Result<Vec<SkippedCapture>, AppError> {
+    let skips = lock_or_recover(&state.recent_skips);
+    Ok(skips.iter().rev().cloned().collect())
+}
+
+// 读取 watcher 初始化或读取剪贴板失败的累计次数，前端可据此提示“捕获异常”
+#[tauri::command]
+pub fn get_capture_error_count(state: State<AppState>) -> Result<u64, AppError> {
+    Ok(state.capture_error_count.load(Ordering::Relaxed))
+}
+
+// 清零读取失败计数，用户确认或处理完异常后调用，之后的增长即为新出现的错误
+#[tauri::command]
+pub fn reset_capture_error_count(state: State<AppState>) -> Result<(), AppError> {
+    state.capture_error_count.store(0, Ordering::Relaxed);
+    Ok(())
+}
+
+// 获取应用写入剪贴板后自动清空的延迟秒数，未开启时返回 None
+#[tauri::command]
+pub fn get_clipboard_auto_clear(state: State<AppState>) -> Result<Option<u64>, AppError> {
+    let seconds = state.clipboard_clear_seconds.load(Ordering::Relaxed);
+    Ok((seconds > 0).then_some(seconds))
+}
+
+// 设置应用写入剪贴板后自动清空的延迟秒数（传 None 关闭），只影响之后的写入
+#[tauri::command]
+pub fn set_clipboard_auto_clear(
+    state: State<AppState>,
+    seconds: Option<u64>,
+) -> Result<Option<u64>, AppError> {
+    if let Some(seconds) = seconds {
+        if !CLIPBOARD_CLEAR_SECONDS_RANGE.contains(&seconds) {
+            return Err(AppError::InvalidInput(Reason::ClipboardClearSecondsRange(
+                *CLIPBOARD_CLEAR_SECONDS_RANGE.start(),
+                *CLIPBOARD_CLEAR_SECONDS_RANGE.end(),
+            )));
+        }
+    }
+    {
+        let conn = lock_or_recover(&state.db);
+        set_app_setting(
+            &conn,
+            CLIPBOARD_CLEAR_SECONDS_KEY,
+            seconds.map(|value| value.to_string()),
+        )?;
+    }
+    state
+        .clipboard_clear_seconds
+        .store(seconds.unwrap_or(0), Ordering::Relaxed);
+    Ok(seconds)
+}
+
+// 获取固定条目自动取消固定的天数，未开启时返回 None
+#[tauri::command]
+pub fn get_auto_unpin_days(state: State<AppState>) -> Result<Option<u64>, AppError> {
+    let days = state.auto_unpin_days.load(Ordering::Relaxed);
+    Ok((days > 0).then_some(days))
+}
+
+// 设置固定条目自动取消固定的天数（传 None 关闭），保存后立即按新设置处理一次已有的固定条目
+#[tauri::command]
+pub fn set_auto_unpin_days(
+    app: tauri::AppHandle,
+    state: State<AppState>,
+    days: Option<u64>,
+) -> Result<Option<u64>, AppError> {
+    if let Some(days) = days {
+        if !AUTO_UNPIN_DAYS_RANGE.contains(&days) {
+            return Err(AppError::InvalidInput(Reason::AutoUnpinDaysRange(
+                *AUTO_UNPIN_DAYS_RANGE.start(),
+                *AUTO_UNPIN_DAYS_RANGE.end(),
+            )));
+        }
+    }
+    let unpinned = {
+        let conn = lock_or_recover(&state.db);
+        set_app_setting(
+            &conn,
+            AUTO_UNPIN_DAYS_KEY,
+            days.map(|value| value.to_string()),
+        )?;
+        unpin_stale_items(
+            &conn,
+            days.unwrap_or(0),
+            state.item_ttl_minutes.load(Ordering::Relaxed),
+        )?
+    };
+    state
+        .auto_unpin_days
+        .store(days.unwrap_or(0), Ordering::Relaxed);
+    emit_clipboard_unpinned(&app, unpinned);
+    Ok(days)
+}
+
+// 获取新捕获条目默认存活的分钟数，未开启时返回 None
+#[tauri::command]
+pub fn get_item_ttl_minutes(state: State<AppState>) -> Result<Option<u64>, AppError> {
+    let minutes = state.item_ttl_minutes.load(Ordering::Relaxed);
+    Ok((minutes > 0).then_some(minutes))
+}
+
+// 设置新捕获条目默认存活的分钟数（传 None 关闭），只影响之后捕获或取消固定的条目，
+// 已有条目的到期时间保持不变
+#[tauri::command]
+pub fn set_item_ttl_minutes(
+    state: State<AppState>,
+    minutes: Option<u64>,
+) -> Result<Option<u64>, AppError> {
+    if let Some(minutes) = minutes {
+        if !ITEM_TTL_MINUTES_RANGE.contains(&minutes) {
+            return Err(AppError::InvalidInput(Reason::ItemTtlMinutesRange(
+                *ITEM_TTL_MINUTES_RANGE.start(),
+                *ITEM_TTL_MINUTES_RANGE.end(),
+            )));
+        }
+    }
+    {
+        let conn = lock_or_recover(&state.db);
+        set_app_setting(
+            &conn,
+            ITEM_TTL_MINUTES_KEY,
+            minutes.map(|value| value.to_string()),
+        )?;
+    }
+    state
+        .item_ttl_minutes
+        .store(minutes.unwrap_or(0), Ordering::Relaxed);
+    Ok(minutes)
+}
+
+// 获取同一文本高频重复捕获时的计数上限，未开启时返回 None
+#[tauri::command]
+pub fn get_capture_burst_threshold(state: State<AppState>) -> Result<Option<u64>, AppError> {
+    let threshold = state.capture_burst_threshold.load(Ordering::Relaxed);
+    Ok((threshold > 0).then_some(threshold))
+}
+
+// 设置同一文本在统计窗口内最多累加的次数（传 None 关闭），超出后只刷新更新时间
+#[tauri::command]
+pub fn set_capture_burst_threshold(
+    state: State<AppState>,
+    threshold: Option<u64>,
+) -> Result<Option<u64>, AppError> {
+    if let Some(threshold) = threshold {
+        if !CAPTURE_BURST_THRESHOLD_RANGE.contains(&threshold) {
+            return Err(AppError::InvalidInput(Reason::CaptureBurstThresholdRange(
+                *CAPTURE_BURST_THRESHOLD_RANGE.start(),
+                *CAPTURE_BURST_THRESHOLD_RANGE.end(),
+            )));
+        }
+    }
+    {
+        let conn = lock_or_recover(&state.db);
+        set_app_setting(
+            &conn,
+            CAPTURE_BURST_THRESHOLD_KEY,
+            threshold.map(|value| value.to_string()),
+        )?;
+    }
+    state
+        .capture_burst_threshold
+        .store(threshold.unwrap_or(0), Ordering::Relaxed);
+    // 上限变化后旧窗口内的计数不再有意义，从零开始统计
+    lock_or_recover(&state.capture_bursts).clear();
+    Ok(threshold)
+}
+
+// 获取 watcher 改为合并广播的频率阈值：未开启时返回 None，供设置页初始化使用
+#[tauri::command]
+pub fn get_broadcast_batch_threshold(state: State<AppState>) -> Result<Option<u64>, AppError> {
+    let threshold = state.broadcast_batch_threshold.load(Ordering::Relaxed);
+    Ok((threshold > 0).then_some(threshold))
+}
+
+// 设置 watcher 改为合并广播的频率阈值（传 None 关闭）：统计窗口内广播次数超过阈值后，
+// 新捕获的条目每隔一段时间通过 clipboard-updated-batch 一次性广播，watcher 下一次轮询即生效
+#[tauri::command]
+pub fn set_broadcast_batch_threshold(
+    state: State<AppState>,
+    threshold: Option<u64>,
+) -> Result<Option<u64>, AppError> {
+    if let Some(threshold) = threshold {
+        if !BROADCAST_BATCH_THRESHOLD_RANGE.contains(&threshold) {
+            return Err(AppError::InvalidInput(
+                Reason::BroadcastBatchThresholdRange(
+                    *BROADCAST_BATCH_THRESHOLD_RANGE.start(),
+                    *BROADCAST_BATCH_THRESHOLD_RANGE.end(),
+                ),
+            ));
+        }
+    }
+    {
+        let conn = lock_or_recover(&state.db);
+        set_app_setting(
+            &conn,
+            BROADCAST_BATCH_THRESHOLD_KEY,
+            threshold.map(|value| value.to_string()),
+        )?;
+    }
+    state
+        .broadcast_batch_threshold
+        .store(threshold.unwrap_or(0), Ordering::Relaxed);
+    Ok(threshold)
+}
+
+// 获取后台轮询剪贴板的间隔（毫秒），供设置页初始化使用
+#[tauri::command]
+pub fn get_poll_interval(state: State<AppState>) -> Result<u64, AppError> {
+    Ok(state.poll_interval_ms.load(Ordering::Relaxed))
+}
+
+// 设置后台轮询剪贴板的间隔（传 None 恢复默认值），超出允许范围时按边界保存；
+// watcher 每次轮询前读取该值，修改后下一次轮询即生效，无需重启监听。返回实际生效的间隔
+#[tauri::command]
+pub fn set_poll_interval(
+    state: State<AppState>,
+    interval_ms: Option<u64>,
+) -> Result<u64, AppError> {
+    let interval_ms = interval_ms.map(|value| {
+        value.clamp(
+            *POLL_INTERVAL_MS_RANGE.start(),
+            *POLL_INTERVAL_MS_RANGE.end(),
+        )
+    });
+    {
+        let conn = lock_or_recover(&state.db);
+        set_app_setting(
+            &conn,
+            POLL_INTERVAL_MS_KEY,
+            interval_ms.map(|value| value.to_string()),
+        )?;
+    }
+    let interval_ms = interval_ms.unwrap_or(CLIPBOARD_POLL_INTERVAL_MS);
+    state.poll_interval_ms.store(interval_ms, Ordering::Relaxed);
+    Ok(interval_ms)
+}
+
+// 获取后台捕获过滤设置：供设置页初始化使用
+#[tauri::command]
+pub fn get_capture_filter(state: State<AppState>) -> Result<CaptureFilterSettings, AppError> {
+    let conn = lock_or_recover(&state.db);
+    load_capture_filter_settings(&conn).map_err(AppError::from)
+}
+
+// 更新后台捕获过滤设置：自定义正则模式下必须提供可编译的正则，保存后立即对 watcher 生效
+#[tauri::command]
+pub fn set_capture_filter(
+    state: State<AppState>,
+    mode: CaptureFilterMode,
+    pattern: Option<String>,
+) -> Result<CaptureFilterSettings, AppError> {
+    let pattern = pattern
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty());
+    let regex = match (mode, pattern.as_deref()) {
+        (CaptureFilterMode::CustomRegex, Some(pattern)) => Some(compile_user_regex(pattern)?),
+        (CaptureFilterMode::CustomRegex, None) => {
+            return Err(AppError::InvalidInput(Reason::CustomFilterNeedsRegex));
+        }
+        _ => None,
+    };
+    {
+        let conn = lock_or_recover(&state.db);
+        set_app_setting(
+            &conn,
+            CAPTURE_FILTER_KEY,
+            Some(mode.as_setting().to_string()),
+        )?;
+        // 非自定义模式下也保留正则原文，便于用户切换回来时无需重新输入
+        set_app_setting(&conn, CAPTURE_FILTER_PATTERN_KEY, pattern.clone())?;
+    }
+    let mut filter = lock_or_recover(&state.capture_filter);
+    *filter = CaptureFilter { mode, regex };
+    Ok(CaptureFilterSettings { mode, pattern })
+}
+
+// 获取锁屏期间是否自动暂停记录：供设置页初始化使用
+#[tauri::command]
+pub fn get_pause_on_lock(state: State<AppState>) -> Result<bool, AppError> {
+    Ok(state.pause_on_lock.load(Ordering::Relaxed))
+}
+
+// 设置锁屏期间是否自动暂停记录，watcher 下一次轮询即按新设置执行
+#[tauri::command]
+pub fn set_pause_on_lock(state: State<AppState>, enabled: bool) -> Result<bool, AppError> {
+    {
+        let conn = lock_or_recover(&state.db);
+        set_app_setting(&conn, PAUSE_ON_LOCK_KEY, Some(enabled.to_string()))?;
+    }
+    state.pause_on_lock.store(enabled, Ordering::Relaxed);
+    Ok(enabled)
+}
+
+// 获取剪贴板变为空时是否重置去重基准：供设置页初始化使用
+#[tauri::command]
+pub fn get_reset_dedup_on_empty(state: State<AppState>) -> Result<bool, AppError> {
+    Ok(state.reset_dedup_on_empty.load(Ordering::Relaxed))
+}
+
+// 设置剪贴板变为空时是否重置去重基准：关闭后清空再复制相同文本会被当作重复内容忽略
+#[tauri::command]
+pub fn set_reset_dedup_on_empty(state: State<AppState>, enabled: bool) -> Result<bool, AppError> {
+    {
+        let conn = lock_or_recover(&state.db);
+        set_app_setting(&conn, RESET_DEDUP_ON_EMPTY_KEY, Some(enabled.to_string()))?;
+    }
+    state.reset_dedup_on_empty.store(enabled, Ordering::Relaxed);
+    Ok(enabled)
+}
+
+// 获取是否忽略在应用自身窗口内复制的内容：供设置页初始化使用
+#[tauri::command]
+pub fn get_ignore_own_window_copies(state: State<AppState>) -> Result<bool, AppError> {
+    Ok(state.ignore_own_window_copies.load(Ordering::Relaxed))
+}
+
+// 设置是否忽略在应用自身窗口内复制的内容：关闭后在列表或编辑框中选中复制的文本也会写入历史
+#[tauri::command]
+pub fn set_ignore_own_window_copies(
+    state: State<AppState>,
+    enabled: bool,
+) -> Result<bool, AppError> {
+    {
+        let conn = lock_or_recover(&state.db);
+        set_app_setting(
+            &conn,
+            IGNORE_OWN_WINDOW_COPIES_KEY,
+            Some(enabled.to_string()),
+        )?;
+    }
+    state
+        .ignore_own_window_copies
+        .store(enabled, Ordering::Relaxed);
+    Ok(enabled)
+}
+
+// 获取捕获时是否清理排版字符（弯引号、不换行空格、零宽字符）：供设置页初始化使用
+#[tauri::command]
+pub fn get_sanitize_on_capture(state: State<AppState>) -> Result<bool, AppError> {
+    Ok(state.sanitize_on_capture.load(Ordering::Relaxed))
+}
+
+// 设置捕获时是否清理排版字符：只影响之后捕获的内容，已保存的条目保持不变
+#[tauri::command]
+pub fn set_sanitize_on_capture(state: State<AppState>, enabled: bool) -> Result<bool, AppError> {
+    {
+        let conn = lock_or_recover(&state.db);
+        set_app_setting(&conn, SANITIZE_ON_CAPTURE_KEY, Some(enabled.to_string()))?;
+    }
+    state.sanitize_on_capture.store(enabled, Ordering::Relaxed);
+    Ok(enabled)
+}
+
+// 获取敏感路径规则原文：供设置页初始化使用
+#[tauri::command]
+pub fn get_sensitive_path_patterns(state: State<AppState>) -> Result<Vec<String>, AppError> {
+    let conn = lock_or_recover(&state.db);
+    load_sensitive_path_patterns(&conn).map_err(AppError::from)
+}
+
+// 更新敏感路径规则：忽略空白规则，全部规则都能编译才保存，保存后立即对 watcher 生效；
+// 传入空列表表示不再检查，传入 None 表示恢复内置规则
+#[tauri::command]
+pub fn set_sensitive_path_patterns(
+    state: State<AppState>,
+    patterns: Option<Vec<String>>,
+) -> Result<Vec<String>, AppError> {
+    let patterns = patterns.map(|patterns| {
+        patterns
+            .into_iter()
+            .map(|pattern| pattern.trim().to_string())
+            .filter(|pattern| !pattern.is_empty())
+            .collect::<Vec<_>>()
+    });
+    let saved = {
+        let conn = lock_or_recover(&state.db);
+        let raw = match &patterns {
+            Some(patterns) => {
+                compile_sensitive_path_rules(patterns)?;
+                Some(serde_json::to_string(patterns)?)
+            }
+            None => None,
+        };
+        set_app_setting(&conn, SENSITIVE_PATH_PATTERNS_KEY, raw)?;
+        load_sensitive_path_patterns(&conn)?
+    };
+    let compiled = compile_sensitive_path_rules(&saved)?;
+    let mut rules = lock_or_recover(&state.sensitive_path_rules);
+    *rules = compiled;
+    Ok(saved)
+}
+
+// 获取排除规则原文：供设置页初始化使用
+#[tauri::command]
+pub fn get_exclusion_patterns(state: State<AppState>) -> Result<Vec<String>, AppError> {
+    let conn = lock_or_recover(&state.db);
+    load_exclusion_patterns(&conn).map_err(AppError::from)
+}
+
+// 更新排除规则：忽略空白规则，全部规则都能编译才保存，保存后立即对 watcher 生效；传入空列表表示不排除
+#[tauri::command]
+pub fn set_exclusion_patterns(
+    state: State<AppState>,
+    patterns: Vec<String>,
+) -> Result<Vec<String>, AppError> {
+    let patterns = patterns
+        .into_iter()
+        .map(|pattern| pattern.trim().to_string())
+        .filter(|pattern| !pattern.is_empty())
+        .collect::<Vec<_>>();
+    let compiled = compile_exclusion_patterns(&patterns)?;
+    {
+        let conn = lock_or_recover(&state.db);
+        let raw = (!patterns.is_empty())
+            .then(|| serde_json::to_string(&patterns))
+            .transpose()?;
+        set_app_setting(&conn, EXCLUSION_PATTERNS_KEY, raw)?;
+    }
+    let mut rules = lock_or_recover(&state.exclusion_rules);
+    *rules = compiled;
+    Ok(patterns)
+}
+
+// 获取来源应用屏蔽列表：供设置页初始化使用
+#[tauri::command]
+pub fn get_source_blocklist(state: State<AppState>) -> Result<Vec<String>, AppError> {
+    let blocklist = lock_or_recover(&state.source_blocklist);
+    Ok(blocklist.clone())
+}
+
+// 更新来源应用屏蔽列表：忽略空白项，忽略大小写去重，保存后立即对 watcher 生效；传入空列表表示不屏蔽
+#[tauri::command]
+pub fn set_source_blocklist(
+    state: State<AppState>,
+    apps: Vec<String>,
+) -> Result<Vec<String>, AppError> {
+    let mut blocklist: Vec<String> = Vec::new();
+    for app in apps {
+        let app = app.trim();
+        if !app.is_empty()
+            && !blocklist
+                .iter()
+                .any(|saved| saved.eq_ignore_ascii_case(app))
+        {
+            blocklist.push(app.to_string());
+        }
+    }
+    {
+        let conn = lock_or_recover(&state.db);
+        let raw = (!blocklist.is_empty())
+            .then(|| serde_json::to_string(&blocklist))
+            .transpose()?;
+        set_app_setting(&conn, SOURCE_BLOCKLIST_KEY, raw)?;
+    }
+    let mut saved = lock_or_recover(&state.source_blocklist);
+    *saved = blocklist.clone();
+    Ok(blocklist)
+}
+
+// 获取是否拦截指向敏感文件的路径：供设置页初始化使用
+#[tauri::command]
+pub fn get_block_risky_captures(state: State<AppState>) -> Result<bool, AppError> {
+    Ok(state.block_risky_captures.load(Ordering::Relaxed))
+}
+
+// 设置是否拦截指向敏感文件的路径：关闭时照常写入并提醒，开启后不写入历史，只广播提醒
+#[tauri::command]
+pub fn set_block_risky_captures(state: State<AppState>, enabled: bool) -> Result<bool, AppError> {
+    {
+        let conn = lock_or_recover(&state.db);
+        set_app_setting(&conn, BLOCK_RISKY_CAPTURES_KEY, Some(enabled.to_string()))?;
+    }
+    state.block_risky_captures.store(enabled, Ordering::Relaxed);
+    Ok(enabled)
+}
+
+// 获取全局快捷键是否切换主窗口显示状态：供设置页初始化使用
+#[tauri::command]
+pub fn get_shortcut_toggles_window(state: State<AppState>) -> Result<bool, AppError> {
+    Ok(state.shortcut_toggles_window.load(Ordering::Relaxed))
+}
+
+// 设置全局快捷键是否切换主窗口显示状态：处理函数每次触发时读取，修改后无需重新注册快捷键
+#[tauri::command]
+pub fn set_shortcut_toggles_window(
+    state: State<AppState>,
+    enabled: bool,
+) -> Result<bool, AppError> {
+    {
+        let conn = lock_or_recover(&state.db);
+        set_app_setting(
+            &conn,
+            SHORTCUT_TOGGLES_WINDOW_KEY,
+            Some(enabled.to_string()),
+        )?;
+    }
+    state
+        .shortcut_toggles_window
+        .store(enabled, Ordering::Relaxed);
+    Ok(enabled)
+}
+
+// 获取是否按显示器布局记住主窗口位置：供设置页初始化使用
+#[tauri::command]
+pub fn get_remember_window_placement(state: State<AppState>) -> Result<bool, AppError> {
+    let conn = lock_or_recover(&state.db);
+    load_remember_window_placement(&conn).map_err(AppError::from)
+}
+
+// 设置是否按显示器布局记住主窗口位置：显示窗口时读取，关闭后不再恢复位置，但保留已记住的位置供重新开启时使用
+#[tauri::command]
+pub fn set_remember_window_placement(
+    state: State<AppState>,
+    enabled: bool,
+) -> Result<bool, AppError> {
+    let conn = lock_or_recover(&state.db);
+    set_app_setting(
+        &conn,
+        REMEMBER_WINDOW_PLACEMENT_KEY,
+        Some(enabled.to_string()),
+    )?;
+    Ok(enabled)
+}
+
+// 获取当前系统开机自启动状态：供设置页初始化使用
+#[tauri::command]
+pub fn get_autostart_status(app: tauri::AppHandle) -> Result<bool, AppError> {
+    let manager = app.autolaunch();
+    manager.is_enabled().map_err(AppError::from)
+}
+
+// 切换系统开机自启动状态：返回实际结果，避免前端显示与系统真实状态不一致
+#[tauri::command]
+pub fn set_autostart_enabled(app: tauri::AppHandle, enabled: bool) -> Result<bool, AppError> {
+    let manager = app.autolaunch();
+    if enabled {
+        manager.enable()?;
+    } else {
+        manager.disable()?;
+    }
+    manager.is_enabled().map_err(AppError::from)
+}
+
+// 读取打开主窗口的快捷键设置：供设置页初始化展示
+#[tauri::command]
+pub fn get_open_window_shortcut(state: State<AppState>) -> Result<Option<String>, AppError> {
+    let conn = lock_or_recover(&state.db);
+    get_app_setting(&conn, OPEN_WINDOW_SHORTCUT_KEY).map_err(AppError::from)
+}
+
+// 校验快捷键写法：供设置页在输入时即时提示，避免保存后注册失败才看到难懂的错误
+#[tauri::command]
+pub fn validate_shortcut(accelerator: String) -> Result<(), AppError> {
+    #[cfg(desktop)]
+    {
+        crate::desktop::parse_shortcut(&accelerator).map(|_| ())
+    }
+    #[cfg(not(desktop))]
+    {
+        let _ = accelerator;
+        Err(AppError::Unsupported(Reason::ShortcutsUnsupported))
+    }
+}
+
+// 更新打开主窗口的快捷键设置：同步更新数据库并注册/取消全局快捷键（desktop 下生效）
+#[tauri::command]
+pub fn set_open_window_shortcut(
+    app: tauri::AppHandle,
+    state: State<AppState>,
+    shortcut: Option<String>,
+) -> Result<Option<String>, AppError> {
+    let normalized = shortcut
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty());
+    let bindings = {
+        let conn = lock_or_recover(&state.db);
+        load_shortcut_bindings(&conn)?
+    };
+    let previous = bindings.open_window;
+    if previous == normalized {
+        return Ok(normalized);
+    }
+    #[cfg(desktop)]
+    {
+        crate::desktop::validate_shortcuts(
+            normalized.as_deref(),
+            bindings.toggle_monitoring.as_deref(),
+            &bindings.filters,
+        )?;
+        crate::desktop::update_open_window_shortcut(
+            &app,
+            previous.as_deref(),
+            normalized.as_deref(),
+        )?;
+    }
+    {
+        let conn = lock_or_recover(&state.db);
+        set_app_setting(&conn, OPEN_WINDOW_SHORTCUT_KEY, normalized.clone())?;
+    }
+    Ok(normalized)
+}
+
+// 读取切换剪贴板监听的快捷键设置：供设置页初始化展示
+#[tauri::command]
+pub fn get_toggle_monitoring_shortcut(state: State<AppState>) -> Result<Option<String>, AppError> {
+    let conn = lock_or_recover(&state.db);
+    get_app_setting(&conn, TOGGLE_MONITORING_SHORTCUT_KEY).map_err(AppError::from)
+}
+
+// 更新切换剪贴板监听的快捷键：写法无效或与已有快捷键冲突时拒绝保存，
+// 否则注册新快捷键、卸载旧快捷键并写入数据库（desktop 下生效）
+#[tauri::command]
+pub fn set_toggle_monitoring_shortcut(
+    app: tauri::AppHandle,
+    state: State<AppState>,
+    shortcut: Option<String>,
+) -> Result<Option<String>, AppError> {
+    let normalized = shortcut
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty());
+    let bindings = {
+        let conn = lock_or_recover(&state.db);
+        load_shortcut_bindings(&conn)?
+    };
+    let previous = bindings.toggle_monitoring;
+    if previous == normalized {
+        return Ok(normalized);
+    }
+    #[cfg(desktop)]
+    {
+        crate::desktop::validate_shortcuts(
+            bindings.open_window.as_deref(),
+            normalized.as_deref(),
+            &bindings.filters,
+        )?;
+        crate::desktop::update_toggle_monitoring_shortcut(
+            &app,
+            previous.as_deref(),
+            normalized.as_deref(),
+        )?;
+    }
+    {
+        let conn = lock_or_recover(&state.db);
+        set_app_setting(&conn, TOGGLE_MONITORING_SHORTCUT_KEY, normalized.clone())?;
+    }
+    Ok(normalized)
+}
+
+// 按给定顺序重排整个历史列表（含非固定条目），配合手动排序模式使用
+#[tauri::command]
+pub fn reorder_items(state: State<AppState>, ordered_ids: Vec<String>) -> Result<(), AppError> {
+    reorder_items_internal(&state, &ordered_ids)
+}
+
+// 获取整个历史列表的排序方式：供设置页与主窗口初始化使用
+#[tauri::command]
+pub fn get_history_sort(state: State<AppState>) -> Result<HistorySortMode, AppError> {
+    let conn = lock_or_recover(&state.db);
+    load_history_sort_mode(&conn).map_err(AppError::from)
+}
+
+// 更新整个历史列表的排序方式，并广播给所有窗口同步前端排序
+#[tauri::command]
+pub fn set_history_sort(
+    app: tauri::AppHandle,
+    state: State<AppState>,
+    mode: HistorySortMode,
+) -> Result<HistorySortMode, AppError> {
+    {
+        let conn = lock_or_recover(&state.db);
+        set_app_setting(&conn, HISTORY_SORT_KEY, Some(mode.as_setting().to_string()))?;
+    }
+    let _ = app.emit("history-sort-changed", mode);
+    Ok(mode)
+}
+
+// 获取固定区的排序方式：供设置页初始化使用
+#[tauri::command]
+pub fn get_pinned_sort(state: State<AppState>) -> Result<PinnedSortMode, AppError> {
+    let conn = lock_or_recover(&state.db);
+    let setting = get_app_setting(&conn, PINNED_SORT_KEY)?;
+    Ok(PinnedSortMode::from_setting(setting.as_deref()))
+}
+
+// 更新固定区的排序方式，并广播给所有窗口，让主窗口无需重新加载即可按新规则排序
+#[tauri::command]
+pub fn set_pinned_sort(
+    app: tauri::AppHandle,
+    state: State<AppState>,
+    mode: PinnedSortMode,
+) -> Result<PinnedSortMode, AppError> {
+    {
+        let conn = lock_or_recover(&state.db);
+        set_app_setting(&conn, PINNED_SORT_KEY, Some(mode.as_setting().to_string()))?;
+    }
+    let _ = app.emit("pinned-sort-changed", mode);
+    Ok(mode)
+}
+
+// 获取编辑后与已有条目重复时保留哪一条：供设置页初始化使用
+#[tauri::command]
+pub fn get_merge_text_winner(state: State<AppState>) -> Result<MergeTextWinner, AppError> {
+    let conn = lock_or_recover(&state.db);
+    let setting = get_app_setting(&conn, MERGE_TEXT_WINNER_KEY)?;
+    Ok(MergeTextWinner::from_setting(setting.as_deref()))
+}
+
+// 更新编辑后与已有条目重复时保留哪一条，下次合并时生效
+#[tauri::command]
+pub fn set_merge_text_winner(
+    state: State<AppState>,
+    winner: MergeTextWinner,
+) -> Result<MergeTextWinner, AppError> {
+    let conn = lock_or_recover(&state.db);
+    set_app_setting(
+        &conn,
+        MERGE_TEXT_WINNER_KEY,
+        Some(winner.as_setting().to_string()),
+    )?;
+    Ok(winner)
+}
+
+// 设置后端提示使用的语言：立即生效并保存，返回实际采用的语言（无法识别的语言回退到英文）
+#[tauri::command]
+pub fn set_locale(state: State<AppState>, locale: String) -> Result<Locale, AppError> {
+    let locale = Locale::from_setting(Some(&locale));
+    let conn = lock_or_recover(&state.db);
+    set_app_setting(&conn, LOCALE_KEY, Some(locale.as_setting().to_string()))?;
+    set_active_locale(locale);
+    Ok(locale)
+}
+
+// 获取左键点击托盘图标时的动作：供设置页初始化使用
+#[tauri::command]
+pub fn get_tray_left_click(state: State<AppState>) -> Result<TrayLeftClickAction, AppError> {
+    let action = lock_or_recover(&state.tray_left_click);
+    Ok(*action)
+}
+
+// 更新左键点击托盘图标时的动作：持久化后同步运行时状态，并切换托盘是否在左键时弹出菜单
+#[tauri::command]
+pub fn set_tray_left_click(
+    app: tauri::AppHandle,
+    state: State<AppState>,
+    action: TrayLeftClickAction,
+) -> Result<TrayLeftClickAction, AppError> {
+    {
+        let conn = lock_or_recover(&state.db);
+        set_app_setting(
+            &conn,
+            TRAY_LEFT_CLICK_KEY,
+            Some(action.as_setting().to_string()),
+        )?;
+    }
+    {
+        let mut current = lock_or_recover(&state.tray_left_click);
+        *current = action;
+    }
+    #[cfg(desktop)]
+    {
+        crate::desktop::apply_tray_left_click(&app, action)?;
+    }
+    Ok(action)
+}
+
+// 获取托盘菜单显示的操作项（按显示顺序）：供设置页初始化使用
+#[tauri::command]
+pub fn get_tray_menu(state: State<AppState>) -> Result<Vec<TrayMenuAction>, AppError> {
+    let conn = lock_or_recover(&state.db);
+    load_tray_menu(&conn).map_err(AppError::from)
+}
+
+// 更新托盘菜单显示的操作项并立即重建菜单；退出项始终保留，返回实际生效的配置
+#[tauri::command]
+pub fn set_tray_menu(
+    app: tauri::AppHandle,
+    state: State<AppState>,
+    actions: Vec<TrayMenuAction>,
+) -> Result<Vec<TrayMenuAction>, AppError> {
+    let actions = TrayMenuAction::normalize(&actions);
+    {
+        let conn = lock_or_recover(&state.db);
+        let raw = serde_json::to_string(&actions)?;
+        set_app_setting(&conn, TRAY_MENU_KEY, Some(raw))?;
+    }
+    #[cfg(desktop)]
+    {
+        crate::desktop::refresh_tray_menu(&app)?;
+    }
+    #[cfg(not(desktop))]
+    let _ = app;
+    Ok(actions)
+}
+
+// 读取托盘“最近复制”子菜单显示的条目数：供设置页初始化展示，0 表示不显示子菜单
+#[tauri::command]
+pub fn get_tray_recent_count(state: State<AppState>) -> Result<u64, AppError> {
+    let conn = lock_or_recover(&state.db);
+    load_tray_recent_count(&conn).map_err(AppError::from)
+}
+
+// 设置托盘“最近复制”子菜单显示的条目数并立即重建菜单，传 0 隐藏子菜单
+#[tauri::command]
+pub fn set_tray_recent_count(
+    app: tauri::AppHandle,
+    state: State<AppState>,
+    count: u64,
+) -> Result<u64, AppError> {
+    if !TRAY_RECENT_COUNT_RANGE.contains(&count) {
+        return Err(AppError::InvalidInput(Reason::TrayRecentCountRange(
+            *TRAY_RECENT_COUNT_RANGE.start(),
+            *TRAY_RECENT_COUNT_RANGE.end(),
+        )));
+    }
+    {
+        let conn = lock_or_recover(&state.db);
+        set_app_setting(&conn, TRAY_RECENT_COUNT_KEY, Some(count.to_string()))?;
+    }
+    #[cfg(desktop)]
+    {
+        crate::desktop::refresh_tray_menu(&app)?;
+    }
+    #[cfg(not(desktop))]
+    let _ = app;
+    Ok(count)
+}
+
+// 规则变更后重新读取并编译全部规则，刷新 AppState 中的缓存
+fn refresh_autotag_rules(state: &AppState) -> Result<(), AppError> {
+    let compiled = {
+        let conn = lock_or_recover(&state.db);
+        load_compiled_autotag_rules(&conn)?
+    };
+    let mut rules_lock = lock_or_recover(&state.autotag_rules);
+    *rules_lock = compiled;
+    Ok(())
+}
+
+// 新增自动打标签规则：保存前先编译正则，无效表达式直接返回错误而不是静默忽略
+#[tauri::command]
+pub fn add_autotag_rule(
+    state: State<AppState>,
+    pattern: String,
+    tag: String,
+) -> Result<AutotagRule, AppError> {
+    let tag = tag.trim().to_string();
+    if tag.is_empty() {
+        return Err(AppError::InvalidInput(Reason::EmptyTag));
+    }
+    if pattern.is_empty() {
+        return Err(AppError::InvalidInput(Reason::EmptyRegex));
+    }
+    compile_user_regex(&pattern)?;
+    let rule = {
+        let conn = lock_or_recover(&state.db);
+        conn.execute(
+            "INSERT INTO autotag_rules (pattern, tag) VALUES (?1, ?2)",
+            params![pattern, tag],
+        )?;
+        AutotagRule {
+            id: conn.last_insert_rowid(),
+            pattern,
+            tag,
+        }
+    };
+    refresh_autotag_rules(&state)?;
+    Ok(rule)
+}
+
+// 列出全部自动打标签规则：供设置页展示与管理
+#[tauri::command]
+pub fn list_autotag_rules(state: State<AppState>) -> Result<Vec<AutotagRule>, AppError> {
+    let conn = lock_or_recover(&state.db);
+    crate::db::list_autotag_rules(&conn).map_err(AppError::from)
+}
+
+// 删除自动打标签规则：只影响之后的捕获，已打上的标签保持不变
+#[tauri::command]
+pub fn remove_autotag_rule(state: State<AppState>, id: i64) -> Result<(), AppError> {
+    {
+        let conn = lock_or_recover(&state.db);
+        conn.execute("DELETE FROM autotag_rules WHERE id = ?1", params![id])?;
+    }
+    refresh_autotag_rules(&state)
+}
+
+// 获取自动归类的类别→标签映射：供设置页初始化使用
+#[tauri::command]
+pub fn get_category_tags(
+    state: State<AppState>,
+) -> Result<BTreeMap<ContentCategory, String>, AppError> {
+    let conn = lock_or_recover(&state.db);
+    load_category_tags(&conn).map_err(AppError::from)
+}
+
+// 更新自动归类的类别→标签映射：标签名为空的类别视为不归类，返回实际保存的映射
+#[tauri::command]
+pub fn set_category_tags(
+    state: State<AppState>,
+    mapping: BTreeMap<ContentCategory, String>,
+) -> Result<BTreeMap<ContentCategory, String>, AppError> {
+    let mapping: BTreeMap<ContentCategory, String> = mapping
+        .into_iter()
+        .map(|(category, tag)| (category, tag.trim().to_string()))
+        .filter(|(_, tag)| !tag.is_empty())
+        .collect();
+    let raw = serde_json::to_string(&mapping)?;
+    let conn = lock_or_recover(&state.db);
+    set_app_setting(&conn, CATEGORY_TAGS_KEY, Some(raw))?;
+    Ok(mapping)
+}
+
+// 给还没有标签的历史条目按内容类别（链接、颜色、JSON 等）与自动标签规则补上标签，
+// 便于后来才开始使用标签的用户整理已有记录；返回被加上标签的条目数
+#[tauri::command]
+pub fn auto_categorize(state: State<AppState>) -> Result<usize, AppError> {
+    auto_categorize_internal(&state).map_err(AppError::from)
+}
+
+// 列出全部标签：按用户调整的顺序排列并带上颜色，供前端渲染标签栏
+#[tauri::command]
+pub fn list_tags(state: State<AppState>) -> Result<Vec<TagInfo>, AppError> {
+    let conn = lock_or_recover(&state.db);
+    crate::db::list_tags(&conn).map_err(AppError::from)
+}
+
+// 给条目添加标签，已有同名标签时不重复添加；返回更新后的条目，便于前端直接替换
+#[tauri::command]
+pub fn add_item_tag(
+    state: State<AppState>,
+    id: String,
+    tag: String,
+) -> Result<ClipboardItem, AppError> {
+    let tag = tag.trim();
+    if tag.is_empty() {
+        return Err(AppError::InvalidInput(Reason::EmptyTag));
+    }
+    let conn = lock_or_recover(&state.db);
+    let exists = conn
+        .query_row(
+            "SELECT 1 FROM clipboard_items WHERE id = ?1",
+            params![id],
+            |_| Ok(()),
+        )
+        .optional()?
+        .is_some();
+    if !exists {
+        return Err(AppError::NotFound);
+    }
+    conn.execute(
+        "INSERT OR IGNORE INTO item_tags (item_id, tag) VALUES (?1, ?2)",
+        params![id, tag],
+    )?;
+    query_clipboard_item(&conn, &id).map_err(AppError::from)
+}
+
+// 移除条目上的标签，条目没有该标签时不做改动；返回更新后的条目
+#[tauri::command]
+pub fn remove_item_tag(
+    state: State<AppState>,
+    id: String,
+    tag: String,
+) -> Result<ClipboardItem, AppError> {
+    let conn = lock_or_recover(&state.db);
+    conn.execute(
+        "DELETE FROM item_tags WHERE item_id = ?1 AND tag = ?2",
+        params![id, tag.trim()],
+    )?;
+    query_clipboard_item(&conn, &id)
+        .optional()?
+        .ok_or(AppError::NotFound)
+}
+
+// 设置标签颜色（#RRGGBB，传 None 恢复默认配色），返回更新后的完整标签列表
+#[tauri::command]
+pub fn set_tag_color(
+    state: State<AppState>,
+    tag: String,
+    color: Option<String>,
+) -> Result<Vec<TagInfo>, AppError> {
+    let tag = tag.trim().to_string();
+    let color = color
+        .map(|value| value.trim().to_ascii_lowercase())
+        .filter(|value| !value.is_empty());
+    if let Some(color) = &color {
+        let valid = color.len() == 7
+            && color.starts_with('#')
+            && color[1..].chars().all(|ch| ch.is_ascii_hexdigit());
+        if !valid {
+            return Err(AppError::InvalidInput(Reason::InvalidTagColor));
+        }
+    }
+    let conn = lock_or_recover(&state.db);
+    let tags = crate::db::list_tags(&conn)?;
+    if !tags.iter().any(|info| info.name == tag) {
+        return Err(AppError::InvalidInput(Reason::TagNotFound(tag)));
+    }
+    conn.execute(
+        "
+        INSERT INTO tags (name, color) VALUES (?1, ?2)
+        ON CONFLICT(name) DO UPDATE SET color = excluded.color
+        ",
+        params![tag, color],
+    )?;
+    crate::db::list_tags(&conn).map_err(AppError::from)
+}
+
+// 按给定顺序重排标签栏，返回重排后的完整标签列表
+#[tauri::command]
+pub fn reorder_tags(
+    state: State<AppState>,
+    ordered_tags: Vec<String>,
+) -> Result<Vec<TagInfo>, AppError> {
+    let mut conn = lock_or_recover(&state.db);
+    reorder_tags_internal(&mut conn, &ordered_tags)?;
+    crate::db::list_tags(&conn).map_err(AppError::from)
+}
+
+// 读取每个标签下最近更新的一条记录，按标签栏的顺序排列；没有条目的标签不会出现在结果中
+#[tauri::command]
+pub fn load_latest_per_tag(state: State<AppState>) -> Result<Vec<TagLatestItem>, AppError> {
+    let conn = lock_or_recover(&state.db);
+    // 在子查询中按标签分组编号，外层只保留每组第一条；标签顺序与 list_tags 保持一致
+    let mut stmt = conn.prepare(&format!(
+        "
+            SELECT {CLIPBOARD_ITEM_COLUMNS}, tag
+            FROM (
+                SELECT
+                    clipboard_items.*,
+                    item_tags.tag AS tag,
+                    tags.position AS tag_position,
+                    ROW_NUMBER() OVER (
+                        PARTITION BY item_tags.tag
+                        ORDER BY clipboard_items.updated_at DESC, clipboard_items.id DESC
+                    ) AS row_number
+                FROM item_tags
+                JOIN clipboard_items ON clipboard_items.id = item_tags.item_id
+                LEFT JOIN tags ON tags.name = item_tags.tag
+            )
+            WHERE row_number = 1
+            ORDER BY tag_position IS NULL, tag_position, tag
+            "
+    ))?;
+    let rows = stmt.query_map([], |row| {
+        Ok(TagLatestItem {
+            tag: row.get("tag")?,
+            item: map_row(row)?,
+        })
+    })?;
+    rows.collect::<Result<Vec<_>, _>>().map_err(AppError::from)
+}
+
+// 读取带筛选预设的快捷键列表：供设置页展示已配置的专用入口
+#[tauri::command]
+pub fn get_filter_shortcuts(state: State<AppState>) -> Result<Vec<FilterShortcut>, AppError> {
+    let conn = lock_or_recover(&state.db);
+    load_filter_shortcuts(&conn).map_err(AppError::from)
+}
+
+// 整体更新带筛选预设的快捷键：清洗空值、校验冲突后重新注册，并持久化为 JSON
+#[tauri::command]
+pub fn set_filter_shortcuts(
+    app: tauri::AppHandle,
+    state: State<AppState>,
+    shortcuts: Vec<FilterShortcut>,
+) -> Result<Vec<FilterShortcut>, AppError> {
+    let trim_optional = |value: Option<String>| {
+        value
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+    };
+    let normalized: Vec<FilterShortcut> = shortcuts
+        .into_iter()
+        .map(|mut entry| {
+            entry.shortcut = entry.shortcut.trim().to_string();
+            entry.filter.content_type = trim_optional(entry.filter.content_type);
+            entry.filter.tag = trim_optional(entry.filter.tag);
+            entry
+        })
+        .filter(|entry| !entry.shortcut.is_empty())
+        .collect();
+    let bindings = {
+        let conn = lock_or_recover(&state.db);
+        load_shortcut_bindings(&conn)?
+    };
+    let previous = bindings.filters;
+    if previous == normalized {
+        return Ok(normalized);
+    }
+    #[cfg(desktop)]
+    {
+        crate::desktop::validate_shortcuts(
+            bindings.open_window.as_deref(),
+            bindings.toggle_monitoring.as_deref(),
+            &normalized,
+        )?;
+        crate::desktop::update_filter_shortcuts(&app, &previous, &normalized)?;
+    }
+    let serialized = if normalized.is_empty() {
+        None
+    } else {
+        Some(serde_json::to_string(&normalized)?)
+    };
+    {
+        let conn = lock_or_recover(&state.db);
+        set_app_setting(&conn, FILTER_SHORTCUTS_KEY, serialized)?;
+    }
+    Ok(normalized)
+}
+
+// 生成条目链接（pure-paste://item/<id>），可贴到笔记等地方，点击后唤起应用并定位到该条目
+#[tauri::command]
+pub fn get_item_uri(state: State<AppState>, id: String) -> Result<String, AppError> {
+    let conn = lock_or_recover(&state.db);
+    let exists = conn
+        .query_row(
+            "SELECT 1 FROM clipboard_items WHERE id = ?1",
+            params![id],
+            |_| Ok(()),
+        )
+        .optional()?
+        .is_some();
+    if !exists {
+        return Err(AppError::NotFound);
+    }
+    Ok(format!("{ITEM_URI_PREFIX}{id}"))
+}
+
+// 领取冷启动时通过条目链接传入的待定位条目，领取后清空，避免重复定位
+#[tauri::command]
+pub fn take_pending_focus_item(state: State<AppState>) -> Result<Option<String>, AppError> {
+    let mut pending = lock_or_recover(&state.pending_focus_item);
+    Ok(pending.take())
+}
+
+// 获取版本信息，只做只读查询。数据库结构版本即已执行的迁移数量
+#[tauri::command]
+pub fn get_version_info(state: State<AppState>) -> Result<VersionInfo, AppError> {
+    let conn = lock_or_recover(&state.db);
+    let db_schema_version = conn.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+    let sqlite_version = conn.query_row("SELECT sqlite_version()", [], |row| row.get(0))?;
+    Ok(VersionInfo {
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        db_schema_version,
+        sqlite_version,
+    })
+}
+
+// 获取数据存放位置：返回数据库路径、所在目录以及是否处于便携模式
+#[tauri::command]
+pub fn get_storage_location(state: State<AppState>) -> Result<StorageLocation, AppError> {
+    storage_location(&state)
+}
+
+fn storage_location(state: &AppState) -> Result<StorageLocation, AppError> {
+    let db_path = lock_or_recover(&state.db_path);
+    let data_dir = db_path
+        .parent()
+        .map(|dir| dir.display().to_string())
+        .unwrap_or_default();
+    Ok(StorageLocation {
+        db_path: db_path.display().to_string(),
+        data_dir,
+        portable: state.portable,
+    })
+}
+
+// 把数据库迁移到新的位置（传入已存在的目录时使用默认文件名），校验副本无误后切换连接并删除原文件，
+// 返回新的存放位置。便携模式下数据库固定在可执行文件旁，不支持迁移
+#[tauri::command]
+pub fn move_db(state: State<AppState>, new_path: String) -> Result<StorageLocation, AppError> {
+    if state.portable {
+        return Err(AppError::Unsupported(Reason::PortableDbMove));
+    }
+    let new_path = new_path.trim();
+    if new_path.is_empty() {
+        return Err(AppError::InvalidInput(Reason::EmptyDbPath));
+    }
+    let mut target = PathBuf::from(new_path);
+    if !target.is_absolute() {
+        return Err(AppError::InvalidInput(Reason::DbPathNotAbsolute));
+    }
+    if target.is_dir() {
+        target = target.join(DB_FILE_NAME);
+    }
+    {
+        // 迁移期间同时持有连接与路径，避免其他命令在切换过程中读写旧数据库
+        let mut conn = lock_or_recover(&state.db);
+        let mut db_path = lock_or_recover(&state.db_path);
+        move_db_internal(&mut conn, &state.app_data_dir, &db_path, &target)?;
+        *db_path = target;
+    }
+    storage_location(&state)
+}
+
+// 读取当前实际生效的全部配置（已套用默认值）：数据库设置在同一次加锁中读取，保证各项彼此一致
+#[tauri::command]
+pub fn get_effective_config(
+    app: tauri::AppHandle,
+    state: State<AppState>,
+) -> Result<EffectiveConfig, AppError> {
+    effective_config(&app, &state)
+}
+
+// 汇总当前实际生效的配置，供诊断命令与导出配置共用
+fn effective_config(app: &tauri::AppHandle, state: &AppState) -> Result<EffectiveConfig, AppError> {
+    let autostart_enabled = app.autolaunch().is_enabled().ok();
+    let tray_left_click = *lock_or_recover(&state.tray_left_click);
+    let conn = lock_or_recover(&state.db);
+    let setting = |key: &str| get_app_setting(&conn, key);
+    let positive = |value: u64| (value > 0).then_some(value);
+    let poll_interval_ms = state.poll_interval_ms.load(Ordering::Relaxed);
+    Ok(EffectiveConfig {
+        poll_interval_ms,
+        retention_days: HISTORY_RETENTION_DAYS,
+        skip_next_text_ttl_ms: skip_next_text_ttl_ms(poll_interval_ms),
+        history_page_limit_max: HISTORY_PAGE_LIMIT_MAX,
+        monitoring_enabled: state.monitoring_enabled.load(Ordering::Relaxed),
+        pause_on_lock: state.pause_on_lock.load(Ordering::Relaxed),
+        reset_dedup_on_empty: state.reset_dedup_on_empty.load(Ordering::Relaxed),
+        ignore_own_window_copies: state.ignore_own_window_copies.load(Ordering::Relaxed),
+        sanitize_on_capture: state.sanitize_on_capture.load(Ordering::Relaxed),
+        sensitive_path_patterns: load_sensitive_path_patterns(&conn)?,
+        exclusion_patterns: load_exclusion_patterns(&conn)?,
+        source_blocklist: load_source_blocklist(&conn)?,
+        block_risky_captures: state.block_risky_captures.load(Ordering::Relaxed),
+        shortcut_toggles_window: state.shortcut_toggles_window.load(Ordering::Relaxed),
+        remember_window_placement: load_remember_window_placement(&conn)?,
+        capture_filter: load_capture_filter_settings(&conn)?,
+        capture_burst_threshold: positive(state.capture_burst_threshold.load(Ordering::Relaxed)),
+        capture_burst_window_secs: CAPTURE_BURST_WINDOW.as_secs(),
+        broadcast_batch_threshold: positive(
+            state.broadcast_batch_threshold.load(Ordering::Relaxed),
+        ),
+        broadcast_rate_window_secs: BROADCAST_RATE_WINDOW.as_secs(),
+        clipboard_auto_clear_seconds: positive(
+            state.clipboard_clear_seconds.load(Ordering::Relaxed),
+        ),
+        auto_unpin_days: positive(state.auto_unpin_days.load(Ordering::Relaxed)),
+        item_ttl_minutes: positive(state.item_ttl_minutes.load(Ordering::Relaxed)),
+        merge_text_winner: MergeTextWinner::from_setting(
+            setting(MERGE_TEXT_WINNER_KEY)?.as_deref(),
+        ),
+        locale: active_locale(),
+        history_sort: load_history_sort_mode(&conn)?,
+        pinned_sort: PinnedSortMode::from_setting(setting(PINNED_SORT_KEY)?.as_deref()),
+        tray_left_click,
+        tray_recent_count: load_tray_recent_count(&conn)?,
+        open_window_shortcut: setting(OPEN_WINDOW_SHORTCUT_KEY)?,
+        toggle_monitoring_shortcut: setting(TOGGLE_MONITORING_SHORTCUT_KEY)?,
+        filter_shortcuts: load_filter_shortcuts(&conn)?,
+        paste_wrapper: load_paste_wrapper(&conn)?,
+        category_tags: load_category_tags(&conn)?,
+        archive_path: setting(ARCHIVE_PATH_KEY)?,
+        autostart_enabled,
+        storage: storage_location(state)?,
+    })
+}
+
+// 导出配置：app_settings 表的原始内容 + 当前生效配置（仅供查看），格式化为便于编辑的 JSON
+#[tauri::command]
+pub fn export_settings(app: tauri::AppHandle, state: State<AppState>) -> Result<String, AppError> {
+    let settings = {
+        let conn = lock_or_recover(&state.db);
+        list_app_settings(&conn)?
+    };
+    let export = SettingsExport {
+        version: SETTINGS_EXPORT_VERSION,
+        exported_at: now_iso_string(),
+        settings,
+        effective: effective_config(&app, &state)?,
+    };
+    serde_json::to_string_pretty(&export).map_err(AppError::from)
+}
+
+// 导入配置：只写入已知的可导入键，未知键原样报告为忽略；文件中未出现的设置保持不变。
+// 快捷键先重新注册成功再落库，落库失败时恢复原来的快捷键；最后刷新运行时状态并广播 settings-changed
+#[tauri::command]
+pub fn import_settings(
+    app: tauri::AppHandle,
+    state: State<AppState>,
+    json: String,
+) -> Result<SettingsImportReport, AppError> {
+    let import: SettingsImport = serde_json::from_str(&json)
+        .map_err(|err| AppError::InvalidInput(Reason::InvalidSettingsFile(err.to_string())))?;
+    if import.version > SETTINGS_EXPORT_VERSION {
+        return Err(AppError::Unsupported(Reason::SettingsVersionTooNew(
+            import.version,
+            SETTINGS_EXPORT_VERSION,
+        )));
+    }
+    let (applied, ignored): (Vec<_>, Vec<_>) = import
+        .settings
+        .into_iter()
+        .partition(|(key, _)| IMPORTABLE_SETTING_KEYS.contains(&key.as_str()));
+    let imported = |key: &str| {
+        applied
+            .iter()
+            .find(|(applied_key, _)| applied_key == key)
+            .map(|(_, value)| value.as_str())
+    };
+    let previous_shortcuts = {
+        let conn = lock_or_recover(&state.db);
+        load_shortcut_bindings(&conn)?
+    };
+    // 单个快捷键去掉首尾空白，空字符串视为清除；配置中未包含的快捷键保持不变
+    let imported_shortcut = |key: &str, previous: &Option<String>| match imported(key) {
+        Some(value) => Some(value.trim().to_string()).filter(|value| !value.is_empty()),
+        None => previous.clone(),
+    };
+    let next_shortcuts = ShortcutBindings {
+        open_window: imported_shortcut(OPEN_WINDOW_SHORTCUT_KEY, &previous_shortcuts.open_window),
+        toggle_monitoring: imported_shortcut(
+            TOGGLE_MONITORING_SHORTCUT_KEY,
+            &previous_shortcuts.toggle_monitoring,
+        ),
+        filters: match imported(FILTER_SHORTCUTS_KEY) {
+            Some(value) => serde_json::from_str(value).map_err(|err| {
+                AppError::InvalidInput(Reason::InvalidImportedFilterShortcuts(err.to_string()))
+            })?,
+            None => previous_shortcuts.filters.clone(),
+        },
+    };
+    let shortcuts_changed = next_shortcuts != previous_shortcuts;
+    #[cfg(desktop)]
+    if shortcuts_changed {
+        crate::desktop::replace_shortcuts(&app, &previous_shortcuts, &next_shortcuts)?;
+    }
+    let written = (|| {
+        let mut conn = lock_or_recover(&state.db);
+        let tx = conn.transaction()?;
+        for (key, value) in &applied {
+            let value = if key == OPEN_WINDOW_SHORTCUT_KEY {
+                next_shortcuts.open_window.clone()
+            } else if key == TOGGLE_MONITORING_SHORTCUT_KEY {
+                next_shortcuts.toggle_monitoring.clone()
+            } else {
+                Some(value.clone())
+            };
+            set_app_setting(&tx, key, value)?;
+        }
+        let runtime = load_runtime_settings(&tx)?;
+        tx.commit()?;
+        Ok::<_, AppError>(runtime)
+    })();
+    let runtime = match written {
+        Ok(runtime) => runtime,
+        Err(err) => {
+            #[cfg(desktop)]
+            if shortcuts_changed {
+                let _ =
+                    crate::desktop::replace_shortcuts(&app, &next_shortcuts, &previous_shortcuts);
+            }
+            return Err(err);
+        }
+    };
+    let tray_left_click = runtime.tray_left_click;
+    runtime.apply(&state);
+    #[cfg(desktop)]
+    {
+        crate::desktop::apply_tray_left_click(&app, tray_left_click)?;
+        crate::desktop::refresh_tray_menu(&app)?;
+    }
+    #[cfg(not(desktop))]
+    let _ = (tray_left_click, shortcuts_changed);
+    let (history_sort, pinned_sort) = {
+        let conn = lock_or_recover(&state.db);
+        (
+            load_history_sort_mode(&conn)?,
+            PinnedSortMode::from_setting(get_app_setting(&conn, PINNED_SORT_KEY)?.as_deref()),
+        )
+    };
+    let report = SettingsImportReport {
+        applied: applied.into_iter().map(|(key, _)| key).collect(),
+        ignored: ignored.into_iter().map(|(key, _)| key).collect(),
+    };
+    let _ = app.emit("history-sort-changed", history_sort);
+    let _ = app.emit("pinned-sort-changed", pinned_sort);
+    let _ = app.emit("settings-changed", &report);
+    Ok(report)
+}
+
+// 打开设置窗口：由后端统一创建/复用窗口，避免前端重复实现多窗口逻辑
+#[tauri::command]
+pub fn open_settings_window_command(app: tauri::AppHandle) -> Result<(), AppError> {
+    #[cfg(desktop)]
+    {
+        crate::desktop::open_settings_window(&app);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::test_state;
+
+    // 写入一条文本条目并返回写入后的条目
+    fn capture(state: &AppState, text: &str) -> ClipboardItem {
+        upsert_clipboard_item_internal(state, build_clipboard_payload(text.to_string()), 30)
+            .expect("写入条目失败")
+            .item
+    }
+
+    fn matched_texts(state: &AppState, field: RegexSearchField, pattern: &str) -> Vec<String> {
+        regex_search_field_internal(state, field, pattern, 10)
+            .unwrap()
+            .into_iter()
+            .map(|item| item.text)
+            .collect()
+    }
+
+    // 同一个正则在正文、标题、备注上各自命中不同的条目
+    #[test]
+    fn regex_search_scopes_to_requested_field() {
+        let state = test_state();
+        let heading = capture(&state, "\n  deploy notes\nrun make release");
+        let body_only = capture(&state, "todo\nremember to deploy");
+        capture(&state, "unrelated");
+        set_item_note_internal(
+            &state,
+            &body_only.id,
+            Some("  deploy on friday ".to_string()),
+        )
+        .unwrap();
+
+        assert_eq!(
+            matched_texts(&state, RegexSearchField::Text, "deploy"),
+            [body_only.text.as_str(), heading.text.as_str()]
+        );
+        assert_eq!(
+            matched_texts(&state, RegexSearchField::Title, "^deploy"),
+            [heading.text.as_str()]
+        );
+        assert_eq!(
+            matched_texts(&state, RegexSearchField::Note, "^deploy on"),
+            [body_only.text.as_str()]
+        );
+
+        // 备注去掉首尾空白后保存，清空后不再参与按备注搜索
+        let item = query_clipboard_item(&lock_or_recover(&state.db), &body_only.id).unwrap();
+        assert_eq!(item.note.as_deref(), Some("deploy on friday"));
+        set_item_note_internal(&state, &body_only.id, Some("   ".to_string())).unwrap();
+        assert!(matched_texts(&state, RegexSearchField::Note, "deploy").is_empty());
+    }
+
+    // 字段只接受白名单中的取值；空正则与不存在的条目返回对应的错误
+    #[test]
+    fn regex_search_rejects_unknown_field_and_empty_pattern() {
+        assert!(serde_json::from_str::<RegexSearchField>("\"note\"").is_ok());
+        assert!(serde_json::from_str::<RegexSearchField>("\"tag\"").is_err());
+        let state = test_state();
+        assert_eq!(
+            regex_search_field_internal(&state, RegexSearchField::Text, "", 10)
+                .unwrap_err()
+                .code(),
+            "invalid_input"
+        );
+        assert_eq!(
+            set_item_note_internal(&state, "missing", Some("note".to_string()))
+                .unwrap_err()
+                .code(),
+            "not_found"
+        );
+    }
+
+    // 游标分页：更新时间相同的条目按 id 区分，逐页读取既不重复也不遗漏；
+    // 翻页过程中新写入的条目排在最前面，不会让后续页面错位
+    #[test]
+    fn history_cursor_pages_without_gaps_or_duplicates() {
+        let state = test_state();
+        for index in 0..7 {
+            capture(&state, &format!("item {index}"));
+        }
+        // 让多条条目的更新时间完全相同（沿用其中一条的时间，避免被保留天数清理）
+        lock_or_recover(&state.db)
+            .execute(
+                "UPDATE clipboard_items SET updated_at = (SELECT updated_at FROM clipboard_items WHERE text = 'item 1') WHERE text IN ('item 1', 'item 2', 'item 3', 'item 4')",
+                [],
+            )
+            .unwrap();
+        let expected: Vec<String> = lock_or_recover(&state.db)
+            .prepare("SELECT id FROM clipboard_items ORDER BY updated_at DESC, id DESC")
+            .unwrap()
+            .query_map([], |row| row.get(0))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        let mut seen = Vec::new();
+        let mut cursor = None;
+        loop {
+            let page = load_history_cursor_internal(&state, cursor, 3).unwrap();
+            assert!(page.items.len() <= 3);
+            seen.extend(page.items.into_iter().map(|item| item.id));
+            if seen.len() == 3 {
+                capture(&state, "written while paging");
+            }
+            match page.next_cursor {
+                Some(next) => cursor = Some(next),
+                None => break,
+            }
+        }
+        assert_eq!(seen, expected);
+    }
+
+    // 游标条件按 (updated_at, id) 索引倒序扫描，翻到很深的位置也不需要临时排序
+    #[test]
+    fn history_cursor_query_uses_index() {
+        let state = test_state();
+        let conn = lock_or_recover(&state.db);
+        let plan: Vec<String> = conn
+            .prepare(
+                "EXPLAIN QUERY PLAN SELECT id FROM clipboard_items WHERE (updated_at, id) < (?1, ?2) ORDER BY updated_at DESC, id DESC LIMIT 50",
+            )
+            .unwrap()
+            .query_map(params!["2024-01-01T00:00:00Z", "id"], |row| row.get(3))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert!(
+            plan.iter()
+                .any(|step| step.contains("idx_clipboard_items_updated_at_id")),
+            "{plan:?}"
+        );
+        assert!(
+            !plan.iter().any(|step| step.contains("TEMP B-TREE")),
+            "{plan:?}"
+        );
     }
-    Ok(())
 }
@@ -1,28 +1,371 @@
-use crate::models::{AppState, ClipboardItem, ClipboardUpdateResult, ClipboardUpsertPayload};
+use crate::clipboard_image::{build_thumbnail, encode_png, image_dedup_key, image_item_label};
+use crate::detect::{detect_category, detect_content_type, detect_script};
+use crate::error::{AppError, Reason};
+use crate::models::{
+    lock_or_recover, now_iso_string, register_capture_burst, AppState, AutotagRule, CaptureFilter,
+    CaptureFilterMode, CaptureFilterSettings, ClipboardImage, ClipboardItem, ClipboardUpdateResult,
+    ClipboardUpsertOutcome, ClipboardUpsertPayload, CompiledAutotagRule, ContentCategory,
+    ContentType, FilterShortcut, HistorySortMode, ItemKind, Locale, MergeTextWinner, PasteWrapper,
+    PinnedSortMode, QueryParams, RuntimeSettings, SensitivePathRule, ShortcutBindings, TagInfo,
+    TrayLeftClickAction, TrayMenuAction, TrayRecentItem, WhitespaceMergeGroup,
+    WhitespaceMergeReport, WindowPlacement, ARCHIVE_PATH_KEY, AUTO_UNPIN_DAYS_KEY,
+    AUTO_UNPIN_DAYS_RANGE, BLOCK_RISKY_CAPTURES_KEY, BROADCAST_BATCH_THRESHOLD_KEY,
+    BROADCAST_BATCH_THRESHOLD_RANGE, CAPTURE_BURST_THRESHOLD_KEY, CAPTURE_BURST_THRESHOLD_RANGE,
+    CAPTURE_FILTER_KEY, CAPTURE_FILTER_PATTERN_KEY, CATEGORY_TAGS_KEY, CLIPBOARD_CLEAR_SECONDS_KEY,
+    CLIPBOARD_CLEAR_SECONDS_RANGE, CLIPBOARD_POLL_INTERVAL_MS, DB_BUSY_TIMEOUT, DB_FILE_NAME,
+    DB_LOCATION_FILE, DEFAULT_SENSITIVE_PATH_PATTERNS, EXCLUSION_PATTERNS_KEY,
+    FILTER_SHORTCUTS_KEY, HISTORY_SORT_KEY, IGNORE_OWN_WINDOW_COPIES_KEY, ITEM_TTL_MINUTES_KEY,
+    ITEM_TTL_MINUTES_RANGE, LOCALE_KEY, MERGE_TEXT_WINNER_KEY, MONITORING_ENABLED_KEY,
+    OPEN_WINDOW_SHORTCUT_KEY, PASTE_PREFIX_KEY, PASTE_SUFFIX_KEY, PAUSE_ON_LOCK_KEY,
+    PINNED_SORT_KEY, POLL_INTERVAL_MS_KEY, POLL_INTERVAL_MS_RANGE, REMEMBER_WINDOW_PLACEMENT_KEY,
+    RESET_DEDUP_ON_EMPTY_KEY, SANITIZE_ON_CAPTURE_KEY, SENSITIVE_PATH_PATTERNS_KEY,
+    SHORTCUT_TOGGLES_WINDOW_KEY, SOURCE_BLOCKLIST_KEY, TOGGLE_MONITORING_SHORTCUT_KEY,
+    TRAY_LEFT_CLICK_KEY, TRAY_MENU_KEY, TRAY_RECENT_COUNT_DEFAULT, TRAY_RECENT_COUNT_KEY,
+    TRAY_RECENT_COUNT_RANGE, USER_REGEX_SIZE_LIMIT, WINDOW_PLACEMENTS_KEY,
+};
+use crate::normalize::{normalize_for_storage, NormalizePolicy};
 use chrono::{Duration, Utc};
-use rusqlite::{params, Connection, OptionalExtension};
+use regex::{Regex, RegexBuilder};
+use rusqlite::types::Value;
+use rusqlite::{params, params_from_iter, Connection, OpenFlags, OptionalExtension};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::Ordering;
+use uuid::Uuid;
+
+// 确定数据库文件路径：数据目录中的位置指针指向已存在的文件时使用该文件，否则使用数据目录中的默认文件。
+// 指针指向的文件不存在（如外接磁盘未连接）时同样回退到默认文件，避免启动失败
+pub(crate) fn resolve_db_path(data_dir: &Path) -> PathBuf {
+    std::fs::read_to_string(data_dir.join(DB_LOCATION_FILE))
+        .ok()
+        .map(|content| PathBuf::from(content.trim()))
+        .filter(|path| !path.as_os_str().is_empty() && path.is_file())
+        .unwrap_or_else(|| data_dir.join(DB_FILE_NAME))
+}
+
+// 打开数据库并设置连接参数：
+// 1. 使用 WAL 日志：写入追加到数据库旁的 -wal 文件，读取不会被写入阻塞（归档搜索、支持包导出等另开的连接也能并发读取），
+//    代价是数据库旁多出 -wal / -shm 两个文件，迁移或删除数据库时需要一并处理；
+//    WAL 模式会记录在数据库文件中，文件系统不支持时 SQLite 保持原有的日志模式，不影响使用；
+// 2. WAL 下 synchronous = NORMAL 只在检查点时同步磁盘，断电可能丢失最近几次写入，但不会损坏数据库，
+//    对剪贴板历史来说换取更少的磁盘同步是值得的；
+// 3. 遇到其他连接持有锁时最多等待 DB_BUSY_TIMEOUT，而不是立即失败
+pub(crate) fn open_db(path: &Path) -> Result<Connection, rusqlite::Error> {
+    let conn = Connection::open(path)?;
+    conn.pragma_update_and_check(None, "journal_mode", "WAL", |row| row.get::<_, String>(0))?;
+    conn.pragma_update(None, "synchronous", "NORMAL")?;
+    conn.busy_timeout(DB_BUSY_TIMEOUT)?;
+    Ok(conn)
+}
 
 // 统一执行表结构初始化，保证首次启动即可持久化
 pub(crate) fn init_db(conn: &Connection) -> Result<(), rusqlite::Error> {
+    // 开启外键约束，删除条目时由 SQLite 级联清理其标签关联
+    conn.execute_batch("PRAGMA foreign_keys = ON;")?;
     conn.execute_batch(
         "
         CREATE TABLE IF NOT EXISTS clipboard_items (
             id TEXT PRIMARY KEY,
-            text TEXT NOT NULL UNIQUE,
+            text TEXT NOT NULL,
+            text_hash TEXT NOT NULL,
             created_at TEXT NOT NULL,
             updated_at TEXT NOT NULL,
             pinned INTEGER NOT NULL DEFAULT 0,
-            count INTEGER NOT NULL DEFAULT 1
+            count INTEGER NOT NULL DEFAULT 1,
+            position INTEGER,
+            pinned_at TEXT,
+            script TEXT,
+            content_type TEXT,
+            transform TEXT,
+            sanitized INTEGER NOT NULL DEFAULT 0,
+            risky INTEGER NOT NULL DEFAULT 0,
+            expires_at TEXT,
+            kind TEXT NOT NULL DEFAULT 'text',
+            image_png BLOB,
+            thumbnail_png BLOB,
+            source_app TEXT,
+            note TEXT
         );
+        -- 游标分页按 (updated_at, id) 倒序扫描，索引保证翻到很深的位置时依然只读取一页的数据
+        CREATE INDEX IF NOT EXISTS idx_clipboard_items_updated_at_id
+            ON clipboard_items (updated_at, id);
         CREATE TABLE IF NOT EXISTS app_settings (
             key TEXT PRIMARY KEY,
             value TEXT NOT NULL
         );
+        CREATE TABLE IF NOT EXISTS item_tags (
+            item_id TEXT NOT NULL REFERENCES clipboard_items(id) ON DELETE CASCADE,
+            tag TEXT NOT NULL,
+            PRIMARY KEY (item_id, tag)
+        );
+        -- 标签本身的展示属性（颜色、排列顺序），条目与标签的对应关系仍保存在 item_tags 中
+        CREATE TABLE IF NOT EXISTS tags (
+            name TEXT PRIMARY KEY,
+            color TEXT,
+            position INTEGER
+        );
+        CREATE TABLE IF NOT EXISTS autotag_rules (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            pattern TEXT NOT NULL,
+            tag TEXT NOT NULL
+        );
+        ",
+    )?;
+    ensure_fts_index(conn)?;
+    Ok(())
+}
+
+// 表结构迁移：按顺序执行，第 n 项执行完后数据库版本（PRAGMA user_version）即为 n。
+// 旧版本数据库中的表不会被 CREATE TABLE IF NOT EXISTS 更新，新增的列都通过迁移补齐；
+// init_db 已按最新结构建表，新建的数据库同样会从版本 0 执行全部迁移，因此每一项都必须可以重复执行
+type Migration = fn(&Connection) -> Result<(), rusqlite::Error>;
+
+const MIGRATIONS: &[Migration] = &[
+    // 1：引入版本号之前陆续新增的列，新增识别列时为已有条目补算一次识别结果，之后由写入路径维护
+    |conn| {
+        ensure_column(conn, "clipboard_items", "position", "INTEGER")?;
+        ensure_column(conn, "clipboard_items", "pinned_at", "TEXT")?;
+        let added_script = ensure_column(conn, "clipboard_items", "script", "TEXT")?;
+        let added_content_type = ensure_column(conn, "clipboard_items", "content_type", "TEXT")?;
+        // 粘贴变换只在写回剪贴板时读取，不在 CLIPBOARD_ITEM_COLUMNS 中，旧的归档库无需补列
+        ensure_column(conn, "clipboard_items", "transform", "TEXT")?;
+        ensure_column(
+            conn,
+            "clipboard_items",
+            "sanitized",
+            "INTEGER NOT NULL DEFAULT 0",
+        )?;
+        ensure_column(
+            conn,
+            "clipboard_items",
+            "risky",
+            "INTEGER NOT NULL DEFAULT 0",
+        )?;
+        if added_script || added_content_type {
+            backfill_detection(conn)?;
+        }
+        Ok(())
+    },
+    // 2：条目的到期时间
+    |conn| ensure_column(conn, "clipboard_items", "expires_at", "TEXT").map(|_| ()),
+    // 3：去重改用正文哈希上的唯一索引，去掉正文本身的唯一约束（大段文本的索引会让数据库膨胀、写入变慢）。
+    // SQLite 无法直接删除列约束，只能新建表、复制数据后替换旧表；替换时旧表上的索引与全文索引触发器一并被删除，需重新创建
+    |conn| {
+        if ensure_column_exists(conn, "clipboard_items", "text_hash")? {
+            return conn.execute_batch(
+                "CREATE UNIQUE INDEX IF NOT EXISTS idx_clipboard_items_text_hash
+                    ON clipboard_items (text_hash);",
+            );
+        }
+        // 复制时先用 id 占位满足非空与唯一，再逐条补算哈希
+        conn.execute_batch(
+            "
+            CREATE TABLE clipboard_items_rebuild (
+                id TEXT PRIMARY KEY,
+                text TEXT NOT NULL,
+                text_hash TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL,
+                pinned INTEGER NOT NULL DEFAULT 0,
+                count INTEGER NOT NULL DEFAULT 1,
+                position INTEGER,
+                pinned_at TEXT,
+                script TEXT,
+                content_type TEXT,
+                transform TEXT,
+                sanitized INTEGER NOT NULL DEFAULT 0,
+                risky INTEGER NOT NULL DEFAULT 0,
+                expires_at TEXT
+            );
+            INSERT INTO clipboard_items_rebuild (
+                id, text, text_hash, created_at, updated_at, pinned, count, position, pinned_at,
+                script, content_type, transform, sanitized, risky, expires_at
+            )
+            SELECT
+                id, text, id, created_at, updated_at, pinned, count, position, pinned_at,
+                script, content_type, transform, sanitized, risky, expires_at
+            FROM clipboard_items;
+            DROP TABLE clipboard_items;
+            ALTER TABLE clipboard_items_rebuild RENAME TO clipboard_items;
+            ",
+        )?;
+        let rows = {
+            let mut stmt = conn.prepare("SELECT id, text FROM clipboard_items")?;
+            let mapped = stmt.query_map([], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+            })?;
+            mapped.collect::<Result<Vec<_>, _>>()?
+        };
+        for (id, text) in rows {
+            conn.execute(
+                "UPDATE clipboard_items SET text_hash = ?1 WHERE id = ?2",
+                params![text_hash(&text), id],
+            )?;
+        }
+        conn.execute_batch(
+            "
+            CREATE UNIQUE INDEX idx_clipboard_items_text_hash ON clipboard_items (text_hash);
+            CREATE INDEX IF NOT EXISTS idx_clipboard_items_updated_at_id
+                ON clipboard_items (updated_at, id);
+            ",
+        )?;
+        ensure_fts_index(conn)
+    },
+    // 4：图片条目。图片与缩略图以 PNG 保存在条目行中，随条目一起删除与过期
+    |conn| {
+        ensure_column(
+            conn,
+            "clipboard_items",
+            "kind",
+            "TEXT NOT NULL DEFAULT 'text'",
+        )?;
+        ensure_column(conn, "clipboard_items", "image_png", "BLOB")?;
+        ensure_column(conn, "clipboard_items", "thumbnail_png", "BLOB").map(|_| ())
+    },
+    // 5：条目的来源应用
+    |conn| ensure_column(conn, "clipboard_items", "source_app", "TEXT").map(|_| ()),
+    // 6：条目的备注
+    |conn| ensure_column(conn, "clipboard_items", "note", "TEXT").map(|_| ()),
+    // 7：图片条目移出全文索引：重建只索引文本条目的写入触发器，并删除已索引的图片说明
+    |conn| {
+        conn.execute_batch(
+            "
+            DROP TRIGGER IF EXISTS clipboard_items_fts_insert;
+            DELETE FROM clipboard_items_fts
+                WHERE id IN (SELECT id FROM clipboard_items WHERE kind <> 'text');
+            ",
+        )?;
+        ensure_fts_index(conn)
+    },
+];
+
+// 按数据库记录的版本执行尚未执行的迁移，每一项与版本号的更新在同一事务中提交，
+// 中途失败时数据库停留在上一个版本，下次启动从失败的那一项重试。
+// 版本号高于已知迁移数量说明数据库来自更新的应用版本，不做任何改动。
+// 重建表的迁移要求外键约束处于关闭状态（否则删除旧表时会级联删除全部标签），
+// 而外键开关在事务中无法修改，因此在执行迁移前关闭、结束后无论成功与否都重新开启
+pub(crate) fn migrate(conn: &mut Connection) -> Result<(), rusqlite::Error> {
+    conn.execute_batch("PRAGMA foreign_keys = OFF;")?;
+    let result = run_migrations(conn);
+    conn.execute_batch("PRAGMA foreign_keys = ON;")?;
+    result
+}
+
+fn run_migrations(conn: &mut Connection) -> Result<(), rusqlite::Error> {
+    let current: usize = conn.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+    for (index, migration) in MIGRATIONS.iter().enumerate().skip(current) {
+        let tx = conn.transaction()?;
+        migration(&tx)?;
+        tx.execute_batch(&format!("PRAGMA user_version = {}", index + 1))?;
+        tx.commit()?;
+    }
+    Ok(())
+}
+
+// 建立全文索引并用触发器与条目表保持同步：
+// 1. 索引表保存文本副本并以条目 id 关联（id 不参与分词），不依赖 rowid，VACUUM INTO 生成的副本同样可用；
+// 2. 使用 trigram 分词，中文等不以空格分隔的文字也能按子串检索，代价是少于 3 个字符的词无法走索引；
+// 3. 索引表首次创建时为已有条目补建索引，之后由触发器维护；
+// 4. 图片条目的正文只是“图片 宽×高”的说明，不写入索引，避免搜索“图片”时命中全部截图。
+//    首次建索引时的数据库都早于图片条目（还没有 kind 列），补建时无需按种类筛选
+fn ensure_fts_index(conn: &Connection) -> Result<(), rusqlite::Error> {
+    let exists: bool = conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'clipboard_items_fts')",
+        [],
+        |row| row.get(0),
+    )?;
+    conn.execute_batch(
+        "
+        CREATE VIRTUAL TABLE IF NOT EXISTS clipboard_items_fts
+            USING fts5(id UNINDEXED, text, tokenize = 'trigram');
+        CREATE TRIGGER IF NOT EXISTS clipboard_items_fts_insert
+            AFTER INSERT ON clipboard_items
+            WHEN new.kind = 'text'
+        BEGIN
+            INSERT INTO clipboard_items_fts (id, text) VALUES (new.id, new.text);
+        END;
+        CREATE TRIGGER IF NOT EXISTS clipboard_items_fts_update
+            AFTER UPDATE OF text ON clipboard_items
+        BEGIN
+            UPDATE clipboard_items_fts SET text = new.text WHERE id = old.id;
+        END;
+        CREATE TRIGGER IF NOT EXISTS clipboard_items_fts_delete
+            AFTER DELETE ON clipboard_items
+        BEGIN
+            DELETE FROM clipboard_items_fts WHERE id = old.id;
+        END;
         ",
     )?;
+    if !exists {
+        conn.execute(
+            "INSERT INTO clipboard_items_fts (id, text) SELECT id, text FROM clipboard_items",
+            [],
+        )?;
+    }
+    Ok(())
+}
+
+// 为所有条目重新计算文字体系与内容类型，仅在新增对应列时执行一次
+fn backfill_detection(conn: &Connection) -> Result<(), rusqlite::Error> {
+    let rows = {
+        let mut stmt = conn.prepare("SELECT id, text FROM clipboard_items")?;
+        let mapped = stmt.query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })?;
+        mapped.collect::<Result<Vec<_>, _>>()?
+    };
+    for (id, text) in rows {
+        conn.execute(
+            "UPDATE clipboard_items SET script = ?1, content_type = ?2 WHERE id = ?3",
+            params![
+                detect_script(&text),
+                detect_content_type(&text).map(ContentType::as_str),
+                id
+            ],
+        )?;
+    }
     Ok(())
 }
 
+// 计算条目正文的去重哈希（SHA-256 十六进制），条目表依靠这一列的唯一索引保证正文不重复
+pub(crate) fn text_hash(text: &str) -> String {
+    hex::encode(Sha256::digest(text.as_bytes()))
+}
+
+// 检查表中是否已存在指定列
+fn ensure_column_exists(
+    conn: &Connection,
+    table: &str,
+    column: &str,
+) -> Result<bool, rusqlite::Error> {
+    let mut stmt = conn.prepare(&format!("PRAGMA table_info({table})"))?;
+    let columns = stmt.query_map([], |row| row.get::<_, String>(1))?;
+    for name in columns {
+        if name? == column {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+// 检查表中是否已存在指定列，不存在时通过 ALTER TABLE 追加，保证重复执行也是安全的；
+// 返回本次是否新增了该列，便于调用方为旧数据补算新列的值
+fn ensure_column(
+    conn: &Connection,
+    table: &str,
+    column: &str,
+    definition: &str,
+) -> Result<bool, rusqlite::Error> {
+    if ensure_column_exists(conn, table, column)? {
+        return Ok(false);
+    }
+    conn.execute_batch(&format!(
+        "ALTER TABLE {table} ADD COLUMN {column} {definition}"
+    ))?;
+    Ok(true)
+}
+
 // 读取通用配置项，找不到时返回 None
 pub(crate) fn get_app_setting(
     conn: &Connection,
@@ -57,6 +400,470 @@ pub(crate) fn set_app_setting(
     Ok(())
 }
 
+// 读取带筛选预设的快捷键列表：未配置或 JSON 损坏时返回空列表，避免影响启动
+pub(crate) fn load_filter_shortcuts(
+    conn: &Connection,
+) -> Result<Vec<FilterShortcut>, rusqlite::Error> {
+    let raw = get_app_setting(conn, FILTER_SHORTCUTS_KEY)?;
+    Ok(raw
+        .and_then(|value| serde_json::from_str(&value).ok())
+        .unwrap_or_default())
+}
+
+// 读取全部全局快捷键配置，供启动注册与导入配置时比较新旧快捷键
+pub(crate) fn load_shortcut_bindings(
+    conn: &Connection,
+) -> Result<ShortcutBindings, rusqlite::Error> {
+    Ok(ShortcutBindings {
+        open_window: get_app_setting(conn, OPEN_WINDOW_SHORTCUT_KEY)?,
+        toggle_monitoring: get_app_setting(conn, TOGGLE_MONITORING_SHORTCUT_KEY)?,
+        filters: load_filter_shortcuts(conn)?,
+    })
+}
+
+// 读取托盘菜单配置：未设置或无法解析时使用默认菜单，读取结果总是经过规范化
+pub(crate) fn load_tray_menu(conn: &Connection) -> Result<Vec<TrayMenuAction>, rusqlite::Error> {
+    let raw = get_app_setting(conn, TRAY_MENU_KEY)?;
+    let actions: Vec<TrayMenuAction> = raw
+        .and_then(|value| serde_json::from_str(&value).ok())
+        .unwrap_or_else(|| TrayMenuAction::DEFAULT_MENU.to_vec());
+    Ok(TrayMenuAction::normalize(&actions))
+}
+
+// 读取托盘“最近复制”子菜单显示的条目数：未设置或超出范围时使用默认值
+pub(crate) fn load_tray_recent_count(conn: &Connection) -> Result<u64, rusqlite::Error> {
+    Ok(get_app_setting(conn, TRAY_RECENT_COUNT_KEY)?
+        .and_then(|value| value.parse::<u64>().ok())
+        .filter(|value| TRAY_RECENT_COUNT_RANGE.contains(value))
+        .unwrap_or(TRAY_RECENT_COUNT_DEFAULT))
+}
+
+// 读取托盘子菜单中的条目：取最近更新的 limit 条，其中固定的条目排在前面，其余保持时间顺序
+pub(crate) fn load_tray_recent_items(
+    conn: &Connection,
+    limit: u64,
+) -> Result<Vec<TrayRecentItem>, rusqlite::Error> {
+    let mut stmt = conn.prepare(
+        "
+        SELECT id, text, content_type, pinned
+        FROM (
+            SELECT id, text, content_type, pinned, updated_at
+            FROM clipboard_items
+            ORDER BY updated_at DESC, id DESC
+            LIMIT ?1
+        )
+        ORDER BY pinned DESC, updated_at DESC, id DESC
+        ",
+    )?;
+    let rows = stmt.query_map(params![limit as i64], |row| {
+        let pinned_value: i64 = row.get(3)?;
+        Ok(TrayRecentItem {
+            id: row.get(0)?,
+            text: row.get(1)?,
+            content_type: row.get(2)?,
+            pinned: pinned_value != 0,
+        })
+    })?;
+    rows.collect()
+}
+
+// 读取自动归类的类别→标签映射：未设置或无法解析时使用默认映射
+pub(crate) fn load_category_tags(
+    conn: &Connection,
+) -> Result<BTreeMap<ContentCategory, String>, rusqlite::Error> {
+    let raw = get_app_setting(conn, CATEGORY_TAGS_KEY)?;
+    Ok(raw
+        .and_then(|value| serde_json::from_str(&value).ok())
+        .unwrap_or_else(ContentCategory::default_tags))
+}
+
+// 给所有还没有标签的条目按内容类别与自动标签规则补上标签，在一个事务中完成，返回被加上标签的条目数。
+// 类别未配置标签名时跳过该类别
+pub(crate) fn auto_categorize_internal(state: &AppState) -> Result<usize, String> {
+    // 与捕获时一样，先复制一份规则并释放锁，避免与数据库锁交叉持有
+    let rules: Vec<(Regex, String)> = lock_or_recover(&state.autotag_rules)
+        .iter()
+        .map(|rule| (rule.regex.clone(), rule.tag.clone()))
+        .collect();
+    let mut conn = lock_or_recover(&state.db);
+    let category_tags = load_category_tags(&conn).map_err(|err| err.to_string())?;
+    let tx = conn.transaction().map_err(|err| err.to_string())?;
+    let untagged: Vec<(String, String, Option<String>)> = {
+        let mut stmt = tx
+            .prepare(
+                "
+                SELECT id, text, content_type FROM clipboard_items
+                WHERE NOT EXISTS (SELECT 1 FROM item_tags WHERE item_tags.item_id = clipboard_items.id)
+                ",
+            )
+            .map_err(|err| err.to_string())?;
+        let rows = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))
+            .map_err(|err| err.to_string())?;
+        rows.collect::<Result<Vec<_>, _>>()
+            .map_err(|err| err.to_string())?
+    };
+    let mut tagged = 0;
+    for (id, text, content_type) in untagged {
+        let category_tag = detect_category(&text, content_type.as_deref())
+            .and_then(|category| category_tags.get(&category));
+        let rule_tags = rules
+            .iter()
+            .filter(|(regex, _)| regex.is_match(&text))
+            .map(|(_, tag)| tag);
+        let mut added = false;
+        for tag in category_tag.into_iter().chain(rule_tags) {
+            added |= tx
+                .execute(
+                    "INSERT OR IGNORE INTO item_tags (item_id, tag) VALUES (?1, ?2)",
+                    params![id, tag],
+                )
+                .map_err(|err| err.to_string())?
+                > 0;
+        }
+        if added {
+            tagged += 1;
+        }
+    }
+    tx.commit().map_err(|err| err.to_string())?;
+    Ok(tagged)
+}
+
+// 删除全部未固定条目，返回被删除的 id 供调用方广播
+pub(crate) fn clear_unpinned_items(conn: &mut Connection) -> Result<Vec<String>, rusqlite::Error> {
+    let tx = conn.transaction()?;
+    let ids = {
+        let mut stmt = tx.prepare("SELECT id FROM clipboard_items WHERE pinned = 0")?;
+        let rows = stmt.query_map([], |row| row.get::<_, String>(0))?;
+        rows.collect::<Result<Vec<_>, _>>()?
+    };
+    tx.execute("DELETE FROM clipboard_items WHERE pinned = 0", [])?;
+    tx.commit()?;
+    Ok(ids)
+}
+
+// 条目查询统一使用的列清单，顺序必须与 map_row 中的下标保持一致；原图数据较大，只在写回剪贴板时单独读取。
+// 最后一列用子查询把条目的标签按名称排序拼成 JSON 数组，子查询中的 id 指外层查询的条目
+pub(crate) const CLIPBOARD_ITEM_COLUMNS: &str = "id, text, created_at, updated_at, pinned, count, position, pinned_at, script, content_type, sanitized, risky, expires_at, kind, source_app, note, \
+     (SELECT json_group_array(tag ORDER BY tag) FROM item_tags WHERE item_tags.item_id = id) AS tags";
+// 归档库中的条目列：归档表沿用早期结构，不含捕获时才有意义的 sanitized / risky 标记，归档条目也不会过期
+const ARCHIVE_ITEM_COLUMNS: &str =
+    "id, text, created_at, updated_at, pinned, count, position, pinned_at, script, content_type";
+
+// 读取整个历史列表的排序方式
+pub(crate) fn load_history_sort_mode(
+    conn: &Connection,
+) -> Result<HistorySortMode, rusqlite::Error> {
+    let setting = get_app_setting(conn, HISTORY_SORT_KEY)?;
+    Ok(HistorySortMode::from_setting(setting.as_deref()))
+}
+
+// 历史列表排序：固定条目置顶并按手动位置排列（未设置位置的排在后面），
+// 其余固定条目按设置选择固定时间或更新时间倒序；非固定条目默认按更新时间倒序，
+// 手动排序模式下与固定条目一样先按手动位置排列。默认模式忽略非固定条目残留的位置，切回手动模式时仍可恢复
+pub(crate) fn history_order_by(conn: &Connection) -> Result<String, rusqlite::Error> {
+    let position = match load_history_sort_mode(conn)? {
+        HistorySortMode::Recent => "CASE WHEN pinned = 1 THEN position END",
+        HistorySortMode::Manual => "position",
+    };
+    let pinned_setting = get_app_setting(conn, PINNED_SORT_KEY)?;
+    let pinned_at = match PinnedSortMode::from_setting(pinned_setting.as_deref()) {
+        PinnedSortMode::UpdatedAt => "",
+        PinnedSortMode::PinnedAt => "pinned_at DESC, ",
+    };
+    Ok(format!(
+        "pinned DESC, {position} IS NULL, {position} ASC, {pinned_at}updated_at DESC"
+    ))
+}
+
+// 编译用户输入的正则（自动打标签规则、自定义捕获过滤）：限制编译后的体积，避免过于复杂的表达式拖慢每次捕获
+pub(crate) fn compile_user_regex(pattern: &str) -> Result<Regex, AppError> {
+    RegexBuilder::new(pattern)
+        .size_limit(USER_REGEX_SIZE_LIMIT)
+        .build()
+        .map_err(|err| AppError::InvalidInput(Reason::InvalidRegex(err.to_string())))
+}
+
+// 读取捕获过滤设置（模式与自定义正则原文），供设置页展示
+pub(crate) fn load_capture_filter_settings(
+    conn: &Connection,
+) -> Result<CaptureFilterSettings, rusqlite::Error> {
+    let mode = get_app_setting(conn, CAPTURE_FILTER_KEY)?;
+    Ok(CaptureFilterSettings {
+        mode: CaptureFilterMode::from_setting(mode.as_deref()),
+        pattern: get_app_setting(conn, CAPTURE_FILTER_PATTERN_KEY)?,
+    })
+}
+
+// 读取并编译捕获过滤规则，供启动时初始化 AppState；已保存的正则无法编译时不做过滤，避免阻断启动
+pub(crate) fn load_capture_filter(conn: &Connection) -> Result<CaptureFilter, rusqlite::Error> {
+    let settings = load_capture_filter_settings(conn)?;
+    let regex = match settings.mode {
+        CaptureFilterMode::CustomRegex => settings
+            .pattern
+            .as_deref()
+            .and_then(|pattern| compile_user_regex(pattern).ok()),
+        _ => None,
+    };
+    Ok(CaptureFilter {
+        mode: settings.mode,
+        regex,
+    })
+}
+
+// 读取运行时缓存在 AppState 中的各项设置，启动与导入配置后共用：
+// 数值超出允许范围（如手动改库）时视为未开启，开关类设置无法解析时使用各自的默认值
+pub(crate) fn load_runtime_settings(conn: &Connection) -> Result<RuntimeSettings, rusqlite::Error> {
+    let number_in = |key: &str, range: &std::ops::RangeInclusive<u64>| {
+        get_app_setting(conn, key).map(|value| {
+            value
+                .and_then(|value| value.parse::<u64>().ok())
+                .filter(|value| range.contains(value))
+                .unwrap_or(0)
+        })
+    };
+    let flag_or = |key: &str, default: bool| {
+        get_app_setting(conn, key).map(|value| {
+            value
+                .and_then(|value| value.parse::<bool>().ok())
+                .unwrap_or(default)
+        })
+    };
+    let tray_left_click = get_app_setting(conn, TRAY_LEFT_CLICK_KEY)?;
+    Ok(RuntimeSettings {
+        // 锁屏暂停默认开启，只有明确关闭过才不暂停
+        pause_on_lock: flag_or(PAUSE_ON_LOCK_KEY, true)?,
+        // 空剪贴板重置去重基准默认开启，只有明确关闭过才保持旧行为
+        reset_dedup_on_empty: flag_or(RESET_DEDUP_ON_EMPTY_KEY, true)?,
+        // 忽略应用内复制默认开启，只有明确关闭过才记录
+        ignore_own_window_copies: flag_or(IGNORE_OWN_WINDOW_COPIES_KEY, true)?,
+        // 快捷键切换窗口默认关闭，保持按下快捷键只负责唤起窗口的旧行为
+        shortcut_toggles_window: flag_or(SHORTCUT_TOGGLES_WINDOW_KEY, false)?,
+        // 清理排版字符会改变复制的原文，默认关闭
+        sanitize_on_capture: flag_or(SANITIZE_ON_CAPTURE_KEY, false)?,
+        // 敏感路径默认只提醒，拦截会让用户找不到刚复制的内容，需要明确开启
+        block_risky_captures: flag_or(BLOCK_RISKY_CAPTURES_KEY, false)?,
+        clipboard_clear_seconds: number_in(
+            CLIPBOARD_CLEAR_SECONDS_KEY,
+            &CLIPBOARD_CLEAR_SECONDS_RANGE,
+        )?,
+        auto_unpin_days: number_in(AUTO_UNPIN_DAYS_KEY, &AUTO_UNPIN_DAYS_RANGE)?,
+        item_ttl_minutes: number_in(ITEM_TTL_MINUTES_KEY, &ITEM_TTL_MINUTES_RANGE)?,
+        capture_burst_threshold: number_in(
+            CAPTURE_BURST_THRESHOLD_KEY,
+            &CAPTURE_BURST_THRESHOLD_RANGE,
+        )?,
+        broadcast_batch_threshold: number_in(
+            BROADCAST_BATCH_THRESHOLD_KEY,
+            &BROADCAST_BATCH_THRESHOLD_RANGE,
+        )?,
+        // 轮询间隔始终生效，未设置或超出范围时回到默认值
+        poll_interval_ms: match number_in(POLL_INTERVAL_MS_KEY, &POLL_INTERVAL_MS_RANGE)? {
+            0 => CLIPBOARD_POLL_INTERVAL_MS,
+            value => value,
+        },
+        tray_left_click: TrayLeftClickAction::from_setting(tray_left_click.as_deref()),
+        locale: Locale::from_setting(get_app_setting(conn, LOCALE_KEY)?.as_deref()),
+        capture_filter: load_capture_filter(conn)?,
+        sensitive_path_rules: load_sensitive_path_rules(conn)?,
+        exclusion_rules: load_exclusion_rules(conn)?,
+        source_blocklist: load_source_blocklist(conn)?,
+    })
+}
+
+// 读取 app_settings 中的全部配置项，按键名排序，用于导出配置
+pub(crate) fn list_app_settings(
+    conn: &Connection,
+) -> Result<BTreeMap<String, String>, rusqlite::Error> {
+    let mut stmt = conn.prepare("SELECT key, value FROM app_settings")?;
+    let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+    rows.collect()
+}
+
+// 读取写回剪贴板时使用的前缀 / 后缀设置，未设置的部分视为空字符串
+pub(crate) fn load_paste_wrapper(conn: &Connection) -> Result<PasteWrapper, rusqlite::Error> {
+    Ok(PasteWrapper {
+        prefix: get_app_setting(conn, PASTE_PREFIX_KEY)?.unwrap_or_default(),
+        suffix: get_app_setting(conn, PASTE_SUFFIX_KEY)?.unwrap_or_default(),
+    })
+}
+
+// 读取剪贴板监听开关，未设置时默认开启，启动时用于恢复上次退出前的监听状态
+pub(crate) fn load_monitoring_enabled(conn: &Connection) -> Result<bool, rusqlite::Error> {
+    Ok(get_app_setting(conn, MONITORING_ENABLED_KEY)?
+        .and_then(|value| value.parse::<bool>().ok())
+        .unwrap_or(true))
+}
+
+// 读取是否按显示器布局记住主窗口位置，未设置时默认开启
+pub(crate) fn load_remember_window_placement(conn: &Connection) -> Result<bool, rusqlite::Error> {
+    Ok(get_app_setting(conn, REMEMBER_WINDOW_PLACEMENT_KEY)?
+        .and_then(|value| value.parse::<bool>().ok())
+        .unwrap_or(true))
+}
+
+// 读取各显示器布局下记住的主窗口位置：未设置或无法解析时视为尚未记住任何位置
+pub(crate) fn load_window_placements(
+    conn: &Connection,
+) -> Result<BTreeMap<String, WindowPlacement>, rusqlite::Error> {
+    let raw = get_app_setting(conn, WINDOW_PLACEMENTS_KEY)?;
+    Ok(raw
+        .and_then(|value| serde_json::from_str(&value).ok())
+        .unwrap_or_default())
+}
+
+// 记住指定显示器布局下的主窗口位置，其他布局已记住的位置保持不变
+pub(crate) fn save_window_placement(
+    conn: &Connection,
+    layout: &str,
+    placement: WindowPlacement,
+) -> Result<(), String> {
+    let mut placements = load_window_placements(conn).map_err(|err| err.to_string())?;
+    if placements.get(layout) == Some(&placement) {
+        return Ok(());
+    }
+    placements.insert(layout.to_string(), placement);
+    let raw = serde_json::to_string(&placements).map_err(|err| err.to_string())?;
+    set_app_setting(conn, WINDOW_PLACEMENTS_KEY, Some(raw)).map_err(|err| err.to_string())
+}
+
+// 读取全部自动打标签规则，按创建顺序返回
+pub(crate) fn list_autotag_rules(conn: &Connection) -> Result<Vec<AutotagRule>, rusqlite::Error> {
+    let mut stmt = conn.prepare("SELECT id, pattern, tag FROM autotag_rules ORDER BY id ASC")?;
+    let rows = stmt.query_map([], |row| {
+        Ok(AutotagRule {
+            id: row.get(0)?,
+            pattern: row.get(1)?,
+            tag: row.get(2)?,
+        })
+    })?;
+    let mut rules = Vec::new();
+    for row in rows {
+        rules.push(row?);
+    }
+    Ok(rules)
+}
+
+// 读取并编译全部规则用于缓存；个别规则无法编译时跳过，避免一条坏规则导致整体失效
+pub(crate) fn load_compiled_autotag_rules(
+    conn: &Connection,
+) -> Result<Vec<CompiledAutotagRule>, rusqlite::Error> {
+    Ok(list_autotag_rules(conn)?
+        .into_iter()
+        .filter_map(|rule| {
+            compile_user_regex(&rule.pattern)
+                .ok()
+                .map(|regex| CompiledAutotagRule {
+                    regex,
+                    tag: rule.tag,
+                })
+        })
+        .collect())
+}
+
+// 编译敏感路径规则：路径大小写不敏感（Windows、macOS 文件系统默认如此），任一规则无效时返回错误
+pub(crate) fn compile_sensitive_path_rules(
+    patterns: &[String],
+) -> Result<Vec<SensitivePathRule>, AppError> {
+    patterns
+        .iter()
+        .map(|pattern| {
+            RegexBuilder::new(pattern)
+                .case_insensitive(true)
+                .size_limit(USER_REGEX_SIZE_LIMIT)
+                .build()
+                .map(|regex| SensitivePathRule {
+                    pattern: pattern.clone(),
+                    regex,
+                })
+                .map_err(|err| {
+                    AppError::InvalidInput(Reason::InvalidSensitivePathRule(
+                        pattern.clone(),
+                        err.to_string(),
+                    ))
+                })
+        })
+        .collect()
+}
+
+// 读取敏感路径规则原文：未设置或无法解析时使用内置规则；保存为空数组表示不检查
+pub(crate) fn load_sensitive_path_patterns(
+    conn: &Connection,
+) -> Result<Vec<String>, rusqlite::Error> {
+    let raw = get_app_setting(conn, SENSITIVE_PATH_PATTERNS_KEY)?;
+    Ok(raw
+        .and_then(|value| serde_json::from_str(&value).ok())
+        .unwrap_or_else(|| {
+            DEFAULT_SENSITIVE_PATH_PATTERNS
+                .iter()
+                .map(|pattern| pattern.to_string())
+                .collect()
+        }))
+}
+
+// 读取并编译敏感路径规则用于缓存；个别规则无法编译（如手动改库）时跳过，避免一条坏规则导致整体失效
+pub(crate) fn load_sensitive_path_rules(
+    conn: &Connection,
+) -> Result<Vec<SensitivePathRule>, rusqlite::Error> {
+    Ok(load_sensitive_path_patterns(conn)?
+        .into_iter()
+        .filter_map(|pattern| compile_sensitive_path_rules(&[pattern]).ok())
+        .flatten()
+        .collect())
+}
+
+// 编译排除规则，任一规则无效时返回带规则原文的错误，用于保存前校验
+pub(crate) fn compile_exclusion_patterns(patterns: &[String]) -> Result<Vec<Regex>, AppError> {
+    patterns
+        .iter()
+        .map(|pattern| {
+            RegexBuilder::new(pattern)
+                .size_limit(USER_REGEX_SIZE_LIMIT)
+                .build()
+                .map_err(|err| {
+                    AppError::InvalidInput(Reason::InvalidExclusionRule(
+                        pattern.clone(),
+                        err.to_string(),
+                    ))
+                })
+        })
+        .collect()
+}
+
+// 读取排除规则原文：未设置或无法解析时视为没有规则
+pub(crate) fn load_exclusion_patterns(conn: &Connection) -> Result<Vec<String>, rusqlite::Error> {
+    let raw = get_app_setting(conn, EXCLUSION_PATTERNS_KEY)?;
+    Ok(raw
+        .and_then(|value| serde_json::from_str(&value).ok())
+        .unwrap_or_default())
+}
+
+// 读取并编译排除规则用于缓存；个别规则无法编译（如手动改库）时跳过，其余规则照常生效
+pub(crate) fn load_exclusion_rules(conn: &Connection) -> Result<Vec<Regex>, rusqlite::Error> {
+    Ok(load_exclusion_patterns(conn)?
+        .into_iter()
+        .filter_map(|pattern| compile_exclusion_patterns(&[pattern]).ok())
+        .flatten()
+        .collect())
+}
+
+// 读取来源应用屏蔽列表：未设置或无法解析时视为不屏蔽任何应用
+pub(crate) fn load_source_blocklist(conn: &Connection) -> Result<Vec<String>, rusqlite::Error> {
+    let raw = get_app_setting(conn, SOURCE_BLOCKLIST_KEY)?;
+    Ok(raw
+        .and_then(|value| serde_json::from_str(&value).ok())
+        .unwrap_or_default())
+}
+
+// 标记条目为指向敏感文件的路径，由 watcher 在写入后调用
+pub(crate) fn mark_item_risky(conn: &Connection, id: &str) -> Result<(), rusqlite::Error> {
+    conn.execute(
+        "UPDATE clipboard_items SET risky = 1 WHERE id = ?1",
+        params![id],
+    )?;
+    Ok(())
+}
+
 // 将 SQLite 行数据映射成前端可用的结构
 pub(crate) fn map_row(row: &rusqlite::Row) -> Result<ClipboardItem, rusqlite::Error> {
     let pinned_value: i64 = row.get(4)?;
@@ -67,9 +874,94 @@ pub(crate) fn map_row(row: &rusqlite::Row) -> Result<ClipboardItem, rusqlite::Er
         updated_at: row.get(3)?,
         pinned: pinned_value != 0,
         count: row.get(5)?,
+        position: row.get(6)?,
+        pinned_at: row.get(7)?,
+        script: row.get(8)?,
+        content_type: row.get(9)?,
+        sanitized: row.get::<_, i64>(10)? != 0,
+        risky: row.get::<_, i64>(11)? != 0,
+        expires_at: row.get(12)?,
+        kind: ItemKind::from_column(&row.get::<_, String>(13)?),
+        source_app: row.get(14)?,
+        note: row.get(15)?,
+        tags: serde_json::from_str(&row.get::<_, String>(16)?).unwrap_or_default(),
     })
 }
 
+// 列出全部标签及其颜色与使用数量：包括已被条目使用的标签和设置过颜色 / 顺序的标签，
+// 设置过顺序的排在前面，其余按名称排列
+pub(crate) fn list_tags(conn: &Connection) -> Result<Vec<TagInfo>, rusqlite::Error> {
+    let mut stmt = conn.prepare(
+        "
+        SELECT
+            names.name,
+            tags.color,
+            tags.position,
+            (SELECT COUNT(*) FROM item_tags WHERE item_tags.tag = names.name)
+        FROM (
+            SELECT name FROM tags
+            UNION
+            SELECT tag AS name FROM item_tags
+        ) AS names
+        LEFT JOIN tags ON tags.name = names.name
+        ORDER BY tags.position IS NULL, tags.position, names.name
+        ",
+    )?;
+    let rows = stmt.query_map([], |row| {
+        Ok(TagInfo {
+            name: row.get(0)?,
+            color: row.get(1)?,
+            position: row.get(2)?,
+            item_count: row.get(3)?,
+        })
+    })?;
+    rows.collect()
+}
+
+// 按给定顺序重排标签：列出的标签依次排在前面，未列出的标签保持当前相对顺序接在后面
+pub(crate) fn reorder_tags_internal(
+    conn: &mut Connection,
+    ordered_tags: &[String],
+) -> Result<(), AppError> {
+    let tx = conn.transaction()?;
+    let current: Vec<String> = list_tags(&tx)?.into_iter().map(|tag| tag.name).collect();
+    let known: HashSet<&str> = current.iter().map(String::as_str).collect();
+    if let Some(missing) = ordered_tags
+        .iter()
+        .find(|tag| !known.contains(tag.as_str()))
+    {
+        return Err(AppError::InvalidInput(Reason::TagNotFound(missing.clone())));
+    }
+    let mut placed = HashSet::new();
+    let final_tags: Vec<&String> = ordered_tags
+        .iter()
+        .chain(current.iter())
+        .filter(|tag| placed.insert(tag.as_str()))
+        .collect();
+    for (index, tag) in final_tags.iter().enumerate() {
+        tx.execute(
+            "
+            INSERT INTO tags (name, position) VALUES (?1, ?2)
+            ON CONFLICT(name) DO UPDATE SET position = excluded.position
+            ",
+            params![tag, index as i64],
+        )?;
+    }
+    Ok(tx.commit()?)
+}
+
+// 按 id 读取单条记录，写入后回读最终状态时统一复用
+pub(crate) fn query_clipboard_item(
+    conn: &Connection,
+    id: &str,
+) -> Result<ClipboardItem, rusqlite::Error> {
+    conn.query_row(
+        &format!("SELECT {CLIPBOARD_ITEM_COLUMNS} FROM clipboard_items WHERE id = ?1"),
+        params![id],
+        map_row,
+    )
+}
+
 // 计算“保留天数”的截止时间字符串，统一使用 RFC3339 格式以便 SQLite 按字符串比较
 fn build_retention_cutoff(retention_days: i64) -> Option<String> {
     if retention_days <= 0 {
@@ -79,150 +971,528 @@ fn build_retention_cutoff(retention_days: i64) -> Option<String> {
     Some(cutoff.to_rfc3339())
 }
 
-// 按时间清理历史记录：仅删除未固定且早于截止时间的条目
+// 按时间清理历史记录：仅删除未固定且早于截止时间的条目，返回被删除的条目 id 便于通知前端同步移除
 pub(crate) fn prune_history_by_days(
     conn: &Connection,
     retention_days: i64,
-) -> Result<(), rusqlite::Error> {
+) -> Result<Vec<String>, rusqlite::Error> {
     let Some(cutoff) = build_retention_cutoff(retention_days) else {
-        return Ok(());
+        return Ok(Vec::new());
     };
-    conn.execute(
+    let mut stmt = conn.prepare(
         "
         DELETE FROM clipboard_items
         WHERE pinned = 0 AND updated_at < ?1
+        RETURNING id
+        ",
+    )?;
+    let rows = stmt.query_map(params![cutoff], |row| row.get(0))?;
+    let mut pruned_ids = Vec::new();
+    for row in rows {
+        pruned_ids.push(row?);
+    }
+    Ok(pruned_ids)
+}
+
+// 自动取消固定：固定时间早于 days 天前的条目取消固定并返回更新后的记录，days 为 0 时不处理。
+// 同时把更新时间刷新为当前时间，否则早已超过保留天数的条目会在下一次过期清理时被直接删除；
+// 没有固定时间的旧条目无法判断固定了多久，保持不变
+pub(crate) fn unpin_stale_items(
+    conn: &Connection,
+    days: u64,
+    ttl_minutes: u64,
+) -> Result<Vec<ClipboardItem>, rusqlite::Error> {
+    if days == 0 {
+        return Ok(Vec::new());
+    }
+    let now = Utc::now();
+    let cutoff = (now - Duration::days(days as i64)).to_rfc3339();
+    // 回到普通列表的条目与手动取消固定一样按默认存活时间重新计时
+    let mut stmt = conn.prepare(&format!(
+        "
+        UPDATE clipboard_items
+        SET pinned = 0, position = NULL, pinned_at = NULL, updated_at = ?2, expires_at = ?3
+        WHERE pinned = 1 AND pinned_at IS NOT NULL AND pinned_at < ?1
+        RETURNING {CLIPBOARD_ITEM_COLUMNS}
+        "
+    ))?;
+    let rows = stmt.query_map(
+        params![cutoff, now.to_rfc3339(), item_expires_at(ttl_minutes)],
+        map_row,
+    )?;
+    rows.collect()
+}
+
+// 按默认存活分钟数计算条目的到期时间，0 表示不过期；与其他时间列一样使用 RFC3339 以便按字符串比较
+pub(crate) fn item_expires_at(ttl_minutes: u64) -> Option<String> {
+    (ttl_minutes > 0).then(|| (Utc::now() + Duration::minutes(ttl_minutes as i64)).to_rfc3339())
+}
+
+// 删除已到期的非固定条目，返回被删除的条目 id 便于通知前端同步移除；标签关联由外键级联清理
+pub(crate) fn delete_expired_items(conn: &Connection) -> Result<Vec<String>, rusqlite::Error> {
+    let mut stmt = conn.prepare(
+        "
+        DELETE FROM clipboard_items
+        WHERE pinned = 0 AND expires_at IS NOT NULL AND expires_at < ?1
+        RETURNING id
         ",
-        params![cutoff],
     )?;
+    let rows = stmt.query_map(params![now_iso_string()], |row| row.get::<_, String>(0))?;
+    rows.collect()
+}
+
+// 数据库中一张表或一个索引的定义，表额外带上行数（索引为 None）
+#[derive(Debug, PartialEq)]
+struct SchemaEntry {
+    name: String,
+    sql: Option<String>,
+    row_count: Option<i64>,
+}
+
+// 读取库中全部表与索引的定义及表的行数，用于校验迁移后的副本与原库一致
+fn database_snapshot(conn: &Connection) -> Result<Vec<SchemaEntry>, rusqlite::Error> {
+    let entries = {
+        let mut stmt = conn.prepare(
+            "
+            SELECT type, name, sql FROM sqlite_master
+            WHERE name NOT LIKE 'sqlite_%'
+            ORDER BY type, name
+            ",
+        )?;
+        let mapped = stmt.query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, Option<String>>(2)?,
+            ))
+        })?;
+        mapped.collect::<Result<Vec<_>, _>>()?
+    };
+    let mut snapshot = Vec::with_capacity(entries.len());
+    for (kind, name, sql) in entries {
+        let row_count = if kind == "table" {
+            Some(
+                conn.query_row(&format!("SELECT COUNT(*) FROM \"{name}\""), [], |row| {
+                    row.get(0)
+                })?,
+            )
+        } else {
+            None
+        };
+        snapshot.push(SchemaEntry {
+            name,
+            sql,
+            row_count,
+        });
+    }
+    Ok(snapshot)
+}
+
+// 校验迁移得到的副本：完整性检查通过，且表结构与各表行数都与原库一致
+fn verify_db_copy(source: &Connection, target: &Path) -> Result<(), AppError> {
+    let copy = Connection::open(target)?;
+    let integrity: String = copy.query_row("PRAGMA integrity_check", [], |row| row.get(0))?;
+    if integrity != "ok" {
+        return Err(AppError::Other(Reason::DbCopyIntegrityFailed(integrity)));
+    }
+    let expected = database_snapshot(source)?;
+    let actual = database_snapshot(&copy)?;
+    if expected != actual {
+        return Err(AppError::Other(Reason::DbCopyMismatch));
+    }
+    Ok(())
+}
+
+// 把数据库迁移到 target，按以下顺序执行，任一步失败都会删除已生成的副本并保持使用原数据库：
+// 1. 先把 WAL 中的内容写回主库，再用 VACUUM INTO 生成一致的副本；
+// 2. 校验副本的完整性、表结构与行数，并在副本上打开新连接、完成初始化；
+// 3. 写入位置指针，保证下次启动打开的是新数据库；
+// 4. 替换当前连接，最后才删除原数据库文件（删除失败只会残留旧文件，不影响数据）
+pub(crate) fn move_db_internal(
+    conn: &mut Connection,
+    data_dir: &Path,
+    current: &Path,
+    target: &Path,
+) -> Result<(), AppError> {
+    if target.exists() {
+        return Err(AppError::InvalidInput(Reason::DbTargetExists));
+    }
+    if let Some(parent) = target.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let discard_copy = |err: AppError| {
+        let _ = std::fs::remove_file(target);
+        err
+    };
+    conn.query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |_| Ok(()))?;
+    conn.execute("VACUUM INTO ?1", params![target.to_string_lossy()])
+        .map_err(|err| discard_copy(err.into()))?;
+    verify_db_copy(conn, target).map_err(discard_copy)?;
+    let next = open_db(target)
+        .and_then(|next| init_db(&next).map(|_| next))
+        .map_err(|err| discard_copy(err.into()))?;
+    if let Err(err) = std::fs::write(
+        data_dir.join(DB_LOCATION_FILE),
+        target.to_string_lossy().as_bytes(),
+    ) {
+        // 先关闭副本上的连接再删除副本
+        drop(next);
+        return Err(discard_copy(err.into()));
+    }
+    // 替换后旧连接随之关闭，此时才能安全删除原数据库及其 WAL / SHM 文件
+    drop(std::mem::replace(conn, next));
+    let _ = std::fs::remove_file(current);
+    for suffix in ["-wal", "-shm"] {
+        let mut sidecar = current.as_os_str().to_owned();
+        sidecar.push(suffix);
+        let _ = std::fs::remove_file(sidecar);
+    }
     Ok(())
 }
 
+// 支持包中删除的设置：排除规则、捕获过滤与敏感路径规则可能直接写着密码片段，粘贴前后缀是用户输入的文本，
+// 来源黑名单、筛选快捷键（含搜索词）与归档路径会暴露使用习惯；删除后打开副本时按默认值处理
+const SUPPORT_BUNDLE_REDACTED_SETTINGS: &[&str] = &[
+    EXCLUSION_PATTERNS_KEY,
+    CAPTURE_FILTER_PATTERN_KEY,
+    SENSITIVE_PATH_PATTERNS_KEY,
+    PASTE_PREFIX_KEY,
+    PASTE_SUFFIX_KEY,
+    SOURCE_BLOCKLIST_KEY,
+    FILTER_SHORTCUTS_KEY,
+    ARCHIVE_PATH_KEY,
+];
+
+// 把数据库复制到指定路径并脱敏正文，用于问题反馈时附带的支持包：
+// 1. 先用 VACUUM INTO 生成一致的副本，不影响正在使用的主库；
+// 2. 删除副本中的全文索引：FTS5 的分段数据在合并前一直保留旧正文的三元组，只改写 text 无法清除；
+//    应用打开该副本时 init_db 会按脱敏后的正文重建索引；
+// 3. 在副本中把 text 替换为长度说明，text_hash 换成加了随机盐的哈希，清空用户填写的备注、粘贴变换、
+//    来源应用、图片与缩略图，保留时间戳、计数、固定状态等元数据以便复现排序与清理问题；
+//    同时删除自动标签规则与 SUPPORT_BUNDLE_REDACTED_SETTINGS 中的设置，规则原文同样可能包含敏感内容；
+// 4. 最后对副本再执行一次 VACUUM，清掉仍残留原文的空闲页。返回被脱敏的条目数量
+pub(crate) fn export_redacted_copy(
+    conn: &Connection,
+    target_path: &str,
+) -> Result<usize, rusqlite::Error> {
+    conn.execute("VACUUM INTO ?1", params![target_path])?;
+    let mut copy = Connection::open(target_path)?;
+    copy.execute_batch(
+        "
+        DROP TRIGGER IF EXISTS clipboard_items_fts_insert;
+        DROP TRIGGER IF EXISTS clipboard_items_fts_update;
+        DROP TRIGGER IF EXISTS clipboard_items_fts_delete;
+        DROP TABLE IF EXISTS clipboard_items_fts;
+        ",
+    )?;
+    let rows = {
+        let mut stmt = copy.prepare("SELECT id, text FROM clipboard_items")?;
+        let mapped = stmt.query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })?;
+        mapped.collect::<Result<Vec<_>, _>>()?
+    };
+    // 每个支持包使用独立的随机盐：哈希仍两两不同、满足唯一索引，但无法通过穷举常见取值（如短密码、PIN）反推原文
+    let salt = Uuid::new_v4().to_string();
+    let tx = copy.transaction()?;
+    for (id, text) in &rows {
+        tx.execute(
+            "UPDATE clipboard_items SET text = ?1, text_hash = ?2 WHERE id = ?3",
+            params![
+                format!("[redacted len={}]", text.chars().count()),
+                text_hash(&format!("{salt}{text}")),
+                id
+            ],
+        )?;
+    }
+    tx.execute(
+        "
+        UPDATE clipboard_items
+        SET note = NULL, transform = NULL, source_app = NULL, image_png = NULL, thumbnail_png = NULL
+        ",
+        [],
+    )?;
+    tx.execute("DELETE FROM autotag_rules", [])?;
+    for key in SUPPORT_BUNDLE_REDACTED_SETTINGS {
+        tx.execute("DELETE FROM app_settings WHERE key = ?1", params![key])?;
+    }
+    tx.commit()?;
+    copy.execute_batch("VACUUM")?;
+    Ok(rows.len())
+}
+
 // 新增或更新历史记录，遇到重复文本时只更新计数与更新时间
 pub(crate) fn upsert_clipboard_item_internal(
     state: &AppState,
-    item: ClipboardUpsertPayload,
+    mut item: ClipboardUpsertPayload,
     retention_days: i64,
-) -> Result<ClipboardItem, String> {
-    if item.text.trim().is_empty() {
-        return Err("剪贴板内容为空，已忽略写入".to_string());
-    }
-    let mut conn = state
-        .db
-        .lock()
-        .map_err(|_| "数据库连接被占用，无法写入历史记录".to_string())?;
-    let tx = conn.transaction().map_err(|err| err.to_string())?;
+) -> Result<ClipboardUpsertOutcome, AppError> {
+    // 所有捕获路径都传入原始文本，在这里统一规整，保证各路径写入的内容一致
+    let normalized = normalize_for_storage(&item.text, NormalizePolicy::capture(state));
+    if normalized.is_empty() {
+        return Err(AppError::EmptyContent);
+    }
+    item.text = normalized.display_text;
+    // 高频捕获的登记同样在获取数据库锁之前完成
+    let count_capped = register_capture_burst(state, &item.text);
+    // 先在规则缓存上完成匹配并立即释放锁，避免与数据库锁交叉持有
+    let matched_tags: Vec<String> = lock_or_recover(&state.autotag_rules)
+        .iter()
+        .filter(|rule| rule.regex.is_match(&item.text))
+        .map(|rule| rule.tag.clone())
+        .collect();
+    let mut conn = lock_or_recover(&state.db);
+    let tx = conn.transaction()?;
     let existing: Option<(String, String, bool, i64)> = tx
         .query_row(
             "
             SELECT id, created_at, pinned, count
             FROM clipboard_items
-            WHERE text = ?1
+            WHERE text_hash = ?1
             ",
-            params![item.text],
+            params![text_hash(&item.text)],
             |row| {
                 let pinned_value: i64 = row.get(2)?;
                 Ok((row.get(0)?, row.get(1)?, pinned_value != 0, row.get(3)?))
             },
         )
-        .optional()
-        .map_err(|err| err.to_string())?;
+        .optional()?;
+    // 手动排序模式下新捕获的条目放在非固定条目的最前面；还没有任何手动位置时保持为空，按时间排序同样在最前
+    let manual_sort = load_history_sort_mode(&tx)? == HistorySortMode::Manual;
+    // 超出高频上限时只刷新更新时间，次数保持不变，避免卡住的应用反复写入同一内容刷高“最常用”
+    let capped = count_capped && existing.is_some();
+    // 每次捕获都按当前的默认存活时间重新计时，固定条目不会过期
+    let expires_at = item_expires_at(state.item_ttl_minutes.load(Ordering::Relaxed));
     let target_id = if let Some((id, _created_at, pinned, count)) = existing {
+        let next_count = if capped { count } else { count + 1 };
         tx.execute(
             "
             UPDATE clipboard_items
-            SET updated_at = ?1, count = ?2, pinned = ?3
+            SET updated_at = ?1, count = ?2, pinned = ?3, sanitized = MAX(sanitized, ?5),
+                expires_at = CASE WHEN ?3 = 1 THEN NULL ELSE ?6 END,
+                source_app = COALESCE(?7, source_app)
             WHERE id = ?4
             ",
-            params![item.updated_at, count + 1, if pinned { 1 } else { 0 }, id],
-        )
-        .map_err(|err| err.to_string())?;
+            params![
+                item.updated_at,
+                next_count,
+                if pinned { 1 } else { 0 },
+                id,
+                if normalized.sanitized { 1 } else { 0 },
+                expires_at,
+                item.source_app
+            ],
+        )?;
         id
     } else {
         tx.execute(
             "
-            INSERT INTO clipboard_items (id, text, created_at, updated_at, pinned, count)
-            VALUES (?1, ?2, ?3, ?4, 0, 1)
+            INSERT INTO clipboard_items (
+                id, text, created_at, updated_at, pinned, count, position, script, content_type,
+                sanitized, expires_at, text_hash, source_app
+            )
+            VALUES (
+                ?1, ?2, ?3, ?4, 0, 1,
+                CASE WHEN ?5 = 1
+                    THEN (SELECT MIN(position) - 1 FROM clipboard_items WHERE pinned = 0)
+                END,
+                ?6, ?7, ?8, ?9, ?10, ?11
+            )
             ",
-            params![item.id, item.text, item.created_at, item.updated_at],
-        )
-        .map_err(|err| err.to_string())?;
+            params![
+                item.id,
+                item.text,
+                item.created_at,
+                item.updated_at,
+                if manual_sort { 1 } else { 0 },
+                detect_script(&item.text),
+                detect_content_type(&item.text).map(ContentType::as_str),
+                if normalized.sanitized { 1 } else { 0 },
+                expires_at,
+                text_hash(&item.text),
+                item.source_app
+            ],
+        )?;
         item.id
     };
-    if let Some(cutoff) = build_retention_cutoff(retention_days) {
+    for tag in &matched_tags {
         tx.execute(
-            "
-            DELETE FROM clipboard_items
-            WHERE pinned = 0 AND updated_at < ?1
-            ",
-            params![cutoff],
-        )
-        .map_err(|err| err.to_string())?;
+            "INSERT OR IGNORE INTO item_tags (item_id, tag) VALUES (?1, ?2)",
+            params![target_id, tag],
+        )?;
     }
-    let persisted = tx
+    // 清理过期条目时记录被删除的 id，事务提交后由调用方广播给所有窗口
+    let pruned_ids = prune_history_by_days(&tx, retention_days)?;
+    let unpinned = unpin_stale_items(
+        &tx,
+        state.auto_unpin_days.load(Ordering::Relaxed),
+        state.item_ttl_minutes.load(Ordering::Relaxed),
+    )?;
+    let persisted = query_clipboard_item(&tx, &target_id)?;
+    tx.commit()?;
+    Ok(ClipboardUpsertOutcome {
+        item: persisted,
+        pruned_ids,
+        count_capped: capped,
+        unpinned,
+    })
+}
+
+// 新增或更新图片条目：按像素内容的哈希去重（保存在 text_hash 列，与文本条目共用唯一索引），
+// 重复捕获同一图片时只更新计数与更新时间。content_hash 由调用方计算，与 watcher 的去重键保持一致。
+// PNG 编码与缩略图在获取数据库锁之前完成，大图编码期间不阻塞其他命令；自动标签规则只针对文本，不作用于图片
+pub(crate) fn upsert_image_item_internal(
+    state: &AppState,
+    image: &ClipboardImage,
+    content_hash: &str,
+    source_app: Option<&str>,
+    retention_days: i64,
+) -> Result<ClipboardUpsertOutcome, AppError> {
+    let png = encode_png(image)?;
+    let thumbnail_png = encode_png(&build_thumbnail(image))?;
+    let label = image_item_label(image);
+    let count_capped = register_capture_burst(state, &image_dedup_key(content_hash));
+    let now = now_iso_string();
+    let mut conn = lock_or_recover(&state.db);
+    let tx = conn.transaction()?;
+    let existing: Option<(String, bool, i64)> = tx
         .query_row(
+            "SELECT id, pinned, count FROM clipboard_items WHERE text_hash = ?1",
+            params![content_hash],
+            |row| {
+                let pinned_value: i64 = row.get(1)?;
+                Ok((row.get(0)?, pinned_value != 0, row.get(2)?))
+            },
+        )
+        .optional()?;
+    let manual_sort = load_history_sort_mode(&tx)? == HistorySortMode::Manual;
+    let capped = count_capped && existing.is_some();
+    let expires_at = item_expires_at(state.item_ttl_minutes.load(Ordering::Relaxed));
+    let target_id = if let Some((id, pinned, count)) = existing {
+        let next_count = if capped { count } else { count + 1 };
+        tx.execute(
             "
-            SELECT id, text, created_at, updated_at, pinned, count
-            FROM clipboard_items
-            WHERE id = ?1
+            UPDATE clipboard_items
+            SET updated_at = ?1, count = ?2,
+                expires_at = CASE WHEN ?3 = 1 THEN NULL ELSE ?4 END,
+                source_app = COALESCE(?6, source_app)
+            WHERE id = ?5
             ",
-            params![target_id],
-            map_row,
-        )
-        .map_err(|err| err.to_string())?;
-    tx.commit().map_err(|err| err.to_string())?;
-    Ok(persisted)
+            params![
+                now,
+                next_count,
+                if pinned { 1 } else { 0 },
+                expires_at,
+                id,
+                source_app
+            ],
+        )?;
+        id
+    } else {
+        let id = Uuid::new_v4().to_string();
+        tx.execute(
+            "
+            INSERT INTO clipboard_items (
+                id, text, created_at, updated_at, pinned, count, position, expires_at, text_hash,
+                kind, image_png, thumbnail_png, source_app
+            )
+            VALUES (
+                ?1, ?2, ?3, ?3, 0, 1,
+                CASE WHEN ?4 = 1
+                    THEN (SELECT MIN(position) - 1 FROM clipboard_items WHERE pinned = 0)
+                END,
+                ?5, ?6, ?7, ?8, ?9, ?10
+            )
+            ",
+            params![
+                id,
+                label,
+                now,
+                if manual_sort { 1 } else { 0 },
+                expires_at,
+                content_hash,
+                ItemKind::Image.as_str(),
+                png,
+                thumbnail_png,
+                source_app
+            ],
+        )?;
+        id
+    };
+    let pruned_ids = prune_history_by_days(&tx, retention_days)?;
+    let unpinned = unpin_stale_items(
+        &tx,
+        state.auto_unpin_days.load(Ordering::Relaxed),
+        state.item_ttl_minutes.load(Ordering::Relaxed),
+    )?;
+    let persisted = query_clipboard_item(&tx, &target_id)?;
+    tx.commit()?;
+    Ok(ClipboardUpsertOutcome {
+        item: persisted,
+        pruned_ids,
+        count_capped: capped,
+        unpinned,
+    })
 }
 
-// 更新条目文本，若文本重复则合并计数并删除旧条目
+// 更新条目文本，若文本与其他条目重复则合并为一条，按设置决定保留已有条目还是被编辑的条目
 pub(crate) fn update_clipboard_item_text_internal(
     state: &AppState,
     id: String,
     text: String,
     updated_at: String,
-) -> Result<ClipboardUpdateResult, String> {
-    let trimmed = text.trim();
-    if trimmed.is_empty() {
-        return Err("剪贴板内容为空，已忽略保存".to_string());
-    }
-    let mut conn = state
-        .db
-        .lock()
-        .map_err(|_| "数据库连接被占用，无法更新内容".to_string())?;
-    let tx = conn.transaction().map_err(|err| err.to_string())?;
-    let source: Option<(String, String, bool, i64)> = tx
+) -> Result<ClipboardUpdateResult, AppError> {
+    let normalized = normalize_for_storage(&text, NormalizePolicy::STORAGE);
+    if normalized.is_empty() {
+        return Err(AppError::EmptyContent);
+    }
+    let trimmed = normalized.display_text.as_str();
+    let mut conn = lock_or_recover(&state.db);
+    let tx = conn.transaction()?;
+    let source: Option<(String, String, bool, i64, String)> = tx
         .query_row(
             "
-            SELECT id, created_at, pinned, count
+            SELECT id, created_at, pinned, count, kind
             FROM clipboard_items
             WHERE id = ?1
             ",
             params![id],
             |row| {
                 let pinned_value: i64 = row.get(2)?;
-                Ok((row.get(0)?, row.get(1)?, pinned_value != 0, row.get(3)?))
+                Ok((
+                    row.get(0)?,
+                    row.get(1)?,
+                    pinned_value != 0,
+                    row.get(3)?,
+                    row.get(4)?,
+                ))
             },
         )
-        .optional()
-        .map_err(|err| err.to_string())?;
-    let Some((source_id, source_created_at, source_pinned, source_count)) = source else {
-        return Err("未找到需要更新的条目".to_string());
+        .optional()?;
+    let Some((source_id, source_created_at, source_pinned, source_count, source_kind)) = source
+    else {
+        return Err(AppError::NotFound);
     };
+    if ItemKind::from_column(&source_kind) == ItemKind::Image {
+        return Err(AppError::Unsupported(Reason::ImageTextNotEditable));
+    }
     let target: Option<(String, String, bool, i64)> = tx
         .query_row(
             "
             SELECT id, created_at, pinned, count
             FROM clipboard_items
-            WHERE text = ?1 AND id <> ?2
+            WHERE text_hash = ?1 AND id <> ?2
             ",
-            params![trimmed, source_id],
+            params![text_hash(trimmed), source_id],
             |row| {
                 let pinned_value: i64 = row.get(2)?;
                 Ok((row.get(0)?, row.get(1)?, pinned_value != 0, row.get(3)?))
             },
         )
-        .optional()
-        .map_err(|err| err.to_string())?;
+        .optional()?;
     if let Some((target_id, target_created_at, target_pinned, target_count)) = target {
+        // 合并规则与保留哪一条无关：计数相加、任一条固定即保持固定、创建时间取较早者、
+        // 更新时间取本次编辑时间、标签取并集；固定时间优先沿用保留条目自身的值
         let merged_count = source_count + target_count;
         let merged_pinned = source_pinned || target_pinned;
         let merged_created_at = if source_created_at <= target_created_at {
@@ -230,10 +1500,20 @@ pub(crate) fn update_clipboard_item_text_internal(
         } else {
             target_created_at
         };
+        let winner_setting = get_app_setting(&tx, MERGE_TEXT_WINNER_KEY)?;
+        let (kept_id, removed_id) = match MergeTextWinner::from_setting(winner_setting.as_deref()) {
+            MergeTextWinner::Existing => (target_id, source_id),
+            MergeTextWinner::Edited => (source_id, target_id),
+        };
         tx.execute(
             "
             UPDATE clipboard_items
-            SET count = ?1, pinned = ?2, created_at = ?3, updated_at = ?4
+            SET count = ?1, pinned = ?2, created_at = ?3, updated_at = ?4,
+                pinned_at = CASE WHEN ?2 = 1 THEN COALESCE(
+                    pinned_at,
+                    (SELECT pinned_at FROM clipboard_items WHERE id = ?6)
+                ) END,
+                expires_at = CASE WHEN ?2 = 1 THEN NULL ELSE expires_at END
             WHERE id = ?5
             ",
             params![
@@ -241,55 +1521,1110 @@ pub(crate) fn update_clipboard_item_text_internal(
                 if merged_pinned { 1 } else { 0 },
                 merged_created_at,
                 updated_at,
-                target_id
+                kept_id,
+                removed_id
             ],
-        )
-        .map_err(|err| err.to_string())?;
+        )?;
+        // 合并前把被删除条目的标签迁移到保留的条目上，避免删除时被级联清理
+        tx.execute(
+            "
+            INSERT OR IGNORE INTO item_tags (item_id, tag)
+            SELECT ?1, tag FROM item_tags WHERE item_id = ?2
+            ",
+            params![kept_id, removed_id],
+        )?;
         tx.execute(
             "DELETE FROM clipboard_items WHERE id = ?1",
-            params![source_id],
-        )
-        .map_err(|err| err.to_string())?;
-        let persisted = tx
-            .query_row(
-                "
-                SELECT id, text, created_at, updated_at, pinned, count
-                FROM clipboard_items
-                WHERE id = ?1
-                ",
-                params![target_id],
-                map_row,
-            )
-            .map_err(|err| err.to_string())?;
-        tx.commit().map_err(|err| err.to_string())?;
+            params![removed_id],
+        )?;
+        // 保留被编辑条目时，需在删除重复条目后再写入新文本，避免触发 text_hash 唯一约束；
+        // 编辑后的文本由用户手动输入，不再沿用捕获时的字符清理标记
+        tx.execute(
+            "
+            UPDATE clipboard_items
+            SET text = ?1, text_hash = ?5, script = ?2, content_type = ?3, sanitized = 0,
+                risky = 0
+            WHERE id = ?4
+            ",
+            params![
+                trimmed,
+                detect_script(trimmed),
+                detect_content_type(trimmed).map(ContentType::as_str),
+                kept_id,
+                text_hash(trimmed)
+            ],
+        )?;
+        let persisted = query_clipboard_item(&tx, &kept_id)?;
+        tx.commit()?;
         return Ok(ClipboardUpdateResult {
             item: persisted,
-            merged_id: Some(source_id),
+            merged_id: Some(removed_id),
         });
     }
     tx.execute(
         "
         UPDATE clipboard_items
-        SET text = ?1, updated_at = ?2
-        WHERE id = ?3
+        SET text = ?1, text_hash = ?6, updated_at = ?2, script = ?3, content_type = ?4,
+            sanitized = 0, risky = 0
+        WHERE id = ?5
         ",
-        params![trimmed, updated_at, source_id],
-    )
-    .map_err(|err| err.to_string())?;
-    let persisted = tx
-        .query_row(
+        params![
+            trimmed,
+            updated_at,
+            detect_script(trimmed),
+            detect_content_type(trimmed).map(ContentType::as_str),
+            source_id,
+            text_hash(trimmed)
+        ],
+    )?;
+    let persisted = query_clipboard_item(&tx, &source_id)?;
+    tx.commit()?;
+    Ok(ClipboardUpdateResult {
+        item: persisted,
+        merged_id: None,
+    })
+}
+
+// 交换两个固定条目的位置：用于键盘“上移/下移”，无需重新提交整个固定列表
+pub(crate) fn swap_item_positions_internal(
+    state: &AppState,
+    id_a: &str,
+    id_b: &str,
+) -> Result<Vec<ClipboardItem>, AppError> {
+    if id_a == id_b {
+        return Err(AppError::InvalidInput(Reason::SelfSwap));
+    }
+    let mut conn = lock_or_recover(&state.db);
+    let tx = conn.transaction()?;
+    for id in [id_a, id_b] {
+        let pinned: Option<i64> = tx
+            .query_row(
+                "SELECT pinned FROM clipboard_items WHERE id = ?1",
+                params![id],
+                |row| row.get(0),
+            )
+            .optional()?;
+        match pinned {
+            None => return Err(AppError::NotFound),
+            Some(0) => return Err(AppError::InvalidInput(Reason::SwapUnpinned)),
+            Some(_) => {}
+        }
+    }
+    // 尚未设置过位置的固定条目按当前展示顺序补齐位置，保证交换前后的顺序与用户所见一致
+    let missing: i64 = tx.query_row(
+        "SELECT COUNT(*) FROM clipboard_items WHERE pinned = 1 AND position IS NULL",
+        [],
+        |row| row.get(0),
+    )?;
+    if missing > 0 {
+        let order_by = history_order_by(&tx)?;
+        let ordered_ids = {
+            let mut stmt = tx.prepare(&format!(
+                "SELECT id FROM clipboard_items WHERE pinned = 1 ORDER BY {order_by}"
+            ))?;
+            let rows = stmt.query_map([], |row| row.get::<_, String>(0))?;
+            let mut ids = Vec::new();
+            for row in rows {
+                ids.push(row?);
+            }
+            ids
+        };
+        for (index, id) in ordered_ids.iter().enumerate() {
+            tx.execute(
+                "UPDATE clipboard_items SET position = ?1 WHERE id = ?2",
+                params![index as i64, id],
+            )?;
+        }
+    }
+    let read_position = |id: &str| {
+        tx.query_row(
+            "SELECT position FROM clipboard_items WHERE id = ?1",
+            params![id],
+            |row| row.get::<_, i64>(0),
+        )
+    };
+    let position_a = read_position(id_a)?;
+    let position_b = read_position(id_b)?;
+    for (id, position) in [(id_a, position_b), (id_b, position_a)] {
+        tx.execute(
+            "UPDATE clipboard_items SET position = ?1 WHERE id = ?2",
+            params![position, id],
+        )?;
+    }
+    let first = query_clipboard_item(&tx, id_a)?;
+    let second = query_clipboard_item(&tx, id_b)?;
+    tx.commit()?;
+    Ok(vec![first, second])
+}
+
+// 按给定 id 顺序重排整个列表：列出的条目依次排在前面，未列出的条目保持当前相对顺序接在后面。
+// 固定条目始终在非固定条目之前，因此两组各自按这个顺序排列
+pub(crate) fn reorder_items_internal(
+    state: &AppState,
+    ordered_ids: &[String],
+) -> Result<(), AppError> {
+    let mut conn = lock_or_recover(&state.db);
+    let tx = conn.transaction()?;
+    let current_ids = {
+        let order_by = history_order_by(&tx)?;
+        let mut stmt = tx.prepare(&format!(
+            "SELECT id FROM clipboard_items ORDER BY {order_by}"
+        ))?;
+        let rows = stmt.query_map([], |row| row.get::<_, String>(0))?;
+        let mut ids = Vec::new();
+        for row in rows {
+            ids.push(row?);
+        }
+        ids
+    };
+    let known: HashSet<&str> = current_ids.iter().map(String::as_str).collect();
+    if ordered_ids.iter().any(|id| !known.contains(id.as_str())) {
+        return Err(AppError::NotFound);
+    }
+    let mut placed = HashSet::new();
+    let final_ids: Vec<&String> = ordered_ids
+        .iter()
+        .chain(current_ids.iter())
+        .filter(|id| placed.insert(id.as_str()))
+        .collect();
+    for (index, id) in final_ids.iter().enumerate() {
+        tx.execute(
+            "UPDATE clipboard_items SET position = ?1 WHERE id = ?2",
+            params![index as i64, id],
+        )?;
+    }
+    tx.commit()?;
+    Ok(())
+}
+
+// 合并仅空白不同的条目（连续空白视为一个空格后文本相同）：
+// 每组保留空白最完整（字符数最多，相同时取最近更新）的一条，合并规则与编辑合并一致：
+// 计数相加、任一条固定即保持固定、创建时间取最早、更新时间取最晚、标签取并集。
+// 图片条目的正文只是尺寸说明，同尺寸的不同图片不能合并，因此只处理文本条目
+pub(crate) fn merge_whitespace_variants_internal(
+    state: &AppState,
+) -> Result<WhitespaceMergeReport, String> {
+    let mut conn = lock_or_recover(&state.db);
+    let tx = conn.transaction().map_err(|err| err.to_string())?;
+    let items = {
+        let mut stmt = tx
+            .prepare(&format!(
+                "SELECT {CLIPBOARD_ITEM_COLUMNS} FROM clipboard_items WHERE kind = 'text'"
+            ))
+            .map_err(|err| err.to_string())?;
+        let rows = stmt.query_map([], map_row).map_err(|err| err.to_string())?;
+        rows.collect::<Result<Vec<_>, _>>()
+            .map_err(|err| err.to_string())?
+    };
+    let mut groups: HashMap<String, Vec<ClipboardItem>> = HashMap::new();
+    for item in items {
+        let key =
+            normalize_for_storage(&item.text, NormalizePolicy::WHITESPACE_INSENSITIVE).dedup_key;
+        groups.entry(key).or_default().push(item);
+    }
+    let mut report = WhitespaceMergeReport {
+        groups: Vec::new(),
+        items: Vec::new(),
+    };
+    for mut variants in groups.into_values().filter(|group| group.len() > 1) {
+        variants.sort_by(|a, b| {
+            b.text
+                .chars()
+                .count()
+                .cmp(&a.text.chars().count())
+                .then_with(|| b.updated_at.cmp(&a.updated_at))
+        });
+        let kept = &variants[0];
+        let others = &variants[1..];
+        let merged_count: i64 = variants.iter().map(|item| item.count).sum();
+        let merged_pinned = variants.iter().any(|item| item.pinned);
+        let merged_created_at = variants.iter().map(|item| &item.created_at).min();
+        let merged_updated_at = variants.iter().map(|item| &item.updated_at).max();
+        // 固定时间优先沿用保留条目自身的值，否则取被合并条目中最近的一次固定
+        let merged_pinned_at = if merged_pinned {
+            kept.pinned_at.clone().or_else(|| {
+                others
+                    .iter()
+                    .filter_map(|item| item.pinned_at.clone())
+                    .max()
+            })
+        } else {
+            None
+        };
+        tx.execute(
             "
-            SELECT id, text, created_at, updated_at, pinned, count
-            FROM clipboard_items
-            WHERE id = ?1
+            UPDATE clipboard_items
+            SET count = ?1, pinned = ?2, created_at = ?3, updated_at = ?4, pinned_at = ?5,
+                expires_at = CASE WHEN ?2 = 1 THEN NULL ELSE expires_at END
+            WHERE id = ?6
             ",
-            params![source_id],
-            map_row,
+            params![
+                merged_count,
+                if merged_pinned { 1 } else { 0 },
+                merged_created_at,
+                merged_updated_at,
+                merged_pinned_at,
+                kept.id
+            ],
         )
         .map_err(|err| err.to_string())?;
+        for other in others {
+            // 删除前把标签迁移到保留的条目上，避免被级联清理
+            tx.execute(
+                "
+                INSERT OR IGNORE INTO item_tags (item_id, tag)
+                SELECT ?1, tag FROM item_tags WHERE item_id = ?2
+                ",
+                params![kept.id, other.id],
+            )
+            .map_err(|err| err.to_string())?;
+            tx.execute(
+                "DELETE FROM clipboard_items WHERE id = ?1",
+                params![other.id],
+            )
+            .map_err(|err| err.to_string())?;
+        }
+        report
+            .items
+            .push(query_clipboard_item(&tx, &kept.id).map_err(|err| err.to_string())?);
+        report.groups.push(WhitespaceMergeGroup {
+            kept_id: kept.id.clone(),
+            merged_ids: others.iter().map(|item| item.id.clone()).collect(),
+        });
+    }
     tx.commit().map_err(|err| err.to_string())?;
-    Ok(ClipboardUpdateResult {
-        item: persisted,
-        merged_id: None,
-    })
+    Ok(report)
+}
+
+// 把早于截止时间的未固定条目移入归档库（独立的 SQLite 文件），返回被移出的条目 id：
+// 1. 归档库通过 ATTACH 挂到当前连接上，迁移与删除在同一事务中完成，失败时两边都不会改动；
+// 2. 归档表不设 text 唯一约束，同一文本在不同时间被归档多次时按 id 各自保留；
+// 3. 标签随条目一起迁移，归档后仍可按标签识别内容；
+// 4. 归档库只保存文本，图片条目不归档，仍留在历史中按保留天数清理。
+// ATTACH 不能在事务内执行，因此先挂载、再开启事务，最后无论成功与否都卸载归档库
+pub(crate) fn archive_items_before_internal(
+    conn: &mut Connection,
+    cutoff: &str,
+    archive_path: &str,
+) -> Result<Vec<String>, rusqlite::Error> {
+    conn.execute("ATTACH DATABASE ?1 AS archive", params![archive_path])?;
+    let result = move_items_to_archive(conn, cutoff);
+    let detached = conn.execute_batch("DETACH DATABASE archive");
+    let archived_ids = result?;
+    detached?;
+    Ok(archived_ids)
+}
+
+// 在已挂载归档库的连接上执行实际的迁移
+fn move_items_to_archive(
+    conn: &mut Connection,
+    cutoff: &str,
+) -> Result<Vec<String>, rusqlite::Error> {
+    conn.execute_batch(
+        "
+        CREATE TABLE IF NOT EXISTS archive.clipboard_items (
+            id TEXT PRIMARY KEY,
+            text TEXT NOT NULL,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL,
+            pinned INTEGER NOT NULL DEFAULT 0,
+            count INTEGER NOT NULL DEFAULT 1,
+            position INTEGER,
+            pinned_at TEXT,
+            script TEXT,
+            content_type TEXT
+        );
+        CREATE TABLE IF NOT EXISTS archive.item_tags (
+            item_id TEXT NOT NULL,
+            tag TEXT NOT NULL,
+            PRIMARY KEY (item_id, tag)
+        );
+        ",
+    )?;
+    let tx = conn.transaction()?;
+    let archived_ids = {
+        let mut stmt = tx.prepare(
+            "
+            SELECT id FROM main.clipboard_items
+            WHERE pinned = 0 AND kind = 'text' AND julianday(updated_at) < julianday(?1)
+            ",
+        )?;
+        let rows = stmt.query_map(params![cutoff], |row| row.get::<_, String>(0))?;
+        rows.collect::<Result<Vec<_>, _>>()?
+    };
+    for id in &archived_ids {
+        tx.execute(
+            &format!(
+                "
+                INSERT OR REPLACE INTO archive.clipboard_items ({ARCHIVE_ITEM_COLUMNS})
+                SELECT {ARCHIVE_ITEM_COLUMNS} FROM main.clipboard_items WHERE id = ?1
+                "
+            ),
+            params![id],
+        )?;
+        tx.execute(
+            "
+            INSERT OR IGNORE INTO archive.item_tags (item_id, tag)
+            SELECT item_id, tag FROM main.item_tags WHERE item_id = ?1
+            ",
+            params![id],
+        )?;
+        tx.execute(
+            "DELETE FROM main.clipboard_items WHERE id = ?1",
+            params![id],
+        )?;
+    }
+    tx.commit()?;
+    Ok(archived_ids)
+}
+
+// 在归档库中按关键字搜索（不区分 ASCII 大小写），以只读方式打开，保证搜索永远不会写入归档；
+// 归档库文件不存在时视为没有结果
+pub(crate) fn search_archive_items(
+    archive_path: &str,
+    query: &str,
+    limit: i64,
+) -> Result<Vec<ClipboardItem>, rusqlite::Error> {
+    if !std::path::Path::new(archive_path).exists() {
+        return Ok(Vec::new());
+    }
+    let conn = Connection::open_with_flags(archive_path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+    let escaped = escape_like(query);
+    let mut stmt = conn.prepare(&format!(
+        "
+        SELECT
+            {ARCHIVE_ITEM_COLUMNS}, 0 AS sanitized, 0 AS risky, NULL AS expires_at,
+            'text' AS kind, NULL AS source_app, NULL AS note,
+            (SELECT json_group_array(tag ORDER BY tag) FROM item_tags WHERE item_tags.item_id = id)
+        FROM clipboard_items
+        WHERE text LIKE '%' || ?1 || '%' ESCAPE '\\'
+        ORDER BY updated_at DESC
+        LIMIT ?2
+        "
+    ))?;
+    let rows = stmt.query_map(params![escaped, limit], map_row)?;
+    rows.collect()
+}
+
+// 全文检索条目：按空白拆分关键词，全部关键词都出现才算匹配。每个关键词都加引号作为短语交给 FTS5，
+// 引号、星号、冒号等特殊字符因此只按普通文字匹配，不会产生语法错误；
+// 少于 3 个字符的关键词无法使用 trigram 索引，改为 LIKE 子串匹配；图片条目不参与检索。
+// 固定条目始终在前，其余按相关度排列，相关度相同（或只有短关键词）时按更新时间倒序
+pub(crate) fn search_items_fts(
+    conn: &Connection,
+    query: &str,
+    limit: i64,
+) -> Result<Vec<ClipboardItem>, rusqlite::Error> {
+    let (long_terms, short_terms): (Vec<&str>, Vec<&str>) = query
+        .split_whitespace()
+        .partition(|term| term.chars().count() >= 3);
+    if long_terms.is_empty() && short_terms.is_empty() {
+        return Ok(Vec::new());
+    }
+    let mut conditions: Vec<&str> = vec!["c.kind = 'text'"];
+    let mut values: Vec<Value> = Vec::new();
+    let (from, rank) = if long_terms.is_empty() {
+        ("clipboard_items c", "")
+    } else {
+        let phrases = long_terms
+            .iter()
+            .map(|term| format!("\"{}\"", term.replace('"', "\"\"")))
+            .collect::<Vec<_>>()
+            .join(" ");
+        values.push(Value::Text(phrases));
+        (
+            "clipboard_items c JOIN (
+                SELECT id AS fts_id, rank FROM clipboard_items_fts WHERE clipboard_items_fts MATCH ?
+            ) m ON m.fts_id = c.id",
+            "m.rank, ",
+        )
+    };
+    for term in short_terms {
+        conditions.push("c.text LIKE '%' || ? || '%' ESCAPE '\\'");
+        values.push(Value::Text(escape_like(term)));
+    }
+    values.push(Value::Integer(limit));
+    let where_clause = conditions.join(" AND ");
+    let mut stmt = conn.prepare(&format!(
+        "
+        SELECT {CLIPBOARD_ITEM_COLUMNS}
+        FROM {from}
+        WHERE {where_clause}
+        ORDER BY c.pinned DESC, {rank}c.updated_at DESC
+        LIMIT ?
+        "
+    ))?;
+    let rows = stmt.query_map(params_from_iter(values), map_row)?;
+    rows.collect()
+}
+
+// 历史列表类查询共用的读取入口：各命令只给出筛选条件、排序与分页，SQL 统一在这里拼接执行。
+// 每次调用只执行一条 SELECT，条目的标签由 CLIPBOARD_ITEM_COLUMNS 中的子查询在同一条语句里取出，
+// 查询次数与返回条数无关；condition 为空表示不筛选，其中的参数使用匿名占位符 ?，
+// 按顺序对应 values；limit 为负数时不限条数（SQLite 中 LIMIT -1 表示不限制）
+pub(crate) fn load_items(
+    conn: &Connection,
+    condition: &str,
+    order_by: &str,
+    values: Vec<Value>,
+    limit: i64,
+    offset: i64,
+) -> Result<Vec<ClipboardItem>, rusqlite::Error> {
+    let where_clause = if condition.is_empty() {
+        String::new()
+    } else {
+        format!("WHERE {condition}")
+    };
+    let mut stmt = conn.prepare(&format!(
+        "
+        SELECT {CLIPBOARD_ITEM_COLUMNS}
+        FROM clipboard_items
+        {where_clause}
+        ORDER BY {order_by}
+        LIMIT ? OFFSET ?
+        "
+    ))?;
+    let bindings = values
+        .into_iter()
+        .chain([Value::Integer(limit), Value::Integer(offset)]);
+    let rows = stmt.query_map(params_from_iter(bindings), map_row)?;
+    rows.collect()
+}
+
+// 按组合条件查询条目，结果顺序与历史列表一致
+pub(crate) fn query_items(
+    conn: &Connection,
+    query: &QueryParams,
+) -> Result<Vec<ClipboardItem>, AppError> {
+    let (condition, values) = build_query_filter(query)?;
+    let order_by = history_order_by(conn)?;
+    Ok(load_items(conn, &condition, &order_by, values, -1, 0)?)
+}
+
+// 给符合组合条件的全部条目加上同一个标签，返回新加上标签的条目数（已有该标签的不计入）；
+// 单条 INSERT ... SELECT 语句本身即在一个事务中完成，不会出现只标记了一部分的情况
+pub(crate) fn tag_query_results_internal(
+    conn: &Connection,
+    query: &QueryParams,
+    tag: &str,
+) -> Result<usize, AppError> {
+    let (condition, values) = build_query_filter(query)?;
+    let where_clause = if condition.is_empty() {
+        String::new()
+    } else {
+        format!("WHERE {condition}")
+    };
+    // 标签占位符位于 WHERE 条件之前，绑定时排在最前面
+    let bindings = std::iter::once(Value::Text(tag.to_string())).chain(values);
+    Ok(conn.execute(
+        &format!(
+            "INSERT OR IGNORE INTO item_tags (item_id, tag) SELECT id, ? FROM clipboard_items {where_clause}"
+        ),
+        params_from_iter(bindings),
+    )?)
+}
+
+// 把组合查询条件拼接成筛选条件（不含 WHERE 关键字，没有条件时为空）：条件按需拼接，参数统一走绑定避免注入
+fn build_query_filter(query: &QueryParams) -> Result<(String, Vec<Value>), AppError> {
+    let mut conditions: Vec<&str> = Vec::new();
+    let mut values: Vec<Value> = Vec::new();
+    if let Some(text) = query
+        .text
+        .as_deref()
+        .map(str::trim)
+        .filter(|text| !text.is_empty())
+    {
+        // 关键字只匹配文本条目的正文，不匹配图片条目的尺寸说明
+        conditions.push("kind = 'text' AND text LIKE '%' || ? || '%' ESCAPE '\\'");
+        values.push(Value::Text(escape_like(text)));
+    }
+    if let Some(tag) = query
+        .tag
+        .as_deref()
+        .map(str::trim)
+        .filter(|tag| !tag.is_empty())
+    {
+        conditions.push("id IN (SELECT item_id FROM item_tags WHERE tag = ?)");
+        values.push(Value::Text(tag.to_string()));
+    }
+    if let Some(pinned) = query.pinned {
+        conditions.push("pinned = ?");
+        values.push(Value::Integer(if pinned { 1 } else { 0 }));
+    }
+    if let Some(script) = &query.script {
+        conditions.push("script = ?");
+        values.push(Value::Text(script.clone()));
+    }
+    if let Some(content_type) = query.content_type {
+        conditions.push("content_type = ?");
+        values.push(Value::Text(content_type.as_str().to_string()));
+    }
+    for (bound, condition) in [
+        (query.since_ms, "julianday(updated_at) >= julianday(?)"),
+        (query.until_ms, "julianday(updated_at) < julianday(?)"),
+    ] {
+        if let Some(ms) = bound {
+            let time = chrono::DateTime::from_timestamp_millis(ms)
+                .ok_or(AppError::InvalidInput(Reason::InvalidQueryRange))?;
+            conditions.push(condition);
+            values.push(Value::Text(time.to_rfc3339()));
+        }
+    }
+    Ok((conditions.join(" AND "), values))
+}
+
+// 转义 LIKE 通配符（以反斜杠为转义符），让用户输入的 % 与 _ 按字面匹配
+pub(crate) fn escape_like(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::build_clipboard_payload;
+    use crate::test_support::test_state;
+
+    // 写入一条文本条目并返回写入后的条目
+    fn capture(state: &AppState, text: &str) -> ClipboardItem {
+        upsert_clipboard_item_internal(state, build_clipboard_payload(text.to_string()), 30)
+            .expect("写入条目失败")
+            .item
+    }
+
+    thread_local! {
+        // 当前线程的连接执行过的 SQL 语句条数，由 trace 回调累加
+        static EXECUTED_STATEMENTS: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+    }
+
+    fn count_statement(_sql: &str) {
+        EXECUTED_STATEMENTS.with(|count| count.set(count.get() + 1));
+    }
+
+    // 列表读取的查询次数固定为一次，不随条数增加：标签在同一条语句中取出，不会逐条补查
+    #[test]
+    fn load_items_runs_one_statement_regardless_of_page_size() {
+        let state = test_state();
+        for index in 0..40 {
+            let item = capture(&state, &format!("item {index}"));
+            lock_or_recover(&state.db)
+                .execute(
+                    "INSERT INTO item_tags (item_id, tag) VALUES (?1, 'b'), (?1, 'a')",
+                    params![item.id],
+                )
+                .unwrap();
+        }
+        let mut conn = lock_or_recover(&state.db);
+        conn.trace(Some(count_statement));
+        for limit in [5, 40] {
+            EXECUTED_STATEMENTS.with(|count| count.set(0));
+            let items =
+                load_items(&conn, "", "updated_at DESC, id DESC", Vec::new(), limit, 0).unwrap();
+            assert_eq!(items.len(), limit as usize);
+            assert!(items.iter().all(|item| item.tags == ["a", "b"]));
+            assert_eq!(EXECUTED_STATEMENTS.with(|count| count.get()), 1);
+        }
+        conn.trace(None);
+    }
+
+    // 编辑后与已有条目重复：两种设置下保留的条目不同，计数、固定状态、固定时间与创建时间的合并规则相同
+    #[test]
+    fn edit_merge_keeps_configured_winner() {
+        for (setting, edited_wins) in [(None, false), (Some("edited"), true)] {
+            let state = test_state();
+            if let Some(setting) = setting {
+                let conn = lock_or_recover(&state.db);
+                set_app_setting(&conn, MERGE_TEXT_WINNER_KEY, Some(setting.to_string())).unwrap();
+            }
+            let existing = capture(&state, "alpha");
+            let edited = capture(&state, "beta");
+            lock_or_recover(&state.db)
+                .execute(
+                    "UPDATE clipboard_items SET pinned = 1, pinned_at = 'P' WHERE id = ?1",
+                    params![edited.id],
+                )
+                .unwrap();
+            let result = update_clipboard_item_text_internal(
+                &state,
+                edited.id.clone(),
+                " alpha ".to_string(),
+                now_iso_string(),
+            )
+            .unwrap();
+            let (kept, removed) = if edited_wins {
+                (&edited.id, &existing.id)
+            } else {
+                (&existing.id, &edited.id)
+            };
+            assert_eq!(&result.item.id, kept);
+            assert_eq!(result.merged_id.as_ref(), Some(removed));
+            assert_eq!(result.item.text, "alpha");
+            assert_eq!(result.item.count, 2);
+            assert!(result.item.pinned);
+            assert_eq!(result.item.pinned_at.as_deref(), Some("P"));
+            assert_eq!(result.item.created_at, existing.created_at);
+            let remaining: i64 = lock_or_recover(&state.db)
+                .query_row("SELECT COUNT(*) FROM clipboard_items", [], |row| row.get(0))
+                .unwrap();
+            assert_eq!(remaining, 1);
+        }
+    }
+
+    // 开启清理后，网页复制的弯引号版本与手动输入的直引号版本合并为同一条，并记录清理标记
+    #[test]
+    fn sanitized_capture_dedups_against_plain_text() {
+        let state = test_state();
+        state.sanitize_on_capture.store(true, Ordering::Relaxed);
+        let pasted = capture(&state, "echo\u{00A0}\u{201C}hi\u{201D}\u{200B}");
+        assert_eq!(pasted.text, "echo \"hi\"");
+        assert!(pasted.sanitized);
+
+        let typed = capture(&state, "echo \"hi\"");
+        assert_eq!(typed.id, pasted.id);
+        assert_eq!(typed.count, 2);
+
+        // 关闭清理后，带排版字符的原文作为新条目保存
+        state.sanitize_on_capture.store(false, Ordering::Relaxed);
+        let raw = capture(&state, "echo\u{00A0}\u{201C}hi\u{201D}");
+        assert_ne!(raw.id, pasted.id);
+        assert!(!raw.sanitized);
+    }
+
+    fn user_version(conn: &Connection) -> usize {
+        conn.query_row("PRAGMA user_version", [], |row| row.get(0))
+            .unwrap()
+    }
+
+    // 新建的数据库执行全部迁移后停在最新版本，重复执行不做任何改动；
+    // 来自更新应用版本的数据库（版本号更高）保持原样
+    #[test]
+    fn migrate_reaches_latest_version_and_is_idempotent() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        init_db(&conn).unwrap();
+        assert_eq!(user_version(&conn), 0);
+        migrate(&mut conn).unwrap();
+        assert_eq!(user_version(&conn), MIGRATIONS.len());
+        migrate(&mut conn).unwrap();
+        assert_eq!(user_version(&conn), MIGRATIONS.len());
+
+        let newer = MIGRATIONS.len() + 3;
+        conn.execute_batch(&format!("PRAGMA user_version = {newer}"))
+            .unwrap();
+        migrate(&mut conn).unwrap();
+        assert_eq!(user_version(&conn), newer);
+    }
+
+    // init_db 已按最新结构建表，每一项迁移在最新结构的数据库上再次执行都不能失败或改动数据
+    #[test]
+    fn every_migration_runs_on_current_schema() {
+        let state = test_state();
+        let item = capture(&state, "kept");
+        let conn = lock_or_recover(&state.db);
+        for migration in MIGRATIONS {
+            migration(&conn).unwrap();
+        }
+        assert_eq!(query_clipboard_item(&conn, &item.id).unwrap().text, "kept");
+        assert_eq!(search_items_fts(&conn, "kept", 10).unwrap().len(), 1);
+    }
+
+    // 引入版本号之前的旧库：补齐新增的列，去掉正文的唯一约束并补算哈希，标签与全文索引保持可用
+    #[test]
+    fn legacy_database_is_upgraded_in_place() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "
+            CREATE TABLE clipboard_items (
+                id TEXT PRIMARY KEY,
+                text TEXT NOT NULL UNIQUE,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL,
+                pinned INTEGER NOT NULL DEFAULT 0,
+                count INTEGER NOT NULL DEFAULT 1
+            );
+            INSERT INTO clipboard_items VALUES
+                ('x', 'hello world', '2020-01-01T00:00:00Z', '2020-01-01T00:00:00Z', 0, 1),
+                ('y', '{\"a\": 1}', '2020-01-02T00:00:00Z', '2020-01-02T00:00:00Z', 1, 3);
+            ",
+        )
+        .unwrap();
+        init_db(&conn).unwrap();
+        conn.execute("INSERT INTO item_tags VALUES ('x', 'keep')", [])
+            .unwrap();
+        migrate(&mut conn).unwrap();
+        assert_eq!(user_version(&conn), MIGRATIONS.len());
+
+        // 重建表时外键处于关闭状态，标签没有被级联删除；迁移结束后外键重新开启
+        let foreign_keys: i64 = conn
+            .query_row("PRAGMA foreign_keys", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(foreign_keys, 1);
+        let legacy = query_clipboard_item(&conn, "x").unwrap();
+        assert_eq!(legacy.tags, ["keep"]);
+        assert_eq!(legacy.position, None);
+        assert_eq!(legacy.kind, ItemKind::Text);
+        // 新增识别列时为已有条目补算了识别结果
+        let json = query_clipboard_item(&conn, "y").unwrap();
+        assert_eq!(json.content_type.as_deref(), Some("json"));
+
+        let schema: String = conn
+            .query_row(
+                "SELECT sql FROM sqlite_master WHERE name = 'clipboard_items'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert!(!schema.contains("UNIQUE"), "{schema}");
+        let hash: String = conn
+            .query_row(
+                "SELECT text_hash FROM clipboard_items WHERE id = 'x'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(hash, text_hash("hello world"));
+        assert_eq!(search_items_fts(&conn, "hello", 10).unwrap().len(), 1);
+
+        // 升级后的库再次启动：建表与迁移都不做任何改动
+        init_db(&conn).unwrap();
+        migrate(&mut conn).unwrap();
+        assert_eq!(query_clipboard_item(&conn, "x").unwrap().tags, ["keep"]);
+        conn.execute("DELETE FROM clipboard_items WHERE id = 'x'", [])
+            .unwrap();
+        let tags: i64 = conn
+            .query_row("SELECT COUNT(*) FROM item_tags", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(tags, 0);
+    }
+
+    // WAL 模式下，写事务进行中另一个连接的读取立即返回上一次提交的数据，而不是等待到忙等待超时；
+    // -wal / -shm 文件与数据库文件位于同一目录
+    #[test]
+    fn wal_reader_is_not_blocked_by_open_write() {
+        let dir = std::env::temp_dir().join(format!("pure-paste-wal-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(DB_FILE_NAME);
+        {
+            let mut writer = open_db(&path).unwrap();
+            init_db(&writer).unwrap();
+            migrate(&mut writer).unwrap();
+            let journal_mode: String = writer
+                .query_row("PRAGMA journal_mode", [], |row| row.get(0))
+                .unwrap();
+            assert_eq!(journal_mode, "wal");
+
+            let tx = writer.transaction().unwrap();
+            tx.execute("INSERT INTO app_settings VALUES ('k', 'v')", [])
+                .unwrap();
+            let reader = open_db(&path).unwrap();
+            let started = std::time::Instant::now();
+            let visible: i64 = reader
+                .query_row(
+                    "SELECT COUNT(*) FROM app_settings WHERE key = 'k'",
+                    [],
+                    |row| row.get(0),
+                )
+                .unwrap();
+            assert_eq!(visible, 0);
+            assert!(started.elapsed() < DB_BUSY_TIMEOUT);
+            tx.commit().unwrap();
+
+            let visible: i64 = reader
+                .query_row(
+                    "SELECT COUNT(*) FROM app_settings WHERE key = 'k'",
+                    [],
+                    |row| row.get(0),
+                )
+                .unwrap();
+            assert_eq!(visible, 1);
+            for suffix in ["-wal", "-shm"] {
+                let mut sidecar = path.clone().into_os_string();
+                sidecar.push(suffix);
+                assert!(PathBuf::from(sidecar).is_file());
+            }
+        }
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    // 转义后的 % 与 _ 只按字面匹配，反斜杠本身同样被转义
+    #[test]
+    fn escape_like_matches_wildcards_literally() {
+        assert_eq!(escape_like("100%_a\\b"), "100\\%\\_a\\\\b");
+        let state = test_state();
+        for text in [
+            "100% done",
+            "1000 done",
+            "snake_case",
+            "snakeXcase",
+            "C:\\tmp",
+        ] {
+            capture(&state, text);
+        }
+        let conn = lock_or_recover(&state.db);
+        let texts = |keyword: &str| {
+            let query = QueryParams {
+                text: Some(keyword.to_string()),
+                ..Default::default()
+            };
+            let mut texts: Vec<String> = query_items(&conn, &query)
+                .unwrap()
+                .into_iter()
+                .map(|item| item.text)
+                .collect();
+            texts.sort();
+            texts
+        };
+        assert_eq!(texts("0%"), ["100% done"]);
+        assert_eq!(texts("e_c"), ["snake_case"]);
+        assert_eq!(texts(":\\t"), ["C:\\tmp"]);
+        // 关键字不区分 ASCII 大小写，首尾空白被忽略
+        assert_eq!(texts("  SNAKE_ "), ["snake_case"]);
+    }
+
+    // 全文检索：中文按子串命中，多个关键词需全部出现，FTS5 语法字符按普通文字匹配，
+    // 短关键词走 LIKE；索引随条目的修改与删除同步
+    #[test]
+    fn fts_search_handles_cjk_syntax_and_short_terms() {
+        let state = test_state();
+        for text in [
+            "剪贴板历史记录",
+            "hello world foo",
+            "say \"quoted\" AND * : NEAR(",
+            "foo bar baz",
+            "ab cd",
+        ] {
+            capture(&state, text);
+        }
+        let conn = lock_or_recover(&state.db);
+        let texts = |query: &str| {
+            let mut texts: Vec<String> = search_items_fts(&conn, query, 50)
+                .unwrap()
+                .into_iter()
+                .map(|item| item.text)
+                .collect();
+            texts.sort();
+            texts
+        };
+        assert!(texts("   ").is_empty());
+        assert_eq!(texts("贴板历"), ["剪贴板历史记录"]);
+        assert_eq!(texts("foo"), ["foo bar baz", "hello world foo"]);
+        assert_eq!(texts("foo baz"), ["foo bar baz"]);
+        assert_eq!(texts("\"quoted\" NEAR("), ["say \"quoted\" AND * : NEAR("]);
+        assert_eq!(texts("* :"), ["say \"quoted\" AND * : NEAR("]);
+        assert_eq!(texts("ab"), ["ab cd"]);
+
+        conn.execute(
+            "UPDATE clipboard_items SET text = 'renamed zzz' WHERE text = 'ab cd'",
+            [],
+        )
+        .unwrap();
+        assert_eq!(texts("zzz"), ["renamed zzz"]);
+        assert!(texts("ab").is_empty());
+        conn.execute("DELETE FROM clipboard_items WHERE text = 'renamed zzz'", [])
+            .unwrap();
+        assert!(texts("zzz").is_empty());
+
+        // 重复建表不会重复补建索引
+        init_db(&conn).unwrap();
+        assert_eq!(texts("foo").len(), 2);
+    }
+
+    // 固定条目始终排在前面
+    #[test]
+    fn fts_search_lists_pinned_first() {
+        let state = test_state();
+        let older = capture(&state, "release notes v1");
+        capture(&state, "release notes v2");
+        let conn = lock_or_recover(&state.db);
+        conn.execute(
+            "UPDATE clipboard_items SET pinned = 1 WHERE id = ?1",
+            params![older.id],
+        )
+        .unwrap();
+        let results = search_items_fts(&conn, "release", 10).unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].id, older.id);
+    }
+
+    // 写入一张纯色的图片条目
+    fn capture_image(state: &AppState, width: usize, height: usize) -> ClipboardItem {
+        let image = ClipboardImage {
+            width,
+            height,
+            rgba: vec![0x80; width * height * 4],
+        };
+        let hash = crate::clipboard_image::image_content_hash(&image);
+        upsert_image_item_internal(state, &image, &hash, None, 30)
+            .expect("写入图片条目失败")
+            .item
+    }
+
+    // 图片条目的尺寸说明不进入全文索引，也不会被关键字查询命中
+    #[test]
+    fn image_labels_are_not_searchable() {
+        let state = test_state();
+        let image = capture_image(&state, 4, 3);
+        assert_eq!(image.kind, ItemKind::Image);
+        capture(&state, "图片 素材链接");
+        let conn = lock_or_recover(&state.db);
+        let indexed: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM clipboard_items_fts WHERE id = ?1",
+                params![image.id],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(indexed, 0);
+        for query in ["图片", "图片 4×3", "4×3"] {
+            assert!(
+                search_items_fts(&conn, query, 10)
+                    .unwrap()
+                    .iter()
+                    .all(|item| item.kind == ItemKind::Text),
+                "{query}"
+            );
+        }
+        let query = QueryParams {
+            text: Some("图片".to_string()),
+            ..Default::default()
+        };
+        let texts: Vec<String> = query_items(&conn, &query)
+            .unwrap()
+            .into_iter()
+            .map(|item| item.text)
+            .collect();
+        assert_eq!(texts, ["图片 素材链接"]);
+    }
+
+    // 旧版本已写入索引的图片说明在迁移时移除，之后写入的图片条目也不再进入索引
+    #[test]
+    fn migration_removes_indexed_image_labels() {
+        let state = test_state();
+        let mut conn = lock_or_recover(&state.db);
+        let index_of_fts_migration = MIGRATIONS.len() - 1;
+        // 模拟旧版本：触发器不区分条目种类，图片说明已在索引中
+        conn.execute_batch(
+            "
+            DROP TRIGGER clipboard_items_fts_insert;
+            CREATE TRIGGER clipboard_items_fts_insert AFTER INSERT ON clipboard_items
+            BEGIN
+                INSERT INTO clipboard_items_fts (id, text) VALUES (new.id, new.text);
+            END;
+            INSERT INTO clipboard_items (id, text, text_hash, created_at, updated_at, kind)
+                VALUES ('old-image', '图片 8×8', 'image:old', '2024-01-01T00:00:00Z', '2024-01-01T00:00:00Z', 'image');
+            ",
+        )
+        .unwrap();
+        conn.execute_batch(&format!("PRAGMA user_version = {index_of_fts_migration}"))
+            .unwrap();
+        migrate(&mut conn).unwrap();
+        let indexed = |conn: &Connection| -> i64 {
+            conn.query_row(
+                "SELECT COUNT(*) FROM clipboard_items_fts WHERE text LIKE '图片%'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap()
+        };
+        assert_eq!(indexed(&conn), 0);
+        drop(conn);
+        capture_image(&state, 2, 2);
+        assert_eq!(indexed(&lock_or_recover(&state.db)), 0);
+    }
+
+    // 支持包中不能残留任何原文：逐表检查副本中的每个值，并直接检查文件字节（覆盖空闲页与索引分段）；
+    // 原文的未加盐哈希同样不能出现，避免短密码被穷举反推
+    #[test]
+    fn support_bundle_contains_no_original_text() {
+        let state = test_state();
+        let secrets = ["hunter2-correct-horse", "token sk-live-9f8e7d"];
+        for secret in secrets {
+            capture(&state, secret);
+        }
+        let note = "vault pin 4417 for staging";
+        let setting = "prefix-with-api-key-55aa";
+        let source = "com.example.secret-messenger";
+        {
+            let conn = lock_or_recover(&state.db);
+            conn.execute(
+                "UPDATE clipboard_items SET note = ?1, source_app = ?2, transform = ?3",
+                params![note, source, format!("{{\"prefix\":\"{setting}\"}}")],
+            )
+            .unwrap();
+            for key in SUPPORT_BUNDLE_REDACTED_SETTINGS {
+                conn.execute(
+                    "INSERT OR REPLACE INTO app_settings (key, value) VALUES (?1, ?2)",
+                    params![key, format!("[\"{setting}\"]")],
+                )
+                .unwrap();
+            }
+            conn.execute(
+                "INSERT INTO autotag_rules (pattern, tag) VALUES (?1, 'work')",
+                params![setting],
+            )
+            .unwrap();
+        }
+        capture_image(&state, 4, 4);
+        let dir = std::env::temp_dir().join(format!("pure-paste-bundle-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("bundle.db");
+        let redacted =
+            export_redacted_copy(&lock_or_recover(&state.db), path.to_str().unwrap()).unwrap();
+        assert_eq!(redacted, secrets.len() + 1);
+
+        let mut needles: Vec<String> =
+            vec![note.to_string(), setting.to_string(), source.to_string()];
+        for secret in secrets {
+            needles.push(secret.to_string());
+            needles.push(secret[secret.len() / 2..].to_string());
+            needles.push(text_hash(secret));
+        }
+        let copy = Connection::open(&path).unwrap();
+        let tables: Vec<String> = copy
+            .prepare("SELECT name FROM sqlite_master WHERE type = 'table'")
+            .unwrap()
+            .query_map([], |row| row.get(0))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        for table in &tables {
+            let mut stmt = copy.prepare(&format!("SELECT * FROM \"{table}\"")).unwrap();
+            let columns = stmt.column_count();
+            let mut rows = stmt.query([]).unwrap();
+            while let Some(row) = rows.next().unwrap() {
+                for index in 0..columns {
+                    let bytes = match row.get_ref(index).unwrap() {
+                        rusqlite::types::ValueRef::Text(bytes)
+                        | rusqlite::types::ValueRef::Blob(bytes) => bytes,
+                        _ => continue,
+                    };
+                    let value = String::from_utf8_lossy(bytes);
+                    for needle in &needles {
+                        assert!(!value.contains(needle.as_str()), "{table} 中残留 {needle}");
+                    }
+                }
+            }
+        }
+        let images: i64 = copy
+            .query_row(
+                "SELECT COUNT(*) FROM clipboard_items
+                 WHERE image_png IS NOT NULL OR thumbnail_png IS NOT NULL",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(images, 0);
+        drop(copy);
+        let file = std::fs::read(&path).unwrap();
+        for needle in &needles {
+            assert!(
+                !file
+                    .windows(needle.len())
+                    .any(|window| window == needle.as_bytes()),
+                "文件中残留 {needle}"
+            );
+        }
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
 }
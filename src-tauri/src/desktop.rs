@@ -2,38 +2,157 @@
 // 说明：所有桌面端能力都必须在 `#[cfg(desktop)]` 下编译，确保未来支持移动端时不会被桌面依赖阻塞。
 
 #[cfg(desktop)]
-use crate::db::upsert_clipboard_item_internal;
+use crate::clipboard_change::ClipboardChangeCounter;
+#[cfg(desktop)]
+use crate::clipboard_image::{image_dedup_key, image_item_label, ImageHashCache};
+#[cfg(desktop)]
+use crate::commands::{
+    copy_image_item_to_clipboard, copy_item_to_clipboard, emit_clipboard_expired,
+    emit_clipboard_pruned, emit_clipboard_unpinned, mark_skip_text, write_text_to_clipboard,
+};
+#[cfg(desktop)]
+use crate::db::{
+    clear_unpinned_items, delete_expired_items, load_remember_window_placement, load_tray_menu,
+    load_tray_recent_count, load_tray_recent_items, load_window_placements, mark_item_risky,
+    save_window_placement, set_app_setting, upsert_clipboard_item_internal,
+    upsert_image_item_internal,
+};
+#[cfg(desktop)]
+use crate::detect::match_sensitive_path;
+#[cfg(desktop)]
+use crate::error::{AppError, Reason};
+#[cfg(desktop)]
+use crate::foreground_app::{frontmost_app_id, is_blocked_app};
+#[cfg(desktop)]
+use crate::format::build_item_preview;
 #[cfg(desktop)]
 use crate::models::{
-    build_clipboard_payload, AppState, ClipboardBroadcastPayload, CLIPBOARD_POLL_INTERVAL_MS,
-    HISTORY_RETENTION_DAYS,
+    build_clipboard_payload, is_own_window_copy, is_repeat_of_last_capture, lock_or_recover,
+    observe_empty_clipboard, parse_item_uri, record_skipped_capture, skip_capture,
+    skip_next_text_ttl_ms, AppState, BroadcastThrottle, ClipboardBatchPayload,
+    ClipboardBroadcastPayload, ClipboardImage, FilterShortcut, ItemKind, RiskyCapturePayload,
+    ShortcutBindings, SkipReason, TrayLeftClickAction, TrayMenuAction, TrayRecentItem,
+    WindowPlacement, HISTORY_RETENTION_DAYS, ITEM_EXPIRY_SWEEP_INTERVAL, MONITORING_ENABLED_KEY,
+    SKIP_PREVIEW_MAX_CHARS, TRAY_RECENT_ID_PREFIX, TRAY_RECENT_PREVIEW_MAX_CHARS,
 };
 #[cfg(desktop)]
+use crate::normalize::{normalize_for_storage, NormalizePolicy};
+#[cfg(desktop)]
+use crate::session_lock::ScreenLockMonitor;
+#[cfg(all(desktop, not(target_os = "linux")))]
 use arboard::Clipboard;
 #[cfg(desktop)]
+use rusqlite::OptionalExtension;
+#[cfg(desktop)]
 use std::error::Error;
 #[cfg(desktop)]
+use std::str::FromStr;
+#[cfg(desktop)]
 use std::sync::atomic::Ordering;
 #[cfg(desktop)]
-use std::time::Duration;
+use std::time::{Duration, Instant};
+#[cfg(desktop)]
+use tauri::menu::{Menu, MenuBuilder, MenuItem, SubmenuBuilder};
+#[cfg(desktop)]
+use tauri::tray::{TrayIcon, TrayIconBuilder};
 #[cfg(desktop)]
-use tauri::menu::{MenuBuilder, MenuItem};
+use tauri::{Emitter, Listener, Manager};
 #[cfg(desktop)]
-use tauri::tray::TrayIconBuilder;
+use tauri_plugin_clipboard_manager::ClipboardExt;
 #[cfg(desktop)]
-use tauri::{Emitter, Manager};
+use tauri_plugin_deep_link::DeepLinkExt;
 #[cfg(desktop)]
-use tauri_plugin_global_shortcut::{GlobalShortcutExt, ShortcutState};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutState};
 
-// 优先使用固定尺寸托盘图标，避免默认图标过大导致菜单栏不可见
+// 后台 watcher 使用的剪贴板读取器：Linux 需要兼容 X11 与 Wayland 的差异，走专用读取路径
+#[cfg(all(desktop, target_os = "linux"))]
+type WatcherClipboard = crate::linux_clipboard::LinuxClipboardReader;
+#[cfg(all(desktop, not(target_os = "linux")))]
+type WatcherClipboard = Clipboard;
+
+// 一次性读取当前系统剪贴板文本，读取方式与 watcher 保持一致，供命令层做即时查询
 #[cfg(desktop)]
-fn load_tray_icon_image() -> Option<tauri::image::Image<'static>> {
-    let bytes = include_bytes!("../icons/32x32.png");
+pub(crate) fn read_clipboard_text() -> Result<String, AppError> {
+    let mut clipboard = WatcherClipboard::new()?;
+    read_watcher_text(&mut clipboard)
+}
+
+// 读取剪贴板文本：剪贴板为空或内容不是文本时统一返回空字符串，与 Linux 读取器的行为保持一致，
+// 便于 watcher 区分“剪贴板被清空”与真正的读取失败
+#[cfg(all(desktop, target_os = "linux"))]
+fn read_watcher_text(clipboard: &mut WatcherClipboard) -> Result<String, AppError> {
+    clipboard.get_text()
+}
+
+#[cfg(all(desktop, not(target_os = "linux")))]
+fn read_watcher_text(clipboard: &mut WatcherClipboard) -> Result<String, AppError> {
+    match clipboard.get_text() {
+        Ok(text) => Ok(text),
+        Err(arboard::Error::ContentNotAvailable) => Ok(String::new()),
+        Err(err) => Err(AppError::Other(Reason::Detail(err.to_string()))),
+    }
+}
+
+// 读取剪贴板图片（如截图）：剪贴板中没有图片或读取失败时返回 None。
+// 只在剪贴板中没有文本时调用，读取失败不计入捕获错误，文本读取仍是判断 watcher 是否正常的依据
+#[cfg(all(desktop, target_os = "linux"))]
+fn read_watcher_image(clipboard: &mut WatcherClipboard) -> Option<ClipboardImage> {
+    clipboard.get_image()
+}
+
+#[cfg(all(desktop, not(target_os = "linux")))]
+fn read_watcher_image(clipboard: &mut WatcherClipboard) -> Option<ClipboardImage> {
+    clipboard.get_image().ok().map(|image| ClipboardImage {
+        width: image.width,
+        height: image.height,
+        rgba: image.bytes.into_owned(),
+    })
+}
+
+// 优先使用固定尺寸托盘图标，避免默认图标过大导致菜单栏不可见；
+// 暂停记录时使用夹板中带暂停符号的图标，两者都只有单一颜色，macOS 下同样可作为模板图标
+#[cfg(desktop)]
+fn load_tray_icon_image(monitoring: bool) -> Option<tauri::image::Image<'static>> {
+    let bytes: &[u8] = if monitoring {
+        include_bytes!("../icons/32x32.png")
+    } else {
+        include_bytes!("../icons/tray-paused.png")
+    };
     tauri::image::Image::from_bytes(bytes)
         .ok()
         .map(|image| image.to_owned())
 }
 
+// 托盘提示文字，鼠标悬停时即可看出是否正在记录
+#[cfg(desktop)]
+fn tray_tooltip(monitoring: bool) -> &'static str {
+    if monitoring {
+        "我的剪贴板 · 正在记录"
+    } else {
+        "我的剪贴板 · 已暂停记录"
+    }
+}
+
+// 按监听开关切换托盘图标与提示文字，托盘尚未创建时直接忽略
+#[cfg(desktop)]
+pub(crate) fn apply_tray_monitoring_state(
+    app: &tauri::AppHandle,
+    monitoring: bool,
+) -> Result<(), String> {
+    let Some(tray) = app.try_state::<TrayIcon>() else {
+        return Ok(());
+    };
+    if let Some(icon) = load_tray_icon_image(monitoring) {
+        tray.set_icon(Some(icon)).map_err(|err| err.to_string())?;
+        // 更换图标后重新标记为模板图标，保持与深浅色菜单栏的适配
+        #[cfg(target_os = "macos")]
+        tray.set_icon_as_template(true)
+            .map_err(|err| err.to_string())?;
+    }
+    tray.set_tooltip(Some(tray_tooltip(monitoring)))
+        .map_err(|err| err.to_string())
+}
+
 // 打开或聚焦设置窗口，避免重复创建并确保跨平台稳定
 #[cfg(desktop)]
 pub(crate) fn open_settings_window(app: &tauri::AppHandle) {
@@ -62,17 +181,537 @@ pub(crate) fn open_settings_window(app: &tauri::AppHandle) {
     });
 }
 
-// 打开并聚焦主窗口，统一在托盘与菜单中复用
+// 主窗口或设置窗口是否处于焦点：剪贴板在此时变化说明是在应用内选中复制的
+#[cfg(desktop)]
+fn own_window_focused(app: &tauri::AppHandle) -> bool {
+    ["main", "settings"].iter().any(|label| {
+        app.get_webview_window(label)
+            .and_then(|window| window.is_focused().ok())
+            .unwrap_or(false)
+    })
+}
+
+// 当前显示器布局的标识：按坐标排序后拼接每台显示器的分辨率、坐标与缩放比例（如 1920x1080@0,0*1.00），
+// 接入、拔出显示器或调整排列后标识随之变化；使用可读的文本而不是哈希值，升级后仍能对应到已记住的位置。
+// 无法读取显示器信息时返回 None
+#[cfg(desktop)]
+fn monitor_layout_key(window: &tauri::WebviewWindow) -> Option<String> {
+    let mut monitors: Vec<(i32, i32, u32, u32, String)> = window
+        .available_monitors()
+        .ok()?
+        .iter()
+        .map(|monitor| {
+            let position = monitor.position();
+            let size = monitor.size();
+            let scale = format!("{:.2}", monitor.scale_factor());
+            (position.x, position.y, size.width, size.height, scale)
+        })
+        .collect();
+    if monitors.is_empty() {
+        return None;
+    }
+    monitors.sort();
+    let parts: Vec<String> = monitors
+        .iter()
+        .map(|(x, y, width, height, scale)| format!("{}x{}@{},{}*{}", width, height, x, y, scale))
+        .collect();
+    Some(parts.join(";"))
+}
+
+// 隐藏主窗口前记住当前显示器布局下的位置与大小；最大化或最小化时的尺寸不代表用户摆放的位置，不记录。
+// 窗口位置只是便利功能，读取或写入失败时静默跳过
+#[cfg(desktop)]
+pub(crate) fn remember_window_placement(app: &tauri::AppHandle) {
+    let Some(window) = app.get_webview_window("main") else {
+        return;
+    };
+    if !window.is_visible().unwrap_or(false)
+        || window.is_maximized().unwrap_or(false)
+        || window.is_minimized().unwrap_or(false)
+    {
+        return;
+    }
+    let (Some(layout), Ok(position), Ok(size)) = (
+        monitor_layout_key(&window),
+        window.outer_position(),
+        window.inner_size(),
+    ) else {
+        return;
+    };
+    let state = app.state::<AppState>();
+    let conn = lock_or_recover(&state.db);
+    if !load_remember_window_placement(&conn).unwrap_or(false) {
+        return;
+    }
+    let placement = WindowPlacement {
+        x: position.x,
+        y: position.y,
+        width: size.width,
+        height: size.height,
+    };
+    let _ = save_window_placement(&conn, &layout, placement);
+}
+
+// 显示主窗口前恢复当前显示器布局下记住的位置；该布局下还没有记住位置时居中显示，
+// 避免拔掉显示器后窗口停留在已不存在的屏幕区域
+#[cfg(desktop)]
+fn restore_window_placement(window: &tauri::WebviewWindow) {
+    let placement = {
+        let state = window.state::<AppState>();
+        let conn = lock_or_recover(&state.db);
+        if !load_remember_window_placement(&conn).unwrap_or(false) {
+            return;
+        }
+        let layout = monitor_layout_key(window);
+        let placements = load_window_placements(&conn).unwrap_or_default();
+        layout.and_then(|layout| placements.get(&layout).copied())
+    };
+    match placement {
+        Some(placement) if placement.width > 0 && placement.height > 0 => {
+            let _ = window.set_size(tauri::PhysicalSize::new(placement.width, placement.height));
+            let _ = window.set_position(tauri::PhysicalPosition::new(placement.x, placement.y));
+        }
+        _ => {
+            let _ = window.center();
+        }
+    }
+}
+
+// 打开并聚焦主窗口，统一在托盘与菜单中复用；从隐藏状态恢复时按显示器布局恢复位置
 #[cfg(desktop)]
 pub(crate) fn show_main_window(app: &tauri::AppHandle) {
     if let Some(window) = app.get_webview_window("main") {
+        if !window.is_visible().unwrap_or(false) {
+            restore_window_placement(&window);
+        }
         // 先显示再聚焦，确保窗口从隐藏状态恢复
         let _ = window.show();
         let _ = window.set_focus();
     }
 }
 
-// 注册全局快捷键，用于唤起主窗口，确保快捷键触发时窗口始终可见
+// 按托盘左键动作切换是否在左键点击时弹出托盘菜单（Linux 托盘始终弹出菜单，设置无效）
+#[cfg(desktop)]
+pub(crate) fn apply_tray_left_click(
+    app: &tauri::AppHandle,
+    action: TrayLeftClickAction,
+) -> Result<(), String> {
+    let Some(tray) = app.try_state::<TrayIcon>() else {
+        return Ok(());
+    };
+    tray.set_show_menu_on_left_click(action == TrayLeftClickAction::ShowMenu)
+        .map_err(|err| err.to_string())
+}
+
+// 按配置的操作项构建托盘菜单：监听开关的文字随当前状态变化，退出前固定加一条分隔线；
+// recent 为 None 时不显示“最近复制”子菜单
+#[cfg(desktop)]
+fn build_tray_menu(
+    app: &tauri::AppHandle,
+    actions: &[TrayMenuAction],
+    recent: Option<&[TrayRecentItem]>,
+) -> tauri::Result<Menu<tauri::Wry>> {
+    let monitoring = app
+        .state::<AppState>()
+        .monitoring_enabled
+        .load(Ordering::Relaxed);
+    let mut builder = MenuBuilder::new(app);
+    if let Some(recent) = recent {
+        // 多行或过长的文本折叠为单行预览，固定的条目排在前面并加标记；没有记录时显示一条不可点击的提示
+        let mut submenu = SubmenuBuilder::new(app, "最近复制");
+        if recent.is_empty() {
+            let empty = MenuItem::with_id(app, "recent_empty", "暂无记录", false, None::<&str>)?;
+            submenu = submenu.item(&empty);
+        }
+        for item in recent {
+            let preview = build_item_preview(
+                &item.text,
+                item.content_type.as_deref(),
+                TRAY_RECENT_PREVIEW_MAX_CHARS,
+            )
+            .preview;
+            let label = if item.pinned {
+                format!("📌 {preview}")
+            } else {
+                preview
+            };
+            // Windows 菜单把 & 当作快捷键前缀，需要转义才能原样显示
+            #[cfg(windows)]
+            let label = label.replace('&', "&&");
+            let id = format!("{TRAY_RECENT_ID_PREFIX}{}", item.id);
+            let entry = MenuItem::with_id(app, id, label, true, None::<&str>)?;
+            submenu = submenu.item(&entry);
+        }
+        builder = builder.item(&submenu.build()?).separator();
+    }
+    for (index, action) in actions.iter().enumerate() {
+        let label = match action {
+            TrayMenuAction::Show => "打开",
+            TrayMenuAction::Settings => "设置",
+            TrayMenuAction::ToggleMonitoring if monitoring => "暂停记录",
+            TrayMenuAction::ToggleMonitoring => "恢复记录",
+            TrayMenuAction::PasteLast => "复制最近一条",
+            TrayMenuAction::ClearUnpinned => "清空未固定条目",
+            TrayMenuAction::Quit => "退出",
+        };
+        if *action == TrayMenuAction::Quit && index > 0 {
+            builder = builder.separator();
+        }
+        let item = MenuItem::with_id(app, action.id(), label, true, None::<&str>)?;
+        builder = builder.item(&item);
+    }
+    builder.build()
+}
+
+// 按数据库中的配置重建托盘菜单：菜单配置或监听状态变化后调用，托盘尚未创建时直接忽略
+#[cfg(desktop)]
+pub(crate) fn refresh_tray_menu(app: &tauri::AppHandle) -> Result<(), String> {
+    let Some(tray) = app.try_state::<TrayIcon>() else {
+        return Ok(());
+    };
+    let (actions, recent) = load_tray_menu_content(app).map_err(|err| err.to_string())?;
+    let menu = build_tray_menu(app, &actions, recent.as_deref()).map_err(|err| err.to_string())?;
+    tray.set_menu(Some(menu)).map_err(|err| err.to_string())
+}
+
+// 读取托盘菜单的操作项与“最近复制”子菜单的条目；子菜单条目数设置为 0 时不显示子菜单
+#[cfg(desktop)]
+fn load_tray_menu_content(
+    app: &tauri::AppHandle,
+) -> Result<(Vec<TrayMenuAction>, Option<Vec<TrayRecentItem>>), rusqlite::Error> {
+    let state = app.state::<AppState>();
+    let conn = lock_or_recover(&state.db);
+    let actions = load_tray_menu(&conn)?;
+    let recent = match load_tray_recent_count(&conn)? {
+        0 => None,
+        count => Some(load_tray_recent_items(&conn, count)?),
+    };
+    Ok((actions, recent))
+}
+
+// 把托盘“最近复制”子菜单中点击的条目写回系统剪贴板，与主窗口复制一致：图片写回原图，文本按设置添加前缀 / 后缀；
+// 条目在菜单刷新前已被删除时忽略
+#[cfg(desktop)]
+pub(crate) fn copy_tray_recent_item(app: &tauri::AppHandle, id: &str) -> Result<(), String> {
+    let state = app.state::<AppState>();
+    let kind = {
+        let conn = lock_or_recover(&state.db);
+        conn.query_row(
+            "SELECT kind FROM clipboard_items WHERE id = ?1",
+            [id],
+            |row| row.get::<_, String>(0),
+        )
+        .optional()
+        .map_err(|err| err.to_string())?
+    };
+    let Some(kind) = kind else {
+        return Ok(());
+    };
+    copy_stored_item(app, id, ItemKind::from_column(&kind))
+}
+
+// 按条目种类写回系统剪贴板：图片写回原图，文本与主窗口复制一致，先执行粘贴变换再按设置添加前缀 / 后缀
+#[cfg(desktop)]
+fn copy_stored_item(app: &tauri::AppHandle, id: &str, kind: ItemKind) -> Result<(), String> {
+    let state = app.state::<AppState>();
+    let result = match kind {
+        ItemKind::Image => copy_image_item_to_clipboard(app.clone(), state, id.to_string()),
+        ItemKind::Text => {
+            copy_item_to_clipboard(app.clone(), state, id.to_string(), None).map(|_| ())
+        }
+    };
+    result.map_err(|err| err.to_string())
+}
+
+// 切换剪贴板监听开关（托盘菜单与全局快捷键共用）
+#[cfg(desktop)]
+pub(crate) fn toggle_clipboard_monitoring(app: &tauri::AppHandle) {
+    let state = app.state::<AppState>();
+    let enabled = !state.monitoring_enabled.load(Ordering::Relaxed);
+    state.monitoring_enabled.store(enabled, Ordering::Relaxed);
+    // 与设置页的开关一样保存到数据库，重启后沿用；保存失败时本次切换照常生效
+    let _ = set_app_setting(
+        &lock_or_recover(&state.db),
+        MONITORING_ENABLED_KEY,
+        Some(enabled.to_string()),
+    );
+    // 通知设置页同步开关状态，并刷新菜单文字与托盘图标
+    let _ = app.emit("clipboard-monitoring-changed", enabled);
+    let _ = refresh_tray_menu(app);
+    let _ = apply_tray_monitoring_state(app, enabled);
+}
+
+// 执行托盘菜单项对应的操作
+#[cfg(desktop)]
+pub(crate) fn handle_tray_menu_action(app: &tauri::AppHandle, action: TrayMenuAction) {
+    let state = app.state::<AppState>();
+    match action {
+        TrayMenuAction::Show => show_main_window(app),
+        // 打开或聚焦设置窗口
+        TrayMenuAction::Settings => open_settings_window(app),
+        TrayMenuAction::ToggleMonitoring => {
+            toggle_clipboard_monitoring(app);
+        }
+        TrayMenuAction::PasteLast => {
+            let _ = copy_latest_item_to_clipboard(app);
+        }
+        TrayMenuAction::ClearUnpinned => {
+            let removed = clear_unpinned_items(&mut lock_or_recover(&state.db)).unwrap_or_default();
+            emit_clipboard_pruned(app, removed);
+        }
+        TrayMenuAction::Quit => {
+            // 标记为允许退出，确保只通过托盘菜单触发真正退出
+            state.allow_exit.store(true, Ordering::Relaxed);
+            app.exit(0);
+        }
+    }
+}
+
+// 处理托盘图标的左键点击：根据设置打开主窗口，或把最近一条记录写回剪贴板；
+// 弹出菜单由托盘自身完成，这里无需额外处理
+#[cfg(desktop)]
+pub(crate) fn handle_tray_left_click(app: &tauri::AppHandle) {
+    let action = *lock_or_recover(&app.state::<AppState>().tray_left_click);
+    match action {
+        TrayLeftClickAction::ShowWindow => show_main_window(app),
+        TrayLeftClickAction::ShowMenu => {}
+        TrayLeftClickAction::PasteLast => {
+            let _ = copy_latest_item_to_clipboard(app);
+        }
+    }
+}
+
+// 应用写入剪贴板后按设置延迟清空：到期时只有在这是最近一次应用写入、且剪贴板内容仍是当时写入的文本时才清空，
+// 避免误清用户之后自行复制的内容
+#[cfg(desktop)]
+pub(crate) fn schedule_clipboard_clear(app: &tauri::AppHandle, text: &str) {
+    let state = app.state::<AppState>();
+    let generation = state
+        .clipboard_clear_generation
+        .fetch_add(1, Ordering::Relaxed)
+        + 1;
+    let seconds = state.clipboard_clear_seconds.load(Ordering::Relaxed);
+    if seconds == 0 {
+        return;
+    }
+    let app_handle = app.clone();
+    let expected = normalize_for_storage(text, NormalizePolicy::STORAGE).dedup_key;
+    std::thread::spawn(move || {
+        std::thread::sleep(Duration::from_secs(seconds));
+        let state = app_handle.state::<AppState>();
+        if state.clipboard_clear_generation.load(Ordering::Relaxed) != generation {
+            return;
+        }
+        let unchanged = read_clipboard_text()
+            .map(|current| {
+                normalize_for_storage(&current, NormalizePolicy::STORAGE).dedup_key == expected
+            })
+            .unwrap_or(false);
+        if unchanged {
+            let _ = app_handle.clipboard().clear();
+        }
+    });
+}
+
+// 临时写入剪贴板，到期后恢复写入前的内容。连续多次临时粘贴时沿用第一次保存的原内容，
+// 保证最终恢复的是用户自己的剪贴板；原剪贴板为空或不是文本时无法恢复，写入的内容会保留
+#[cfg(desktop)]
+pub(crate) fn write_transient_text(
+    app: &tauri::AppHandle,
+    state: &AppState,
+    text: &str,
+    restore_after: Duration,
+) -> Result<(), String> {
+    let generation = state
+        .clipboard_restore_generation
+        .fetch_add(1, Ordering::Relaxed)
+        + 1;
+    let pending = lock_or_recover(&state.pending_clipboard_restore).take();
+    let original = match pending {
+        Some((_, original)) => Some(original),
+        None => read_clipboard_text()
+            .ok()
+            .filter(|original| !original.is_empty()),
+    };
+    write_text_to_clipboard(app, state, text).map_err(|err| err.to_string())?;
+    let Some(original) = original else {
+        return Ok(());
+    };
+    *lock_or_recover(&state.pending_clipboard_restore) = Some((generation, original));
+    let app_handle = app.clone();
+    let expected = normalize_for_storage(text, NormalizePolicy::STORAGE).dedup_key;
+    std::thread::spawn(move || {
+        std::thread::sleep(restore_after);
+        let state = app_handle.state::<AppState>();
+        // 之后又发起了新的临时粘贴时，由最新的一次负责恢复
+        let original = {
+            let mut pending = lock_or_recover(&state.pending_clipboard_restore);
+            if pending.as_ref().is_some_and(|(id, _)| *id == generation) {
+                pending.take().map(|(_, original)| original)
+            } else {
+                None
+            }
+        };
+        let Some(original) = original else {
+            return;
+        };
+        // 剪贴板仍是临时写入的内容（或已被自动清空）时才恢复，用户期间复制了新内容则保留用户的内容
+        let untouched = read_clipboard_text()
+            .map(|current| {
+                current.is_empty()
+                    || normalize_for_storage(&current, NormalizePolicy::STORAGE).dedup_key
+                        == expected
+            })
+            .unwrap_or(false);
+        if !untouched {
+            return;
+        }
+        // 恢复的内容同样标记为应用自身写入，避免 watcher 把它当作一次新的复制
+        if mark_skip_text(&state, &original).is_ok() {
+            let _ = app_handle.clipboard().write_text(original);
+        }
+    });
+    Ok(())
+}
+
+// 把最近更新的一条记录写回系统剪贴板，与托盘“最近复制”一样按条目种类处理：图片写回原图，
+// 文本按设置添加前缀 / 后缀；历史为空时不做任何事
+#[cfg(desktop)]
+fn copy_latest_item_to_clipboard(app: &tauri::AppHandle) -> Result<(), String> {
+    let state = app.state::<AppState>();
+    let latest = {
+        let conn = lock_or_recover(&state.db);
+        conn.query_row(
+            "SELECT id, kind FROM clipboard_items ORDER BY updated_at DESC, id DESC LIMIT 1",
+            [],
+            |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)),
+        )
+        .optional()
+        .map_err(|err| err.to_string())?
+    };
+    let Some((id, kind)) = latest else {
+        return Ok(());
+    };
+    copy_stored_item(app, &id, ItemKind::from_column(&kind))
+}
+
+// 全局快捷键允许使用的修饰键写法（大小写不敏感），与 tauri 快捷键语法保持一致
+#[cfg(desktop)]
+const SHORTCUT_MODIFIERS: [&str; 12] = [
+    "ALT",
+    "OPTION",
+    "CONTROL",
+    "CTRL",
+    "COMMAND",
+    "CMD",
+    "SUPER",
+    "SHIFT",
+    "COMMANDORCONTROL",
+    "COMMANDORCTRL",
+    "CMDORCTRL",
+    "CMDORCONTROL",
+];
+
+// 按 tauri 快捷键语法解析组合键：先逐段检查常见写法问题给出明确提示，再交给插件做最终解析。
+// 除 F1~F24 外的按键必须带修饰键，避免单个字母等按键被全局占用后无法正常输入
+#[cfg(desktop)]
+pub(crate) fn parse_shortcut(accelerator: &str) -> Result<Shortcut, AppError> {
+    let trimmed = accelerator.trim();
+    if trimmed.is_empty() {
+        return Err(AppError::InvalidInput(Reason::EmptyShortcut));
+    }
+    let tokens: Vec<&str> = trimmed.split('+').map(str::trim).collect();
+    if tokens.iter().any(|token| token.is_empty()) {
+        return Err(AppError::InvalidInput(Reason::ShortcutEmptyKey(
+            trimmed.to_string(),
+        )));
+    }
+    let Some((key, modifiers)) = tokens.split_last() else {
+        return Err(AppError::InvalidInput(Reason::EmptyShortcut));
+    };
+    let is_modifier = |token: &str| SHORTCUT_MODIFIERS.contains(&token.to_uppercase().as_str());
+    if is_modifier(key) {
+        return Err(AppError::InvalidInput(Reason::ShortcutMissingKey(
+            trimmed.to_string(),
+        )));
+    }
+    if let Some(token) = modifiers.iter().find(|token| !is_modifier(token)) {
+        return Err(AppError::InvalidInput(Reason::ShortcutMisplacedModifier(
+            trimmed.to_string(),
+            token.to_string(),
+        )));
+    }
+    if modifiers.is_empty() && !is_function_key(key) {
+        return Err(AppError::InvalidInput(Reason::ShortcutMissingModifier(
+            trimmed.to_string(),
+        )));
+    }
+    Shortcut::from_str(trimmed).map_err(|_| {
+        AppError::InvalidInput(Reason::ShortcutUnknownKey(
+            trimmed.to_string(),
+            key.to_string(),
+        ))
+    })
+}
+
+// 判断是否为 F1~F24 功能键，这类按键可以不带修饰键单独作为快捷键
+#[cfg(desktop)]
+fn is_function_key(key: &str) -> bool {
+    let upper = key.to_uppercase();
+    upper
+        .strip_prefix('F')
+        .and_then(|number| number.parse::<u8>().ok())
+        .is_some_and(|number| (1..=24).contains(&number))
+}
+
+// 处理通过条目链接唤起应用：显示主窗口并广播 focus-item，前端据此选中对应条目
+#[cfg(desktop)]
+fn open_item_uri(app: &tauri::AppHandle, uri: &str) {
+    if let Some(id) = parse_item_uri(uri) {
+        show_main_window(app);
+        let _ = app.emit("focus-item", id);
+    }
+}
+
+// 注册条目链接（pure-paste://）处理：运行中收到的链接直接定位；
+// 冷启动时携带的链接先暂存，等前端加载完成后通过命令领取，避免事件在前端监听前丢失
+#[cfg(desktop)]
+fn setup_item_uri_handler(app: &tauri::App) -> Result<(), Box<dyn Error>> {
+    // Linux 与 Windows 开发模式下需要在运行时注册协议，打包后的安装程序会自动注册
+    #[cfg(any(target_os = "linux", all(debug_assertions, windows)))]
+    app.deep_link().register_all()?;
+    let app_handle = app.handle().clone();
+    app.deep_link().on_open_url(move |event| {
+        for url in event.urls() {
+            open_item_uri(&app_handle, url.as_str());
+        }
+    });
+    if let Some(urls) = app.deep_link().get_current()? {
+        let pending = urls.iter().find_map(|url| parse_item_uri(url.as_str()));
+        if let Some(id) = pending {
+            *lock_or_recover(&app.state::<AppState>().pending_focus_item) = Some(id);
+            show_main_window(app.handle());
+        }
+    }
+    Ok(())
+}
+
+// 全局快捷键切换主窗口：窗口可见且处于焦点时隐藏，否则唤起；只在被其他窗口遮挡时按下不会误隐藏
+#[cfg(desktop)]
+fn toggle_main_window(app: &tauri::AppHandle) {
+    if let Some(window) = app.get_webview_window("main") {
+        let visible = window.is_visible().unwrap_or(false);
+        let focused = window.is_focused().unwrap_or(false);
+        if visible && focused {
+            remember_window_placement(app);
+            let _ = window.hide();
+            return;
+        }
+    }
+    show_main_window(app);
+}
+
+// 注册全局快捷键，用于唤起主窗口；开启切换设置后再次按下会隐藏已处于前台的窗口
 #[cfg(desktop)]
 pub(crate) fn register_open_window_shortcut(
     app: &tauri::AppHandle,
@@ -80,7 +719,16 @@ pub(crate) fn register_open_window_shortcut(
 ) -> Result<(), String> {
     app.global_shortcut()
         .on_shortcut(shortcut, |app, _shortcut, event| {
-            if event.state == ShortcutState::Pressed {
+            if event.state != ShortcutState::Pressed {
+                return;
+            }
+            let toggles = app
+                .state::<AppState>()
+                .shortcut_toggles_window
+                .load(Ordering::Relaxed);
+            if toggles {
+                toggle_main_window(app);
+            } else {
                 show_main_window(app);
             }
         })
@@ -116,54 +764,287 @@ pub(crate) fn update_open_window_shortcut(
     Ok(())
 }
 
+// 注册切换剪贴板监听的全局快捷键：每次按下在记录与暂停之间切换
+#[cfg(desktop)]
+pub(crate) fn register_toggle_monitoring_shortcut(
+    app: &tauri::AppHandle,
+    shortcut: &str,
+) -> Result<(), String> {
+    app.global_shortcut()
+        .on_shortcut(shortcut, |app, _shortcut, event| {
+            if event.state == ShortcutState::Pressed {
+                toggle_clipboard_monitoring(app);
+            }
+        })
+        .map_err(|err| err.to_string())
+}
+
+// 切换监听开关快捷键的注册状态，流程与打开窗口快捷键一致：先注册新快捷键，再卸载旧快捷键
+#[cfg(desktop)]
+pub(crate) fn update_toggle_monitoring_shortcut(
+    app: &tauri::AppHandle,
+    previous: Option<&str>,
+    next: Option<&str>,
+) -> Result<(), String> {
+    if previous == next {
+        return Ok(());
+    }
+    let manager = app.global_shortcut();
+    if let Some(next) = next {
+        register_toggle_monitoring_shortcut(app, next)?;
+        if let Some(previous) = previous {
+            if let Err(err) = manager.unregister(previous).map_err(|err| err.to_string()) {
+                let _ = manager.unregister(next);
+                return Err(err);
+            }
+        }
+        return Ok(());
+    }
+    if let Some(previous) = previous {
+        manager
+            .unregister(previous)
+            .map_err(|err| err.to_string())?;
+    }
+    Ok(())
+}
+
+// 注册带筛选预设的全局快捷键：触发时唤起主窗口，并广播 apply-filter 让前端应用对应筛选
+#[cfg(desktop)]
+pub(crate) fn register_filter_shortcut(
+    app: &tauri::AppHandle,
+    entry: &FilterShortcut,
+) -> Result<(), String> {
+    let preset = entry.filter.clone();
+    app.global_shortcut()
+        .on_shortcut(entry.shortcut.as_str(), move |app, _shortcut, event| {
+            if event.state == ShortcutState::Pressed {
+                show_main_window(app);
+                let _ = app.emit("apply-filter", preset.clone());
+            }
+        })
+        .map_err(|err| err.to_string())
+}
+
+// 校验全部快捷键：打开窗口、切换监听与筛选快捷键逐一解析，并按解析后的组合键判重，
+// 避免 "Cmd+V" 与 "Command+V" 这类写法不同但实际冲突的情况；任一写法无效时返回对应的解析错误
+#[cfg(desktop)]
+pub(crate) fn validate_shortcuts(
+    open_window_shortcut: Option<&str>,
+    toggle_monitoring_shortcut: Option<&str>,
+    filter_shortcuts: &[FilterShortcut],
+) -> Result<(), AppError> {
+    let mut seen: Vec<Shortcut> = Vec::new();
+    let singles = [open_window_shortcut, toggle_monitoring_shortcut];
+    for shortcut in singles.into_iter().flatten() {
+        let parsed = parse_shortcut(shortcut)?;
+        if seen.contains(&parsed) {
+            return Err(AppError::InvalidInput(Reason::ShortcutConflict(
+                shortcut.to_string(),
+            )));
+        }
+        seen.push(parsed);
+    }
+    for entry in filter_shortcuts {
+        let parsed = parse_shortcut(&entry.shortcut)?;
+        if seen.contains(&parsed) {
+            return Err(AppError::InvalidInput(Reason::ShortcutConflict(
+                entry.shortcut.clone(),
+            )));
+        }
+        seen.push(parsed);
+    }
+    Ok(())
+}
+
+// 整体替换筛选快捷键：先卸载旧列表再注册新列表，任一注册失败时回滚到旧列表
+#[cfg(desktop)]
+pub(crate) fn update_filter_shortcuts(
+    app: &tauri::AppHandle,
+    previous: &[FilterShortcut],
+    next: &[FilterShortcut],
+) -> Result<(), String> {
+    let manager = app.global_shortcut();
+    for entry in previous {
+        let _ = manager.unregister(entry.shortcut.as_str());
+    }
+    for (index, entry) in next.iter().enumerate() {
+        if let Err(err) = register_filter_shortcut(app, entry) {
+            for registered in &next[..index] {
+                let _ = manager.unregister(registered.shortcut.as_str());
+            }
+            for entry in previous {
+                let _ = register_filter_shortcut(app, entry);
+            }
+            return Err(err);
+        }
+    }
+    Ok(())
+}
+
+// 同时替换全部快捷键（导入配置时使用）：先卸载旧的筛选快捷键与切换监听快捷键，
+// 避免新快捷键与旧快捷键相同（如两者互换）时注册失败；任一步失败都恢复到原来的注册状态
+#[cfg(desktop)]
+pub(crate) fn replace_shortcuts(
+    app: &tauri::AppHandle,
+    previous: &ShortcutBindings,
+    next: &ShortcutBindings,
+) -> Result<(), AppError> {
+    let previous_open = previous.open_window.as_deref();
+    let next_open = next.open_window.as_deref();
+    let previous_toggle = previous.toggle_monitoring.as_deref();
+    let next_toggle = next.toggle_monitoring.as_deref();
+    validate_shortcuts(next_open, next_toggle, &next.filters)?;
+    update_filter_shortcuts(app, &previous.filters, &[])?;
+    if let Err(err) = update_toggle_monitoring_shortcut(app, previous_toggle, None) {
+        let _ = update_filter_shortcuts(app, &[], &previous.filters);
+        return Err(err.into());
+    }
+    if let Err(err) = update_open_window_shortcut(app, previous_open, next_open) {
+        let _ = update_toggle_monitoring_shortcut(app, None, previous_toggle);
+        let _ = update_filter_shortcuts(app, &[], &previous.filters);
+        return Err(err.into());
+    }
+    if let Err(err) = update_toggle_monitoring_shortcut(app, None, next_toggle) {
+        let _ = update_open_window_shortcut(app, next_open, previous_open);
+        let _ = update_toggle_monitoring_shortcut(app, None, previous_toggle);
+        let _ = update_filter_shortcuts(app, &[], &previous.filters);
+        return Err(err.into());
+    }
+    if let Err(err) = update_filter_shortcuts(app, &[], &next.filters) {
+        let _ = update_toggle_monitoring_shortcut(app, next_toggle, None);
+        let _ = update_open_window_shortcut(app, next_open, previous_open);
+        let _ = update_toggle_monitoring_shortcut(app, None, previous_toggle);
+        let _ = update_filter_shortcuts(app, &[], &previous.filters);
+        return Err(err.into());
+    }
+    Ok(())
+}
+
 // 后台剪贴板轮询任务，负责捕获系统剪贴板并写入数据库
 #[cfg(desktop)]
 pub(crate) fn start_clipboard_watcher(app_handle: tauri::AppHandle) {
     std::thread::spawn(move || {
         // 只在后台线程中持有剪贴板实例，避免跨线程竞争导致死锁
         let mut clipboard = loop {
-            match Clipboard::new() {
+            match WatcherClipboard::new() {
                 Ok(instance) => break instance,
                 Err(_) => {
-                    // 初始化失败时稍后重试，避免启动瞬间阻塞
+                    // 初始化失败时稍后重试，避免启动瞬间阻塞；失败次数计入捕获错误，便于发现剪贴板后端不可用
+                    app_handle
+                        .state::<AppState>()
+                        .capture_error_count
+                        .fetch_add(1, Ordering::Relaxed);
                     std::thread::sleep(Duration::from_millis(1200));
                 }
             }
         };
 
+        // 平台提供剪贴板变化序号时，只在序号变化后读取内容，空闲时不再反复读取剪贴板
+        let mut change_counter = ClipboardChangeCounter::new();
+        // 剪贴板中只有图片且没有变化序号时（Linux）每次轮询都会读到同一张图，沿用上一次的哈希
+        let mut image_hashes = ImageHashCache::default();
+
         // 启动后先读取一次当前剪贴板，避免重复计数已有内容
-        if let Ok(initial_text) = clipboard.get_text() {
-            let trimmed = initial_text.trim();
-            if !trimmed.is_empty() {
-                let state = app_handle.state::<AppState>();
-                if let Ok(mut last_lock) = state.last_clipboard_text.lock() {
-                    *last_lock = Some(trimmed.to_string());
-                };
+        change_counter.has_changed();
+        if let Ok(initial_text) = read_watcher_text(&mut clipboard) {
+            change_counter.mark_read();
+            let state = app_handle.state::<AppState>();
+            let normalized = normalize_for_storage(&initial_text, NormalizePolicy::capture(&state));
+            if !normalized.is_empty() {
+                *lock_or_recover(&state.last_clipboard_text) = Some(normalized.dedup_key);
+            } else if let Some(image) = read_watcher_image(&mut clipboard) {
+                *lock_or_recover(&state.last_clipboard_text) =
+                    Some(image_dedup_key(&image_hashes.content_hash(&image)));
             }
         }
 
+        let mut lock_monitor = ScreenLockMonitor::new();
+        // 是否因锁屏暂停过：解锁后的第一次读取只作为去重基准，不写入历史
+        let mut paused_by_lock = false;
+        // 高频捕获时的广播节流状态，每次轮询先把到期的缓存条目合并广播
+        let mut throttle = BroadcastThrottle::default();
+        // 上一次清理到期条目的时间，按固定间隔清理，不必每次轮询都访问数据库
+        let mut last_expiry_sweep = Instant::now();
         loop {
-            std::thread::sleep(Duration::from_millis(CLIPBOARD_POLL_INTERVAL_MS));
+            // 每次轮询前读取间隔，设置页修改后无需重启 watcher；能检测变化序号时按更短的间隔检查序号
+            let poll_interval_ms = app_handle
+                .state::<AppState>()
+                .poll_interval_ms
+                .load(Ordering::Relaxed);
+            std::thread::sleep(change_counter.next_wait(Duration::from_millis(poll_interval_ms)));
             let state = app_handle.state::<AppState>();
+            let batch_threshold = state.broadcast_batch_threshold.load(Ordering::Relaxed);
+            if let Some(items) = throttle.take_due(batch_threshold, Instant::now()) {
+                let _ = app_handle.emit("clipboard-updated-batch", ClipboardBatchPayload { items });
+            }
+            // 到期条目的清理与是否监听剪贴板无关，暂停监听或锁屏期间同样按时删除
+            if last_expiry_sweep.elapsed() >= ITEM_EXPIRY_SWEEP_INTERVAL {
+                last_expiry_sweep = Instant::now();
+                let expired_ids =
+                    delete_expired_items(&lock_or_recover(&state.db)).unwrap_or_default();
+                emit_clipboard_expired(&app_handle, expired_ids);
+            }
             if !state.monitoring_enabled.load(Ordering::Relaxed) {
                 continue;
             }
-            let content = match clipboard.get_text() {
-                Ok(text) => text,
-                Err(_) => continue,
+            // 锁屏期间不读取剪贴板，避免记录锁屏界面自动填充的内容，也节省轮询开销
+            if state.pause_on_lock.load(Ordering::Relaxed) && lock_monitor.is_locked() {
+                paused_by_lock = true;
+                continue;
+            }
+            // 序号未变化说明剪贴板没有被写入过，无需读取；解锁后的第一次读取用于更新去重基准，照常进行
+            if !change_counter.has_changed() && !paused_by_lock {
+                continue;
+            }
+            let content = match read_watcher_text(&mut clipboard) {
+                Ok(text) => {
+                    change_counter.mark_read();
+                    text
+                }
+                Err(_) => {
+                    // 读取失败时下次轮询重试，同时累计错误次数，避免后端持续异常却无从察觉
+                    state.capture_error_count.fetch_add(1, Ordering::Relaxed);
+                    continue;
+                }
             };
-            let trimmed = content.trim();
+            // 写入历史与去重比较统一使用规整后的文本：trimmed 为写入的内容，dedup_key 为去重基准
+            let normalized = normalize_for_storage(&content, NormalizePolicy::capture(&state));
+            let trimmed = normalized.display_text.as_str();
+            let dedup_key = normalized.dedup_key.as_str();
+            if paused_by_lock {
+                // 锁屏期间写入剪贴板的内容在解锁后同样不记录，只更新去重基准
+                paused_by_lock = false;
+                if !trimmed.is_empty() {
+                    *lock_or_recover(&state.last_clipboard_text) = Some(dedup_key.to_string());
+                } else if let Some(image) = read_watcher_image(&mut clipboard) {
+                    *lock_or_recover(&state.last_clipboard_text) =
+                        Some(image_dedup_key(&image_hashes.content_hash(&image)));
+                }
+                continue;
+            }
             if trimmed.is_empty() {
+                // 没有文本时再检查图片，也没有图片才视为剪贴板被清空
+                match read_watcher_image(&mut clipboard) {
+                    Some(image) => {
+                        let content_hash = image_hashes.content_hash(&image);
+                        capture_clipboard_image(&app_handle, &state, image, content_hash)
+                    }
+                    None => observe_empty_clipboard(&state),
+                }
                 continue;
             }
 
-            // 如果是应用自身写入的内容则跳过一次，避免重复计数
+            // 如果是应用自身写入的内容则跳过一次，避免重复计数；标记超过有效期后直接丢弃，不再跳过
             let should_skip = {
-                let mut skip_lock = match state.skip_next_text.lock() {
-                    Ok(lock) => lock,
-                    Err(_) => continue,
-                };
-                if skip_lock.as_deref() == Some(trimmed) {
+                let mut skip_lock = lock_or_recover(&state.skip_next_text);
+                let expired = skip_lock.as_ref().is_some_and(|(_, marked_at)| {
+                    marked_at.elapsed()
+                        > Duration::from_millis(skip_next_text_ttl_ms(poll_interval_ms))
+                });
+                if expired {
+                    *skip_lock = None;
+                }
+                if skip_lock.as_ref().map(|(text, _)| text.as_str()) == Some(dedup_key) {
                     *skip_lock = None;
                     true
                 } else {
@@ -171,52 +1052,213 @@ pub(crate) fn start_clipboard_watcher(app_handle: tauri::AppHandle) {
                 }
             };
             if should_skip {
-                if let Ok(mut last_lock) = state.last_clipboard_text.lock() {
-                    *last_lock = Some(trimmed.to_string());
-                }
+                skip_capture(&state, dedup_key, trimmed, SkipReason::SelfWrite);
                 continue;
             }
 
-            // 与最近一次记录对比，避免剪贴板未变化时重复写入
-            let is_duplicate = match state.last_clipboard_text.lock() {
-                Ok(lock) => lock.as_deref() == Some(trimmed),
-                Err(_) => true,
+            // 与最近一次记录对比，避免剪贴板未变化时重复写入；这是轮询的常态，不记入跳过记录
+            if is_repeat_of_last_capture(&state, dedup_key) {
+                continue;
+            }
+
+            // 匹配排除规则的内容（如密钥）不写入历史，同样更新去重基准；跳过记录中不保留内容预览
+            let excluded = lock_or_recover(&state.exclusion_rules)
+                .iter()
+                .any(|regex| regex.is_match(trimmed));
+            if excluded {
+                skip_capture(&state, dedup_key, "", SkipReason::Excluded);
+                continue;
+            }
+
+            // 复制时处于前台的应用既记录为条目来源，也用于判断是否来自被屏蔽的应用（如密码管理器）：
+            // 被屏蔽时不写入历史，同样更新去重基准，避免之后切换到其他应用时被当作新内容记录；
+            // 无法判断前台应用时照常记录，来源留空
+            let source_app = frontmost_app_id();
+            let from_blocked_source = {
+                let blocklist = lock_or_recover(&state.source_blocklist);
+                !blocklist.is_empty()
+                    && source_app
+                        .as_deref()
+                        .is_some_and(|app| is_blocked_app(app, &blocklist))
             };
-            if is_duplicate {
+            if from_blocked_source {
+                skip_capture(&state, dedup_key, "", SkipReason::BlockedSource);
+                continue;
+            }
+
+            // 在应用自身窗口内复制的内容（如选中条目文本后 Ctrl+C）不写入历史，同样更新去重基准
+            if is_own_window_copy(&state, || own_window_focused(&app_handle)) {
+                skip_capture(&state, dedup_key, trimmed, SkipReason::OwnWindow);
+                continue;
+            }
+
+            // 不符合捕获过滤设置的内容不写入历史，但仍更新去重基准，避免每次轮询重复判断与记录
+            let allowed = lock_or_recover(&state.capture_filter).allows(trimmed);
+            if !allowed {
+                skip_capture(&state, dedup_key, trimmed, SkipReason::Filtered);
                 continue;
             }
 
-            let payload = build_clipboard_payload(trimmed.to_string());
+            // 内容像是指向密钥、凭据文件的路径时提醒用户；默认照常写入并标记，开启拦截后不写入历史
+            let risky_reason =
+                match_sensitive_path(trimmed, &lock_or_recover(&state.sensitive_path_rules))
+                    .map(str::to_string);
+            if let Some(reason) = &risky_reason {
+                if state.block_risky_captures.load(Ordering::Relaxed) {
+                    skip_capture(&state, dedup_key, trimmed, SkipReason::RiskyPath);
+                    emit_risky_capture(&app_handle, None, trimmed, reason, true);
+                    continue;
+                }
+            }
+
+            // 传入原始内容，由写入逻辑统一规整并记录是否清理过排版字符
+            let mut payload = build_clipboard_payload(content);
+            payload.source_app = source_app;
             match upsert_clipboard_item_internal(&state, payload, HISTORY_RETENTION_DAYS) {
-                Ok(persisted) => {
-                    if let Ok(mut last_lock) = state.last_clipboard_text.lock() {
-                        *last_lock = Some(trimmed.to_string());
+                Ok(mut outcome) => {
+                    *lock_or_recover(&state.last_clipboard_text) = Some(dedup_key.to_string());
+                    if outcome.count_capped {
+                        record_skipped_capture(&state, trimmed, SkipReason::BurstCapped);
+                    }
+                    if risky_reason.is_some() {
+                        let conn = lock_or_recover(&state.db);
+                        outcome.item.risky = mark_item_risky(&conn, &outcome.item.id).is_ok();
+                    }
+                    let item_id = outcome.item.id.clone();
+                    if let Some(item) =
+                        throttle.offer(outcome.item, batch_threshold, Instant::now())
+                    {
+                        let _ = app_handle.emit(
+                            "clipboard-updated",
+                            ClipboardBroadcastPayload {
+                                item,
+                                merged_id: None,
+                            },
+                        );
+                    }
+                    emit_clipboard_pruned(&app_handle, outcome.pruned_ids);
+                    emit_clipboard_unpinned(&app_handle, outcome.unpinned);
+                    if let Some(reason) = &risky_reason {
+                        emit_risky_capture(&app_handle, Some(item_id), trimmed, reason, false);
                     }
-                    let _ = app_handle.emit(
-                        "clipboard-updated",
-                        ClipboardBroadcastPayload {
-                            item: persisted,
-                            merged_id: None,
-                        },
-                    );
                 }
                 Err(_) => {
                     // 写入失败时保持 last_clipboard_text 不更新，便于下次重试
+                    record_skipped_capture(&state, trimmed, SkipReason::WriteFailed);
                 }
             }
         }
     });
 }
 
+// 处理剪贴板中的图片：以图片内容哈希作为去重键，与文本共用去重基准和自身写入的跳过标记；
+// 屏蔽来源应用与忽略应用内复制同样适用，排除规则、捕获过滤、敏感路径等针对文本的规则不适用于图片。
+// 图片捕获频率很低，写入后直接广播，不经过文本的广播节流
+#[cfg(desktop)]
+fn capture_clipboard_image(
+    app_handle: &tauri::AppHandle,
+    state: &AppState,
+    image: ClipboardImage,
+    content_hash: String,
+) {
+    let dedup_key = image_dedup_key(&content_hash);
+    let label = image_item_label(&image);
+    let self_write = {
+        let mut skip_lock = lock_or_recover(&state.skip_next_text);
+        let matched = skip_lock.as_ref().map(|(key, _)| key.as_str()) == Some(dedup_key.as_str());
+        if matched {
+            *skip_lock = None;
+        }
+        matched
+    };
+    if self_write {
+        skip_capture(state, &dedup_key, &label, SkipReason::SelfWrite);
+        return;
+    }
+    if is_repeat_of_last_capture(state, &dedup_key) {
+        return;
+    }
+    let source_app = frontmost_app_id();
+    let from_blocked_source = {
+        let blocklist = lock_or_recover(&state.source_blocklist);
+        !blocklist.is_empty()
+            && source_app
+                .as_deref()
+                .is_some_and(|app| is_blocked_app(app, &blocklist))
+    };
+    if from_blocked_source {
+        skip_capture(state, &dedup_key, "", SkipReason::BlockedSource);
+        return;
+    }
+    if is_own_window_copy(state, || own_window_focused(app_handle)) {
+        skip_capture(state, &dedup_key, &label, SkipReason::OwnWindow);
+        return;
+    }
+    match upsert_image_item_internal(
+        state,
+        &image,
+        &content_hash,
+        source_app.as_deref(),
+        HISTORY_RETENTION_DAYS,
+    ) {
+        Ok(outcome) => {
+            *lock_or_recover(&state.last_clipboard_text) = Some(dedup_key);
+            if outcome.count_capped {
+                record_skipped_capture(state, &label, SkipReason::BurstCapped);
+            }
+            let _ = app_handle.emit(
+                "clipboard-updated",
+                ClipboardBroadcastPayload {
+                    item: outcome.item,
+                    merged_id: None,
+                },
+            );
+            emit_clipboard_pruned(app_handle, outcome.pruned_ids);
+            emit_clipboard_unpinned(app_handle, outcome.unpinned);
+        }
+        Err(_) => {
+            // 写入失败时保持去重基准不更新，便于下次重试
+            record_skipped_capture(state, &label, SkipReason::WriteFailed);
+        }
+    }
+}
+
+// 广播捕获到敏感路径的提醒，前端据此提示用户；预览与跳过记录一样只保留开头部分
+#[cfg(desktop)]
+fn emit_risky_capture(
+    app: &tauri::AppHandle,
+    item_id: Option<String>,
+    text: &str,
+    reason: &str,
+    blocked: bool,
+) {
+    let _ = app.emit(
+        "risky-capture",
+        RiskyCapturePayload {
+            item_id,
+            text_preview: text.chars().take(SKIP_PREVIEW_MAX_CHARS).collect(),
+            reason: reason.to_string(),
+            blocked,
+        },
+    );
+}
+
 // 在 setup 阶段一次性完成桌面端能力初始化：快捷键、自启动插件、托盘、后台 watcher。
 #[cfg(desktop)]
 pub(crate) fn setup_desktop(
     app: &mut tauri::App,
-    open_window_shortcut: Option<&str>,
+    shortcuts: &ShortcutBindings,
 ) -> Result<(), Box<dyn Error>> {
     // 根据已保存的配置注册全局快捷键，保证启动后即可生效
-    if let Some(shortcut) = open_window_shortcut {
-        register_open_window_shortcut(&app.handle(), shortcut)?;
+    if let Some(shortcut) = shortcuts.open_window.as_deref() {
+        register_open_window_shortcut(app.handle(), shortcut)?;
+    }
+    // 切换监听快捷键与筛选快捷键属于附加入口，单个注册失败（如被其他应用占用）不应阻断启动
+    if let Some(shortcut) = shortcuts.toggle_monitoring.as_deref() {
+        let _ = register_toggle_monitoring_shortcut(app.handle(), shortcut);
+    }
+    for entry in &shortcuts.filters {
+        let _ = register_filter_shortcut(app.handle(), entry);
     }
 
     // 初始化开机自启动插件，保证设置页可以读取/切换系统自启动状态
@@ -227,22 +1269,24 @@ pub(crate) fn setup_desktop(
         ))
         .map_err(|err| err.to_string())?;
 
-    // 创建托盘菜单，确保应用关闭窗口后仍可快速唤起
-    let show_item = MenuItem::with_id(app, "show", "打开", true, None::<&str>)?;
-    let settings_item = MenuItem::with_id(app, "settings", "设置", true, None::<&str>)?;
-    let quit_item = MenuItem::with_id(app, "quit", "退出", true, None::<&str>)?;
-    let tray_menu = MenuBuilder::new(app)
-        .item(&show_item)
-        .item(&settings_item)
-        .separator()
-        .item(&quit_item)
-        .build()?;
+    // 按用户配置创建托盘菜单，确保应用关闭窗口后仍可快速唤起
+    let (tray_actions, tray_recent) =
+        load_tray_menu_content(app.handle()).map_err(|err| err.to_string())?;
+    let tray_menu = build_tray_menu(app.handle(), &tray_actions, tray_recent.as_deref())?;
+    // 左键是否弹出菜单取决于托盘左键动作设置，其余动作由点击事件回调处理
+    let tray_left_click = *lock_or_recover(&app.state::<AppState>().tray_left_click);
+    // 图标与提示文字按恢复后的监听开关选择，启动时即可看出是否处于暂停状态
+    let monitoring = app
+        .state::<AppState>()
+        .monitoring_enabled
+        .load(Ordering::Relaxed);
     let mut tray_builder = TrayIconBuilder::new()
         .menu(&tray_menu)
-        .tooltip("我的剪贴板")
-        // 左键点击直接触发点击事件，退出改为右键/菜单操作
-        .show_menu_on_left_click(false);
-    if let Some(icon) = load_tray_icon_image().or_else(|| app.default_window_icon().cloned()) {
+        .tooltip(tray_tooltip(monitoring))
+        .show_menu_on_left_click(tray_left_click == TrayLeftClickAction::ShowMenu);
+    if let Some(icon) =
+        load_tray_icon_image(monitoring).or_else(|| app.default_window_icon().cloned())
+    {
         tray_builder = tray_builder.icon(icon);
     }
     #[cfg(target_os = "macos")]
@@ -255,6 +1299,22 @@ pub(crate) fn setup_desktop(
     // 保持托盘实例存活，避免离开作用域后图标被自动移除
     app.manage(tray);
 
+    // 新条目写入、固定状态变化或条目被清理后重建托盘菜单，让“最近复制”子菜单保持最新
+    for event in [
+        "clipboard-updated",
+        "clipboard-updated-batch",
+        "clipboard-pinned",
+        "clipboard-pruned",
+    ] {
+        let app_handle = app.handle().clone();
+        app.listen_any(event, move |_| {
+            let _ = refresh_tray_menu(&app_handle);
+        });
+    }
+
+    // 条目链接属于附加入口，协议注册失败不应阻断启动
+    let _ = setup_item_uri_handler(app);
+
     // 启动后台剪贴板监听任务，确保隐藏窗口后仍可记录
     start_clipboard_watcher(app.handle().clone());
 
@@ -0,0 +1,322 @@
+// detect.rs：捕获时对文本做的轻量内容识别，结果写入条目的元数据列，供筛选使用。
+// 说明：这里只做基于字符/格式的启发式判断，必须足够便宜，因为每次捕获都会在写入事务中执行。
+
+use crate::format::{is_json_document, is_xml_document};
+use crate::models::{ContentCategory, ContentType, SensitivePathRule, SENSITIVE_PATH_MAX_CHARS};
+
+// 识别文本是否为可格式化的结构化内容（JSON / XML），其余普通文本返回 None；
+// 先按首字符快速排除，只有形似 JSON/XML 的文本才会进入完整解析
+pub(crate) fn detect_content_type(text: &str) -> Option<ContentType> {
+    if is_json_document(text) {
+        Some(ContentType::Json)
+    } else if is_xml_document(text) {
+        Some(ContentType::Xml)
+    } else {
+        None
+    }
+}
+
+// 看起来像代码的行常见的开头，覆盖主流语言的声明、导入与注释写法
+const CODE_LINE_PREFIXES: [&str; 24] = [
+    "fn ",
+    "pub ",
+    "let ",
+    "const ",
+    "var ",
+    "function ",
+    "def ",
+    "class ",
+    "import ",
+    "from ",
+    "return ",
+    "package ",
+    "use ",
+    "if (",
+    "for (",
+    "while (",
+    "} else",
+    "#include",
+    "#!/",
+    "//",
+    "/*",
+    "SELECT ",
+    "INSERT ",
+    "UPDATE ",
+];
+
+// 识别条目的内容类别，供自动归类使用：结构化类型沿用捕获时保存的 content_type，
+// 其余依次判断链接、颜色值与代码，都不符合时返回 None
+pub(crate) fn detect_category(text: &str, content_type: Option<&str>) -> Option<ContentCategory> {
+    match content_type {
+        Some(value) if value == ContentType::Json.as_str() => Some(ContentCategory::Json),
+        Some(value) if value == ContentType::Xml.as_str() => Some(ContentCategory::Xml),
+        _ if is_url(text) => Some(ContentCategory::Url),
+        _ if is_color(text) => Some(ContentCategory::Color),
+        _ if looks_like_code(text) => Some(ContentCategory::Code),
+        _ => None,
+    }
+}
+
+// 判断文本是否为单个 CSS 颜色值：#rgb / #rgba / #rrggbb / #rrggbbaa，或 rgb()/rgba()/hsl()/hsla() 函数写法
+pub(crate) fn is_color(text: &str) -> bool {
+    let trimmed = text.trim();
+    if let Some(hex) = trimmed.strip_prefix('#') {
+        return matches!(hex.len(), 3 | 4 | 6 | 8) && hex.chars().all(|ch| ch.is_ascii_hexdigit());
+    }
+    let lower = trimmed.to_ascii_lowercase();
+    ["rgb(", "rgba(", "hsl(", "hsla("].iter().any(|prefix| {
+        lower.strip_prefix(prefix).is_some_and(|rest| {
+            rest.strip_suffix(')').is_some_and(|args| {
+                !args.is_empty()
+                    && args
+                        .chars()
+                        .all(|ch| ch.is_ascii_digit() || " ,./%deg".contains(ch))
+            })
+        })
+    })
+}
+
+// 判断文本是否为单个 http/https 链接：与前端“打开链接”的判断保持一致，只接受完整的链接
+pub(crate) fn is_url(text: &str) -> bool {
+    let trimmed = text.trim();
+    if trimmed.chars().any(char::is_whitespace) {
+        return false;
+    }
+    let lower = trimmed.to_ascii_lowercase();
+    let rest = lower
+        .strip_prefix("https://")
+        .or_else(|| lower.strip_prefix("http://"));
+    rest.is_some_and(|rest| !rest.is_empty() && !rest.starts_with('/'))
+}
+
+// 粗略判断文本是否为代码片段：JSON/XML 直接视为代码，其余按“像代码的行”所占比例判断；
+// 单行文本需要本身就像代码，多行文本只要一半以上的行像代码即可
+pub(crate) fn looks_like_code(text: &str) -> bool {
+    if detect_content_type(text).is_some() {
+        return true;
+    }
+    let lines: Vec<&str> = text
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect();
+    let code_lines = lines.iter().filter(|line| is_code_line(line)).count();
+    code_lines > 0 && code_lines * 2 >= lines.len()
+}
+
+// 判断单行是否像代码：以语句结束符或括号结尾，或以常见关键字开头
+fn is_code_line(line: &str) -> bool {
+    line.ends_with([';', '{', '}'])
+        || line.contains("=>")
+        || CODE_LINE_PREFIXES
+            .iter()
+            .any(|prefix| line.starts_with(prefix))
+}
+
+// 文字体系识别结果对应的取值，与数据库 script 列及前端筛选项保持一致
+pub(crate) const SCRIPT_CJK: &str = "cjk";
+pub(crate) const SCRIPT_LATIN: &str = "latin";
+pub(crate) const SCRIPT_CYRILLIC: &str = "cyrillic";
+pub(crate) const SCRIPT_GREEK: &str = "greek";
+pub(crate) const SCRIPT_ARABIC: &str = "arabic";
+pub(crate) const SCRIPT_HEBREW: &str = "hebrew";
+pub(crate) const SCRIPT_DEVANAGARI: &str = "devanagari";
+pub(crate) const SCRIPT_THAI: &str = "thai";
+
+// 全部可识别的文字体系，用于校验前端传入的筛选值
+pub(crate) const KNOWN_SCRIPTS: [&str; 8] = [
+    SCRIPT_CJK,
+    SCRIPT_LATIN,
+    SCRIPT_CYRILLIC,
+    SCRIPT_GREEK,
+    SCRIPT_ARABIC,
+    SCRIPT_HEBREW,
+    SCRIPT_DEVANAGARI,
+    SCRIPT_THAI,
+];
+
+// 识别文本的主要文字体系：按 Unicode 区段统计字母类字符，取数量最多的一类；
+// 没有可识别的字母（纯数字、符号、emoji 等）时返回 None
+pub(crate) fn detect_script(text: &str) -> Option<&'static str> {
+    let mut counts = [0usize; KNOWN_SCRIPTS.len()];
+    for ch in text.chars() {
+        if let Some(index) = script_index(ch) {
+            counts[index] += 1;
+        }
+    }
+    let (index, count) = counts.iter().enumerate().max_by_key(|(_, count)| **count)?;
+    (*count > 0).then_some(KNOWN_SCRIPTS[index])
+}
+
+// 判断单个字符所属的文字体系，返回其在 KNOWN_SCRIPTS 中的下标
+fn script_index(ch: char) -> Option<usize> {
+    let index = match ch as u32 {
+        // 汉字（含扩展 A 与兼容区）、日文假名、韩文音节与字母
+        0x4E00..=0x9FFF
+        | 0x3400..=0x4DBF
+        | 0xF900..=0xFAFF
+        | 0x3040..=0x30FF
+        | 0x31F0..=0x31FF
+        | 0xAC00..=0xD7AF
+        | 0x1100..=0x11FF
+        | 0x3130..=0x318F
+        | 0x20000..=0x2A6DF => 0,
+        // 基本拉丁字母与带变音符号的扩展拉丁字母
+        0x41..=0x5A | 0x61..=0x7A | 0xC0..=0x24F | 0x1E00..=0x1EFF => 1,
+        0x400..=0x52F => 2,
+        0x370..=0x3FF | 0x1F00..=0x1FFF => 3,
+        0x600..=0x6FF | 0x750..=0x77F => 4,
+        0x590..=0x5FF => 5,
+        0x900..=0x97F => 6,
+        0xE00..=0xE7F => 7,
+        _ => return None,
+    };
+    // 区段内的标点、数字等非字母字符不参与统计（如 0xD7 乘号位于拉丁扩展区段内）
+    ch.is_alphabetic().then_some(index)
+}
+
+// 判断文本是否指向敏感文件（私钥、凭据、.env 等），返回命中的规则原文作为原因。
+// 只检查像单个文件路径的文本：单行、不超过长度上限、不是网页链接，含空白时必须以绝对路径开头
+// （如 C:\Users\John Doe\.ssh）；引号与 file:// 前缀会先去掉，反斜杠统一为 /，规则只需按 / 书写
+pub(crate) fn match_sensitive_path<'a>(
+    text: &str,
+    rules: &'a [SensitivePathRule],
+) -> Option<&'a str> {
+    if rules.is_empty() {
+        return None;
+    }
+    let trimmed = text.trim();
+    if trimmed.is_empty()
+        || trimmed.contains('\n')
+        || trimmed.chars().count() > SENSITIVE_PATH_MAX_CHARS
+        || is_url(trimmed)
+    {
+        return None;
+    }
+    let unquoted = trimmed.trim_matches(|ch| ch == '"' || ch == '\'');
+    let path = unquoted.strip_prefix("file://").unwrap_or(unquoted);
+    let absolute = path.starts_with(['/', '~', '\\'])
+        || path
+            .as_bytes()
+            .get(..2)
+            .is_some_and(|prefix| prefix[0].is_ascii_alphabetic() && prefix[1] == b':');
+    if !absolute && path.chars().any(char::is_whitespace) {
+        return None;
+    }
+    let path = path.replace('\\', "/");
+    let path = path.trim_end_matches('/');
+    rules
+        .iter()
+        .find(|rule| rule.regex.is_match(path))
+        .map(|rule| rule.pattern.as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 按字母数量最多的文字体系判断，数字、标点与 emoji 不参与统计
+    #[test]
+    fn detect_script_picks_dominant_script() {
+        assert_eq!(detect_script("剪贴板历史"), Some(SCRIPT_CJK));
+        assert_eq!(detect_script("こんにちは"), Some(SCRIPT_CJK));
+        assert_eq!(detect_script("안녕하세요"), Some(SCRIPT_CJK));
+        assert_eq!(detect_script("Crème brûlée"), Some(SCRIPT_LATIN));
+        assert_eq!(detect_script("Привет, мир"), Some(SCRIPT_CYRILLIC));
+        assert_eq!(detect_script("Καλημέρα"), Some(SCRIPT_GREEK));
+        assert_eq!(detect_script("مرحبا"), Some(SCRIPT_ARABIC));
+        assert_eq!(detect_script("שלום"), Some(SCRIPT_HEBREW));
+        assert_eq!(detect_script("नमस्ते"), Some(SCRIPT_DEVANAGARI));
+        assert_eq!(detect_script("สวัสดี"), Some(SCRIPT_THAI));
+        // 中英混排时取数量更多的一类
+        assert_eq!(
+            detect_script("使用 Rust 编写的剪贴板工具"),
+            Some(SCRIPT_CJK)
+        );
+        assert_eq!(detect_script("see 文档 for details"), Some(SCRIPT_LATIN));
+    }
+
+    #[test]
+    fn detect_script_ignores_non_letters() {
+        assert_eq!(detect_script(""), None);
+        assert_eq!(detect_script("12345 + 67 = ?"), None);
+        assert_eq!(detect_script("😀🎉 ×÷"), None);
+        assert_eq!(detect_script("3×4"), None);
+    }
+
+    // 只有 JSON 对象 / 数组与格式良好的 XML 文档才算结构化内容
+    #[test]
+    fn detect_content_type_accepts_documents_only() {
+        assert_eq!(
+            detect_content_type(" {\"a\": [1, 2]}\n"),
+            Some(ContentType::Json)
+        );
+        assert_eq!(detect_content_type("[]"), Some(ContentType::Json));
+        assert_eq!(
+            detect_content_type("<?xml version=\"1.0\"?><root><a x=\"1\"/></root>"),
+            Some(ContentType::Xml)
+        );
+        // 合法但没有格式化意义的 JSON 标量、残缺的 JSON、多个根元素与未闭合的 XML
+        for text in [
+            "42",
+            "\"text\"",
+            "{\"a\": 1",
+            "[1, 2,]",
+            "<a></a><b></b>",
+            "<a><b></a>",
+            "<a>",
+            "<a></a> trailing",
+            "<not xml",
+            "plain words",
+        ] {
+            assert_eq!(detect_content_type(text), None, "{text}");
+        }
+    }
+
+    // 只接受单个完整的 http/https 链接
+    #[test]
+    fn is_url_accepts_single_http_links() {
+        for text in [
+            "https://example.com",
+            "  HTTP://Example.com/path?q=1#top\n",
+            "https://例子.测试/路径",
+        ] {
+            assert!(is_url(text), "{text}");
+        }
+        for text in [
+            "example.com",
+            "ftp://example.com",
+            "https://",
+            "https:///path",
+            "see https://example.com",
+            "https://example.com https://example.org",
+        ] {
+            assert!(!is_url(text), "{text}");
+        }
+    }
+
+    // 单行需本身像代码，多行只要一半以上的行像代码；结构化内容直接视为代码
+    #[test]
+    fn looks_like_code_uses_line_ratio() {
+        for text in [
+            "let x = 1;",
+            "fn main() {\n    println!(\"hi\");\n}",
+            "def f(x):\n    return x\n",
+            "items.map(item => item.id)",
+            "SELECT * FROM clipboard_items",
+            "{\"a\": 1}",
+            "<root/>",
+            "#!/bin/sh\necho hi",
+        ] {
+            assert!(looks_like_code(text), "{text}");
+        }
+        for text in [
+            "",
+            "just a sentence.",
+            "Dear team,\nthe meeting is moved.\nThanks;",
+            "import",
+        ] {
+            assert!(!looks_like_code(text), "{text}");
+        }
+    }
+}
@@ -0,0 +1,865 @@
+// error.rs：命令层统一使用的错误类型。
+// 说明：
+// 1. 序列化为 { code, message }：code 是稳定的错误码，前端据此区分错误而不依赖提示文案；message 为展示给用户的提示；
+// 2. 常见的业务错误（条目不存在、内容为空等）各有独立的错误码，其余错误按来源归类，保留原始说明；
+// 3. 会被命令直接调用的辅助函数返回 AppError，保留 not_found、invalid_input 等错误码；
+//    其余仍返回 String 错误的内部函数（只携带底层库的原始说明）经 `?` 转换为 Other；
+// 4. 提示按当前语言从对照表生成：调用处只给出 Reason（原因的种类与参数），中英文提示都在对照表中维护。
+
+use crate::models::Locale;
+use serde::ser::SerializeStruct;
+use serde::{Serialize, Serializer};
+use std::fmt;
+use std::sync::RwLock;
+
+// 当前提示使用的语言：错误在序列化时才生成提示，拿不到 AppState，因此单独保存；启动时与修改语言时写入
+static ACTIVE_LOCALE: RwLock<Locale> = RwLock::new(Locale::ZhCn);
+
+// 提示对照表：(键, 英文, 中文)。前半部分以错误码为键，是各类错误的通用提示；
+// 后半部分以错误原因为键，是调用处给出具体原因时的完整提示，{0}、{1} 依次替换为原因携带的参数
+const ERROR_MESSAGES: &[(&str, &str, &str)] = &[
+    (
+        "not_found",
+        "The requested item was not found",
+        "未找到对应的条目",
+    ),
+    (
+        "empty_content",
+        "The content is empty and was not saved",
+        "内容为空，已忽略保存",
+    ),
+    ("invalid_input", "Invalid input", "参数无效"),
+    (
+        "unsupported",
+        "This operation is not supported",
+        "不支持该操作",
+    ),
+    ("sqlite", "Database operation failed", "数据库读写失败"),
+    ("io", "File operation failed", "文件读写失败"),
+    ("other", "Operation failed", "操作失败"),
+    // 历史列表与统计
+    ("invalid_cursor", "The paging cursor is invalid", "游标时间无效"),
+    (
+        "stats_range_reversed",
+        "The start of the statistics range cannot be later than its end",
+        "统计开始时间不能晚于结束时间",
+    ),
+    (
+        "invalid_stats_range",
+        "The statistics time range is invalid",
+        "统计时间范围无效",
+    ),
+    (
+        "invalid_query_range",
+        "The query time range is invalid",
+        "查询时间范围无效",
+    ),
+    (
+        "invalid_date",
+        "Invalid date {0}, expected YYYY-MM-DD",
+        "日期格式无效：{0}，应为 YYYY-MM-DD",
+    ),
+    (
+        "date_out_of_range",
+        "The date is out of range: {0}",
+        "日期超出范围：{0}",
+    ),
+    (
+        "unconvertible_date",
+        "The date cannot be converted to local time: {0}",
+        "无法换算本地日期：{0}",
+    ),
+    (
+        "negative_offset",
+        "The read offset cannot be negative",
+        "读取位置不能为负数",
+    ),
+    (
+        "invalid_created_at",
+        "The creation time is invalid; it can only be set to a reasonable time in the past",
+        "创建时间无效，只能设置为过去的合理时间",
+    ),
+    // 条目内容与格式化
+    (
+        "invalid_json",
+        "The content is not valid JSON: {0}",
+        "内容不是有效的 JSON：{0}",
+    ),
+    (
+        "invalid_xml",
+        "The content is not valid XML: {0}",
+        "内容不是有效的 XML：{0}",
+    ),
+    (
+        "xml_multiple_roots",
+        "The content is not valid XML: it has more than one root element",
+        "内容不是有效的 XML：存在多个根元素",
+    ),
+    (
+        "xml_text_outside_root",
+        "The content is not valid XML: it has text outside the root element",
+        "内容不是有效的 XML：根元素之外存在正文内容",
+    ),
+    (
+        "xml_unclosed_element",
+        "The content is not valid XML: an element is not closed",
+        "内容不是有效的 XML：存在未闭合的元素",
+    ),
+    (
+        "xml_missing_root",
+        "The content is not valid XML: the root element is missing",
+        "内容不是有效的 XML：缺少根元素",
+    ),
+    ("unknown_script", "Unsupported script: {0}", "不支持的文字类型：{0}"),
+    (
+        "invalid_paste_transform",
+        "The item's paste transform setting is invalid",
+        "条目的粘贴变换设置无效",
+    ),
+    (
+        "no_items_selected",
+        "Select the items to combine",
+        "请选择需要拼接的条目",
+    ),
+    (
+        "empty_template",
+        "The template cannot be empty",
+        "模板内容不能为空",
+    ),
+    (
+        "self_swap",
+        "An item cannot swap positions with itself",
+        "无法与自身交换位置",
+    ),
+    (
+        "swap_unpinned",
+        "Only pinned items can be reordered",
+        "只能调整固定条目的顺序",
+    ),
+    // 图片条目
+    ("not_an_image", "This item is not an image", "该条目不是图片"),
+    (
+        "image_needs_image_copy",
+        "Image items must be copied as images",
+        "图片条目需要按图片复制",
+    ),
+    (
+        "image_text_not_editable",
+        "The text of an image item cannot be edited",
+        "图片条目不支持编辑文本",
+    ),
+    ("image_too_large", "The image is too large", "图片尺寸过大"),
+    // 标签与正则
+    ("empty_tag", "The tag cannot be empty", "标签不能为空"),
+    ("tag_not_found", "Tag {0} does not exist", "标签 {0} 不存在"),
+    (
+        "invalid_tag_color",
+        "The tag color must use the #RRGGBB format",
+        "标签颜色需为 #RRGGBB 格式",
+    ),
+    (
+        "empty_regex",
+        "The regular expression cannot be empty",
+        "正则表达式不能为空",
+    ),
+    (
+        "invalid_regex",
+        "Invalid regular expression: {0}",
+        "正则表达式无效：{0}",
+    ),
+    (
+        "custom_filter_needs_regex",
+        "A custom filter requires a regular expression",
+        "自定义过滤需要填写正则表达式",
+    ),
+    (
+        "invalid_sensitive_path_rule",
+        "Rule {0} is invalid: {1}",
+        "规则 {0} 无效：{1}",
+    ),
+    (
+        "invalid_exclusion_rule",
+        "Exclusion rule {0} is invalid: {1}",
+        "排除规则 {0} 无效：{1}",
+    ),
+    // 设置取值范围
+    (
+        "clipboard_clear_seconds_range",
+        "The auto-clear delay must be between {0} and {1} seconds",
+        "自动清空延迟需在 {0} 到 {1} 秒之间",
+    ),
+    (
+        "auto_unpin_days_range",
+        "The auto-unpin period must be between {0} and {1} days",
+        "自动取消固定天数需在 {0} 到 {1} 天之间",
+    ),
+    (
+        "item_ttl_minutes_range",
+        "The item lifetime must be between {0} and {1} minutes",
+        "条目存活时间需在 {0} 到 {1} 分钟之间",
+    ),
+    (
+        "capture_burst_threshold_range",
+        "The burst capture limit must be between {0} and {1}",
+        "高频捕获上限需在 {0} 到 {1} 次之间",
+    ),
+    (
+        "broadcast_batch_threshold_range",
+        "The batch broadcast threshold must be between {0} and {1}",
+        "合并广播阈值需在 {0} 到 {1} 次之间",
+    ),
+    (
+        "tray_recent_count_range",
+        "The number of recent tray items must be between {0} and {1}",
+        "托盘最近条目数需在 {0} 到 {1} 之间",
+    ),
+    // 快捷键
+    (
+        "shortcuts_unsupported",
+        "Global shortcuts are not supported on this platform",
+        "当前平台不支持全局快捷键",
+    ),
+    (
+        "empty_shortcut",
+        "The shortcut cannot be empty",
+        "快捷键不能为空",
+    ),
+    (
+        "shortcut_empty_key",
+        "Shortcut {0} contains an empty key; check for an extra \"+\"",
+        "快捷键 {0} 中存在空的按键，请检查多余的“+”",
+    ),
+    (
+        "shortcut_missing_key",
+        "Shortcut {0} has no main key; add a key after the modifiers",
+        "快捷键 {0} 缺少主键，修饰键之后需要再加一个按键",
+    ),
+    (
+        "shortcut_misplaced_modifier",
+        "{1} in shortcut {0} is not a modifier; modifiers come first and only one main key is allowed",
+        "快捷键 {0} 中的 {1} 不是修饰键，修饰键需写在前面且只能有一个主键",
+    ),
+    (
+        "shortcut_missing_modifier",
+        "Shortcut {0} needs a modifier such as Ctrl, Alt, Shift or Command",
+        "快捷键 {0} 缺少修饰键，请搭配 Ctrl、Alt、Shift 或 Command 使用",
+    ),
+    (
+        "shortcut_unknown_key",
+        "Key {1} in shortcut {0} is not recognized",
+        "快捷键 {0} 中的按键 {1} 无法识别",
+    ),
+    (
+        "shortcut_conflict",
+        "Shortcut {0} conflicts with an existing shortcut",
+        "快捷键 {0} 与已有快捷键冲突",
+    ),
+    // 导出、归档与数据库迁移
+    (
+        "empty_export_path",
+        "The export path cannot be empty",
+        "导出路径不能为空",
+    ),
+    (
+        "target_exists",
+        "The target file already exists: {0}",
+        "目标文件已存在：{0}",
+    ),
+    (
+        "invalid_archive_cutoff",
+        "The archive cutoff time is invalid",
+        "归档截止时间无效",
+    ),
+    (
+        "empty_archive_path",
+        "The archive path cannot be empty",
+        "归档路径不能为空",
+    ),
+    (
+        "archive_is_live_db",
+        "The archive cannot be the current database file",
+        "归档库不能与当前数据库是同一个文件",
+    ),
+    (
+        "portable_db_move",
+        "In portable mode the database stays next to the executable and cannot be moved",
+        "便携模式下数据库固定存放在可执行文件旁，无法迁移",
+    ),
+    (
+        "empty_db_path",
+        "The new database path cannot be empty",
+        "新的数据库路径不能为空",
+    ),
+    (
+        "db_path_not_absolute",
+        "The new database path must be absolute",
+        "新的数据库路径需要是绝对路径",
+    ),
+    (
+        "db_target_exists",
+        "A file with the same name already exists at the target; choose a new path",
+        "目标位置已存在同名文件，请选择新的文件路径",
+    ),
+    (
+        "db_copy_integrity_failed",
+        "The migrated database failed the integrity check: {0}",
+        "迁移后的数据库完整性检查未通过：{0}",
+    ),
+    (
+        "db_copy_mismatch",
+        "The migrated database does not match the original schema or row counts",
+        "迁移后的数据库与原数据库的表结构或行数不一致",
+    ),
+    // 配置导入
+    (
+        "invalid_settings_file",
+        "The settings file is invalid: {0}",
+        "配置文件格式无效：{0}",
+    ),
+    (
+        "invalid_imported_filter_shortcuts",
+        "The filter shortcuts in the settings file are invalid: {0}",
+        "配置中的筛选快捷键格式无效：{0}",
+    ),
+    (
+        "settings_version_too_new",
+        "Settings file version {0} is newer than the supported version {1}; update the app before importing",
+        "配置文件版本 {0} 高于当前支持的版本 {1}，请升级应用后再导入",
+    ),
+    // 系统剪贴板
+    (
+        "clipboard_clear_failed",
+        "Failed to clear the clipboard: {0}",
+        "清空剪贴板失败：{0}",
+    ),
+    (
+        "clipboard_init_timed_out",
+        "Timed out initializing the clipboard",
+        "剪贴板初始化超时",
+    ),
+    (
+        "clipboard_read_timed_out",
+        "Timed out reading the clipboard",
+        "读取剪贴板超时",
+    ),
+    (
+        "wl_paste_output_unavailable",
+        "Cannot read the output of wl-paste",
+        "无法读取 wl-paste 输出",
+    ),
+    (
+        "wl_paste_read_failed",
+        "Failed to read the output of wl-paste",
+        "读取 wl-paste 输出失败",
+    ),
+];
+
+// 切换提示使用的语言
+pub(crate) fn set_active_locale(locale: Locale) {
+    let mut active = ACTIVE_LOCALE
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    *active = locale;
+}
+
+// 读取提示使用的语言
+pub(crate) fn active_locale() -> Locale {
+    *ACTIVE_LOCALE
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+// 按语言查找键对应的提示，未收录的键使用 other 的提示
+fn localized_text(key: &str, locale: Locale) -> &'static str {
+    let find = |key: &str| ERROR_MESSAGES.iter().find(|(entry, _, _)| *entry == key);
+    let (_, en, zh) = find(key)
+        .or_else(|| find("other"))
+        .expect("对照表缺少 other 提示");
+    match locale {
+        Locale::En => en,
+        Locale::ZhCn => zh,
+    }
+}
+
+// 错误的具体原因：调用处只给出原因的种类与参数（路径、取值范围、底层库的说明等），
+// 提示文案按语言从对照表生成，不在调用处拼写某一种语言的句子
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Reason {
+    // 底层库给出的原始说明，无法翻译，附在错误码的通用提示之后
+    Detail(String),
+    InvalidCursor,
+    StatsRangeReversed,
+    InvalidStatsRange,
+    InvalidQueryRange,
+    InvalidDate(String),
+    DateOutOfRange(String),
+    UnconvertibleDate(String),
+    NegativeOffset,
+    InvalidCreatedAt,
+    InvalidJson(String),
+    InvalidXml(String),
+    XmlMultipleRoots,
+    XmlTextOutsideRoot,
+    XmlUnclosedElement,
+    XmlMissingRoot,
+    UnknownScript(String),
+    InvalidPasteTransform,
+    NoItemsSelected,
+    EmptyTemplate,
+    SelfSwap,
+    SwapUnpinned,
+    NotAnImage,
+    ImageNeedsImageCopy,
+    ImageTextNotEditable,
+    ImageTooLarge,
+    EmptyTag,
+    TagNotFound(String),
+    InvalidTagColor,
+    EmptyRegex,
+    InvalidRegex(String),
+    CustomFilterNeedsRegex,
+    // (规则原文, 编译错误)
+    InvalidSensitivePathRule(String, String),
+    InvalidExclusionRule(String, String),
+    // 设置项的取值范围 (最小值, 最大值)
+    ClipboardClearSecondsRange(u64, u64),
+    AutoUnpinDaysRange(u64, u64),
+    ItemTtlMinutesRange(u64, u64),
+    CaptureBurstThresholdRange(u64, u64),
+    BroadcastBatchThresholdRange(u64, u64),
+    TrayRecentCountRange(u64, u64),
+    // 只在没有全局快捷键的平台（非桌面端）使用
+    #[cfg_attr(desktop, allow(dead_code))]
+    ShortcutsUnsupported,
+    EmptyShortcut,
+    ShortcutEmptyKey(String),
+    ShortcutMissingKey(String),
+    // (快捷键, 写错位置的按键)
+    ShortcutMisplacedModifier(String, String),
+    ShortcutMissingModifier(String),
+    // (快捷键, 无法识别的按键)
+    ShortcutUnknownKey(String, String),
+    ShortcutConflict(String),
+    EmptyExportPath,
+    TargetExists(String),
+    InvalidArchiveCutoff,
+    EmptyArchivePath,
+    ArchiveIsLiveDb,
+    PortableDbMove,
+    EmptyDbPath,
+    DbPathNotAbsolute,
+    DbTargetExists,
+    DbCopyIntegrityFailed(String),
+    DbCopyMismatch,
+    InvalidSettingsFile(String),
+    InvalidImportedFilterShortcuts(String),
+    // (配置文件版本, 当前支持的版本)
+    SettingsVersionTooNew(u32, u32),
+    ClipboardClearFailed(String),
+    ClipboardInitTimedOut,
+    ClipboardReadTimedOut,
+    WlPasteOutputUnavailable,
+    WlPasteReadFailed,
+}
+
+impl Reason {
+    // 原因在对照表中的键与填入提示的参数；Detail 没有对应的键，返回 None
+    fn key_and_args(&self) -> Option<(&'static str, Vec<String>)> {
+        let range = |min: &u64, max: &u64| vec![min.to_string(), max.to_string()];
+        Some(match self {
+            Self::Detail(_) => return None,
+            Self::InvalidCursor => ("invalid_cursor", Vec::new()),
+            Self::StatsRangeReversed => ("stats_range_reversed", Vec::new()),
+            Self::InvalidStatsRange => ("invalid_stats_range", Vec::new()),
+            Self::InvalidQueryRange => ("invalid_query_range", Vec::new()),
+            Self::InvalidDate(date) => ("invalid_date", vec![date.clone()]),
+            Self::DateOutOfRange(date) => ("date_out_of_range", vec![date.clone()]),
+            Self::UnconvertibleDate(date) => ("unconvertible_date", vec![date.clone()]),
+            Self::NegativeOffset => ("negative_offset", Vec::new()),
+            Self::InvalidCreatedAt => ("invalid_created_at", Vec::new()),
+            Self::InvalidJson(detail) => ("invalid_json", vec![detail.clone()]),
+            Self::InvalidXml(detail) => ("invalid_xml", vec![detail.clone()]),
+            Self::XmlMultipleRoots => ("xml_multiple_roots", Vec::new()),
+            Self::XmlTextOutsideRoot => ("xml_text_outside_root", Vec::new()),
+            Self::XmlUnclosedElement => ("xml_unclosed_element", Vec::new()),
+            Self::XmlMissingRoot => ("xml_missing_root", Vec::new()),
+            Self::UnknownScript(script) => ("unknown_script", vec![script.clone()]),
+            Self::InvalidPasteTransform => ("invalid_paste_transform", Vec::new()),
+            Self::NoItemsSelected => ("no_items_selected", Vec::new()),
+            Self::EmptyTemplate => ("empty_template", Vec::new()),
+            Self::SelfSwap => ("self_swap", Vec::new()),
+            Self::SwapUnpinned => ("swap_unpinned", Vec::new()),
+            Self::NotAnImage => ("not_an_image", Vec::new()),
+            Self::ImageNeedsImageCopy => ("image_needs_image_copy", Vec::new()),
+            Self::ImageTextNotEditable => ("image_text_not_editable", Vec::new()),
+            Self::ImageTooLarge => ("image_too_large", Vec::new()),
+            Self::EmptyTag => ("empty_tag", Vec::new()),
+            Self::TagNotFound(tag) => ("tag_not_found", vec![tag.clone()]),
+            Self::InvalidTagColor => ("invalid_tag_color", Vec::new()),
+            Self::EmptyRegex => ("empty_regex", Vec::new()),
+            Self::InvalidRegex(detail) => ("invalid_regex", vec![detail.clone()]),
+            Self::CustomFilterNeedsRegex => ("custom_filter_needs_regex", Vec::new()),
+            Self::InvalidSensitivePathRule(pattern, detail) => (
+                "invalid_sensitive_path_rule",
+                vec![pattern.clone(), detail.clone()],
+            ),
+            Self::InvalidExclusionRule(pattern, detail) => (
+                "invalid_exclusion_rule",
+                vec![pattern.clone(), detail.clone()],
+            ),
+            Self::ClipboardClearSecondsRange(min, max) => {
+                ("clipboard_clear_seconds_range", range(min, max))
+            }
+            Self::AutoUnpinDaysRange(min, max) => ("auto_unpin_days_range", range(min, max)),
+            Self::ItemTtlMinutesRange(min, max) => ("item_ttl_minutes_range", range(min, max)),
+            Self::CaptureBurstThresholdRange(min, max) => {
+                ("capture_burst_threshold_range", range(min, max))
+            }
+            Self::BroadcastBatchThresholdRange(min, max) => {
+                ("broadcast_batch_threshold_range", range(min, max))
+            }
+            Self::TrayRecentCountRange(min, max) => ("tray_recent_count_range", range(min, max)),
+            Self::ShortcutsUnsupported => ("shortcuts_unsupported", Vec::new()),
+            Self::EmptyShortcut => ("empty_shortcut", Vec::new()),
+            Self::ShortcutEmptyKey(shortcut) => ("shortcut_empty_key", vec![shortcut.clone()]),
+            Self::ShortcutMissingKey(shortcut) => ("shortcut_missing_key", vec![shortcut.clone()]),
+            Self::ShortcutMisplacedModifier(shortcut, key) => (
+                "shortcut_misplaced_modifier",
+                vec![shortcut.clone(), key.clone()],
+            ),
+            Self::ShortcutMissingModifier(shortcut) => {
+                ("shortcut_missing_modifier", vec![shortcut.clone()])
+            }
+            Self::ShortcutUnknownKey(shortcut, key) => {
+                ("shortcut_unknown_key", vec![shortcut.clone(), key.clone()])
+            }
+            Self::ShortcutConflict(shortcut) => ("shortcut_conflict", vec![shortcut.clone()]),
+            Self::EmptyExportPath => ("empty_export_path", Vec::new()),
+            Self::TargetExists(path) => ("target_exists", vec![path.clone()]),
+            Self::InvalidArchiveCutoff => ("invalid_archive_cutoff", Vec::new()),
+            Self::EmptyArchivePath => ("empty_archive_path", Vec::new()),
+            Self::ArchiveIsLiveDb => ("archive_is_live_db", Vec::new()),
+            Self::PortableDbMove => ("portable_db_move", Vec::new()),
+            Self::EmptyDbPath => ("empty_db_path", Vec::new()),
+            Self::DbPathNotAbsolute => ("db_path_not_absolute", Vec::new()),
+            Self::DbTargetExists => ("db_target_exists", Vec::new()),
+            Self::DbCopyIntegrityFailed(detail) => {
+                ("db_copy_integrity_failed", vec![detail.clone()])
+            }
+            Self::DbCopyMismatch => ("db_copy_mismatch", Vec::new()),
+            Self::InvalidSettingsFile(detail) => ("invalid_settings_file", vec![detail.clone()]),
+            Self::InvalidImportedFilterShortcuts(detail) => {
+                ("invalid_imported_filter_shortcuts", vec![detail.clone()])
+            }
+            Self::SettingsVersionTooNew(found, supported) => (
+                "settings_version_too_new",
+                vec![found.to_string(), supported.to_string()],
+            ),
+            Self::ClipboardClearFailed(detail) => ("clipboard_clear_failed", vec![detail.clone()]),
+            Self::ClipboardInitTimedOut => ("clipboard_init_timed_out", Vec::new()),
+            Self::ClipboardReadTimedOut => ("clipboard_read_timed_out", Vec::new()),
+            Self::WlPasteOutputUnavailable => ("wl_paste_output_unavailable", Vec::new()),
+            Self::WlPasteReadFailed => ("wl_paste_read_failed", Vec::new()),
+        })
+    }
+
+    // 按语言生成原因的完整提示；Detail 返回 None，由调用方附在错误码的提示之后
+    fn message_in(&self, locale: Locale) -> Option<String> {
+        let (key, args) = self.key_and_args()?;
+        let mut text = localized_text(key, locale).to_string();
+        for (index, arg) in args.iter().enumerate() {
+            text = text.replace(&format!("{{{index}}}"), arg);
+        }
+        Some(text)
+    }
+}
+
+#[derive(Debug)]
+pub(crate) enum AppError {
+    // 请求的条目不存在（可能已被删除或清理）
+    NotFound,
+    // 要写入的内容为空（规整后没有可保存的文本）
+    EmptyContent,
+    // 参数不合法，附带具体原因
+    InvalidInput(Reason),
+    // 当前平台或条目类型不支持该操作，附带具体原因
+    Unsupported(Reason),
+    // 数据库读写失败
+    Sqlite(rusqlite::Error),
+    // 文件读写失败
+    Io(std::io::Error),
+    // 其他错误，附带具体原因或底层库的原始说明
+    Other(Reason),
+}
+
+impl AppError {
+    // 稳定的错误码，前端按错误码判断错误种类；新增变体时只能追加新的错误码，不能修改已有的取值
+    pub(crate) fn code(&self) -> &'static str {
+        match self {
+            Self::NotFound => "not_found",
+            Self::EmptyContent => "empty_content",
+            Self::InvalidInput(_) => "invalid_input",
+            Self::Unsupported(_) => "unsupported",
+            Self::Sqlite(_) => "sqlite",
+            Self::Io(_) => "io",
+            Self::Other(_) => "other",
+        }
+    }
+
+    // 展示给用户的提示，使用当前语言
+    pub(crate) fn message(&self) -> String {
+        self.message_in(active_locale())
+    }
+
+    // 按指定语言生成提示：带具体原因时原因的提示本身就是完整的句子，直接使用；
+    // 其余错误以错误码的提示开头，再附上底层库的原始说明
+    pub(crate) fn message_in(&self, locale: Locale) -> String {
+        let text = localized_text(self.code(), locale);
+        let detail = match self {
+            Self::NotFound | Self::EmptyContent => None,
+            Self::InvalidInput(reason) | Self::Unsupported(reason) | Self::Other(reason) => {
+                match reason {
+                    Reason::Detail(detail) => Some(detail.clone()),
+                    _ => return reason.message_in(locale).unwrap_or_default(),
+                }
+            }
+            Self::Sqlite(err) => Some(err.to_string()),
+            Self::Io(err) => Some(err.to_string()),
+        };
+        match (detail, locale) {
+            (None, _) => text.to_string(),
+            (Some(detail), Locale::En) => format!("{text}: {detail}"),
+            (Some(detail), Locale::ZhCn) => format!("{text}：{detail}"),
+        }
+    }
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message())
+    }
+}
+
+impl std::error::Error for AppError {}
+
+impl Serialize for AppError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut error = serializer.serialize_struct("AppError", 2)?;
+        error.serialize_field("code", self.code())?;
+        error.serialize_field("message", &self.message())?;
+        error.end()
+    }
+}
+
+impl From<rusqlite::Error> for AppError {
+    fn from(err: rusqlite::Error) -> Self {
+        Self::Sqlite(err)
+    }
+}
+
+impl From<std::io::Error> for AppError {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl From<serde_json::Error> for AppError {
+    fn from(err: serde_json::Error) -> Self {
+        Self::Other(Reason::Detail(err.to_string()))
+    }
+}
+
+impl From<tauri::Error> for AppError {
+    fn from(err: tauri::Error) -> Self {
+        Self::Other(Reason::Detail(err.to_string()))
+    }
+}
+
+impl From<tauri_plugin_clipboard_manager::Error> for AppError {
+    fn from(err: tauri_plugin_clipboard_manager::Error) -> Self {
+        Self::Other(Reason::Detail(err.to_string()))
+    }
+}
+
+impl From<String> for AppError {
+    fn from(message: String) -> Self {
+        Self::Other(Reason::Detail(message))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{
+        compile_user_regex, swap_item_positions_internal, upsert_clipboard_item_internal,
+    };
+    use crate::export::write_items_json;
+    use crate::models::{build_clipboard_payload, ClipboardItem};
+    use crate::test_support::test_state;
+
+    // 序列化为前端收到的 { code, message }
+    fn serialized(err: &AppError) -> serde_json::Value {
+        serde_json::to_value(err).expect("序列化错误失败")
+    }
+
+    // 辅助函数直接返回带错误码的错误，经命令层序列化后前端能区分“条目不存在”与“参数无效”，不会都变成 other
+    #[test]
+    fn helper_errors_keep_their_codes_when_serialized() {
+        let state = test_state();
+        let item = upsert_clipboard_item_internal(&state, build_clipboard_payload("a".into()), 30)
+            .expect("写入条目失败")
+            .item;
+
+        let missing = swap_item_positions_internal(&state, "missing", &item.id).unwrap_err();
+        assert_eq!(serialized(&missing)["code"], "not_found");
+        // 新写入的条目没有固定，不能调整顺序
+        let unpinned = swap_item_positions_internal(&state, &item.id, "other").unwrap_err();
+        assert_eq!(serialized(&unpinned)["code"], "invalid_input");
+
+        let regex = compile_user_regex("(").unwrap_err();
+        let value = serialized(&regex);
+        assert_eq!(value["code"], "invalid_input");
+        assert_eq!(value["message"], regex.message());
+
+        // 条目读取失败时，导出写入器原样传出错误，不再改写成字符串
+        let failed = write_items_json::<_, ClipboardItem>(
+            &mut Vec::new(),
+            [Err(AppError::Sqlite(rusqlite::Error::QueryReturnedNoRows))],
+        )
+        .unwrap_err();
+        assert_eq!(serialized(&failed)["code"], "sqlite");
+    }
+
+    // 每一种原因（含参数的取第一个样例值）
+    fn all_reasons() -> Vec<Reason> {
+        let text = || "x".to_string();
+        vec![
+            Reason::InvalidCursor,
+            Reason::StatsRangeReversed,
+            Reason::InvalidStatsRange,
+            Reason::InvalidQueryRange,
+            Reason::InvalidDate(text()),
+            Reason::DateOutOfRange(text()),
+            Reason::UnconvertibleDate(text()),
+            Reason::NegativeOffset,
+            Reason::InvalidCreatedAt,
+            Reason::InvalidJson(text()),
+            Reason::InvalidXml(text()),
+            Reason::XmlMultipleRoots,
+            Reason::XmlTextOutsideRoot,
+            Reason::XmlUnclosedElement,
+            Reason::XmlMissingRoot,
+            Reason::UnknownScript(text()),
+            Reason::InvalidPasteTransform,
+            Reason::NoItemsSelected,
+            Reason::EmptyTemplate,
+            Reason::SelfSwap,
+            Reason::SwapUnpinned,
+            Reason::NotAnImage,
+            Reason::ImageNeedsImageCopy,
+            Reason::ImageTextNotEditable,
+            Reason::ImageTooLarge,
+            Reason::EmptyTag,
+            Reason::TagNotFound(text()),
+            Reason::InvalidTagColor,
+            Reason::EmptyRegex,
+            Reason::InvalidRegex(text()),
+            Reason::CustomFilterNeedsRegex,
+            Reason::InvalidSensitivePathRule(text(), text()),
+            Reason::InvalidExclusionRule(text(), text()),
+            Reason::ClipboardClearSecondsRange(1, 2),
+            Reason::AutoUnpinDaysRange(1, 2),
+            Reason::ItemTtlMinutesRange(1, 2),
+            Reason::CaptureBurstThresholdRange(1, 2),
+            Reason::BroadcastBatchThresholdRange(1, 2),
+            Reason::TrayRecentCountRange(1, 2),
+            Reason::ShortcutsUnsupported,
+            Reason::EmptyShortcut,
+            Reason::ShortcutEmptyKey(text()),
+            Reason::ShortcutMissingKey(text()),
+            Reason::ShortcutMisplacedModifier(text(), text()),
+            Reason::ShortcutMissingModifier(text()),
+            Reason::ShortcutUnknownKey(text(), text()),
+            Reason::ShortcutConflict(text()),
+            Reason::EmptyExportPath,
+            Reason::TargetExists(text()),
+            Reason::InvalidArchiveCutoff,
+            Reason::EmptyArchivePath,
+            Reason::ArchiveIsLiveDb,
+            Reason::PortableDbMove,
+            Reason::EmptyDbPath,
+            Reason::DbPathNotAbsolute,
+            Reason::DbTargetExists,
+            Reason::DbCopyIntegrityFailed(text()),
+            Reason::DbCopyMismatch,
+            Reason::InvalidSettingsFile(text()),
+            Reason::InvalidImportedFilterShortcuts(text()),
+            Reason::SettingsVersionTooNew(2, 1),
+            Reason::ClipboardClearFailed(text()),
+            Reason::ClipboardInitTimedOut,
+            Reason::ClipboardReadTimedOut,
+            Reason::WlPasteOutputUnavailable,
+            Reason::WlPasteReadFailed,
+        ]
+    }
+
+    fn has_cjk(text: &str) -> bool {
+        text.chars()
+            .any(|ch| ('\u{4e00}'..='\u{9fff}').contains(&ch))
+    }
+
+    // 每种原因在对照表中都有中英文提示，参数全部填入；英文提示不含中文，中文提示不是通用的兜底文案
+    #[test]
+    fn every_reason_is_localized_in_both_locales() {
+        let reasons = all_reasons();
+        for reason in &reasons {
+            let (key, _) = reason.key_and_args().expect("原因缺少对照表键");
+            assert!(
+                ERROR_MESSAGES.iter().any(|(entry, _, _)| *entry == key),
+                "对照表缺少 {key}"
+            );
+            let en = reason.message_in(Locale::En).unwrap();
+            let zh = reason.message_in(Locale::ZhCn).unwrap();
+            assert!(!has_cjk(&en), "{key} 的英文提示含有中文：{en}");
+            assert!(has_cjk(&zh), "{key} 的中文提示缺失：{zh}");
+            assert!(!en.contains('{') && !zh.contains('{'), "{key} 的参数未填入");
+        }
+        // 对照表中错误码（以 other 结尾）之后的原因都有对应的变体，没有遗留的条目
+        let keys: Vec<&str> = reasons
+            .iter()
+            .filter_map(|reason| reason.key_and_args().map(|(key, _)| key))
+            .collect();
+        let reason_rows = ERROR_MESSAGES
+            .iter()
+            .skip_while(|(entry, _, _)| *entry != "other")
+            .skip(1);
+        for (entry, en, _) in reason_rows {
+            assert!(keys.contains(entry), "{entry} 没有对应的原因");
+            assert!(!has_cjk(en));
+        }
+    }
+
+    // 英文界面下调用处的原因同样使用英文，中文界面使用中文；只有底层库的原始说明原样附在通用提示之后
+    #[test]
+    fn message_follows_locale() {
+        let err = AppError::InvalidInput(Reason::EmptyExportPath);
+        assert_eq!(
+            err.message_in(Locale::En),
+            "The export path cannot be empty"
+        );
+        assert_eq!(err.message_in(Locale::ZhCn), "导出路径不能为空");
+        let err = AppError::InvalidInput(Reason::ClipboardClearSecondsRange(5, 600));
+        assert_eq!(
+            err.message_in(Locale::En),
+            "The auto-clear delay must be between 5 and 600 seconds"
+        );
+        assert_eq!(
+            err.message_in(Locale::ZhCn),
+            "自动清空延迟需在 5 到 600 秒之间"
+        );
+        let err = AppError::from("disk full".to_string());
+        assert_eq!(err.code(), "other");
+        assert_eq!(err.message_in(Locale::En), "Operation failed: disk full");
+        assert_eq!(err.message_in(Locale::ZhCn), "操作失败：disk full");
+    }
+}
@@ -0,0 +1,350 @@
+// export.rs：把剪贴板条目拼装成各种可读的导出文本。
+// 说明：这里只负责格式化字符串，不访问数据库，条目（含标签）由命令层查询后传入。
+
+use crate::error::{AppError, Reason};
+use crate::format::{build_item_preview, item_title};
+use crate::models::{
+    ClipboardItem, ItemTemplate, MarkdownExportOptions, MarkdownItemStyle, ITEM_URI_PREFIX,
+};
+use chrono::{DateTime, Local};
+use std::borrow::Borrow;
+use std::io::Write;
+
+// Markdown 小标题取条目首行的最大字符数，过长时截断并追加省略号
+const MARKDOWN_TITLE_MAX_CHARS: usize = 40;
+
+// Atom 条目标题取单行预览的最大字符数，与列表预览保持一致的截断规则
+const FEED_TITLE_MAX_CHARS: usize = 80;
+
+// 生成 Markdown 文档：每个条目一节，按选项附带小标题、时间与标签，正文用代码块或引用块包裹
+pub(crate) fn build_history_markdown(
+    items: &[ClipboardItem],
+    options: &MarkdownExportOptions,
+    exported_at: &str,
+) -> String {
+    let mut output = String::from("# 剪贴板历史\n\n");
+    output.push_str(&format!(
+        "> 导出于 {}，共 {} 条\n",
+        format_local_time(exported_at),
+        items.len()
+    ));
+    for (index, item) in items.iter().enumerate() {
+        output.push('\n');
+        if options.include_titles {
+            output.push_str(&format!(
+                "## {}. {}\n\n",
+                index + 1,
+                build_title(&item.text)
+            ));
+        }
+        let mut meta = Vec::new();
+        if options.include_timestamps {
+            meta.push(format!("复制于 {}", format_local_time(&item.updated_at)));
+        }
+        if options.include_tags && !item.tags.is_empty() {
+            let joined = item
+                .tags
+                .iter()
+                .map(|tag| format!("`{}`", tag))
+                .collect::<Vec<_>>()
+                .join(" ");
+            meta.push(format!("标签 {}", joined));
+        }
+        if !meta.is_empty() {
+            output.push_str(&format!("_{}_\n\n", meta.join(" · ")));
+        }
+        match options.style {
+            MarkdownItemStyle::CodeBlock => push_code_block(&mut output, &item.text),
+            MarkdownItemStyle::Blockquote => push_blockquote(&mut output, &item.text),
+        }
+    }
+    output
+}
+
+// 按模板拼接条目：依次替换 body 中的占位符，替换结果不会再被当作占位符解析，
+// 因此条目文本中出现的 {text} 等字样会原样保留
+pub(crate) fn build_items_from_template(
+    items: &[ClipboardItem],
+    template: &ItemTemplate,
+) -> String {
+    let separator = template.separator.as_deref().unwrap_or("\n");
+    let rendered: Vec<String> = items
+        .iter()
+        .enumerate()
+        .map(|(index, item)| {
+            let mut output = String::new();
+            let mut rest = template.body.as_str();
+            while let Some(start) = rest.find('{') {
+                output.push_str(&rest[..start]);
+                let tail = &rest[start..];
+                let (value, consumed) = if tail.starts_with("{text}") {
+                    (item.text.clone(), "{text}".len())
+                } else if tail.starts_with("{index}") {
+                    ((index + 1).to_string(), "{index}".len())
+                } else if tail.starts_with("{title}") {
+                    (build_title(&item.text), "{title}".len())
+                } else {
+                    ("{".to_string(), 1)
+                };
+                output.push_str(&value);
+                rest = &tail[consumed..];
+            }
+            output.push_str(rest);
+            output
+        })
+        .collect();
+    format!(
+        "{}{}{}",
+        template.header,
+        rendered.join(separator),
+        template.footer
+    )
+}
+
+// 生成 JSON 数组：每个条目附带其标签，字段命名与前端使用的结构保持一致
+pub(crate) fn build_items_json(items: &[ClipboardItem]) -> Result<String, AppError> {
+    let mut output = Vec::new();
+    write_items_json(&mut output, items.iter().map(Ok))?;
+    String::from_utf8(output).map_err(|err| AppError::Other(Reason::Detail(err.to_string())))
+}
+
+// 逐条把条目写成格式化的 JSON 数组，输出与对整个数组调用 serde_json::to_string_pretty 一致，
+// 字段名沿用 ClipboardItem 的 camelCase 序列化，便于再导入；导出大量历史时无需先把全部条目读入内存。
+// 返回写入的条目数量
+pub(crate) fn write_items_json<W: Write, I: Borrow<ClipboardItem>>(
+    writer: &mut W,
+    items: impl IntoIterator<Item = Result<I, AppError>>,
+) -> Result<usize, AppError> {
+    let mut written = 0;
+    for item in items {
+        let item = item?;
+        let json = serde_json::to_string_pretty(item.borrow())?;
+        // 数组元素比顶层多缩进两格，逐行补齐缩进
+        let indented = json.replace('\n', "\n  ");
+        let separator = if written == 0 { "[\n  " } else { ",\n  " };
+        write!(writer, "{separator}{indented}")?;
+        written += 1;
+    }
+    let closing = if written == 0 { "[]" } else { "\n]" };
+    writer.write_all(closing.as_bytes())?;
+    writer.flush()?;
+    Ok(written)
+}
+
+// 生成 CSV 表格，内容与导出 CSV 文件完全一致
+pub(crate) fn build_items_csv(items: &[ClipboardItem]) -> Result<String, AppError> {
+    let mut output = Vec::new();
+    write_items_csv(&mut output, items.iter().map(Ok))?;
+    String::from_utf8(output).map_err(|err| AppError::Other(Reason::Detail(err.to_string())))
+}
+
+// 逐条把条目写成 CSV 表格（RFC 4180）：首行为表头，列名与数据库字段一致，最后一列为标签，
+// 多个标签用分号连接；时间保留存储的原始格式便于再处理。返回写入的行数（不含表头）
+pub(crate) fn write_items_csv<W: Write, I: Borrow<ClipboardItem>>(
+    writer: &mut W,
+    items: impl IntoIterator<Item = Result<I, AppError>>,
+) -> Result<usize, AppError> {
+    writer.write_all(b"id,text,created_at,updated_at,pinned,count,tags\r\n")?;
+    let mut written = 0;
+    for item in items {
+        let item = item?;
+        let item = item.borrow();
+        let fields = [
+            escape_csv_field(&item.id),
+            escape_csv_field(&item.text),
+            escape_csv_field(&item.created_at),
+            escape_csv_field(&item.updated_at),
+            item.pinned.to_string(),
+            item.count.to_string(),
+            escape_csv_field(&item.tags.join(";")),
+        ];
+        write!(writer, "{}\r\n", fields.join(","))?;
+        written += 1;
+    }
+    writer.flush()?;
+    Ok(written)
+}
+
+// 生成 Atom 订阅文档（RFC 4287）：每个条目一个 entry，标题为单行预览、正文为完整文本、标签写成 category；
+// 条目 id 与链接使用 pure-paste://item/ 链接，阅读器点击后可直接唤起应用定位到该条目。
+// 订阅的更新时间取最新条目的更新时间，没有条目时使用 generated_at
+pub(crate) fn build_items_atom(items: &[ClipboardItem], generated_at: &str) -> String {
+    let feed_updated = items
+        .iter()
+        .map(|item| item.updated_at.as_str())
+        .max()
+        .unwrap_or(generated_at);
+    let mut output = String::new();
+    output.push_str("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
+    output.push_str("<feed xmlns=\"http://www.w3.org/2005/Atom\">\n");
+    output.push_str("  <title>剪贴板历史</title>\n");
+    output.push_str(&format!("  <id>{}</id>\n", ITEM_URI_PREFIX));
+    output.push_str(&format!(
+        "  <updated>{}</updated>\n",
+        xml_text(feed_updated)
+    ));
+    output.push_str("  <author><name>pure-paster-material</name></author>\n");
+    for item in items {
+        let uri = xml_text(&format!("{}{}", ITEM_URI_PREFIX, item.id));
+        let title = build_item_preview(
+            &item.text,
+            item.content_type.as_deref(),
+            FEED_TITLE_MAX_CHARS,
+        )
+        .preview;
+        output.push_str("  <entry>\n");
+        output.push_str(&format!("    <id>{}</id>\n", uri));
+        output.push_str(&format!("    <title>{}</title>\n", xml_text(&title)));
+        output.push_str(&format!("    <link href=\"{}\"/>\n", uri));
+        output.push_str(&format!(
+            "    <published>{}</published>\n",
+            xml_text(&item.created_at)
+        ));
+        output.push_str(&format!(
+            "    <updated>{}</updated>\n",
+            xml_text(&item.updated_at)
+        ));
+        for tag in &item.tags {
+            output.push_str(&format!("    <category term=\"{}\"/>\n", xml_text(tag)));
+        }
+        output.push_str(&format!(
+            "    <content type=\"text\">{}</content>\n",
+            xml_text(&item.text)
+        ));
+        output.push_str("  </entry>\n");
+    }
+    output.push_str("</feed>\n");
+    output
+}
+
+// XML 1.0 不允许出现除制表、换行、回车外的控制字符，剪贴板文本中偶尔会带上，直接去掉后再转义
+fn xml_text(value: &str) -> String {
+    let cleaned: String = value
+        .chars()
+        .filter(|ch| matches!(ch, '\t' | '\n' | '\r') || !ch.is_control())
+        .collect();
+    quick_xml::escape::escape(cleaned.as_str()).into_owned()
+}
+
+// 含逗号、引号或换行的字段需要整体加引号，内部引号写成两个
+fn escape_csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+// 用条目标题（首个非空行）作为小标题，超长时按字符截断，避免多字节文本被截坏
+fn build_title(text: &str) -> String {
+    let first_line = item_title(text);
+    if first_line.chars().count() > MARKDOWN_TITLE_MAX_CHARS {
+        let truncated: String = first_line.chars().take(MARKDOWN_TITLE_MAX_CHARS).collect();
+        format!("{}…", truncated)
+    } else {
+        first_line.to_string()
+    }
+}
+
+// 代码块的围栏长度需超过正文中最长的连续反引号，保证正文里的 ``` 不会提前结束代码块
+fn push_code_block(output: &mut String, text: &str) {
+    let mut longest = 0;
+    let mut current = 0;
+    for ch in text.chars() {
+        if ch == '`' {
+            current += 1;
+            longest = longest.max(current);
+        } else {
+            current = 0;
+        }
+    }
+    let fence = "`".repeat((longest + 1).max(3));
+    output.push_str(&format!("{}\n{}\n{}\n", fence, text, fence));
+}
+
+// 引用块逐行加前缀，空行保留单独的 ">" 以免引用被拆成多段
+fn push_blockquote(output: &mut String, text: &str) {
+    for line in text.lines() {
+        if line.is_empty() {
+            output.push_str(">\n");
+        } else {
+            output.push_str(&format!("> {}\n", line));
+        }
+    }
+}
+
+// 把存储的 RFC3339 时间转换为本地时间展示，解析失败时原样输出
+fn format_local_time(value: &str) -> String {
+    DateTime::parse_from_rfc3339(value)
+        .map(|time| {
+            time.with_timezone(&Local)
+                .format("%Y-%m-%d %H:%M:%S")
+                .to_string()
+        })
+        .unwrap_or_else(|_| value.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(id: &str, text: &str, tags: &[&str]) -> ClipboardItem {
+        ClipboardItem {
+            id: id.to_string(),
+            text: text.to_string(),
+            created_at: "2024-01-01T00:00:00Z".to_string(),
+            updated_at: "2024-01-02T00:00:00Z".to_string(),
+            pinned: false,
+            count: 1,
+            position: None,
+            pinned_at: None,
+            script: None,
+            content_type: None,
+            sanitized: false,
+            risky: false,
+            expires_at: None,
+            kind: Default::default(),
+            source_app: None,
+            note: None,
+            tags: tags.iter().map(|tag| tag.to_string()).collect(),
+        }
+    }
+
+    // 逐条写入的 JSON 与对整个数组格式化的结果一致，并能读回同样的条目
+    #[test]
+    fn items_json_matches_whole_array_serialization() {
+        for items in [
+            Vec::new(),
+            vec![item("a", "one", &[])],
+            vec![
+                item("a", "line\n  \"two\"", &["x", "y"]),
+                item("b", "中文", &[]),
+            ],
+        ] {
+            let json = build_items_json(&items).unwrap();
+            assert_eq!(json, serde_json::to_string_pretty(&items).unwrap());
+            let parsed: Vec<ClipboardItem> = serde_json::from_str(&json).unwrap();
+            assert_eq!(parsed.len(), items.len());
+        }
+    }
+
+    // 含逗号、引号、换行的字段整体加引号；内存中生成的表格与写入文件的表格一致
+    #[test]
+    fn items_csv_escapes_fields_and_matches_file_output() {
+        let items = [
+            item("a", "plain", &[]),
+            item("b", "say \"hi\", then\r\nleave", &["x", "y,z"]),
+        ];
+        let csv = build_items_csv(&items).unwrap();
+        assert_eq!(
+            csv,
+            "id,text,created_at,updated_at,pinned,count,tags\r\n\
+             a,plain,2024-01-01T00:00:00Z,2024-01-02T00:00:00Z,false,1,\r\n\
+             b,\"say \"\"hi\"\", then\r\nleave\",2024-01-01T00:00:00Z,2024-01-02T00:00:00Z,false,1,\"x;y,z\"\r\n"
+        );
+        let mut file = Vec::new();
+        let written = write_items_csv(&mut file, items.iter().map(Ok)).unwrap();
+        assert_eq!(written, 2);
+        assert_eq!(String::from_utf8(file).unwrap(), csv);
+    }
+}
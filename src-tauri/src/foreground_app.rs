@@ -0,0 +1,184 @@
+// foreground_app.rs：获取当前处于前台的应用标识，供后台 watcher 忽略来自指定应用（如密码管理器）的复制。
+// 说明：
+// 1. Windows 通过前台窗口所属进程的可执行文件名识别（如 1Password.exe）；
+// 2. macOS 通过 NSWorkspace 的 frontmostApplication 读取 bundle id（如 com.1password.1password）；
+// 3. Linux 在 X11 下通过 xprop 读取活动窗口的 PID，再从 /proc 读取进程名；
+//    Wayland 不允许普通应用获取其他应用的窗口信息，直接视为无法判断；
+// 无法判断前台应用时返回 None，由 watcher 照常记录，宁可多记录也不能让监听意外漏掉内容。
+
+// 判断前台应用是否在屏蔽列表中：忽略大小写，Windows 下写不写 .exe 后缀都能匹配
+pub(crate) fn is_blocked_app(app: &str, blocklist: &[String]) -> bool {
+    let app = strip_exe_suffix(app);
+    blocklist
+        .iter()
+        .any(|entry| strip_exe_suffix(entry).eq_ignore_ascii_case(app))
+}
+
+fn strip_exe_suffix(name: &str) -> &str {
+    let trimmed = name.trim();
+    match trimmed.len().checked_sub(4) {
+        Some(split) if trimmed.is_char_boundary(split) => {
+            let (stem, suffix) = trimmed.split_at(split);
+            if suffix.eq_ignore_ascii_case(".exe") {
+                stem
+            } else {
+                trimmed
+            }
+        }
+        _ => trimmed,
+    }
+}
+
+#[cfg(target_os = "windows")]
+pub(crate) fn frontmost_app_id() -> Option<String> {
+    use std::ffi::c_void;
+
+    // 只需要读取进程的可执行文件路径，申请最小的查询权限即可
+    const PROCESS_QUERY_LIMITED_INFORMATION: u32 = 0x1000;
+
+    #[link(name = "user32")]
+    extern "system" {
+        fn GetForegroundWindow() -> *mut c_void;
+        fn GetWindowThreadProcessId(window: *mut c_void, process_id: *mut u32) -> u32;
+    }
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn OpenProcess(desired_access: u32, inherit: i32, process_id: u32) -> *mut c_void;
+        fn QueryFullProcessImageNameW(
+            process: *mut c_void,
+            flags: u32,
+            name: *mut u16,
+            size: *mut u32,
+        ) -> i32;
+        fn CloseHandle(handle: *mut c_void) -> i32;
+    }
+
+    // SAFETY：窗口与进程句柄只在本函数内使用，进程句柄打开成功后在读取完路径后立即关闭；
+    // 路径缓冲区的长度通过 size 传入，返回的长度不会超过缓冲区
+    unsafe {
+        let window = GetForegroundWindow();
+        if window.is_null() {
+            return None;
+        }
+        let mut process_id = 0u32;
+        GetWindowThreadProcessId(window, &mut process_id);
+        if process_id == 0 {
+            return None;
+        }
+        let process = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, 0, process_id);
+        if process.is_null() {
+            return None;
+        }
+        let mut buffer = [0u16; 1024];
+        let mut size = buffer.len() as u32;
+        let succeeded = QueryFullProcessImageNameW(process, 0, buffer.as_mut_ptr(), &mut size);
+        CloseHandle(process);
+        if succeeded == 0 {
+            return None;
+        }
+        let path = String::from_utf16_lossy(&buffer[..size as usize]);
+        path.rsplit('\\')
+            .next()
+            .filter(|name| !name.is_empty())
+            .map(str::to_string)
+    }
+}
+
+#[cfg(target_os = "macos")]
+pub(crate) fn frontmost_app_id() -> Option<String> {
+    use std::ffi::{c_char, c_void, CStr};
+
+    #[link(name = "AppKit", kind = "framework")]
+    extern "C" {}
+
+    #[link(name = "objc")]
+    extern "C" {
+        fn objc_getClass(name: *const c_char) -> *mut c_void;
+        fn sel_registerName(name: *const c_char) -> *mut c_void;
+        fn objc_msgSend();
+        fn objc_autoreleasePoolPush() -> *mut c_void;
+        fn objc_autoreleasePoolPop(pool: *mut c_void);
+    }
+
+    // SAFETY：objc_msgSend 按各方法的实际签名转换后调用；frontmostApplication 等返回的是自动释放对象，
+    // watcher 线程没有默认的自动释放池，因此在调用期间手动创建，并在复制出字符串后再释放；
+    // 任一环节返回空对象时直接结束，不向空指针发送消息
+    unsafe {
+        let send_object: unsafe extern "C" fn(*mut c_void, *mut c_void) -> *mut c_void =
+            std::mem::transmute(objc_msgSend as unsafe extern "C" fn());
+        let send_string: unsafe extern "C" fn(*mut c_void, *mut c_void) -> *const c_char =
+            std::mem::transmute(objc_msgSend as unsafe extern "C" fn());
+        let pool = objc_autoreleasePoolPush();
+        let bundle_id = (|| {
+            let class = objc_getClass(c"NSWorkspace".as_ptr());
+            if class.is_null() {
+                return None;
+            }
+            let workspace = send_object(class, sel_registerName(c"sharedWorkspace".as_ptr()));
+            if workspace.is_null() {
+                return None;
+            }
+            let app = send_object(
+                workspace,
+                sel_registerName(c"frontmostApplication".as_ptr()),
+            );
+            if app.is_null() {
+                return None;
+            }
+            let bundle_id = send_object(app, sel_registerName(c"bundleIdentifier".as_ptr()));
+            if bundle_id.is_null() {
+                return None;
+            }
+            let utf8 = send_string(bundle_id, sel_registerName(c"UTF8String".as_ptr()));
+            if utf8.is_null() {
+                return None;
+            }
+            Some(CStr::from_ptr(utf8).to_string_lossy().into_owned())
+        })();
+        objc_autoreleasePoolPop(pool);
+        bundle_id
+    }
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn frontmost_app_id() -> Option<String> {
+    use std::process::{Command, Stdio};
+
+    // Wayland 下 xprop 只能看到 XWayland 窗口，读到的活动窗口并不可靠
+    if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+        return None;
+    }
+    // xprop 的输出形如 “_NET_ACTIVE_WINDOW(WINDOW): window id # 0x3a00007”，取最后一段
+    let xprop = |args: &[&str]| {
+        Command::new("xprop")
+            .args(args)
+            .stdin(Stdio::null())
+            .stderr(Stdio::null())
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .and_then(|output| {
+                String::from_utf8_lossy(&output.stdout)
+                    .split_whitespace()
+                    .last()
+                    .map(str::to_string)
+            })
+    };
+    let window = xprop(&["-root", "_NET_ACTIVE_WINDOW"])?;
+    if window == "0x0" {
+        return None;
+    }
+    let pid = xprop(&["-id", &window, "_NET_WM_PID"])?
+        .parse::<u32>()
+        .ok()?;
+    let name = std::fs::read_to_string(format!("/proc/{pid}/comm")).ok()?;
+    let name = name.trim();
+    (!name.is_empty()).then(|| name.to_string())
+}
+
+// 其他桌面平台暂未实现前台应用检测，始终视为无法判断
+#[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
+pub(crate) fn frontmost_app_id() -> Option<String> {
+    None
+}
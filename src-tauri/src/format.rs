@@ -0,0 +1,192 @@
+// format.rs：结构化文本（JSON / XML）的校验与美化输出。
+// 说明：捕获时的内容类型识别与 format_item 命令共用这里的解析逻辑，保证“被识别为 JSON/XML 的条目一定能被格式化”。
+
+use crate::detect::{is_url, looks_like_code};
+use crate::error::{AppError, Reason};
+use crate::models::{ContentType, ItemPreview, PreviewKind};
+use quick_xml::events::Event;
+use quick_xml::{Reader, Writer};
+
+// 美化输出时每一级缩进的空格数
+const INDENT_SIZE: usize = 2;
+
+// 判断文本是否为 JSON 对象或数组：纯数字、字符串等标量虽然也是合法 JSON，但没有格式化的意义，这里不计入
+pub(crate) fn is_json_document(text: &str) -> bool {
+    let trimmed = text.trim();
+    if !(trimmed.starts_with('{') || trimmed.starts_with('[')) {
+        return false;
+    }
+    // IgnoredAny 只做语法校验，不构建完整的值，捕获时的开销更小
+    serde_json::from_str::<serde::de::IgnoredAny>(trimmed).is_ok()
+}
+
+// 判断文本是否为格式良好的 XML 文档（恰好一个根元素，根元素之外不含正文）
+pub(crate) fn is_xml_document(text: &str) -> bool {
+    let trimmed = text.trim();
+    trimmed.starts_with('<') && walk_xml(trimmed, |_| Ok(())).is_ok()
+}
+
+// 美化 JSON：保持原有的键顺序，只调整缩进与换行
+pub(crate) fn pretty_json(text: &str) -> Result<String, AppError> {
+    let value: serde_json::Value = serde_json::from_str(text.trim())
+        .map_err(|err| AppError::InvalidInput(Reason::InvalidJson(err.to_string())))?;
+    Ok(serde_json::to_string_pretty(&value)?)
+}
+
+// 美化 XML：丢弃原有的空白缩进后按层级重新缩进
+pub(crate) fn pretty_xml(text: &str) -> Result<String, AppError> {
+    let mut writer = Writer::new_with_indent(Vec::new(), b' ', INDENT_SIZE);
+    walk_xml(text.trim(), |event| {
+        writer
+            .write_event(event)
+            .map_err(|err| Reason::InvalidXml(err.to_string()))
+    })
+    .map_err(AppError::InvalidInput)?;
+    String::from_utf8(writer.into_inner())
+        .map_err(|err| AppError::Other(Reason::Detail(err.to_string())))
+}
+
+// 生成条目的单行预览：所有连续空白（含换行）折叠为一个空格，超过 max_chars 时截断并以省略号结尾；
+// 链接去掉协议头，让域名出现在最前面，截断时也能看出来源。content_type 为捕获时识别并保存的结构化类型
+pub(crate) fn build_item_preview(
+    text: &str,
+    content_type: Option<&str>,
+    max_chars: usize,
+) -> ItemPreview {
+    let kind = match content_type {
+        Some(value) if value == ContentType::Json.as_str() => PreviewKind::Json,
+        Some(value) if value == ContentType::Xml.as_str() => PreviewKind::Xml,
+        _ if is_url(text) => PreviewKind::Url,
+        _ if looks_like_code(text) => PreviewKind::Code,
+        _ => PreviewKind::Text,
+    };
+    let trimmed = text.trim();
+    let single_line = if kind == PreviewKind::Url {
+        let lower = trimmed.to_ascii_lowercase();
+        let scheme_len = if lower.starts_with("https://") { 8 } else { 7 };
+        trimmed[scheme_len..].trim_end_matches('/').to_string()
+    } else {
+        trimmed.split_whitespace().collect::<Vec<_>>().join(" ")
+    };
+    let max_chars = max_chars.max(1);
+    let truncated = single_line.chars().count() > max_chars;
+    let preview = if truncated {
+        // 省略号占用一个字符，保证结果不超过请求的长度
+        let mut cut: String = single_line.chars().take(max_chars - 1).collect();
+        cut.push('…');
+        cut
+    } else {
+        single_line
+    };
+    ItemPreview {
+        preview,
+        content_type: kind,
+        truncated,
+    }
+}
+
+// 条目的标题：首个非空行（去掉首尾空白），全部为空白时为空字符串。
+// Markdown 导出的小标题与按标题的正则搜索都以此为准
+pub(crate) fn item_title(text: &str) -> &str {
+    text.lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .unwrap_or_default()
+}
+
+// 顺序读取 XML 事件并交给回调处理，同时校验文档结构：
+// quick-xml 本身只检查标签配对，未闭合的元素、多个根元素、根元素之外的正文需要在这里额外判断
+fn walk_xml<'a>(
+    text: &'a str,
+    mut on_event: impl FnMut(Event<'a>) -> Result<(), Reason>,
+) -> Result<(), Reason> {
+    let mut reader = Reader::from_str(text);
+    let mut depth = 0usize;
+    let mut has_root = false;
+    loop {
+        let event = reader
+            .read_event()
+            .map_err(|err| Reason::InvalidXml(err.to_string()))?;
+        match &event {
+            Event::Eof => break,
+            // 纯空白的文本节点只是原有的缩进换行，直接丢弃由写入端重新缩进；
+            // 不使用 trim_text，避免把“a &amp; b”这类混合内容两侧的空格一并去掉
+            Event::Text(content) if content.iter().all(u8::is_ascii_whitespace) => continue,
+            Event::Start(_) | Event::Empty(_) if depth == 0 && has_root => {
+                return Err(Reason::XmlMultipleRoots);
+            }
+            Event::Start(_) => {
+                depth += 1;
+                has_root = true;
+            }
+            Event::Empty(_) => has_root = true,
+            Event::End(_) => depth = depth.saturating_sub(1),
+            Event::Text(_) | Event::CData(_) | Event::GeneralRef(_) if depth == 0 => {
+                return Err(Reason::XmlTextOutsideRoot);
+            }
+            _ => {}
+        }
+        on_event(event)?;
+    }
+    if depth > 0 {
+        return Err(Reason::XmlUnclosedElement);
+    }
+    if !has_root {
+        return Err(Reason::XmlMissingRoot);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::detect::detect_content_type;
+
+    // JSON 美化保持原有的键顺序，只调整缩进与换行
+    #[test]
+    fn pretty_json_keeps_key_order() {
+        assert_eq!(
+            pretty_json(" {\"b\":1,\"a\":[true,null]} ").unwrap(),
+            "{\n  \"b\": 1,\n  \"a\": [\n    true,\n    null\n  ]\n}"
+        );
+        assert!(matches!(
+            pretty_json("{\"a\":"),
+            Err(AppError::InvalidInput(Reason::InvalidJson(_)))
+        ));
+    }
+
+    // XML 美化丢弃原有缩进后按层级重新缩进，属性、文本与声明保持原样
+    #[test]
+    fn pretty_xml_reindents_elements() {
+        assert_eq!(
+            pretty_xml("<?xml version=\"1.0\"?>\n<root>\n      <a x=\"1\">text</a><b/></root>")
+                .unwrap(),
+            "<?xml version=\"1.0\"?>\n<root>\n  <a x=\"1\">text</a>\n  <b/>\n</root>"
+        );
+        assert!(matches!(
+            pretty_xml("<a><b></a>"),
+            Err(AppError::InvalidInput(Reason::InvalidXml(_)))
+        ));
+        assert!(matches!(
+            pretty_xml("<a></a><b></b>"),
+            Err(AppError::InvalidInput(Reason::XmlMultipleRoots))
+        ));
+    }
+
+    // 被识别为 JSON / XML 的内容一定能被格式化，且格式化结果仍被识别为同一类型
+    #[test]
+    fn detected_documents_can_be_formatted() {
+        for text in [
+            "{\"a\":{\"b\":[1,2,{\"c\":\"d\"}]}}",
+            "[\"中文\", \"😀\"]",
+            "<root><item id=\"1\">中文</item><!-- note --><empty/></root>",
+        ] {
+            let formatted = match detect_content_type(text) {
+                Some(ContentType::Json) => pretty_json(text).unwrap(),
+                Some(ContentType::Xml) => pretty_xml(text).unwrap(),
+                None => panic!("未识别为结构化内容：{text}"),
+            };
+            assert_eq!(detect_content_type(&formatted), detect_content_type(text));
+        }
+    }
+}
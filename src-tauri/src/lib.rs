@@ -1,20 +1,62 @@
+#[cfg(desktop)]
+mod clipboard_change;
+mod clipboard_image;
 mod commands;
 mod db;
 mod desktop;
+mod detect;
+mod error;
+mod export;
+#[cfg(desktop)]
+mod foreground_app;
+mod format;
+#[cfg(all(desktop, target_os = "linux"))]
+mod linux_clipboard;
 mod models;
+mod normalize;
+#[cfg(desktop)]
+mod session_lock;
+#[cfg(test)]
+mod test_support;
 
 use crate::models::AppState;
-use rusqlite::Connection;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::collections::{HashMap, VecDeque};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Mutex;
 use tauri::{Manager, RunEvent, WindowEvent};
 
+// 便携模式标记文件名：与可执行文件放在同一目录时，数据库改为存放在可执行文件旁边
+const PORTABLE_MARKER_FILE: &str = "portable";
+
+// 确定数据目录：可执行文件旁存在便携标记时使用可执行文件所在目录（如从 U 盘运行），否则使用系统应用数据目录。
+// 返回值第二项表示是否处于便携模式
+fn resolve_data_dir(app: &tauri::App) -> Result<(PathBuf, bool), String> {
+    let exe_dir = std::env::current_exe()
+        .ok()
+        .and_then(|path| path.parent().map(|dir| dir.to_path_buf()));
+    if let Some(exe_dir) = exe_dir {
+        if exe_dir.join(PORTABLE_MARKER_FILE).is_file() {
+            return Ok((exe_dir, true));
+        }
+    }
+    let app_data_dir = app.path().app_data_dir().map_err(|err| err.to_string())?;
+    Ok((app_data_dir, false))
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     // 注册剪贴板插件，并在启动时初始化 SQLite，确保历史记录持久化
-    let builder = tauri::Builder::default()
+    let builder = tauri::Builder::default();
+    // 单实例插件需最先注册：再次启动应用（包括点击条目链接）时只唤起已运行的实例
+    #[cfg(desktop)]
+    let builder = builder.plugin(tauri_plugin_single_instance::init(|app, _argv, _cwd| {
+        desktop::show_main_window(app);
+    }));
+    let builder = builder
         .plugin(tauri_plugin_opener::init())
-        .plugin(tauri_plugin_clipboard_manager::init());
+        .plugin(tauri_plugin_clipboard_manager::init())
+        .plugin(tauri_plugin_deep_link::init());
     #[cfg(desktop)]
     let builder = builder.plugin(tauri_plugin_global_shortcut::Builder::new().build());
     let builder = builder
@@ -24,76 +66,226 @@ pub fn run() {
                 // 将应用切换为辅助应用模式，隐藏 Dock 与 Cmd+Tab，仅通过托盘入口访问
                 let _ = app.set_activation_policy(tauri::ActivationPolicy::Accessory);
             }
-            let app_data_dir = app.path().app_data_dir().map_err(|err| err.to_string())?;
+            let (app_data_dir, portable) = resolve_data_dir(app)?;
             std::fs::create_dir_all(&app_data_dir).map_err(|err| err.to_string())?;
-            let db_path = app_data_dir.join("clipboard.db");
-            let conn = Connection::open(db_path).map_err(|err| err.to_string())?;
+            let db_path = db::resolve_db_path(&app_data_dir);
+            // -wal / -shm 文件由 SQLite 创建在数据库文件旁，随数据库位于数据目录或自定义位置中
+            let mut conn = db::open_db(&db_path).map_err(|err| err.to_string())?;
             db::init_db(&conn).map_err(|err| err.to_string())?;
+            db::migrate(&mut conn).map_err(|err| err.to_string())?;
             // 启动前读取快捷键设置，稍后用于注册全局快捷键
-            let open_window_shortcut = db::get_app_setting(&conn, models::OPEN_WINDOW_SHORTCUT_KEY)
-                .map_err(|err| err.to_string())?;
+            let shortcuts = db::load_shortcut_bindings(&conn).map_err(|err| err.to_string())?;
+            let autotag_rules =
+                db::load_compiled_autotag_rules(&conn).map_err(|err| err.to_string())?;
+            // 可在运行时修改的设置统一读取，导入配置后也用同一份逻辑刷新
+            let runtime = db::load_runtime_settings(&conn).map_err(|err| err.to_string())?;
+            error::set_active_locale(runtime.locale);
+            // 恢复上次退出前的监听开关，从未切换过时默认开启
+            let monitoring_enabled =
+                db::load_monitoring_enabled(&conn).map_err(|err| err.to_string())?;
             app.manage(AppState {
                 db: Mutex::new(conn),
-                monitoring_enabled: AtomicBool::new(true),
+                monitoring_enabled: AtomicBool::new(monitoring_enabled),
+                pause_on_lock: AtomicBool::new(runtime.pause_on_lock),
+                reset_dedup_on_empty: AtomicBool::new(runtime.reset_dedup_on_empty),
+                ignore_own_window_copies: AtomicBool::new(runtime.ignore_own_window_copies),
+                shortcut_toggles_window: AtomicBool::new(runtime.shortcut_toggles_window),
+                sanitize_on_capture: AtomicBool::new(runtime.sanitize_on_capture),
+                capture_filter: Mutex::new(runtime.capture_filter),
+                sensitive_path_rules: Mutex::new(runtime.sensitive_path_rules),
+                exclusion_rules: Mutex::new(runtime.exclusion_rules),
+                source_blocklist: Mutex::new(runtime.source_blocklist),
+                block_risky_captures: AtomicBool::new(runtime.block_risky_captures),
                 last_clipboard_text: Mutex::new(None),
                 skip_next_text: Mutex::new(None),
                 allow_exit: AtomicBool::new(false),
+                session_started_at: models::now_iso_string(),
+                autotag_rules: Mutex::new(autotag_rules),
+                tray_left_click: Mutex::new(runtime.tray_left_click),
+                recent_skips: Mutex::new(VecDeque::new()),
+                capture_error_count: AtomicU64::new(0),
+                clipboard_clear_seconds: AtomicU64::new(runtime.clipboard_clear_seconds),
+                clipboard_clear_generation: AtomicU64::new(0),
+                clipboard_restore_generation: AtomicU64::new(0),
+                pending_clipboard_restore: Mutex::new(None),
+                auto_unpin_days: AtomicU64::new(runtime.auto_unpin_days),
+                item_ttl_minutes: AtomicU64::new(runtime.item_ttl_minutes),
+                capture_burst_threshold: AtomicU64::new(runtime.capture_burst_threshold),
+                capture_bursts: Mutex::new(HashMap::new()),
+                broadcast_batch_threshold: AtomicU64::new(runtime.broadcast_batch_threshold),
+                poll_interval_ms: AtomicU64::new(runtime.poll_interval_ms),
+                app_data_dir,
+                db_path: Mutex::new(db_path),
+                portable,
+                pending_focus_item: Mutex::new(None),
             });
             #[cfg(desktop)]
             {
-                desktop::setup_desktop(app, open_window_shortcut.as_deref())?;
+                desktop::setup_desktop(app, &shortcuts)?;
             }
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
             commands::load_clipboard_history,
+            commands::load_clipboard_history_page,
+            commands::load_session_items,
+            commands::load_by_script,
+            commands::load_multiline_items,
+            commands::load_history_by_tag,
+            commands::load_history_cursor,
+            commands::load_history_before,
+            commands::get_activity_for_day,
+            commands::count_captured_between,
+            commands::format_item,
+            commands::get_item_preview,
+            commands::read_item_chunk,
+            commands::load_around,
+            commands::find_by_current_clipboard,
+            commands::capture_now,
             commands::upsert_clipboard_item,
             commands::update_clipboard_item_text,
             commands::set_clipboard_item_pinned,
+            commands::set_item_created_at,
+            commands::swap_item_positions,
+            commands::reorder_items,
+            commands::get_history_sort,
+            commands::set_history_sort,
+            commands::get_pinned_sort,
+            commands::set_pinned_sort,
+            commands::get_merge_text_winner,
+            commands::set_merge_text_winner,
+            commands::set_locale,
             commands::delete_clipboard_item,
             commands::clear_clipboard_history,
+            commands::merge_whitespace_variants,
+            commands::search_clipboard_history,
+            commands::archive_items_before,
+            commands::search_archive,
+            commands::regex_search_field,
+            commands::export_history_markdown,
+            commands::export_query_results,
+            commands::render_items_template,
+            commands::export_recent_feed,
+            commands::export_support_bundle,
+            commands::export_history,
+            commands::export_history_csv,
+            commands::mark_all_seen,
+            commands::count_unseen,
             commands::set_clipboard_monitoring,
             commands::get_clipboard_monitoring,
             commands::mark_clipboard_skip,
+            commands::copy_item_to_clipboard,
+            commands::copy_image_item_to_clipboard,
+            commands::get_item_thumbnail,
+            commands::paste_transient,
+            commands::set_item_transform,
+            commands::set_item_note,
+            commands::clear_system_clipboard,
+            commands::get_paste_wrapper,
+            commands::set_paste_wrapper,
+            commands::get_recent_skips,
+            commands::get_capture_error_count,
+            commands::reset_capture_error_count,
+            commands::get_clipboard_auto_clear,
+            commands::set_clipboard_auto_clear,
+            commands::get_auto_unpin_days,
+            commands::set_auto_unpin_days,
+            commands::get_item_ttl_minutes,
+            commands::set_item_ttl_minutes,
+            commands::get_capture_burst_threshold,
+            commands::set_capture_burst_threshold,
+            commands::get_broadcast_batch_threshold,
+            commands::set_broadcast_batch_threshold,
+            commands::get_poll_interval,
+            commands::set_poll_interval,
             commands::get_autostart_status,
             commands::set_autostart_enabled,
+            commands::validate_shortcut,
             commands::get_open_window_shortcut,
             commands::set_open_window_shortcut,
+            commands::get_toggle_monitoring_shortcut,
+            commands::set_toggle_monitoring_shortcut,
+            commands::get_filter_shortcuts,
+            commands::set_filter_shortcuts,
+            commands::add_autotag_rule,
+            commands::list_autotag_rules,
+            commands::remove_autotag_rule,
+            commands::get_category_tags,
+            commands::set_category_tags,
+            commands::auto_categorize,
+            commands::list_tags,
+            commands::add_item_tag,
+            commands::remove_item_tag,
+            commands::set_tag_color,
+            commands::reorder_tags,
+            commands::tag_query_results,
+            commands::load_latest_per_tag,
+            commands::get_capture_filter,
+            commands::set_capture_filter,
+            commands::get_pause_on_lock,
+            commands::set_pause_on_lock,
+            commands::get_reset_dedup_on_empty,
+            commands::set_reset_dedup_on_empty,
+            commands::get_ignore_own_window_copies,
+            commands::set_ignore_own_window_copies,
+            commands::get_shortcut_toggles_window,
+            commands::set_shortcut_toggles_window,
+            commands::get_remember_window_placement,
+            commands::set_remember_window_placement,
+            commands::get_sanitize_on_capture,
+            commands::set_sanitize_on_capture,
+            commands::get_sensitive_path_patterns,
+            commands::set_sensitive_path_patterns,
+            commands::get_exclusion_patterns,
+            commands::set_exclusion_patterns,
+            commands::get_source_blocklist,
+            commands::set_source_blocklist,
+            commands::get_block_risky_captures,
+            commands::set_block_risky_captures,
+            commands::get_tray_left_click,
+            commands::set_tray_left_click,
+            commands::get_tray_menu,
+            commands::set_tray_menu,
+            commands::get_tray_recent_count,
+            commands::set_tray_recent_count,
+            commands::get_item_uri,
+            commands::take_pending_focus_item,
+            commands::get_storage_location,
+            commands::move_db,
+            commands::get_effective_config,
+            commands::get_version_info,
+            commands::export_settings,
+            commands::import_settings,
             commands::open_settings_window_command
         ]);
     #[cfg(desktop)]
     let builder = builder
         // 托盘菜单与主菜单共享同一事件回调，统一处理“打开/设置/退出”
         .on_menu_event(|app, event| {
-            if event.id() == "show" {
-                desktop::show_main_window(app);
-            }
-            if event.id() == "settings" {
-                // 打开或聚焦设置窗口
-                desktop::open_settings_window(app);
-            }
-            if event.id() == "quit" {
-                // 标记为允许退出，确保只通过托盘菜单触发真正退出
-                app.state::<AppState>()
-                    .allow_exit
-                    .store(true, Ordering::Relaxed);
-                app.exit(0);
+            let id = event.id().as_ref();
+            if let Some(action) = models::TrayMenuAction::from_id(id) {
+                desktop::handle_tray_menu_action(app, action);
+            } else if let Some(item_id) = id.strip_prefix(models::TRAY_RECENT_ID_PREFIX) {
+                let _ = desktop::copy_tray_recent_item(app, item_id);
             }
         })
-        // 仅左键点击托盘图标时显示主窗口，右键只负责弹出菜单避免误触打开
+        // 左键点击托盘图标按设置执行动作，右键只负责弹出菜单避免误触打开
         .on_tray_icon_event(|app, event| {
             if let tauri::tray::TrayIconEvent::Click {
                 button: tauri::tray::MouseButton::Left,
                 ..
             } = event
             {
-                desktop::show_main_window(app);
+                desktop::handle_tray_left_click(app);
             }
         })
         // 关闭窗口时隐藏到托盘，保持后台监听不中断
         .on_window_event(|window, event| {
             if let WindowEvent::CloseRequested { api, .. } = event {
                 api.prevent_close();
+                if window.label() == "main" {
+                    desktop::remember_window_placement(window.app_handle());
+                }
                 let _ = window.hide();
             }
         });
@@ -0,0 +1,331 @@
+// linux_clipboard.rs：Linux 专用的剪贴板读取路径，供后台 watcher 使用。
+// 说明：
+// 1. arboard 在 Wayland 下依赖 data-control 协议，GNOME 等合成器不支持时会回退到 XWayland，后台应用往往一直读不到内容；
+// 2. Wayland 读取需要等待来源应用把数据写完管道，来源应用卡死时 `get_text()` 会无限阻塞整个监听线程；
+// 因此这里把 arboard 读取放到独立线程并加超时，并在 Wayland 会话下连续失败时回退到 wl-paste 命令行工具
+// （超时后不重建读取线程：卡住的线程仍占着剪贴板连接，重建只会每次轮询泄漏一个线程；
+// 这里始终只保留一个读取线程，在它答复上一次请求之前跳过新的读取）
+// （不支持 data-control 的合成器上 wl-paste 需要短暂获取焦点，因此只作为兜底方案）。
+// 对外暴露与 arboard::Clipboard 同名的 `new`/`get_text`，让 watcher 的去重与跳过逻辑保持不变；
+// 图片同样经由读取线程获取，wl-paste 兜底路径只读取文本。
+
+use crate::error::{AppError, Reason};
+use crate::models::ClipboardImage;
+use arboard::{Clipboard, GetExtLinux, ImageData, LinuxClipboardKind};
+use std::io::Read;
+use std::process::{Command, Stdio};
+use std::sync::mpsc::{RecvTimeoutError, TryRecvError};
+use std::sync::{mpsc, OnceLock};
+use std::time::{Duration, Instant};
+
+// 单次读取的超时时间：超过该时间视为来源应用无响应，放弃本次读取
+const READ_TIMEOUT: Duration = Duration::from_millis(1500);
+// arboard 连续失败达到该次数后，在 Wayland 会话下切换到 wl-paste
+const ARBOARD_FAILURE_THRESHOLD: u32 = 5;
+// 轮询 wl-paste 子进程是否结束的间隔
+const WL_PASTE_POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+// 读取线程的请求：每次读取都携带一个一次性的回复发送端
+enum ReadRequest {
+    Text(mpsc::Sender<Result<String, arboard::Error>>),
+    Image(mpsc::Sender<Result<ImageData<'static>, arboard::Error>>),
+}
+
+// 已超时但读取线程尚未答复的请求：保留回复接收端，用于判断读取线程何时重新空闲
+enum PendingRead {
+    Text(mpsc::Receiver<Result<String, arboard::Error>>),
+    Image(mpsc::Receiver<Result<ImageData<'static>, arboard::Error>>),
+}
+
+// arboard 读取失败的类型
+enum ReadFailure {
+    // 本次请求超时，或读取线程仍卡在上一次请求上而跳过了本次读取
+    TimedOut,
+    // 读取线程已退出（通道断开），可以安全地重建
+    Disconnected,
+    Failed(String),
+}
+
+enum Backend {
+    // arboard 读取线程（X11 或 Wayland data-control），通过通道发送读取请求
+    Arboard(mpsc::Sender<ReadRequest>),
+    // 调用 wl-paste 命令读取，适用于不支持 data-control 的 Wayland 合成器
+    WlPaste,
+}
+
+pub(crate) struct LinuxClipboardReader {
+    backend: Backend,
+    // arboard 连续失败次数，成功读取后清零
+    failures: u32,
+    // 读取线程尚未答复的超时请求；存在时不再发送新请求
+    pending: Option<PendingRead>,
+}
+
+impl LinuxClipboardReader {
+    // 优先使用 arboard；Wayland 会话下 arboard 无法初始化时直接使用 wl-paste
+    pub(crate) fn new() -> Result<Self, AppError> {
+        match spawn_arboard_reader() {
+            Ok(sender) => Ok(Self {
+                backend: Backend::Arboard(sender),
+                failures: 0,
+                pending: None,
+            }),
+            Err(err) => {
+                if is_wayland_session() && is_wl_paste_available() {
+                    Ok(Self {
+                        backend: Backend::WlPaste,
+                        failures: 0,
+                        pending: None,
+                    })
+                } else {
+                    Err(err)
+                }
+            }
+        }
+    }
+
+    // 读取剪贴板（CLIPBOARD 选区）文本：剪贴板为空或不是文本时返回空字符串，交由 watcher 的空内容分支处理
+    pub(crate) fn get_text(&mut self) -> Result<String, AppError> {
+        let result = match &self.backend {
+            Backend::Arboard(sender) => read_with_arboard(sender, &mut self.pending),
+            Backend::WlPaste => return read_with_wl_paste(),
+        };
+        match result {
+            Ok(text) => {
+                self.failures = 0;
+                Ok(text)
+            }
+            Err(failure) => {
+                self.failures += 1;
+                let disconnected = matches!(failure, ReadFailure::Disconnected);
+                self.recover_after_failure(disconnected);
+                match failure {
+                    ReadFailure::TimedOut | ReadFailure::Disconnected => {
+                        Err(AppError::Other(Reason::ClipboardReadTimedOut))
+                    }
+                    ReadFailure::Failed(message) => Err(AppError::Other(Reason::Detail(message))),
+                }
+            }
+        }
+    }
+
+    // 读取剪贴板图片：没有图片、读取失败或使用 wl-paste 时返回 None，不计入文本读取的失败次数；
+    // 超时同样记为未答复请求，读取线程答复之前文本与图片读取都会被跳过
+    pub(crate) fn get_image(&mut self) -> Option<ClipboardImage> {
+        let Backend::Arboard(sender) = &self.backend else {
+            return None;
+        };
+        if !reader_idle(&mut self.pending) {
+            return None;
+        }
+        let (reply_tx, reply_rx) = mpsc::channel();
+        if sender.send(ReadRequest::Image(reply_tx)).is_err() {
+            self.respawn_reader();
+            return None;
+        }
+        match reply_rx.recv_timeout(READ_TIMEOUT) {
+            Ok(result) => result.ok().map(|image| ClipboardImage {
+                width: image.width,
+                height: image.height,
+                rgba: image.bytes.into_owned(),
+            }),
+            Err(RecvTimeoutError::Timeout) => {
+                self.pending = Some(PendingRead::Image(reply_rx));
+                None
+            }
+            Err(RecvTimeoutError::Disconnected) => {
+                self.respawn_reader();
+                None
+            }
+        }
+    }
+
+    // 读取失败后的恢复策略：Wayland 下连续失败（包括读取线程一直卡住）则切换到 wl-paste；
+    // 只有读取线程已经退出时才重建，超时不重建，避免卡住的旧线程越积越多
+    fn recover_after_failure(&mut self, disconnected: bool) {
+        if self.failures >= ARBOARD_FAILURE_THRESHOLD
+            && is_wayland_session()
+            && is_wl_paste_available()
+        {
+            // 丢弃请求端后，卡住的读取线程在当前读取返回后自行退出
+            self.backend = Backend::WlPaste;
+            self.pending = None;
+            self.failures = 0;
+            return;
+        }
+        if disconnected {
+            self.respawn_reader();
+        }
+    }
+
+    // 重建读取线程：只在旧线程已退出时调用，因此不会留下阻塞中的线程
+    fn respawn_reader(&mut self) {
+        if let Ok(sender) = spawn_arboard_reader() {
+            self.backend = Backend::Arboard(sender);
+            self.pending = None;
+        }
+    }
+}
+
+// 读取线程是否空闲：没有未答复请求，或未答复请求已经有了结果（结果已过时，直接丢弃）；
+// 线程退出导致通道断开时同样视为空闲，随后的发送失败会触发重建
+fn reader_idle(pending: &mut Option<PendingRead>) -> bool {
+    let settled = match pending {
+        None => return true,
+        Some(PendingRead::Text(reply)) => !matches!(reply.try_recv(), Err(TryRecvError::Empty)),
+        Some(PendingRead::Image(reply)) => !matches!(reply.try_recv(), Err(TryRecvError::Empty)),
+    };
+    if settled {
+        *pending = None;
+    }
+    settled
+}
+
+// 启动 arboard 读取线程：剪贴板实例只在该线程内创建和使用，初始化失败时立即返回错误
+fn spawn_arboard_reader() -> Result<mpsc::Sender<ReadRequest>, AppError> {
+    let (request_tx, request_rx) = mpsc::channel::<ReadRequest>();
+    let (init_tx, init_rx) = mpsc::channel::<Result<(), String>>();
+    std::thread::spawn(move || {
+        let mut clipboard = match Clipboard::new() {
+            Ok(instance) => {
+                let _ = init_tx.send(Ok(()));
+                instance
+            }
+            Err(err) => {
+                let _ = init_tx.send(Err(err.to_string()));
+                return;
+            }
+        };
+        // 请求端被丢弃（切换到 wl-paste 或读取器被释放）时通道关闭，线程随之退出
+        while let Ok(request) = request_rx.recv() {
+            let get = clipboard.get().clipboard(LinuxClipboardKind::Clipboard);
+            match request {
+                ReadRequest::Text(reply) => {
+                    let _ = reply.send(get.text());
+                }
+                ReadRequest::Image(reply) => {
+                    let _ = reply.send(get.image());
+                }
+            }
+        }
+    });
+    init_rx
+        .recv_timeout(READ_TIMEOUT)
+        .map_err(|_| AppError::Other(Reason::ClipboardInitTimedOut))?
+        .map_err(|err| AppError::Other(Reason::Detail(err)))?;
+    Ok(request_tx)
+}
+
+// 通过读取线程获取文本并等待结果，超时则视为失败，避免阻塞监听循环；
+// 读取线程仍卡在上一次请求上时直接跳过本次读取，超时的请求记入 pending 等待线程答复
+fn read_with_arboard(
+    sender: &mpsc::Sender<ReadRequest>,
+    pending: &mut Option<PendingRead>,
+) -> Result<String, ReadFailure> {
+    if !reader_idle(pending) {
+        return Err(ReadFailure::TimedOut);
+    }
+    let (reply_tx, reply_rx) = mpsc::channel();
+    // 发送失败说明读取线程已退出
+    sender
+        .send(ReadRequest::Text(reply_tx))
+        .map_err(|_| ReadFailure::Disconnected)?;
+    match reply_rx.recv_timeout(READ_TIMEOUT) {
+        Ok(Ok(text)) => Ok(text),
+        // 剪贴板为空或不包含文本不算失败
+        Ok(Err(arboard::Error::ContentNotAvailable)) => Ok(String::new()),
+        Ok(Err(err)) => Err(ReadFailure::Failed(err.to_string())),
+        Err(RecvTimeoutError::Timeout) => {
+            *pending = Some(PendingRead::Text(reply_rx));
+            Err(ReadFailure::TimedOut)
+        }
+        Err(RecvTimeoutError::Disconnected) => Err(ReadFailure::Disconnected),
+    }
+}
+
+// 调用 wl-paste 读取文本：子进程超时会被终止；"剪贴板为空"时 wl-paste 以非零状态退出，这里统一视为空内容
+fn read_with_wl_paste() -> Result<String, AppError> {
+    let mut child = Command::new("wl-paste")
+        .args(["--no-newline", "--type", "text"])
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()?;
+    // 在独立线程中读取输出，避免内容较大时子进程写满管道而无法退出
+    let mut stdout = child
+        .stdout
+        .take()
+        .ok_or(AppError::Other(Reason::WlPasteOutputUnavailable))?;
+    let output_reader = std::thread::spawn(move || {
+        let mut buffer = Vec::new();
+        stdout.read_to_end(&mut buffer).map(|_| buffer)
+    });
+    let deadline = Instant::now() + READ_TIMEOUT;
+    let status = loop {
+        match child.try_wait()? {
+            Some(status) => break status,
+            None if Instant::now() >= deadline => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(AppError::Other(Reason::ClipboardReadTimedOut));
+            }
+            None => std::thread::sleep(WL_PASTE_POLL_INTERVAL),
+        }
+    };
+    let buffer = output_reader
+        .join()
+        .map_err(|_| AppError::Other(Reason::WlPasteReadFailed))??;
+    if !status.success() {
+        return Ok(String::new());
+    }
+    String::from_utf8(buffer).map_err(|err| AppError::Other(Reason::Detail(err.to_string())))
+}
+
+// 当前会话是否运行在 Wayland 下
+fn is_wayland_session() -> bool {
+    std::env::var_os("WAYLAND_DISPLAY").is_some()
+}
+
+// 检查系统中是否安装了 wl-paste（wl-clipboard），未安装时不做回退；结果只探测一次并缓存
+fn is_wl_paste_available() -> bool {
+    static AVAILABLE: OnceLock<bool> = OnceLock::new();
+    *AVAILABLE.get_or_init(|| {
+        Command::new("wl-paste")
+            .arg("--version")
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .map(|status| status.success())
+            .unwrap_or(false)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 超时请求未答复时不发送新请求，也不重建线程；答复到达或线程退出后恢复空闲
+    #[test]
+    fn pending_read_blocks_until_reader_replies() {
+        let (request_tx, request_rx) = mpsc::channel::<ReadRequest>();
+        let (reply_tx, reply_rx) = mpsc::channel();
+        let mut pending = Some(PendingRead::Text(reply_rx));
+
+        assert!(matches!(
+            read_with_arboard(&request_tx, &mut pending),
+            Err(ReadFailure::TimedOut)
+        ));
+        assert!(request_rx.try_recv().is_err());
+        assert!(pending.is_some());
+
+        reply_tx.send(Ok("stale".to_string())).unwrap();
+        assert!(reader_idle(&mut pending));
+        assert!(pending.is_none());
+
+        let (reply_tx, reply_rx) = mpsc::channel::<Result<String, arboard::Error>>();
+        drop(reply_tx);
+        let mut pending = Some(PendingRead::Text(reply_rx));
+        assert!(reader_idle(&mut pending));
+    }
+}
@@ -1,15 +1,204 @@
-use chrono::Utc;
+use crate::detect::{is_url, looks_like_code};
+use crate::error::{set_active_locale, AppError, Reason};
+use chrono::{DateTime, Local, NaiveDate, NaiveTime, TimeZone, Utc};
+use regex::Regex;
 use rusqlite::Connection;
 use serde::{Deserialize, Serialize};
-use std::sync::{atomic::AtomicBool, Mutex};
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant};
 use uuid::Uuid;
 
 // 剪贴板历史保留天数：超过该天数且未固定的条目会被自动清理
 pub(crate) const HISTORY_RETENTION_DAYS: i64 = 7;
-// 后台轮询间隔，兼顾响应速度与 CPU 占用
+// 后台轮询间隔的默认值，兼顾响应速度与 CPU 占用
 pub(crate) const CLIPBOARD_POLL_INTERVAL_MS: u64 = 900;
+// 后台轮询间隔在数据库中对应的键名，未设置时使用默认值
+pub(crate) const POLL_INTERVAL_MS_KEY: &str = "poll_interval_ms";
+// 后台轮询间隔允许的范围（毫秒）
+pub(crate) const POLL_INTERVAL_MS_RANGE: std::ops::RangeInclusive<u64> = 200..=10000;
+// 应用自身写入的跳过标记有效期下限：需覆盖写入到下一次轮询读取之间的延迟（至少两个轮询间隔），
+// 轮询间隔调大时按 skip_next_text_ttl_ms 随之延长
+pub(crate) const SKIP_NEXT_TEXT_TTL_MS: u64 = 2000;
+// 手动修正创建时间时允许的最早时间（2000-01-01T00:00:00Z），用于拦截明显错误的时间戳
+pub(crate) const MIN_CREATED_AT_MS: i64 = 946_684_800_000;
+// 用户输入的正则（自动打标签规则、自定义捕获过滤）编译后的体积上限（字节），防止表达式过于复杂
+pub(crate) const USER_REGEX_SIZE_LIMIT: usize = 1 << 20;
+// 游标分页每页允许的最大条数
+pub(crate) const HISTORY_PAGE_LIMIT_MAX: i64 = 500;
+// 读取相邻记录时单侧允许的最大条数，避免一次返回过多数据
+pub(crate) const NEIGHBOR_RADIUS_MAX: i64 = 100;
+// 单个条目预览允许的最大字符数，超出的请求按上限截取
+pub(crate) const ITEM_PREVIEW_MAX_CHARS: usize = 1000;
+// 临时粘贴后恢复原剪贴板的延迟范围（毫秒），超出的请求按边界截取
+pub(crate) const TRANSIENT_RESTORE_MS_RANGE: std::ops::RangeInclusive<u64> = 100..=60_000;
+// 分段读取条目文本时单段允许的最大字符数，超出的请求按上限截取
+pub(crate) const ITEM_CHUNK_MAX_CHARS: i64 = 256 * 1024;
+// 图片条目缩略图的最长边（像素），列表中按该尺寸展示
+pub(crate) const IMAGE_THUMBNAIL_MAX_SIDE: usize = 160;
+// 最近跳过记录的保留条数，只在内存中保存，供排查“复制了却没出现”的问题
+pub(crate) const RECENT_SKIPS_LIMIT: usize = 50;
+// 跳过记录中文本预览的最大字符数，避免在内存与界面中保留完整内容
+pub(crate) const SKIP_PREVIEW_MAX_CHARS: usize = 80;
+// 条目链接的前缀，完整形式为 pure-paste://item/<id>，点击后唤起应用并定位到该条目
+pub(crate) const ITEM_URI_PREFIX: &str = "pure-paste://item/";
 // 快捷键配置在数据库中对应的键名，统一集中管理
 pub(crate) const OPEN_WINDOW_SHORTCUT_KEY: &str = "open_window_shortcut";
+// 带筛选预设的快捷键列表在数据库中对应的键名，值为 JSON 数组
+pub(crate) const FILTER_SHORTCUTS_KEY: &str = "filter_shortcuts";
+// 切换剪贴板监听开关的全局快捷键在数据库中对应的键名，未设置时不注册
+pub(crate) const TOGGLE_MONITORING_SHORTCUT_KEY: &str = "toggle_monitoring_shortcut";
+// 用户最近一次查看历史的时间在数据库中对应的键名，之后新增/更新的条目视为未读
+pub(crate) const LAST_SEEN_AT_KEY: &str = "last_seen_at";
+// 整个历史列表排序方式在数据库中对应的键名
+pub(crate) const HISTORY_SORT_KEY: &str = "history_sort";
+// 固定条目排序方式在数据库中对应的键名
+pub(crate) const PINNED_SORT_KEY: &str = "pinned_sort";
+// 编辑后与已有条目重复时保留哪一条的设置在数据库中对应的键名
+pub(crate) const MERGE_TEXT_WINNER_KEY: &str = "merge_text_winner";
+// 后端提示文案使用的语言在数据库中对应的键名，未设置时使用中文
+pub(crate) const LOCALE_KEY: &str = "locale";
+// 应用写入剪贴板后自动清空的延迟秒数在数据库中对应的键名，未设置表示不自动清空
+pub(crate) const CLIPBOARD_CLEAR_SECONDS_KEY: &str = "clipboard_clear_seconds";
+// 自动清空延迟允许的范围（秒）
+pub(crate) const CLIPBOARD_CLEAR_SECONDS_RANGE: std::ops::RangeInclusive<u64> = 5..=600;
+// 条目默认存活分钟数在数据库中对应的键名：新捕获或取消固定的条目到期后自动删除，未设置表示不过期
+pub(crate) const ITEM_TTL_MINUTES_KEY: &str = "item_ttl_minutes";
+// 条目默认存活时间允许的范围（分钟），上限与历史保留天数一致，更长的存活时间没有意义
+pub(crate) const ITEM_TTL_MINUTES_RANGE: std::ops::RangeInclusive<u64> =
+    1..=(HISTORY_RETENTION_DAYS as u64) * 24 * 60;
+// watcher 清理到期条目的间隔，到期条目最多延迟这么久才被删除
+pub(crate) const ITEM_EXPIRY_SWEEP_INTERVAL: Duration = Duration::from_secs(30);
+// 固定条目自动取消固定的天数在数据库中对应的键名，未设置表示不自动取消
+pub(crate) const AUTO_UNPIN_DAYS_KEY: &str = "auto_unpin_days";
+// 自动取消固定天数允许的范围（天）
+pub(crate) const AUTO_UNPIN_DAYS_RANGE: std::ops::RangeInclusive<u64> = 1..=365;
+// 同一文本高频重复捕获时的计数上限在数据库中对应的键名，未设置表示不限制
+pub(crate) const CAPTURE_BURST_THRESHOLD_KEY: &str = "capture_burst_threshold";
+// 高频捕获计数上限允许的范围（次）
+pub(crate) const CAPTURE_BURST_THRESHOLD_RANGE: std::ops::RangeInclusive<u64> = 2..=100;
+// 高频捕获的统计窗口：窗口内同一文本的捕获次数超过上限后不再累加计数
+pub(crate) const CAPTURE_BURST_WINDOW: Duration = Duration::from_secs(60);
+// 合并 watcher 广播的频率阈值在数据库中对应的键名，未设置表示逐条广播
+pub(crate) const BROADCAST_BATCH_THRESHOLD_KEY: &str = "broadcast_batch_threshold";
+// 合并广播阈值允许的范围（统计窗口内的广播次数）
+pub(crate) const BROADCAST_BATCH_THRESHOLD_RANGE: std::ops::RangeInclusive<u64> = 2..=100;
+// 广播频率的统计窗口：窗口内广播次数超过阈值后，之后的条目先缓存再合并广播
+pub(crate) const BROADCAST_RATE_WINDOW: Duration = Duration::from_secs(10);
+// 合并广播的间隔：从缓存第一条开始计时，到期后把缓存的条目一次性广播
+pub(crate) const BROADCAST_BATCH_INTERVAL: Duration = Duration::from_secs(2);
+// 自动归类时内容类别到标签名的映射（JSON 对象）在数据库中对应的键名，未设置时使用默认映射
+pub(crate) const CATEGORY_TAGS_KEY: &str = "category_tags";
+// 托盘菜单显示的操作项（JSON 数组，按显示顺序保存）在数据库中对应的键名，未设置时使用默认菜单
+pub(crate) const TRAY_MENU_KEY: &str = "tray_menu";
+// 左键点击托盘图标时执行的动作在数据库中对应的键名
+pub(crate) const TRAY_LEFT_CLICK_KEY: &str = "tray_left_click";
+// 托盘“最近复制”子菜单显示的条目数在数据库中对应的键名，未设置时显示 TRAY_RECENT_COUNT_DEFAULT 条，0 表示不显示子菜单
+pub(crate) const TRAY_RECENT_COUNT_KEY: &str = "tray_recent_count";
+pub(crate) const TRAY_RECENT_COUNT_DEFAULT: u64 = 8;
+pub(crate) const TRAY_RECENT_COUNT_RANGE: std::ops::RangeInclusive<u64> = 0..=20;
+// 托盘子菜单中条目预览的最大字符数，过长的菜单项会把托盘菜单撑得很宽
+pub(crate) const TRAY_RECENT_PREVIEW_MAX_CHARS: usize = 40;
+// 托盘子菜单中条目菜单项 id 的前缀，后接条目 id，用于与固定操作项的 id 区分
+pub(crate) const TRAY_RECENT_ID_PREFIX: &str = "recent:";
+// 后台捕获过滤方式在数据库中对应的键名，未设置时记录全部内容
+pub(crate) const CAPTURE_FILTER_KEY: &str = "capture_filter";
+// 自定义捕获过滤使用的正则在数据库中对应的键名
+pub(crate) const CAPTURE_FILTER_PATTERN_KEY: &str = "capture_filter_pattern";
+// 归档库文件路径在数据库中对应的键名，未设置表示尚未归档过
+pub(crate) const ARCHIVE_PATH_KEY: &str = "archive_path";
+// 归档库搜索每次允许返回的最大条数
+pub(crate) const ARCHIVE_SEARCH_LIMIT_MAX: i64 = 500;
+// 写回剪贴板时统一添加的前缀 / 后缀在数据库中对应的键名，未设置表示不添加
+pub(crate) const PASTE_PREFIX_KEY: &str = "paste_prefix";
+pub(crate) const PASTE_SUFFIX_KEY: &str = "paste_suffix";
+// 锁屏期间是否暂停记录剪贴板在数据库中对应的键名，未设置时默认开启
+pub(crate) const PAUSE_ON_LOCK_KEY: &str = "pause_on_lock";
+// 剪贴板变为空时是否重置去重基准在数据库中对应的键名，未设置时默认开启
+pub(crate) const RESET_DEDUP_ON_EMPTY_KEY: &str = "reset_dedup_on_empty";
+// 是否忽略在应用自身窗口内复制的内容在数据库中对应的键名，未设置时默认开启
+pub(crate) const IGNORE_OWN_WINDOW_COPIES_KEY: &str = "ignore_own_window_copies";
+// 全局快捷键是否在显示 / 隐藏主窗口之间切换在数据库中对应的键名，未设置时只负责唤起窗口
+pub(crate) const SHORTCUT_TOGGLES_WINDOW_KEY: &str = "shortcut_toggles_window";
+// 捕获时是否清理排版字符在数据库中对应的键名，未设置时默认关闭
+pub(crate) const SANITIZE_ON_CAPTURE_KEY: &str = "sanitize_on_capture";
+// 敏感路径规则（正则列表）在数据库中对应的键名，值为 JSON 数组，未设置时使用内置规则
+pub(crate) const SENSITIVE_PATH_PATTERNS_KEY: &str = "sensitive_path_patterns";
+// 排除规则（正则列表）在数据库中对应的键名，值为 JSON 数组，匹配任一规则的内容不写入历史
+pub(crate) const EXCLUSION_PATTERNS_KEY: &str = "exclusion_patterns";
+// 来源应用屏蔽列表在数据库中对应的键名，值为 JSON 数组（macOS 为 bundle id，Windows / Linux 为进程名）
+pub(crate) const SOURCE_BLOCKLIST_KEY: &str = "source_blocklist";
+// 是否拦截（不写入历史）指向敏感文件的路径在数据库中对应的键名，未设置时只提醒不拦截
+pub(crate) const BLOCK_RISKY_CAPTURES_KEY: &str = "block_risky_captures";
+// 剪贴板监听开关在数据库中对应的键名，未设置时默认开启；它记录的是当前状态而非偏好，不随配置导入导出
+pub(crate) const MONITORING_ENABLED_KEY: &str = "monitoring_enabled";
+// 是否按显示器布局分别记住主窗口位置在数据库中对应的键名，未设置时默认开启
+pub(crate) const REMEMBER_WINDOW_PLACEMENT_KEY: &str = "remember_window_placement";
+// 各显示器布局下记住的主窗口位置在数据库中对应的键名，值为“布局标识 → 窗口位置”的 JSON
+pub(crate) const WINDOW_PLACEMENTS_KEY: &str = "window_placements";
+
+// 默认的数据库文件名，位于数据目录中；迁移时传入目录也使用该文件名
+pub(crate) const DB_FILE_NAME: &str = "clipboard.db";
+// 数据库被其他连接锁住时等待的最长时间，超过后才返回 SQLITE_BUSY
+pub(crate) const DB_BUSY_TIMEOUT: Duration = Duration::from_millis(5000);
+// 自定义数据库位置的指针文件名，保存在默认数据目录中，内容为数据库文件的完整路径。
+// 数据库位置需要在打开数据库之前确定，因此不能保存在 app_settings 中
+pub(crate) const DB_LOCATION_FILE: &str = "db_location";
+
+// 导出配置文件的格式版本，导入时拒绝更新版本的文件
+pub(crate) const SETTINGS_EXPORT_VERSION: u32 = 1;
+// 导入配置时接受的设置键：与具体机器或使用记录相关的键（如归档路径、已读时间、窗口位置）不会被导入，
+// 未知的键同样忽略，便于旧版本读取新版本导出的文件
+pub(crate) const IMPORTABLE_SETTING_KEYS: &[&str] = &[
+    OPEN_WINDOW_SHORTCUT_KEY,
+    TOGGLE_MONITORING_SHORTCUT_KEY,
+    FILTER_SHORTCUTS_KEY,
+    HISTORY_SORT_KEY,
+    PINNED_SORT_KEY,
+    MERGE_TEXT_WINNER_KEY,
+    LOCALE_KEY,
+    CLIPBOARD_CLEAR_SECONDS_KEY,
+    AUTO_UNPIN_DAYS_KEY,
+    ITEM_TTL_MINUTES_KEY,
+    CAPTURE_BURST_THRESHOLD_KEY,
+    BROADCAST_BATCH_THRESHOLD_KEY,
+    POLL_INTERVAL_MS_KEY,
+    TRAY_MENU_KEY,
+    TRAY_LEFT_CLICK_KEY,
+    TRAY_RECENT_COUNT_KEY,
+    CATEGORY_TAGS_KEY,
+    CAPTURE_FILTER_KEY,
+    CAPTURE_FILTER_PATTERN_KEY,
+    PASTE_PREFIX_KEY,
+    PASTE_SUFFIX_KEY,
+    PAUSE_ON_LOCK_KEY,
+    RESET_DEDUP_ON_EMPTY_KEY,
+    IGNORE_OWN_WINDOW_COPIES_KEY,
+    SHORTCUT_TOGGLES_WINDOW_KEY,
+    SANITIZE_ON_CAPTURE_KEY,
+    SENSITIVE_PATH_PATTERNS_KEY,
+    EXCLUSION_PATTERNS_KEY,
+    SOURCE_BLOCKLIST_KEY,
+    BLOCK_RISKY_CAPTURES_KEY,
+    REMEMBER_WINDOW_PLACEMENT_KEY,
+];
+
+// 内置的敏感路径规则：匹配指向私钥、凭据、环境变量文件的路径（不区分大小写，路径分隔符统一为 /）。
+// 这类内容本身不是密钥，但粘贴到聊天或工单中会暴露密钥所在位置，也常是误复制的前兆
+pub(crate) const DEFAULT_SENSITIVE_PATH_PATTERNS: &[&str] = &[
+    r"(^|/)\.ssh(/|$)",
+    r"(^|/)id_(rsa|dsa|ecdsa|ed25519)$",
+    r"(^|/)\.env(\.(local|development|production|staging|test)(\.local)?)?$",
+    r"(^|/)\.aws/(credentials|config)$",
+    r"(^|/)\.kube/config$",
+    r"(^|/)\.gnupg(/|$)",
+    r"(^|/)\.(netrc|pgpass|npmrc|pypirc|git-credentials)$",
+    r"\.(pem|key|p12|pfx|jks|keystore)$",
+];
+// 只检查不超过该字符数的单行文本，长文本不会是单个路径，避免每次捕获都对大段内容跑全部规则
+pub(crate) const SENSITIVE_PATH_MAX_CHARS: usize = 1024;
 
 // 剪贴板历史记录的数据结构，字段与前端状态保持一致
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -21,6 +210,70 @@ pub(crate) struct ClipboardItem {
     pub(crate) updated_at: String,
     pub(crate) pinned: bool,
     pub(crate) count: i64,
+    // 手动排序位置，数值越小越靠前；未手动排序时为 None。
+    // 固定条目始终生效，非固定条目只在手动排序模式下生效
+    pub(crate) position: Option<i64>,
+    // 最近一次被固定的时间（RFC3339），未固定时为 None
+    pub(crate) pinned_at: Option<String>,
+    // 捕获时识别的主要文字体系（如 cjk、latin），无法识别时为 None
+    pub(crate) script: Option<String>,
+    // 捕获时识别的结构化内容类型（json / xml），普通文本为 None
+    pub(crate) content_type: Option<String>,
+    // 捕获时是否清理过排版字符（弯引号、不换行空格、零宽字符），即保存的文本与复制的原文不同
+    pub(crate) sanitized: bool,
+    // 捕获时内容像是指向敏感文件（私钥、凭据、.env 等）的路径；编辑文本后清除
+    pub(crate) risky: bool,
+    // 到期后自动删除的时间（RFC3339），固定条目与未设置默认存活时间时为 None
+    #[serde(default)]
+    pub(crate) expires_at: Option<String>,
+    // 条目内容的种类：文本或图片
+    #[serde(default)]
+    pub(crate) kind: ItemKind,
+    // 最近一次捕获时处于前台的应用（Windows 为可执行文件名，macOS 为 bundle id，Linux 为进程名），
+    // 无法判断或手动写入的条目为 None
+    #[serde(default)]
+    pub(crate) source_app: Option<String>,
+    // 用户为条目填写的备注，未填写时为 None
+    #[serde(default)]
+    pub(crate) note: Option<String>,
+    // 条目的标签，按名称排序
+    #[serde(default)]
+    pub(crate) tags: Vec<String>,
+}
+
+// 条目内容的种类，即 kind 列的取值：图片条目的正文只是“图片 宽×高”的说明，图片本身以 PNG 保存
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum ItemKind {
+    #[default]
+    Text,
+    Image,
+}
+
+impl ItemKind {
+    // 写入 kind 列时使用的取值
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            Self::Text => "text",
+            Self::Image => "image",
+        }
+    }
+
+    // 解析 kind 列的取值，无法识别时按文本处理
+    pub(crate) fn from_column(value: &str) -> Self {
+        match value {
+            "image" => Self::Image,
+            _ => Self::Text,
+        }
+    }
+}
+
+// 从剪贴板读到的图片：未压缩的 RGBA 像素，每个像素 4 字节，逐行排列
+#[derive(Debug, Clone)]
+pub(crate) struct ClipboardImage {
+    pub(crate) width: usize,
+    pub(crate) height: usize,
+    pub(crate) rgba: Vec<u8>,
 }
 
 // 前端传入的新增/更新数据，用于执行去重写入与计数更新
@@ -31,6 +284,9 @@ pub(crate) struct ClipboardUpsertPayload {
     pub(crate) text: String,
     pub(crate) created_at: String,
     pub(crate) updated_at: String,
+    // 捕获时处于前台的应用，只由 watcher 填写，前端传入的数据不含该字段
+    #[serde(skip)]
+    pub(crate) source_app: Option<String>,
 }
 
 // 文本编辑可能触发合并，返回合并后的条目以及被移除的条目 id
@@ -41,6 +297,54 @@ pub(crate) struct ClipboardUpdateResult {
     pub(crate) merged_id: Option<String>,
 }
 
+// 一组仅空白不同的条目合并后的结果：保留的条目与被并入后删除的条目
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WhitespaceMergeGroup {
+    pub(crate) kept_id: String,
+    pub(crate) merged_ids: Vec<String>,
+}
+
+// 合并空白变体的报告：每组的合并情况，以及合并后的保留条目（供前端同步计数与固定状态）
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WhitespaceMergeReport {
+    pub(crate) groups: Vec<WhitespaceMergeGroup>,
+    pub(crate) items: Vec<ClipboardItem>,
+}
+
+// 某条记录及其在历史列表中前后相邻的记录，用于跳转定位后展示上下文
+#[derive(Debug, Serialize)]
+pub(crate) struct ClipboardItemNeighborhood {
+    pub(crate) before: Vec<ClipboardItem>,
+    pub(crate) item: ClipboardItem,
+    pub(crate) after: Vec<ClipboardItem>,
+}
+
+// 数据存放位置信息，供设置页展示当前数据库所在目录以及是否处于便携模式
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct StorageLocation {
+    pub(crate) db_path: String,
+    pub(crate) data_dir: String,
+    pub(crate) portable: bool,
+}
+
+// 版本信息：应用版本、数据库结构版本（PRAGMA user_version）与 SQLite 版本，排查“数据库打不开 / 导入失败”时对照
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct VersionInfo {
+    pub(crate) app_version: String,
+    pub(crate) db_schema_version: i64,
+    pub(crate) sqlite_version: String,
+}
+
+// 高频捕获时合并广播给前端的结构（clipboard-updated-batch），按捕获先后排列，同一条目只保留最新状态
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct ClipboardBatchPayload {
+    pub(crate) items: Vec<ClipboardItem>,
+}
+
 // 后台监听写入后广播给前端的结构，保持字段命名一致便于直接复用
 #[derive(Debug, Clone, Serialize)]
 pub(crate) struct ClipboardBroadcastPayload {
@@ -49,18 +353,875 @@ pub(crate) struct ClipboardBroadcastPayload {
     pub(crate) merged_id: Option<String>,
 }
 
+// upsert 的内部结果：除最终条目外，还带上同一事务中被过期清理掉的条目 id
+#[derive(Debug)]
+pub(crate) struct ClipboardUpsertOutcome {
+    pub(crate) item: ClipboardItem,
+    pub(crate) pruned_ids: Vec<String>,
+    // 本次命中已有条目但因高频重复捕获而没有累加次数
+    pub(crate) count_capped: bool,
+    // 同一事务中因固定时间过久而被自动取消固定的条目
+    pub(crate) unpinned: Vec<ClipboardItem>,
+}
+
+// 捕获到指向敏感文件的路径时广播给前端的结构：reason 为命中的规则，
+// blocked 为 true 时内容未写入历史，item_id 为 None
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RiskyCapturePayload {
+    pub(crate) item_id: Option<String>,
+    pub(crate) text_preview: String,
+    pub(crate) reason: String,
+    pub(crate) blocked: bool,
+}
+
+// 过期清理后广播给前端的结构，前端据此移除已不存在的条目
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct ClipboardPrunedPayload {
+    pub(crate) ids: Vec<String>,
+}
+
+// 快捷键触发时携带的筛选预设：字段均可选，前端只应用存在的条件
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct FilterPreset {
+    pub(crate) content_type: Option<String>,
+    pub(crate) tag: Option<String>,
+    pub(crate) pinned: Option<bool>,
+}
+
+// 当前实际生效的全部配置：已套用默认值，汇总数据库设置、运行时状态与编译期常量，供设置页与排查问题使用
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct EffectiveConfig {
+    pub(crate) poll_interval_ms: u64,
+    pub(crate) retention_days: i64,
+    pub(crate) skip_next_text_ttl_ms: u64,
+    pub(crate) history_page_limit_max: i64,
+    pub(crate) monitoring_enabled: bool,
+    pub(crate) pause_on_lock: bool,
+    pub(crate) reset_dedup_on_empty: bool,
+    pub(crate) ignore_own_window_copies: bool,
+    pub(crate) sanitize_on_capture: bool,
+    pub(crate) sensitive_path_patterns: Vec<String>,
+    pub(crate) exclusion_patterns: Vec<String>,
+    pub(crate) source_blocklist: Vec<String>,
+    pub(crate) block_risky_captures: bool,
+    pub(crate) shortcut_toggles_window: bool,
+    pub(crate) remember_window_placement: bool,
+    pub(crate) capture_filter: CaptureFilterSettings,
+    pub(crate) capture_burst_threshold: Option<u64>,
+    pub(crate) capture_burst_window_secs: u64,
+    pub(crate) broadcast_batch_threshold: Option<u64>,
+    pub(crate) broadcast_rate_window_secs: u64,
+    pub(crate) clipboard_auto_clear_seconds: Option<u64>,
+    pub(crate) auto_unpin_days: Option<u64>,
+    pub(crate) item_ttl_minutes: Option<u64>,
+    pub(crate) merge_text_winner: MergeTextWinner,
+    pub(crate) locale: Locale,
+    pub(crate) history_sort: HistorySortMode,
+    pub(crate) pinned_sort: PinnedSortMode,
+    pub(crate) tray_left_click: TrayLeftClickAction,
+    pub(crate) tray_recent_count: u64,
+    pub(crate) open_window_shortcut: Option<String>,
+    pub(crate) toggle_monitoring_shortcut: Option<String>,
+    pub(crate) filter_shortcuts: Vec<FilterShortcut>,
+    pub(crate) paste_wrapper: PasteWrapper,
+    pub(crate) category_tags: BTreeMap<ContentCategory, String>,
+    pub(crate) archive_path: Option<String>,
+    // 开机自启动状态由系统维护，读取失败时为 None，不影响其他配置的展示
+    pub(crate) autostart_enabled: Option<bool>,
+    pub(crate) storage: StorageLocation,
+}
+
+// 具名快捷键：一个快捷键绑定一个筛选预设，作为“打开并只看链接/固定条目”等专用入口
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct FilterShortcut {
+    pub(crate) shortcut: String,
+    pub(crate) filter: FilterPreset,
+}
+
+// 全部全局快捷键的配置：启动时注册与导入配置时整体替换使用
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct ShortcutBindings {
+    pub(crate) open_window: Option<String>,
+    pub(crate) toggle_monitoring: Option<String>,
+    pub(crate) filters: Vec<FilterShortcut>,
+}
+
+// 自动打标签规则：捕获内容匹配正则时自动为条目添加对应标签
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct AutotagRule {
+    pub(crate) id: i64,
+    pub(crate) pattern: String,
+    pub(crate) tag: String,
+}
+
+// 标签及其展示属性：颜色为 #RRGGBB 形式，未设置时由前端使用默认配色；item_count 为使用该标签的条目数
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TagInfo {
+    pub(crate) name: String,
+    pub(crate) color: Option<String>,
+    pub(crate) position: Option<i64>,
+    pub(crate) item_count: i64,
+}
+
+// 某个标签下最近更新的一条记录，用于按分类快速取用（如最新的地址、代码片段、链接）
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TagLatestItem {
+    pub(crate) tag: String,
+    pub(crate) item: ClipboardItem,
+}
+
+// 导出的配置：settings 为 app_settings 表的原始内容，effective 为导出时实际生效的配置，仅供查看
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SettingsExport {
+    pub(crate) version: u32,
+    pub(crate) exported_at: String,
+    pub(crate) settings: BTreeMap<String, String>,
+    pub(crate) effective: EffectiveConfig,
+}
+
+// 导入时只读取版本与原始设置，其他字段（如 effective）直接忽略
+#[derive(Debug, Deserialize)]
+pub(crate) struct SettingsImport {
+    pub(crate) version: u32,
+    pub(crate) settings: BTreeMap<String, String>,
+}
+
+// 导入配置的结果：applied 为实际写入的键，ignored 为未知或不可导入的键
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SettingsImportReport {
+    pub(crate) applied: Vec<String>,
+    pub(crate) ignored: Vec<String>,
+}
+
+// 历史记录游标：按 (updated_at, id) 定位上一页的最后一条，id 用于区分更新时间相同的条目
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct HistoryCursor {
+    pub(crate) updated_at: String,
+    pub(crate) id: String,
+}
+
+// 游标分页的一页结果：next_cursor 为 None 表示已经到底
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct HistoryPage {
+    pub(crate) items: Vec<ClipboardItem>,
+    pub(crate) next_cursor: Option<HistoryCursor>,
+}
+
+// 按偏移量分页的一页结果：total 为与本页同一时刻统计的总条数，前端据此判断是否还有更多
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct ClipboardHistoryPage {
+    pub(crate) items: Vec<ClipboardItem>,
+    pub(crate) total: i64,
+}
+
+// 主窗口的位置与大小（物理像素），按显示器布局分别保存，隐藏窗口时写入、再次显示时恢复
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WindowPlacement {
+    pub(crate) x: i32,
+    pub(crate) y: i32,
+    pub(crate) width: u32,
+    pub(crate) height: u32,
+}
+
+// 写回剪贴板时包裹在条目文本两侧的前缀与后缀（如结尾换行、代码反引号），只影响写入的内容，不修改存储
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PasteWrapper {
+    pub(crate) prefix: String,
+    pub(crate) suffix: String,
+}
+
+impl PasteWrapper {
+    // 按设置包裹文本，前后缀均为空时原样返回
+    pub(crate) fn apply(&self, text: &str) -> String {
+        format!("{}{}{}", self.prefix, text, self.suffix)
+    }
+}
+
+// 粘贴变换中的大小写转换方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum TextCase {
+    Upper,
+    Lower,
+}
+
+// 单个条目在每次写回剪贴板时自动执行的变换，以 JSON 保存在 clipboard_items.transform 中；
+// 与前缀 / 后缀一样只影响写入的内容，动态值在每次粘贴时重新计算
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PasteTransform {
+    // 是否展开 {date_now}、{time_now}、{datetime_now} 占位符为粘贴时的本地时间
+    #[serde(default)]
+    pub(crate) expand_tokens: bool,
+    // 展开占位符之后再统一转换大小写，未设置时保持原样
+    #[serde(default)]
+    pub(crate) case: Option<TextCase>,
+}
+
+impl PasteTransform {
+    // 没有任何变换时不需要保存，直接清空该条目的设置
+    pub(crate) fn is_noop(&self) -> bool {
+        !self.expand_tokens && self.case.is_none()
+    }
+
+    // 按粘贴时刻计算变换后的文本
+    pub(crate) fn apply(&self, text: &str, now: DateTime<Local>) -> String {
+        let mut output = if self.expand_tokens {
+            text.replace(
+                "{datetime_now}",
+                &now.format("%Y-%m-%d %H:%M:%S").to_string(),
+            )
+            .replace("{date_now}", &now.format("%Y-%m-%d").to_string())
+            .replace("{time_now}", &now.format("%H:%M:%S").to_string())
+        } else {
+            text.to_string()
+        };
+        match self.case {
+            Some(TextCase::Upper) => output = output.to_uppercase(),
+            Some(TextCase::Lower) => output = output.to_lowercase(),
+            None => {}
+        }
+        output
+    }
+}
+
+// 某个本地日历日内的复制记录：用于日历/热力图点击某天后查看明细
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DayActivity {
+    pub(crate) date: String,
+    pub(crate) count: usize,
+    pub(crate) items: Vec<ClipboardItem>,
+}
+
+// 已编译的自动打标签规则，缓存在 AppState 中，避免每次捕获都重新编译正则
+#[derive(Debug, Clone)]
+pub(crate) struct CompiledAutotagRule {
+    pub(crate) regex: Regex,
+    pub(crate) tag: String,
+}
+
+// 已编译的敏感路径规则，缓存在 AppState 中；pattern 保留规则原文，作为提醒中的命中原因
+#[derive(Debug, Clone)]
+pub(crate) struct SensitivePathRule {
+    pub(crate) pattern: String,
+    pub(crate) regex: Regex,
+}
+
+// 后台捕获过滤方式：记录全部、只记录链接、记录链接与代码，或只记录匹配自定义正则的内容
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum CaptureFilterMode {
+    #[default]
+    All,
+    UrlsOnly,
+    UrlsAndCode,
+    CustomRegex,
+}
+
+impl CaptureFilterMode {
+    // 解析数据库中保存的设置值，未设置或无法识别时回退到记录全部内容
+    pub(crate) fn from_setting(value: Option<&str>) -> Self {
+        match value {
+            Some("urls_only") => Self::UrlsOnly,
+            Some("urls_and_code") => Self::UrlsAndCode,
+            Some("custom_regex") => Self::CustomRegex,
+            _ => Self::All,
+        }
+    }
+
+    // 写入数据库时使用的设置值，与前端传入的取值保持一致
+    pub(crate) fn as_setting(self) -> &'static str {
+        match self {
+            Self::All => "all",
+            Self::UrlsOnly => "urls_only",
+            Self::UrlsAndCode => "urls_and_code",
+            Self::CustomRegex => "custom_regex",
+        }
+    }
+}
+
+// 捕获过滤设置：与设置页交互使用，pattern 仅在自定义正则模式下生效
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CaptureFilterSettings {
+    pub(crate) mode: CaptureFilterMode,
+    pub(crate) pattern: Option<String>,
+}
+
+// 已编译的捕获过滤规则，缓存在 AppState 中供 watcher 每次捕获前判断
+#[derive(Debug, Clone, Default)]
+pub(crate) struct CaptureFilter {
+    pub(crate) mode: CaptureFilterMode,
+    pub(crate) regex: Option<Regex>,
+}
+
+impl CaptureFilter {
+    // 判断文本是否应被记录；自定义正则缺失时（如正则已失效）按记录全部处理
+    pub(crate) fn allows(&self, text: &str) -> bool {
+        match self.mode {
+            CaptureFilterMode::All => true,
+            CaptureFilterMode::UrlsOnly => is_url(text),
+            CaptureFilterMode::UrlsAndCode => is_url(text) || looks_like_code(text),
+            CaptureFilterMode::CustomRegex => {
+                self.regex.as_ref().is_none_or(|regex| regex.is_match(text))
+            }
+        }
+    }
+}
+
+// Markdown 导出时单条内容的呈现方式：代码块适合代码片段，引用块适合普通文字
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum MarkdownItemStyle {
+    #[default]
+    CodeBlock,
+    Blockquote,
+}
+
+// Markdown 导出选项：未传的字段使用默认值，默认导出全部条目且不附带标题/标签/时间
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub(crate) struct MarkdownExportOptions {
+    // 仅导出指定 id 的条目（按历史列表顺序输出）；为 None 时导出全部
+    pub(crate) ids: Option<Vec<String>>,
+    pub(crate) style: MarkdownItemStyle,
+    // 以条目首行作为小标题
+    pub(crate) include_titles: bool,
+    pub(crate) include_tags: bool,
+    pub(crate) include_timestamps: bool,
+}
+
+// 拼接条目用的模板：body 对每个条目套用一次，支持 {text}（条目全文）、{index}（从 1 开始的序号）
+// 与 {title}（首个非空行）占位符，其余花括号原样保留；各条目之间插入 separator（默认换行），
+// header / footer 原样放在开头与结尾，例如拼出数组字面量或 switch 语句
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ItemTemplate {
+    #[serde(default)]
+    pub(crate) header: String,
+    pub(crate) body: String,
+    #[serde(default)]
+    pub(crate) footer: String,
+    pub(crate) separator: Option<String>,
+}
+
+// 组合查询条件：各条件之间为“且”的关系，未传的条件不参与筛选
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub(crate) struct QueryParams {
+    // 文本包含的关键字（不区分 ASCII 大小写）
+    pub(crate) text: Option<String>,
+    pub(crate) tag: Option<String>,
+    pub(crate) pinned: Option<bool>,
+    pub(crate) script: Option<String>,
+    pub(crate) content_type: Option<ContentType>,
+    // 最近复制时间的范围（毫秒时间戳），包含起点、不包含终点
+    pub(crate) since_ms: Option<i64>,
+    pub(crate) until_ms: Option<i64>,
+}
+
+// 查询结果的导出格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum ExportFormat {
+    Json,
+    Csv,
+    Markdown,
+}
+
+// 整个历史列表的排序方式：按最近复制时间（默认），或完全手动排序（非固定条目也按手动位置排列）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum HistorySortMode {
+    #[default]
+    Recent,
+    Manual,
+}
+
+impl HistorySortMode {
+    // 解析数据库中保存的设置值，未设置或无法识别时回退到按最近复制时间排序
+    pub(crate) fn from_setting(value: Option<&str>) -> Self {
+        match value {
+            Some("manual") => Self::Manual,
+            _ => Self::Recent,
+        }
+    }
+
+    // 写入数据库时使用的设置值，与前端传入的取值保持一致
+    pub(crate) fn as_setting(self) -> &'static str {
+        match self {
+            Self::Recent => "recent",
+            Self::Manual => "manual",
+        }
+    }
+}
+
+// 固定区的排序方式：按最近复制时间（默认），或按固定时间，后者在再次复制固定内容时不会打乱顺序
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum PinnedSortMode {
+    #[default]
+    UpdatedAt,
+    PinnedAt,
+}
+
+impl PinnedSortMode {
+    // 解析数据库中保存的设置值，未设置或无法识别时回退到按最近复制时间排序
+    pub(crate) fn from_setting(value: Option<&str>) -> Self {
+        match value {
+            Some("pinned_at") => Self::PinnedAt,
+            _ => Self::UpdatedAt,
+        }
+    }
+
+    // 写入数据库时使用的设置值，与前端传入的取值保持一致
+    pub(crate) fn as_setting(self) -> &'static str {
+        match self {
+            Self::UpdatedAt => "updated_at",
+            Self::PinnedAt => "pinned_at",
+        }
+    }
+}
+
+// 列表预览的类型提示：链接、JSON、XML、代码片段或普通文本
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum PreviewKind {
+    Text,
+    Url,
+    Code,
+    Json,
+    Xml,
+}
+
+// 分段读取的条目文本：offset 与长度均按字符（Unicode 码点）计算，分段不会截断多字节字符；
+// eof 表示这一段已经读到文本末尾
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ItemChunk {
+    pub(crate) chunk: String,
+    pub(crate) eof: bool,
+}
+
+// 条目的单行预览：由后端统一生成，保证各个窗口的列表展示一致；truncated 表示预览是否被截断
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ItemPreview {
+    pub(crate) preview: String,
+    pub(crate) content_type: PreviewKind,
+    pub(crate) truncated: bool,
+}
+
+// 正则搜索的目标字段：正文、标题（正文首个非空行）或备注；前端传入其他值时反序列化即失败
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum RegexSearchField {
+    Text,
+    Title,
+    Note,
+}
+
+// 自动归类使用的内容类别：由内容识别得出，再按设置映射为标签名
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum ContentCategory {
+    Url,
+    Color,
+    Json,
+    Xml,
+    Code,
+}
+
+impl ContentCategory {
+    // 未设置映射时使用的默认标签名
+    pub(crate) fn default_tags() -> BTreeMap<Self, String> {
+        [
+            (Self::Url, "link"),
+            (Self::Color, "color"),
+            (Self::Json, "data"),
+            (Self::Xml, "data"),
+            (Self::Code, "code"),
+        ]
+        .into_iter()
+        .map(|(category, tag)| (category, tag.to_string()))
+        .collect()
+    }
+}
+
+// 可格式化的结构化内容类型：既是 content_type 列的取值，也是 format_item 命令的格式参数
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum ContentType {
+    Json,
+    Xml,
+}
+
+impl ContentType {
+    // 写入 content_type 列时使用的取值，与前端传入的取值保持一致
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            Self::Json => "json",
+            Self::Xml => "xml",
+        }
+    }
+}
+
+// 编辑条目后文本与另一条已有记录重复时，合并后保留哪一条：
+// 两条记录的文本此时完全相同，区别在于保留下来的 id、位置（手动排序/固定顺序）与固定时间；
+// 计数、固定状态、创建时间与标签的合并规则与保留哪一条无关
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum MergeTextWinner {
+    // 保留已有条目，删除被编辑的条目（默认，与旧版本行为一致）
+    #[default]
+    Existing,
+    // 保留被编辑的条目，删除与之重复的已有条目
+    Edited,
+}
+
+impl MergeTextWinner {
+    // 解析数据库中保存的设置值，未设置或无法识别时回退到保留已有条目
+    pub(crate) fn from_setting(value: Option<&str>) -> Self {
+        match value {
+            Some("edited") => Self::Edited,
+            _ => Self::Existing,
+        }
+    }
+
+    // 写入数据库时使用的设置值，与前端传入的取值保持一致
+    pub(crate) fn as_setting(self) -> &'static str {
+        match self {
+            Self::Existing => "existing",
+            Self::Edited => "edited",
+        }
+    }
+}
+
+// 托盘“最近复制”子菜单中的一条记录，菜单文字由 text 生成单行预览
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct TrayRecentItem {
+    pub(crate) id: String,
+    pub(crate) text: String,
+    pub(crate) content_type: Option<String>,
+    pub(crate) pinned: bool,
+}
+
+// 后端提示文案（错误提示等）使用的语言，取值与前端的语言标记一致
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub(crate) enum Locale {
+    #[serde(rename = "en")]
+    En,
+    // 默认中文，与旧版本的提示一致
+    #[default]
+    #[serde(rename = "zh-CN")]
+    ZhCn,
+}
+
+impl Locale {
+    // 解析语言标记：只比较主语言部分，en-US、zh-Hans 等写法同样可以识别；
+    // 未设置时沿用中文，无法识别的语言回退到英文
+    pub(crate) fn from_setting(value: Option<&str>) -> Self {
+        let Some(value) = value else {
+            return Self::ZhCn;
+        };
+        let primary = value.trim().split(['-', '_']).next().unwrap_or_default();
+        if primary.eq_ignore_ascii_case("zh") {
+            Self::ZhCn
+        } else {
+            Self::En
+        }
+    }
+
+    // 写入数据库时使用的设置值
+    pub(crate) fn as_setting(self) -> &'static str {
+        match self {
+            Self::En => "en",
+            Self::ZhCn => "zh-CN",
+        }
+    }
+}
+
+// 托盘菜单中可以显示的操作项；取值同时作为菜单项 id，点击事件据此分发
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum TrayMenuAction {
+    Show,
+    Settings,
+    ToggleMonitoring,
+    PasteLast,
+    ClearUnpinned,
+    Quit,
+}
+
+impl TrayMenuAction {
+    // 未自定义时的菜单：打开、设置、退出
+    pub(crate) const DEFAULT_MENU: [Self; 3] = [Self::Show, Self::Settings, Self::Quit];
+
+    // 根据菜单项 id 还原操作项，不是托盘菜单项时返回 None
+    pub(crate) fn from_id(id: &str) -> Option<Self> {
+        match id {
+            "show" => Some(Self::Show),
+            "settings" => Some(Self::Settings),
+            "toggle_monitoring" => Some(Self::ToggleMonitoring),
+            "paste_last" => Some(Self::PasteLast),
+            "clear_unpinned" => Some(Self::ClearUnpinned),
+            "quit" => Some(Self::Quit),
+            _ => None,
+        }
+    }
+
+    // 菜单项 id，与前端传入的取值保持一致
+    pub(crate) fn id(self) -> &'static str {
+        match self {
+            Self::Show => "show",
+            Self::Settings => "settings",
+            Self::ToggleMonitoring => "toggle_monitoring",
+            Self::PasteLast => "paste_last",
+            Self::ClearUnpinned => "clear_unpinned",
+            Self::Quit => "quit",
+        }
+    }
+
+    // 规范化菜单配置：去掉重复项，并确保包含退出——关闭窗口只会隐藏到托盘，托盘菜单是唯一的退出入口
+    pub(crate) fn normalize(actions: &[Self]) -> Vec<Self> {
+        let mut normalized: Vec<Self> = Vec::with_capacity(actions.len() + 1);
+        for action in actions {
+            if !normalized.contains(action) {
+                normalized.push(*action);
+            }
+        }
+        if !normalized.contains(&Self::Quit) {
+            normalized.push(Self::Quit);
+        }
+        normalized
+    }
+}
+
+// 左键点击托盘图标时的动作：打开主窗口、弹出托盘菜单，或把最近一条记录写回剪贴板
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum TrayLeftClickAction {
+    #[default]
+    ShowWindow,
+    ShowMenu,
+    PasteLast,
+}
+
+impl TrayLeftClickAction {
+    // 解析数据库中保存的设置值，未设置或无法识别时回退到默认的打开主窗口
+    pub(crate) fn from_setting(value: Option<&str>) -> Self {
+        match value {
+            Some("show_menu") => Self::ShowMenu,
+            Some("paste_last") => Self::PasteLast,
+            _ => Self::ShowWindow,
+        }
+    }
+
+    // 写入数据库时使用的设置值，与前端传入的取值保持一致
+    pub(crate) fn as_setting(self) -> &'static str {
+        match self {
+            Self::ShowWindow => "show_window",
+            Self::ShowMenu => "show_menu",
+            Self::PasteLast => "paste_last",
+        }
+    }
+}
+
+// watcher 没有记录某次剪贴板内容的原因
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum SkipReason {
+    // 内容由应用自身写入（如从历史中复制），按约定跳过一次
+    SelfWrite,
+    // 写入数据库失败，下次轮询会重试
+    WriteFailed,
+    // 不符合当前的捕获过滤设置（如只记录链接）
+    Filtered,
+    // 短时间内重复捕获同一文本超过上限：仍会刷新更新时间，但不再累加次数
+    BurstCapped,
+    // 剪贴板变化时应用自身窗口处于焦点，视为在应用内复制的内容
+    OwnWindow,
+    // 内容是指向敏感文件的路径，且设置为拦截
+    RiskyPath,
+    // 内容匹配用户设置的排除规则（如密钥格式），跳过记录中不保留预览
+    Excluded,
+    // 复制时处于前台的是被屏蔽的来源应用（如密码管理器），跳过记录中不保留预览
+    BlockedSource,
+}
+
+// 一次被跳过的捕获：只保留文本预览，次数用于合并连续相同的记录
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SkippedCapture {
+    pub(crate) text_preview: String,
+    pub(crate) reason: SkipReason,
+    pub(crate) at: String,
+    pub(crate) count: i64,
+}
+
+// 同一文本在一个统计窗口内的捕获情况
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct CaptureBurst {
+    pub(crate) started_at: Instant,
+    pub(crate) hits: u64,
+}
+
+// 缓存在 AppState 中、可在运行时修改的设置：启动时从数据库读取，导入配置后整体替换
+pub(crate) struct RuntimeSettings {
+    pub(crate) pause_on_lock: bool,
+    pub(crate) reset_dedup_on_empty: bool,
+    pub(crate) ignore_own_window_copies: bool,
+    pub(crate) shortcut_toggles_window: bool,
+    pub(crate) sanitize_on_capture: bool,
+    pub(crate) block_risky_captures: bool,
+    pub(crate) clipboard_clear_seconds: u64,
+    pub(crate) auto_unpin_days: u64,
+    pub(crate) item_ttl_minutes: u64,
+    pub(crate) capture_burst_threshold: u64,
+    pub(crate) broadcast_batch_threshold: u64,
+    pub(crate) poll_interval_ms: u64,
+    pub(crate) tray_left_click: TrayLeftClickAction,
+    pub(crate) locale: Locale,
+    pub(crate) capture_filter: CaptureFilter,
+    pub(crate) sensitive_path_rules: Vec<SensitivePathRule>,
+    pub(crate) exclusion_rules: Vec<Regex>,
+    pub(crate) source_blocklist: Vec<String>,
+}
+
+impl RuntimeSettings {
+    // 把设置写入运行时状态；高频捕获的统计随上限一起重置，避免沿用旧上限下的计数
+    pub(crate) fn apply(self, state: &AppState) {
+        state
+            .pause_on_lock
+            .store(self.pause_on_lock, Ordering::Relaxed);
+        state
+            .reset_dedup_on_empty
+            .store(self.reset_dedup_on_empty, Ordering::Relaxed);
+        state
+            .ignore_own_window_copies
+            .store(self.ignore_own_window_copies, Ordering::Relaxed);
+        state
+            .shortcut_toggles_window
+            .store(self.shortcut_toggles_window, Ordering::Relaxed);
+        state
+            .sanitize_on_capture
+            .store(self.sanitize_on_capture, Ordering::Relaxed);
+        state
+            .block_risky_captures
+            .store(self.block_risky_captures, Ordering::Relaxed);
+        state
+            .clipboard_clear_seconds
+            .store(self.clipboard_clear_seconds, Ordering::Relaxed);
+        state
+            .auto_unpin_days
+            .store(self.auto_unpin_days, Ordering::Relaxed);
+        state
+            .item_ttl_minutes
+            .store(self.item_ttl_minutes, Ordering::Relaxed);
+        state
+            .capture_burst_threshold
+            .store(self.capture_burst_threshold, Ordering::Relaxed);
+        state
+            .broadcast_batch_threshold
+            .store(self.broadcast_batch_threshold, Ordering::Relaxed);
+        state
+            .poll_interval_ms
+            .store(self.poll_interval_ms, Ordering::Relaxed);
+        lock_or_recover(&state.capture_bursts).clear();
+        *lock_or_recover(&state.tray_left_click) = self.tray_left_click;
+        set_active_locale(self.locale);
+        *lock_or_recover(&state.capture_filter) = self.capture_filter;
+        *lock_or_recover(&state.sensitive_path_rules) = self.sensitive_path_rules;
+        *lock_or_recover(&state.exclusion_rules) = self.exclusion_rules;
+        *lock_or_recover(&state.source_blocklist) = self.source_blocklist;
+    }
+}
+
 // 统一持有数据库连接与运行时状态，避免每次调用命令都反复打开文件导致性能抖动
 pub(crate) struct AppState {
     // SQLite 连接在多个命令间共享，避免频繁打开文件
     pub(crate) db: Mutex<Connection>,
     // 是否启用后台剪贴板监听，可由前端随时切换
     pub(crate) monitoring_enabled: AtomicBool,
+    // 锁屏期间是否自动暂停记录，与手动开关 monitoring_enabled 相互独立
+    pub(crate) pause_on_lock: AtomicBool,
+    // 观察到剪贴板为空（被清空或内容不是文本）时是否清除 last_clipboard_text，
+    // 开启后清空再复制与之前相同的文本仍会被记录
+    pub(crate) reset_dedup_on_empty: AtomicBool,
+    // 剪贴板变化时应用自身窗口处于焦点，说明内容是在应用内选中复制的，开启后不写入历史
+    pub(crate) ignore_own_window_copies: AtomicBool,
+    // 全局快捷键是否作为显示 / 隐藏切换：开启后主窗口可见且处于焦点时再按一次会隐藏窗口
+    pub(crate) shortcut_toggles_window: AtomicBool,
+    // 捕获时是否把弯引号、不换行空格换成普通字符并去掉零宽字符，开启后只影响之后捕获的内容
+    pub(crate) sanitize_on_capture: AtomicBool,
+    // 当前生效的捕获过滤规则，修改设置后立即替换
+    pub(crate) capture_filter: Mutex<CaptureFilter>,
+    // 已编译的敏感路径规则，修改设置后整体替换
+    pub(crate) sensitive_path_rules: Mutex<Vec<SensitivePathRule>>,
+    // 已编译的排除规则，匹配任一规则的内容不写入历史，修改设置后整体替换
+    pub(crate) exclusion_rules: Mutex<Vec<Regex>>,
+    // 屏蔽的来源应用标识，复制时处于前台的应用在列表中则不写入历史
+    pub(crate) source_blocklist: Mutex<Vec<String>>,
+    // 命中敏感路径规则的内容是否不写入历史；关闭时照常写入，只标记并提醒
+    pub(crate) block_risky_captures: AtomicBool,
     // 记录后台上一次处理过的剪贴板文本，用于去重
     pub(crate) last_clipboard_text: Mutex<Option<String>>,
     // 标记下一次需要跳过的剪贴板文本，避免应用自身写入导致重复计数
-    pub(crate) skip_next_text: Mutex<Option<String>>,
+    // 记录标记时间，超过 SKIP_NEXT_TEXT_TTL_MS 仍未出现则失效，避免之后用户自己复制的相同内容被误跳过
+    pub(crate) skip_next_text: Mutex<Option<(String, Instant)>>,
     // 仅允许通过托盘菜单退出应用，其他退出请求需要被拦截
     pub(crate) allow_exit: AtomicBool,
+    // 本次启动的时间（RFC3339），用于筛选“本次会话中复制过的内容”
+    pub(crate) session_started_at: String,
+    // 已编译的自动打标签规则缓存，规则增删后整体刷新
+    pub(crate) autotag_rules: Mutex<Vec<CompiledAutotagRule>>,
+    // 左键点击托盘图标时的动作，托盘事件回调中直接读取，避免每次点击都查询数据库
+    pub(crate) tray_left_click: Mutex<TrayLeftClickAction>,
+    // 最近被 watcher 跳过的捕获（环形缓冲，最新的在末尾），仅用于诊断展示
+    pub(crate) recent_skips: Mutex<VecDeque<SkippedCapture>>,
+    // watcher 初始化剪贴板或读取内容失败的累计次数，持续增长说明剪贴板后端异常，用于健康提示
+    pub(crate) capture_error_count: AtomicU64,
+    // 应用写入剪贴板后自动清空的延迟秒数，0 表示不自动清空
+    pub(crate) clipboard_clear_seconds: AtomicU64,
+    // 自动清空计时的代次：每次应用写入剪贴板都会递增，旧计时到期后发现代次变化即放弃清空
+    pub(crate) clipboard_clear_generation: AtomicU64,
+    // 临时粘贴的代次：每次临时粘贴都会递增，只有最近一次负责恢复原剪贴板
+    pub(crate) clipboard_restore_generation: AtomicU64,
+    // 等待恢复的原剪贴板内容及其所属的临时粘贴代次，恢复或放弃后清空
+    pub(crate) pending_clipboard_restore: Mutex<Option<(u64, String)>>,
+    // 固定超过该天数的条目会被自动取消固定（条目本身保留），0 表示不自动取消
+    pub(crate) auto_unpin_days: AtomicU64,
+    // 新捕获或取消固定的条目默认存活的分钟数，到期后由 watcher 删除，0 表示不过期
+    pub(crate) item_ttl_minutes: AtomicU64,
+    // 同一文本在统计窗口内的最大计数次数，0 表示不限制
+    pub(crate) capture_burst_threshold: AtomicU64,
+    // 各文本当前统计窗口的开始时间与捕获次数，只保留仍在窗口内的记录
+    pub(crate) capture_bursts: Mutex<HashMap<String, CaptureBurst>>,
+    // 统计窗口内 watcher 广播次数超过该值后改为合并广播，0 表示始终逐条广播
+    pub(crate) broadcast_batch_threshold: AtomicU64,
+    // 后台轮询剪贴板的间隔（毫秒），watcher 每次轮询前读取，修改后下一次轮询即生效
+    pub(crate) poll_interval_ms: AtomicU64,
+    // 默认数据目录（便携模式下为可执行文件所在目录），数据库位置指针文件保存在这里
+    pub(crate) app_data_dir: PathBuf,
+    // 数据库文件的完整路径，便携模式下位于可执行文件旁；迁移数据库后与连接一同替换
+    pub(crate) db_path: Mutex<PathBuf>,
+    // 是否处于便携模式（可执行文件旁存在便携标记文件）
+    pub(crate) portable: bool,
+    // 通过条目链接冷启动时待定位的条目 id：此时前端尚未监听事件，由前端加载后主动领取
+    pub(crate) pending_focus_item: Mutex<Option<String>>,
 }
 
 // 生成当前时间的 ISO-8601 字符串，前后端统一使用字符串存储时间
@@ -68,6 +1229,163 @@ pub(crate) fn now_iso_string() -> String {
     Utc::now().to_rfc3339()
 }
 
+// 把本地日期（YYYY-MM-DD）换算成该日在 UTC 下的起止时间 [start, end)，用于按本地日历日筛选存储的 UTC 时间；
+// 按各自零点分别换算，夏令时切换当天的时长不足或超过 24 小时也能正确覆盖
+pub(crate) fn local_day_range(date: &str) -> Result<(String, String), AppError> {
+    let day = NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d")
+        .map_err(|_| AppError::InvalidInput(Reason::InvalidDate(date.to_string())))?;
+    let next_day = day
+        .succ_opt()
+        .ok_or_else(|| AppError::InvalidInput(Reason::DateOutOfRange(date.to_string())))?;
+    let to_utc = |day: NaiveDate| {
+        Local
+            .from_local_datetime(&day.and_time(NaiveTime::MIN))
+            .earliest()
+            .map(|time| time.with_timezone(&Utc).to_rfc3339())
+            .ok_or_else(|| AppError::InvalidInput(Reason::UnconvertibleDate(date.to_string())))
+    };
+    Ok((to_utc(day)?, to_utc(next_day)?))
+}
+
+// 记录一次被跳过的捕获：与最近一条文本和原因都相同时只累加次数并刷新时间，避免轮询重试刷满缓冲区
+pub(crate) fn record_skipped_capture(state: &AppState, text: &str, reason: SkipReason) {
+    let mut skips = lock_or_recover(&state.recent_skips);
+    let text_preview: String = text.chars().take(SKIP_PREVIEW_MAX_CHARS).collect();
+    let at = now_iso_string();
+    if let Some(last) = skips.back_mut() {
+        if last.reason == reason && last.text_preview == text_preview {
+            last.at = at;
+            last.count += 1;
+            return;
+        }
+    }
+    if skips.len() >= RECENT_SKIPS_LIMIT {
+        skips.pop_front();
+    }
+    skips.push_back(SkippedCapture {
+        text_preview,
+        reason,
+        at,
+        count: 1,
+    });
+}
+
+// 获取共享状态的互斥锁，所有共享状态都经由这里加锁。持有锁的线程 panic 会让锁进入中毒状态，
+// 此后每次 lock() 都返回错误，数据库等状态便永久不可用；这里沿用锁内的数据继续使用并清除中毒标记，
+// 之后的加锁不再经过恢复分支
+pub(crate) fn lock_or_recover<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|poisoned| {
+        mutex.clear_poison();
+        poisoned.into_inner()
+    })
+}
+
+// 跳过标记的实际有效期：至少覆盖两个轮询间隔，避免轮询调慢后标记在被读到前就已失效
+pub(crate) fn skip_next_text_ttl_ms(poll_interval_ms: u64) -> u64 {
+    SKIP_NEXT_TEXT_TTL_MS.max(poll_interval_ms.saturating_mul(2))
+}
+
+// watcher 读到空剪贴板时调用：按设置清除去重基准，让之后再次复制同一文本时不被当作重复内容
+pub(crate) fn observe_empty_clipboard(state: &AppState) {
+    if !state.reset_dedup_on_empty.load(Ordering::Relaxed) {
+        return;
+    }
+    *lock_or_recover(&state.last_clipboard_text) = None;
+}
+
+// 剪贴板内容是否与去重基准相同：相同说明剪贴板自上次处理后没有变化，本次轮询无需再记录
+pub(crate) fn is_repeat_of_last_capture(state: &AppState, dedup_key: &str) -> bool {
+    lock_or_recover(&state.last_clipboard_text).as_deref() == Some(dedup_key)
+}
+
+// 按规则跳过一次捕获：同样更新去重基准，剪贴板不变时之后的轮询不再重复判断与记录；
+// preview 为跳过记录中展示的内容，不宜保留内容的规则（如排除规则）传入空字符串
+pub(crate) fn skip_capture(state: &AppState, dedup_key: &str, preview: &str, reason: SkipReason) {
+    *lock_or_recover(&state.last_clipboard_text) = Some(dedup_key.to_string());
+    record_skipped_capture(state, preview, reason);
+}
+
+// 本次剪贴板变化是否来自应用自身窗口（在应用内选中文本后复制）：开启忽略设置且此时应用窗口处于焦点。
+// 焦点通过回调读取，设置关闭时不查询窗口状态
+pub(crate) fn is_own_window_copy(
+    state: &AppState,
+    own_window_focused: impl FnOnce() -> bool,
+) -> bool {
+    state.ignore_own_window_copies.load(Ordering::Relaxed) && own_window_focused()
+}
+
+// 登记一次对该文本的捕获，返回这次是否超出了窗口内的计数上限；
+// 窗口从第一次捕获开始计时，到期后重新计数。未设置上限时不做任何记录
+pub(crate) fn register_capture_burst(state: &AppState, text: &str) -> bool {
+    let threshold = state.capture_burst_threshold.load(Ordering::Relaxed);
+    if threshold == 0 {
+        return false;
+    }
+    let mut bursts = lock_or_recover(&state.capture_bursts);
+    let now = Instant::now();
+    bursts.retain(|_, burst| now.duration_since(burst.started_at) < CAPTURE_BURST_WINDOW);
+    let burst = bursts.entry(text.to_string()).or_insert(CaptureBurst {
+        started_at: now,
+        hits: 0,
+    });
+    burst.hits += 1;
+    burst.hits > threshold
+}
+
+// watcher 广播的节流状态，只在 watcher 线程内使用：记录窗口内的广播时间，
+// 超过阈值后把条目缓存起来，到期后由 take_due 取出合并广播，避免脚本循环复制时事件淹没前端
+#[derive(Debug, Default)]
+pub(crate) struct BroadcastThrottle {
+    recent: VecDeque<Instant>,
+    pending: Vec<ClipboardItem>,
+    batch_started_at: Option<Instant>,
+}
+
+impl BroadcastThrottle {
+    // 登记一条待广播的条目：未超过阈值时原样返回，由调用方立即广播；否则缓存并返回 None。
+    // 已有缓存时新条目同样进入缓存，保证前端收到的顺序与捕获顺序一致
+    pub(crate) fn offer(
+        &mut self,
+        item: ClipboardItem,
+        threshold: u64,
+        now: Instant,
+    ) -> Option<ClipboardItem> {
+        while self
+            .recent
+            .front()
+            .is_some_and(|at| now.duration_since(*at) >= BROADCAST_RATE_WINDOW)
+        {
+            self.recent.pop_front();
+        }
+        self.recent.push_back(now);
+        let over_limit = threshold > 0 && self.recent.len() as u64 > threshold;
+        if !over_limit && self.pending.is_empty() {
+            return Some(item);
+        }
+        // 同一条目在一批内多次更新时只保留最新状态，并移到末尾
+        self.pending.retain(|pending| pending.id != item.id);
+        self.pending.push(item);
+        self.batch_started_at.get_or_insert(now);
+        None
+    }
+
+    // 取出到期的缓存条目；关闭合并（阈值为 0）后立即取出剩余的缓存，避免条目滞留
+    pub(crate) fn take_due(&mut self, threshold: u64, now: Instant) -> Option<Vec<ClipboardItem>> {
+        let started_at = self.batch_started_at?;
+        if threshold > 0 && now.duration_since(started_at) < BROADCAST_BATCH_INTERVAL {
+            return None;
+        }
+        self.batch_started_at = None;
+        Some(std::mem::take(&mut self.pending))
+    }
+}
+
+// 从条目链接中解析条目 id，不是本应用的条目链接时返回 None
+pub(crate) fn parse_item_uri(uri: &str) -> Option<String> {
+    let id = uri.strip_prefix(ITEM_URI_PREFIX)?.trim_end_matches('/');
+    (!id.is_empty() && !id.contains('/')).then(|| id.to_string())
+}
+
 // 构造用于写入数据库的剪贴板条目，确保字段完整且格式一致
 pub(crate) fn build_clipboard_payload(text: String) -> ClipboardUpsertPayload {
     let now = now_iso_string();
@@ -76,5 +1394,119 @@ pub(crate) fn build_clipboard_payload(text: String) -> ClipboardUpsertPayload {
         text,
         created_at: now.clone(),
         updated_at: now,
+        source_app: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::test_state;
+
+    // 复制 X、清空剪贴板、再复制 X：开启设置时第二次复制不再被当作重复内容，关闭时保持旧行为
+    #[test]
+    fn recopy_after_empty_clipboard_is_not_a_duplicate() {
+        let state = test_state();
+        assert!(state.reset_dedup_on_empty.load(Ordering::Relaxed));
+        // watcher 记录 X 后把它作为去重基准，剪贴板未变化时的轮询都视为重复
+        *lock_or_recover(&state.last_clipboard_text) = Some("X".to_string());
+        assert!(is_repeat_of_last_capture(&state, "X"));
+        observe_empty_clipboard(&state);
+        assert!(!is_repeat_of_last_capture(&state, "X"));
+
+        state.reset_dedup_on_empty.store(false, Ordering::Relaxed);
+        *lock_or_recover(&state.last_clipboard_text) = Some("X".to_string());
+        observe_empty_clipboard(&state);
+        assert!(is_repeat_of_last_capture(&state, "X"));
+    }
+
+    // 在应用窗口内复制：默认跳过且不写入历史，剪贴板不变时之后的轮询视为重复；关闭设置后照常记录
+    #[test]
+    fn copy_from_own_window_is_skipped() {
+        let state = test_state();
+        assert!(is_own_window_copy(&state, || true));
+        assert!(!is_own_window_copy(&state, || false));
+        skip_capture(&state, "X", "X", SkipReason::OwnWindow);
+        assert!(is_repeat_of_last_capture(&state, "X"));
+        let last_skip = lock_or_recover(&state.recent_skips).back().cloned();
+        assert_eq!(
+            last_skip.map(|skip| skip.reason),
+            Some(SkipReason::OwnWindow)
+        );
+        let stored: i64 = lock_or_recover(&state.db)
+            .query_row("SELECT COUNT(*) FROM clipboard_items", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(stored, 0);
+
+        state
+            .ignore_own_window_copies
+            .store(false, Ordering::Relaxed);
+        assert!(!is_own_window_copy(&state, || panic!(
+            "设置关闭时不应查询窗口焦点"
+        )));
+    }
+
+    // 各过滤模式下允许记录的内容；自定义正则缺失（已失效）时按记录全部处理
+    #[test]
+    fn capture_filter_modes() {
+        let filter = |mode, pattern: Option<&str>| CaptureFilter {
+            mode,
+            regex: pattern.map(|pattern| Regex::new(pattern).unwrap()),
+        };
+        let url = "https://example.com";
+        let code = "let x = 1;";
+        let prose = "hello there";
+
+        let all = filter(CaptureFilterMode::All, None);
+        assert!(all.allows(url) && all.allows(code) && all.allows(prose));
+        let urls = filter(CaptureFilterMode::UrlsOnly, None);
+        assert!(urls.allows(url) && !urls.allows(code) && !urls.allows(prose));
+        let urls_and_code = filter(CaptureFilterMode::UrlsAndCode, None);
+        assert!(urls_and_code.allows(url) && urls_and_code.allows(code));
+        assert!(!urls_and_code.allows(prose));
+        let custom = filter(CaptureFilterMode::CustomRegex, Some("^hello"));
+        assert!(custom.allows(prose) && !custom.allows(url));
+        assert!(filter(CaptureFilterMode::CustomRegex, None).allows(url));
+
+        // 设置值往返一致，无法识别的取值回退到记录全部
+        for mode in [
+            CaptureFilterMode::All,
+            CaptureFilterMode::UrlsOnly,
+            CaptureFilterMode::UrlsAndCode,
+            CaptureFilterMode::CustomRegex,
+        ] {
+            assert_eq!(
+                CaptureFilterMode::from_setting(Some(mode.as_setting())),
+                mode
+            );
+        }
+        assert_eq!(
+            CaptureFilterMode::from_setting(Some("unknown")),
+            CaptureFilterMode::All
+        );
+    }
+
+    // 持有锁的线程 panic 后，锁内的数据照常取回，之后的加锁也不再处于中毒状态
+    #[test]
+    fn lock_or_recover_returns_guard_of_poisoned_mutex() {
+        let mutex = Mutex::new(vec![1]);
+        let result = std::thread::scope(|scope| {
+            scope
+                .spawn(|| {
+                    let mut guard = mutex.lock().unwrap();
+                    guard.push(2);
+                    panic!("持有锁时 panic");
+                })
+                .join()
+        });
+        assert!(result.is_err());
+        assert!(mutex.is_poisoned());
+
+        let mut guard = lock_or_recover(&mutex);
+        assert_eq!(*guard, [1, 2]);
+        guard.push(3);
+        drop(guard);
+        assert!(!mutex.is_poisoned());
+        assert_eq!(*mutex.lock().unwrap(), [1, 2, 3]);
     }
 }
@@ -0,0 +1,197 @@
+// normalize.rs：剪贴板文本写入历史前的统一规整逻辑。
+// 说明：watcher、手动快照、前端写入与编辑条目都经过这里，保证“存下来的文本”与“用来判断重复的键”在各条路径上完全一致。
+
+use crate::models::AppState;
+use std::sync::atomic::Ordering;
+
+// 规整策略：trim 去掉首尾空白，collapse_whitespace 让去重键忽略内部空白差异（连续空白、换行方式），
+// sanitize 把排版字符（弯引号、不换行空格、零宽字符）换成普通字符后再写入
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct NormalizePolicy {
+    pub(crate) trim: bool,
+    pub(crate) collapse_whitespace: bool,
+    pub(crate) sanitize: bool,
+}
+
+impl NormalizePolicy {
+    // 写入历史时使用：只去掉首尾空白，去重按完整文本比较
+    pub(crate) const STORAGE: Self = Self {
+        trim: true,
+        collapse_whitespace: false,
+        sanitize: false,
+    };
+    // 合并空白差异时使用：内部空白不同的文本视为同一条
+    pub(crate) const WHITESPACE_INSENSITIVE: Self = Self {
+        trim: true,
+        collapse_whitespace: true,
+        sanitize: false,
+    };
+
+    // 捕获剪贴板内容时使用：在写入规则的基础上按设置决定是否清理排版字符。
+    // 去重基准与跳过标记也必须使用同一策略，否则清理前后的文本会被当成两条不同的内容
+    pub(crate) fn capture(state: &AppState) -> Self {
+        Self {
+            sanitize: state.sanitize_on_capture.load(Ordering::Relaxed),
+            ..Self::STORAGE
+        }
+    }
+}
+
+// 规整结果：display_text 为实际写入与展示的文本，dedup_key 为判断重复时比较的键，
+// sanitized 表示清理排版字符确实改变了文本
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct NormalizedText {
+    pub(crate) display_text: String,
+    pub(crate) dedup_key: String,
+    pub(crate) sanitized: bool,
+}
+
+impl NormalizedText {
+    // 规整后为空（纯空白）的文本不写入历史
+    pub(crate) fn is_empty(&self) -> bool {
+        self.display_text.is_empty()
+    }
+}
+
+// 按策略规整文本；去重键只影响比较，不改变展示的内容
+pub(crate) fn normalize_for_storage(text: &str, policy: NormalizePolicy) -> NormalizedText {
+    let cleaned = policy.sanitize.then(|| sanitize_typography(text));
+    let sanitized = cleaned.as_deref().is_some_and(|cleaned| cleaned != text);
+    let text = cleaned.as_deref().unwrap_or(text);
+    let display_text = if policy.trim { text.trim() } else { text }.to_string();
+    let dedup_key = if policy.collapse_whitespace {
+        display_text
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+    } else {
+        display_text.clone()
+    };
+    NormalizedText {
+        display_text,
+        dedup_key,
+        sanitized,
+    }
+}
+
+// 从文字处理软件、网页复制的文本常带有排版字符，粘贴到代码或终端中会出错，也会让相同内容去重失败：
+// 弯引号换成直引号，各类不换行空格换成普通空格，零宽字符直接去掉。
+// 注意零宽连接符也用于组合 emoji（如家庭表情），清理后会拆成多个单独的表情，因此该功能默认关闭
+fn sanitize_typography(text: &str) -> String {
+    text.chars()
+        .filter_map(|ch| match ch {
+            '\u{2018}' | '\u{2019}' | '\u{201A}' | '\u{201B}' => Some('\''),
+            '\u{201C}' | '\u{201D}' | '\u{201E}' | '\u{201F}' => Some('"'),
+            '\u{00A0}' | '\u{2007}' | '\u{202F}' => Some(' '),
+            '\u{200B}' | '\u{200C}' | '\u{200D}' | '\u{2060}' | '\u{FEFF}' => None,
+            _ => Some(ch),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::test_state;
+
+    // 写入规则：只去掉首尾空白，内部空白与换行方式的差异都保留在去重键中
+    #[test]
+    fn storage_policy_trims_but_keeps_inner_whitespace() {
+        let normalized = normalize_for_storage("  a  b\r\nc \n", NormalizePolicy::STORAGE);
+        assert_eq!(normalized.display_text, "a  b\r\nc");
+        assert_eq!(normalized.dedup_key, "a  b\r\nc");
+        assert!(!normalized.sanitized);
+        assert!(normalize_for_storage(" \n\t ", NormalizePolicy::STORAGE).is_empty());
+        assert_ne!(
+            normalize_for_storage("a b", NormalizePolicy::STORAGE).dedup_key,
+            normalize_for_storage("a\nb", NormalizePolicy::STORAGE).dedup_key
+        );
+    }
+
+    // 合并空白差异：展示的文本保持原样，去重键折叠全部连续空白
+    #[test]
+    fn whitespace_insensitive_policy_collapses_only_the_dedup_key() {
+        let policy = NormalizePolicy::WHITESPACE_INSENSITIVE;
+        let normalized = normalize_for_storage(" a  b\r\n\tc ", policy);
+        assert_eq!(normalized.display_text, "a  b\r\n\tc");
+        assert_eq!(normalized.dedup_key, "a b c");
+        assert_eq!(
+            normalize_for_storage("a b c", policy).dedup_key,
+            normalized.dedup_key
+        );
+        // 空白之外的差异（大小写、标点）仍视为不同的内容
+        assert_ne!(
+            normalize_for_storage("A b c", policy).dedup_key,
+            normalized.dedup_key
+        );
+    }
+
+    // 捕获规则跟随设置：关闭时与写入规则一致，开启时清理排版字符并标记
+    #[test]
+    fn capture_policy_follows_sanitize_setting() {
+        let state = test_state();
+        let text = " \u{201C}quoted\u{201D} ";
+
+        state.sanitize_on_capture.store(false, Ordering::Relaxed);
+        let policy = NormalizePolicy::capture(&state);
+        assert!(policy.trim && !policy.collapse_whitespace && !policy.sanitize);
+        let normalized = normalize_for_storage(text, policy);
+        assert_eq!(normalized.display_text, "\u{201C}quoted\u{201D}");
+        assert!(!normalized.sanitized);
+
+        state.sanitize_on_capture.store(true, Ordering::Relaxed);
+        let policy = NormalizePolicy::capture(&state);
+        assert!(policy.trim && !policy.collapse_whitespace && policy.sanitize);
+        let normalized = normalize_for_storage(text, policy);
+        assert_eq!(normalized.display_text, "\"quoted\"");
+        assert_eq!(normalized.dedup_key, "\"quoted\"");
+        assert!(normalized.sanitized);
+
+        // 开启清理但文本中没有排版字符时，不标记为清理过
+        assert!(!normalize_for_storage("plain", policy).sanitized);
+    }
+
+    const SANITIZE: NormalizePolicy = NormalizePolicy {
+        sanitize: true,
+        ..NormalizePolicy::STORAGE
+    };
+
+    // 从文字处理软件、网页、聊天软件复制的真实片段
+    #[test]
+    fn sanitize_cleans_real_world_pasted_text() {
+        let cases = [
+            // Word 自动替换的弯引号与撇号
+            (
+                "\u{201C}Don\u{2019}t panic,\u{201D} she said.",
+                "\"Don't panic,\" she said.",
+            ),
+            // 网页代码示例中的不换行空格与弯引号，直接粘贴到终端会报错
+            (
+                "curl\u{00A0}-H\u{00A0}\u{2018}Accept: application/json\u{2019} https://example.com",
+                "curl -H 'Accept: application/json' https://example.com",
+            ),
+            // 排版软件在长链接中插入的零宽空格，以及文件开头的 BOM
+            (
+                "\u{FEFF}https://example.com/very\u{200B}/long\u{200B}/path",
+                "https://example.com/very/long/path",
+            ),
+            // 数字与单位之间的窄不换行空格、数字间的等宽空格
+            ("10\u{202F}km, 1\u{2007}000", "10 km, 1 000"),
+            // 德文低位引号
+            ("\u{201E}Zitat\u{201C}", "\"Zitat\""),
+        ];
+        for (pasted, expected) in cases {
+            let normalized = normalize_for_storage(pasted, SANITIZE);
+            assert_eq!(normalized.display_text, expected);
+            assert!(normalized.sanitized);
+        }
+    }
+
+    // 清理后才去掉首尾空白：开头的不换行空格与零宽字符不会残留在保存的文本中
+    #[test]
+    fn sanitize_runs_before_trim() {
+        let normalized = normalize_for_storage("\u{00A0}\u{200B}value\u{2060}\u{00A0}", SANITIZE);
+        assert_eq!(normalized.display_text, "value");
+        assert!(normalize_for_storage("\u{200B}\u{FEFF}\u{00A0}", SANITIZE).is_empty());
+    }
+}
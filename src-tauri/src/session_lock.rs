@@ -0,0 +1,138 @@
+// session_lock.rs：检测系统是否处于锁屏状态，供后台 watcher 在锁屏期间暂停记录。
+// 说明：
+// 1. Windows 下锁屏时输入桌面会切换到 Winlogon 安全桌面，普通进程无法打开，以此判断是否锁屏；
+// 2. macOS 通过 CGSessionCopyCurrentDictionary 读取会话信息中的 CGSSessionScreenIsLocked 标记；
+// 3. Linux 通过 loginctl 读取当前会话的 LockedHint（由桌面环境的锁屏程序维护），未安装 systemd 时视为未锁屏；
+// 检测失败时一律按“未锁屏”处理，宁可多记录也不能让监听意外停止。
+
+use std::time::{Duration, Instant};
+
+// 两次检测之间的最短间隔：Linux 下每次检测都要启动子进程，不能跟随剪贴板轮询的频率执行
+const LOCK_CHECK_INTERVAL: Duration = Duration::from_secs(2);
+
+// 带缓存的锁屏状态检测器，只在后台 watcher 线程中使用
+pub(crate) struct ScreenLockMonitor {
+    locked: bool,
+    last_checked: Option<Instant>,
+}
+
+impl ScreenLockMonitor {
+    pub(crate) fn new() -> Self {
+        Self {
+            locked: false,
+            last_checked: None,
+        }
+    }
+
+    // 返回当前是否锁屏：距上次检测不足间隔时直接返回缓存结果
+    pub(crate) fn is_locked(&mut self) -> bool {
+        let due = self
+            .last_checked
+            .is_none_or(|checked_at| checked_at.elapsed() >= LOCK_CHECK_INTERVAL);
+        if due {
+            self.locked = query_screen_locked();
+            self.last_checked = Some(Instant::now());
+        }
+        self.locked
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn query_screen_locked() -> bool {
+    use std::ffi::c_void;
+
+    // 只需要判断能否打开输入桌面，申请最小的读取权限即可
+    const DESKTOP_READOBJECTS: u32 = 0x0001;
+
+    #[link(name = "user32")]
+    extern "system" {
+        fn OpenInputDesktop(flags: u32, inherit: i32, desired_access: u32) -> *mut c_void;
+        fn CloseDesktop(desktop: *mut c_void) -> i32;
+    }
+
+    // SAFETY：参数均为按值传递的常量，返回的句柄在非空时立即关闭，不会被重复释放
+    unsafe {
+        let desktop = OpenInputDesktop(0, 0, DESKTOP_READOBJECTS);
+        if desktop.is_null() {
+            return true;
+        }
+        CloseDesktop(desktop);
+        false
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn query_screen_locked() -> bool {
+    use std::ffi::{c_char, c_void};
+
+    const K_CF_STRING_ENCODING_UTF8: u32 = 0x0800_0100;
+
+    #[link(name = "CoreFoundation", kind = "framework")]
+    extern "C" {
+        fn CFStringCreateWithCString(
+            allocator: *const c_void,
+            value: *const c_char,
+            encoding: u32,
+        ) -> *const c_void;
+        fn CFDictionaryGetValue(dict: *const c_void, key: *const c_void) -> *const c_void;
+        fn CFGetTypeID(value: *const c_void) -> usize;
+        fn CFBooleanGetTypeID() -> usize;
+        fn CFBooleanGetValue(value: *const c_void) -> u8;
+        fn CFRelease(value: *const c_void);
+    }
+
+    #[link(name = "CoreGraphics", kind = "framework")]
+    extern "C" {
+        fn CGSessionCopyCurrentDictionary() -> *const c_void;
+    }
+
+    // SAFETY：遵循 CoreFoundation 的所有权规则：Copy/Create 得到的对象由这里释放，
+    // Get 得到的值归字典所有，只在字典释放前读取
+    unsafe {
+        let session = CGSessionCopyCurrentDictionary();
+        if session.is_null() {
+            return false;
+        }
+        let key = CFStringCreateWithCString(
+            std::ptr::null(),
+            c"CGSSessionScreenIsLocked".as_ptr(),
+            K_CF_STRING_ENCODING_UTF8,
+        );
+        let locked = if key.is_null() {
+            false
+        } else {
+            // 未锁屏时会话信息中通常不包含该键，取到空值即视为未锁屏
+            let value = CFDictionaryGetValue(session, key);
+            let locked = !value.is_null()
+                && CFGetTypeID(value) == CFBooleanGetTypeID()
+                && CFBooleanGetValue(value) != 0;
+            CFRelease(key);
+            locked
+        };
+        CFRelease(session);
+        locked
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn query_screen_locked() -> bool {
+    use std::process::{Command, Stdio};
+
+    // 优先使用当前进程所在的会话，未设置时交给 loginctl 自动选择图形会话
+    let session = std::env::var("XDG_SESSION_ID").unwrap_or_else(|_| "auto".to_string());
+    Command::new("loginctl")
+        .args(["show-session", &session, "--property=LockedHint", "--value"])
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+        .map(|output| {
+            output.status.success() && String::from_utf8_lossy(&output.stdout).trim() == "yes"
+        })
+        .unwrap_or(false)
+}
+
+// 其他桌面平台暂未实现锁屏检测，始终视为未锁屏
+#[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
+fn query_screen_locked() -> bool {
+    false
+}
@@ -0,0 +1,62 @@
+// test_support.rs：单元测试共用的辅助函数，只在 cargo test 时编译。
+// 说明：
+// 1. 使用内存数据库，建表、迁移与运行时设置的读取方式和启动时（lib.rs 的 setup）一致；
+// 2. 每次调用都得到一份独立的状态，测试之间互不影响，也不会碰到用户的数据目录。
+
+use crate::db::{init_db, load_runtime_settings, migrate};
+use crate::models::{now_iso_string, AppState};
+use rusqlite::Connection;
+use std::collections::{HashMap, VecDeque};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicU64};
+use std::sync::Mutex;
+
+// 打开一个已完成建表与迁移的内存数据库
+pub(crate) fn test_connection() -> Connection {
+    let mut conn = Connection::open_in_memory().expect("打开内存数据库失败");
+    init_db(&conn).expect("建表失败");
+    migrate(&mut conn).expect("迁移失败");
+    conn
+}
+
+// 构造使用内存数据库的应用状态，各项运行时设置取默认值，监听默认开启
+pub(crate) fn test_state() -> AppState {
+    let conn = test_connection();
+    let runtime = load_runtime_settings(&conn).expect("读取运行时设置失败");
+    AppState {
+        db: Mutex::new(conn),
+        monitoring_enabled: AtomicBool::new(true),
+        pause_on_lock: AtomicBool::new(runtime.pause_on_lock),
+        reset_dedup_on_empty: AtomicBool::new(runtime.reset_dedup_on_empty),
+        ignore_own_window_copies: AtomicBool::new(runtime.ignore_own_window_copies),
+        shortcut_toggles_window: AtomicBool::new(runtime.shortcut_toggles_window),
+        sanitize_on_capture: AtomicBool::new(runtime.sanitize_on_capture),
+        capture_filter: Mutex::new(runtime.capture_filter),
+        sensitive_path_rules: Mutex::new(runtime.sensitive_path_rules),
+        exclusion_rules: Mutex::new(runtime.exclusion_rules),
+        source_blocklist: Mutex::new(runtime.source_blocklist),
+        block_risky_captures: AtomicBool::new(runtime.block_risky_captures),
+        last_clipboard_text: Mutex::new(None),
+        skip_next_text: Mutex::new(None),
+        allow_exit: AtomicBool::new(false),
+        session_started_at: now_iso_string(),
+        autotag_rules: Mutex::new(Vec::new()),
+        tray_left_click: Mutex::new(runtime.tray_left_click),
+        recent_skips: Mutex::new(VecDeque::new()),
+        capture_error_count: AtomicU64::new(0),
+        clipboard_clear_seconds: AtomicU64::new(runtime.clipboard_clear_seconds),
+        clipboard_clear_generation: AtomicU64::new(0),
+        clipboard_restore_generation: AtomicU64::new(0),
+        pending_clipboard_restore: Mutex::new(None),
+        auto_unpin_days: AtomicU64::new(runtime.auto_unpin_days),
+        item_ttl_minutes: AtomicU64::new(runtime.item_ttl_minutes),
+        capture_burst_threshold: AtomicU64::new(runtime.capture_burst_threshold),
+        capture_bursts: Mutex::new(HashMap::new()),
+        broadcast_batch_threshold: AtomicU64::new(runtime.broadcast_batch_threshold),
+        poll_interval_ms: AtomicU64::new(runtime.poll_interval_ms),
+        app_data_dir: PathBuf::new(),
+        db_path: Mutex::new(PathBuf::new()),
+        portable: false,
+        pending_focus_item: Mutex::new(None),
+    }
+}
@@ -0,0 +1,37 @@
+import { useEffect, useState } from "react";
+import { Box } from "@mui/material";
+import { getItemThumbnail } from "../tauri/clipboardCommands";
+
+// 图片条目的缩略图：历史列表不附带缩略图，组件显示时再向后端读取。
+// 同一条目的缩略图不会变化，读取结果按条目 id 缓存，列表重新渲染或切换选中时不重复请求。
+
+const thumbnailCache = new Map();
+
+export const ItemThumbnail = ({ item, sx }) => {
+  const [src, setSrc] = useState(() => thumbnailCache.get(item.id) ?? null);
+
+  useEffect(() => {
+    if (thumbnailCache.has(item.id)) {
+      setSrc(thumbnailCache.get(item.id));
+      return undefined;
+    }
+    setSrc(null);
+    // 条目切换或组件卸载后忽略先前请求的结果，避免显示到其他条目上
+    let cancelled = false;
+    getItemThumbnail(item.id)
+      .then((thumbnail) => {
+        thumbnailCache.set(item.id, thumbnail);
+        if (!cancelled) {
+          setSrc(thumbnail);
+        }
+      })
+      .catch(() => {
+        // 条目已被删除等情况下不显示缩略图，列表会随删除事件刷新
+      });
+    return () => {
+      cancelled = true;
+    };
+  }, [item.id]);
+
+  return <Box component="img" src={src ?? undefined} alt={item.text} sx={sx} />;
+};
@@ -0,0 +1,18 @@
+import { Alert, Snackbar } from "@mui/material";
+
+// 敏感路径提醒：复制到私钥、凭据等文件的路径时提示用户，拦截时说明内容未写入历史。
+
+export const RiskyCaptureToast = ({ capture, onClose }) => (
+  <Snackbar
+    open={Boolean(capture)}
+    autoHideDuration={6000}
+    onClose={onClose}
+    anchorOrigin={{ vertical: "bottom", horizontal: "center" }}
+  >
+    <Alert severity="warning" variant="filled" onClose={onClose} sx={{ width: "100%" }}>
+      {capture?.blocked
+        ? `复制的内容指向敏感文件，未写入历史：${capture.textPreview}`
+        : `复制的内容指向敏感文件，请注意不要外传：${capture?.textPreview ?? ""}`}
+    </Alert>
+  </Snackbar>
+);
@@ -6,15 +6,25 @@ import { DETAIL_SAVE_DELAY, HISTORY_RETENTION_DAYS } from "../lib/constants";
 import { normalizeHttpUrl } from "../lib/url";
 import { useErrorMessage } from "./useErrorMessage";
 import {
+  captureNow,
   clearClipboardHistory,
+  copyImageItemToClipboard,
+  copyItemToClipboard,
   deleteClipboardItem,
+  formatItem,
   loadClipboardHistory,
+  markAllSeen,
   markClipboardSkip,
   setClipboardItemPinned,
+  takePendingFocusItem,
   updateClipboardItemText,
   upsertClipboardItem,
 } from "../tauri/clipboardCommands";
-import { openSettingsWindow as openSettingsWindowCommand } from "../tauri/settingsCommands";
+import {
+  getHistorySort,
+  getPinnedSort,
+  openSettingsWindow as openSettingsWindowCommand,
+} from "../tauri/settingsCommands";
 
 /**
  * @typedef {import("../lib/types.js").ClipboardItem} ClipboardItem
@@ -44,11 +54,21 @@ export const useClipboardController = (options = {}) => {
   const [isConfirmOpen, setIsConfirmOpen] = useState(false);
   // 控制复制成功提示的显示状态，避免频繁复制时提示残留
   const [isCopyToastOpen, setIsCopyToastOpen] = useState(false);
+  // 最近一次敏感路径提醒（risky-capture 广播的内容），为 null 时不显示
+  const [riskyCapture, setRiskyCapture] = useState(null);
+  // 固定区排序方式，需与后端排序保持一致，设置页修改后通过广播同步
+  const [pinnedSort, setPinnedSort] = useState("updated_at");
+  // 整个列表的排序方式（按时间 / 手动），同样通过设置页广播同步
+  const [historySort, setHistorySort] = useState("recent");
+  // 条目链接定位请求的计数，每次收到请求递增以触发定位检查
+  const [focusRequest, setFocusRequest] = useState(0);
 
   // 缓存详情编辑的保存计划，避免频繁写入数据库
   const detailSaveTimerRef = useRef(/** @type {ReturnType<typeof setTimeout> | null} */ (null));
   // 保存最新待提交的详情编辑内容，确保定时器触发时可获取最新值
   const pendingDetailRef = useRef({ id: "", text: "" });
+  // 通过条目链接请求定位、但尚未出现在列表中的条目 id
+  const pendingFocusIdRef = useRef("");
 
   // 创建一条标准化的历史记录结构（用于前端乐观更新）
   const buildItem = useCallback((text) => {
@@ -60,6 +80,10 @@ export const useClipboardController = (options = {}) => {
       updatedAt: now.toISOString(),
       pinned: false,
       count: 1,
+      position: null,
+      pinnedAt: null,
+      script: null,
+      contentType: null,
     };
   }, []);
 
@@ -91,7 +115,12 @@ export const useClipboardController = (options = {}) => {
           if (item.id === nextItem.id) {
             return false;
           }
-          if (item.text === nextItem.text && item.id !== nextItem.id) {
+          // 图片条目的 text 只是尺寸说明，同尺寸的不同图片不能按 text 去重
+          if (
+            nextItem.kind !== "image" &&
+            item.text === nextItem.text &&
+            item.id !== nextItem.id
+          ) {
             return false;
           }
           return true;
@@ -227,9 +256,13 @@ export const useClipboardController = (options = {}) => {
       }
       await runAction(
         async () => {
-          await writeText(item.text, { label: "pure-paster" });
-          await markClipboardSkip(item.text);
-          await upsertItem(item.text);
+          // 由后端写入剪贴板：图片条目写回原图；其余条目由后端按设置添加前缀 / 后缀，历史中仍按原文计数
+          if (item.kind === "image") {
+            await copyImageItemToClipboard(item.id);
+          } else {
+            await copyItemToClipboard(item.id);
+            await upsertItem(item.text);
+          }
           setIsCopyToastOpen(true);
         },
         undefined,
@@ -241,6 +274,37 @@ export const useClipboardController = (options = {}) => {
     [runAction, upsertItem],
   );
 
+  // 将 JSON/XML 条目美化后写入系统剪贴板：美化结果作为新内容记入历史，原条目保持不变
+  const handleCopyFormatted = useCallback(
+    async (item) => {
+      if (!item?.contentType) {
+        return;
+      }
+      await runAction(
+        async () => {
+          const formatted = await formatItem(item.id, item.contentType);
+          await writeText(formatted, { label: "pure-paster" });
+          await markClipboardSkip(formatted);
+          await upsertItem(formatted);
+          setIsCopyToastOpen(true);
+        },
+        undefined,
+        // 同 handleCopy：落库错误由 upsertItem 自行处理，这里不主动清空
+        { clearOnSuccess: false },
+      );
+    },
+    [runAction, upsertItem],
+  );
+
+  // 立即记录当前剪贴板内容（监听关闭时同样可用），记录后选中该条目
+  const handleCaptureNow = useCallback(async () => {
+    const persisted = await runAction(() => captureNow());
+    if (persisted) {
+      applyPersistedItem(persisted, null);
+      setSelectedId(persisted.id);
+    }
+  }, [applyPersistedItem, runAction]);
+
   // 手动将输入框内容写入系统剪贴板，并同步到历史记录
   const handleWrite = useCallback(async () => {
     const trimmed = draft.trim();
@@ -322,7 +386,13 @@ export const useClipboardController = (options = {}) => {
       const nextPinned = !item.pinned;
       setItems((prev) =>
         prev.map((entry) =>
-          entry.id === item.id ? { ...entry, pinned: nextPinned } : entry,
+          entry.id === item.id
+            ? {
+                ...entry,
+                pinned: nextPinned,
+                pinnedAt: nextPinned ? new Date().toISOString() : null,
+              }
+            : entry,
         ),
       );
       const persisted = await runAction(() =>
@@ -350,7 +420,7 @@ export const useClipboardController = (options = {}) => {
     [runAction],
   );
 
-  // 根据搜索词过滤并排序：固定条目优先，其次按更新时间倒序
+  // 根据搜索词过滤并排序：固定条目优先（按手动位置排列），其次按更新时间倒序
   const visibleItems = useMemo(() => {
     const keyword = query.trim().toLowerCase();
     const filtered = keyword
@@ -360,9 +430,29 @@ export const useClipboardController = (options = {}) => {
       if (a.pinned !== b.pinned) {
         return a.pinned ? -1 : 1;
       }
+      // 与后端排序保持一致：设置过位置的条目在前，并按位置升序排列；非固定条目的位置只在手动排序模式下生效
+      if (a.pinned || historySort === "manual") {
+        const aHasPosition = typeof a.position === "number";
+        const bHasPosition = typeof b.position === "number";
+        if (aHasPosition !== bHasPosition) {
+          return aHasPosition ? -1 : 1;
+        }
+        if (aHasPosition && a.position !== b.position) {
+          return a.position - b.position;
+        }
+      }
+      if (a.pinned) {
+        // 按固定时间排序时，未记录固定时间的旧数据排在后面，再按更新时间兜底
+        if (pinnedSort === "pinned_at" && a.pinnedAt !== b.pinnedAt) {
+          if (!a.pinnedAt || !b.pinnedAt) {
+            return a.pinnedAt ? -1 : 1;
+          }
+          return new Date(b.pinnedAt).getTime() - new Date(a.pinnedAt).getTime();
+        }
+      }
       return new Date(b.updatedAt).getTime() - new Date(a.updatedAt).getTime();
     });
-  }, [items, query]);
+  }, [historySort, items, pinnedSort, query]);
 
   // 当列表变化时自动校准当前选中项
   useEffect(() => {
@@ -375,6 +465,16 @@ export const useClipboardController = (options = {}) => {
     }
   }, [visibleItems, selectedId]);
 
+  // 条目链接请求定位的条目出现在列表中后再选中（冷启动时历史可能尚未加载完成）；
+  // 放在校准选中项之后，保证同一轮更新中以定位目标为准
+  useEffect(() => {
+    const targetId = pendingFocusIdRef.current;
+    if (targetId && visibleItems.some((item) => item.id === targetId)) {
+      pendingFocusIdRef.current = "";
+      setSelectedId(targetId);
+    }
+  }, [focusRequest, visibleItems]);
+
   const selectedItem = visibleItems.find((item) => item.id === selectedId) ?? null;
   // 仅当选中条目是完整链接时返回标准化地址，便于控制按钮状态
   const selectedItemUrl = selectedItem ? normalizeHttpUrl(selectedItem.text) : null;
@@ -414,6 +514,193 @@ export const useClipboardController = (options = {}) => {
     };
   }, [applyPersistedItem, runAction]);
 
+  // 高频捕获时后端按批次广播新记录（按捕获先后排列），逐条应用即可保持与单条广播相同的顺序
+  useEffect(() => {
+    let unlisten = null;
+    const registerListener = async () => {
+      const stop = await runAction(() =>
+        listen("clipboard-updated-batch", (event) => {
+          const items = event.payload?.items;
+          if (!Array.isArray(items)) {
+            return;
+          }
+          items.forEach((item) => applyPersistedItem(item, null));
+        }),
+      );
+      if (typeof stop === "function") {
+        unlisten = stop;
+      }
+    };
+    registerListener();
+    return () => {
+      if (unlisten) {
+        unlisten();
+      }
+    };
+  }, [applyPersistedItem, runAction]);
+
+  // 后端按设置自动取消固定后会逐条广播更新后的条目，这里替换本地记录使其离开固定区
+  useEffect(() => {
+    let unlisten = null;
+    const registerListener = async () => {
+      const stop = await runAction(() =>
+        listen("clipboard-pinned", (event) => {
+          if (!event.payload?.id) {
+            return;
+          }
+          applyPersistedItem(event.payload, null);
+        }),
+      );
+      if (typeof stop === "function") {
+        unlisten = stop;
+      }
+    };
+    registerListener();
+    return () => {
+      if (unlisten) {
+        unlisten();
+      }
+    };
+  }, [applyPersistedItem, runAction]);
+
+  // 后台捕获到指向敏感文件的路径时提醒用户；条目本身（若已写入）仍通过 clipboard-updated 同步
+  useEffect(() => {
+    let unlisten = null;
+    const registerListener = async () => {
+      const stop = await runAction(() =>
+        listen("risky-capture", (event) => {
+          if (!event.payload?.reason) {
+            return;
+          }
+          setRiskyCapture(event.payload);
+        }),
+      );
+      if (typeof stop === "function") {
+        unlisten = stop;
+      }
+    };
+    registerListener();
+    return () => {
+      if (unlisten) {
+        unlisten();
+      }
+    };
+  }, [runAction]);
+
+  // 通过条目链接（pure-paste://item/<id>）唤起时定位到对应条目：清空搜索避免目标被过滤掉。
+  // 冷启动时链接早于监听注册到达，因此先领取一次后端暂存的待定位条目
+  useEffect(() => {
+    const focusItem = (id) => {
+      if (typeof id !== "string" || !id) {
+        return;
+      }
+      setQuery("");
+      pendingFocusIdRef.current = id;
+      setFocusRequest((prev) => prev + 1);
+    };
+    runAction(() => takePendingFocusItem()).then(focusItem);
+    let unlisten = null;
+    const registerListener = async () => {
+      const stop = await runAction(() =>
+        listen("focus-item", (event) => {
+          focusItem(event.payload);
+        }),
+      );
+      if (typeof stop === "function") {
+        unlisten = stop;
+      }
+    };
+    registerListener();
+    return () => {
+      if (unlisten) {
+        unlisten();
+      }
+    };
+  }, [runAction]);
+
+  // 主窗口获得焦点即视为用户已查看历史，标记全部已读以清零未读计数；
+  // 主窗口启动时是隐藏的，因此只在 focus 事件中标记，不在挂载时标记
+  useEffect(() => {
+    const handleFocus = () => {
+      runAction(() => markAllSeen(), undefined, { clearOnSuccess: false });
+    };
+    window.addEventListener("focus", handleFocus);
+    return () => {
+      window.removeEventListener("focus", handleFocus);
+    };
+  }, [runAction]);
+
+  // 读取列表与固定区的排序方式，并监听设置页的修改广播，保证前端排序与后端一致
+  useEffect(() => {
+    const sortSettings = [
+      { load: getHistorySort, event: "history-sort-changed", apply: setHistorySort },
+      { load: getPinnedSort, event: "pinned-sort-changed", apply: setPinnedSort },
+    ];
+    const unlisteners = [];
+    let disposed = false;
+    sortSettings.forEach(({ load, event: eventName, apply }) => {
+      runAction(() => load()).then((mode) => {
+        if (typeof mode === "string") {
+          apply(mode);
+        }
+      });
+      runAction(() =>
+        listen(eventName, (event) => {
+          if (typeof event.payload === "string") {
+            apply(event.payload);
+          }
+        }),
+      ).then((stop) => {
+        if (typeof stop !== "function") {
+          return;
+        }
+        if (disposed) {
+          stop();
+        } else {
+          unlisteners.push(stop);
+        }
+      });
+    });
+    return () => {
+      disposed = true;
+      unlisteners.forEach((stop) => stop());
+    };
+  }, [runAction]);
+
+  // 后端按保留天数清理条目或删除到期条目后会广播被删除的 id，这里同步移除，
+  // 避免列表残留已不存在的数据
+  useEffect(() => {
+    const unlisteners = [];
+    let disposed = false;
+    const handleRemoved = (event) => {
+      const ids = event.payload?.ids;
+      if (!Array.isArray(ids) || !ids.length) {
+        return;
+      }
+      const removed = new Set(ids);
+      setItems((prev) => prev.filter((item) => !removed.has(item.id)));
+      if (removed.has(pendingDetailRef.current.id)) {
+        pendingDetailRef.current = { id: "", text: "" };
+      }
+    };
+    ["clipboard-pruned", "clipboard-expired"].forEach((eventName) => {
+      runAction(() => listen(eventName, handleRemoved)).then((stop) => {
+        if (typeof stop !== "function") {
+          return;
+        }
+        if (disposed) {
+          stop();
+        } else {
+          unlisteners.push(stop);
+        }
+      });
+    });
+    return () => {
+      disposed = true;
+      unlisteners.forEach((stop) => stop());
+    };
+  }, [runAction]);
+
   return {
     errorMessage,
     items,
@@ -430,6 +717,8 @@ export const useClipboardController = (options = {}) => {
     isConfirmOpen,
     isCopyToastOpen,
     setIsCopyToastOpen,
+    riskyCapture,
+    setRiskyCapture,
     requestClear,
     cancelClear,
     confirmClear,
@@ -437,6 +726,8 @@ export const useClipboardController = (options = {}) => {
     handleDetailChange,
     flushDetailPersist,
     handleCopy,
+    handleCopyFormatted,
+    handleCaptureNow,
     handleWrite,
     openSettingsWindow,
     handleOpenLink,
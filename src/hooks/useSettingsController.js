@@ -1,12 +1,68 @@
 import { useCallback, useEffect, useMemo, useState } from "react";
+import { listen } from "@tauri-apps/api/event";
 import { buildShortcutFromEvent } from "../lib/shortcut";
 import { useErrorMessage } from "./useErrorMessage";
 import { getClipboardMonitoring, setClipboardMonitoring } from "../tauri/clipboardCommands";
 import {
+  exportSettings,
+  getAutoUnpinDays,
+  getItemTtlMinutes,
   getAutostartStatus,
+  getCaptureBurstThreshold,
+  getBroadcastBatchThreshold,
+  getPollInterval,
+  getCaptureFilter,
+  getClipboardAutoClear,
+  getHistorySort,
+  getIgnoreOwnWindowCopies,
+  getMergeTextWinner,
   getOpenWindowShortcut,
+  getToggleMonitoringShortcut,
+  getPasteWrapper,
+  getPauseOnLock,
+  getPinnedSort,
+  getResetDedupOnEmpty,
+  getSanitizeOnCapture,
+  getSensitivePathPatterns,
+  getExclusionPatterns,
+  getSourceBlocklist,
+  getBlockRiskyCaptures,
+  getShortcutTogglesWindow,
+  getRememberWindowPlacement,
+  getStorageLocation,
+  getTrayLeftClick,
+  getTrayMenu,
+  getTrayRecentCount,
+  importSettings,
+  moveDb,
+  setAutoUnpinDays,
+  setItemTtlMinutes,
   setAutostartEnabled,
+  setCaptureBurstThreshold,
+  setBroadcastBatchThreshold,
+  setPollInterval,
+  setCaptureFilter,
+  setClipboardAutoClear,
+  setHistorySort,
+  setIgnoreOwnWindowCopies,
+  setMergeTextWinner,
   setOpenWindowShortcut,
+  setToggleMonitoringShortcut,
+  setPasteWrapper,
+  setPauseOnLock,
+  setPinnedSort,
+  setResetDedupOnEmpty,
+  setSanitizeOnCapture,
+  setSensitivePathPatterns,
+  setExclusionPatterns,
+  setSourceBlocklist,
+  setBlockRiskyCaptures,
+  setShortcutTogglesWindow,
+  setRememberWindowPlacement,
+  setTrayLeftClick,
+  setTrayMenu,
+  setTrayRecentCount,
+  validateShortcut,
 } from "../tauri/settingsCommands";
 
 // 设置窗口 controller：只聚焦“设置项状态 + 与系统/后端同步”，让视图组件保持纯 UI 拼装。
@@ -22,16 +78,140 @@ export const useSettingsController = () => {
   const [autostartEnabled, setAutostartEnabledState] = useState(false);
   // 记录开机自启动读取/切换过程，避免频繁点击导致状态错乱
   const [isAutostartLoading, setIsAutostartLoading] = useState(false);
+  // 后台捕获过滤方式，默认记录全部内容
+  const [captureFilterMode, setCaptureFilterModeState] = useState("all");
+  // 自定义过滤正则的草稿，切换到自定义模式后点击保存才会提交
+  const [captureFilterPattern, setCaptureFilterPattern] = useState("");
+  // 捕获过滤设置读取/保存过程状态，避免重复提交
+  const [isCaptureFilterLoading, setIsCaptureFilterLoading] = useState(false);
+  // 已保存的粘贴前缀 / 后缀，用于判断草稿是否有改动
+  const [pasteWrapper, setPasteWrapperState] = useState({ prefix: "", suffix: "" });
+  // 正在编辑的粘贴前缀 / 后缀草稿
+  const [pasteWrapperDraft, setPasteWrapperDraft] = useState({ prefix: "", suffix: "" });
+  // 粘贴前缀 / 后缀读取/保存过程状态，避免重复提交
+  const [isPasteWrapperLoading, setIsPasteWrapperLoading] = useState(false);
+  // 锁屏期间是否自动暂停记录，默认开启
+  const [pauseOnLock, setPauseOnLockState] = useState(true);
+  // 锁屏暂停设置读取/保存过程状态，避免重复提交
+  const [isPauseOnLockLoading, setIsPauseOnLockLoading] = useState(false);
+  // 剪贴板被清空后是否重新记录之前出现过的内容，默认开启
+  const [resetDedupOnEmpty, setResetDedupOnEmptyState] = useState(true);
+  // 清空重置设置读取/保存过程状态，避免重复提交
+  const [isResetDedupOnEmptyLoading, setIsResetDedupOnEmptyLoading] = useState(false);
+  // 是否忽略在应用自身窗口内复制的内容，默认开启
+  const [ignoreOwnWindowCopies, setIgnoreOwnWindowCopiesState] = useState(true);
+  // 应用内复制设置读取/保存过程状态，避免重复提交
+  const [isIgnoreOwnWindowCopiesLoading, setIsIgnoreOwnWindowCopiesLoading] = useState(false);
+  // 捕获时是否清理排版字符，默认关闭
+  const [sanitizeOnCapture, setSanitizeOnCaptureState] = useState(false);
+  // 字符清理设置读取/保存过程状态，避免重复提交
+  const [isSanitizeOnCaptureLoading, setIsSanitizeOnCaptureLoading] = useState(false);
+  // 敏感路径规则草稿，每行一条正则，保存前不影响后台检查
+  const [sensitivePathPatternsDraft, setSensitivePathPatternsDraft] = useState("");
+  // 敏感路径规则读取/保存过程状态，避免重复提交
+  const [isSensitivePathPatternsLoading, setIsSensitivePathPatternsLoading] = useState(false);
+  // 排除规则草稿，每行一条正则，保存前不影响后台记录
+  const [exclusionPatternsDraft, setExclusionPatternsDraft] = useState("");
+  // 排除规则读取/保存过程状态，避免重复提交
+  const [isExclusionPatternsLoading, setIsExclusionPatternsLoading] = useState(false);
+  // 屏蔽的来源应用草稿，每行一个应用标识，保存前不影响后台记录
+  const [sourceBlocklistDraft, setSourceBlocklistDraft] = useState("");
+  // 屏蔽应用列表读取/保存过程状态，避免重复提交
+  const [isSourceBlocklistLoading, setIsSourceBlocklistLoading] = useState(false);
+  // 是否拦截指向敏感文件的路径，默认只提醒不拦截
+  const [blockRiskyCaptures, setBlockRiskyCapturesState] = useState(false);
+  // 拦截设置读取/保存过程状态，避免重复提交
+  const [isBlockRiskyCapturesLoading, setIsBlockRiskyCapturesLoading] = useState(false);
   // 打开剪贴板窗口的快捷键配置，供设置页展示和编辑
   const [openWindowShortcut, setOpenWindowShortcutState] = useState("");
   // 设置页正在编辑的快捷键草稿，避免输入中覆盖已保存值
   const [shortcutDraft, setShortcutDraft] = useState("");
   // 快捷键配置加载状态，避免重复点击导致状态错乱
   const [isShortcutLoading, setIsShortcutLoading] = useState(false);
+  // 快捷键是否在显示 / 隐藏主窗口之间切换，默认只负责唤起
+  const [shortcutTogglesWindow, setShortcutTogglesWindowState] = useState(false);
+  // 快捷键切换设置读取/保存过程状态，避免重复提交
+  const [isShortcutTogglesWindowLoading, setIsShortcutTogglesWindowLoading] = useState(false);
+  // 是否按显示器布局分别记住主窗口位置，默认开启
+  const [rememberWindowPlacement, setRememberWindowPlacementState] = useState(true);
+  // 窗口位置设置读取/保存过程状态，避免重复提交
+  const [isRememberWindowPlacementLoading, setIsRememberWindowPlacementLoading] = useState(false);
   // 快捷键保存过程状态，用于按钮禁用与文案反馈
   const [isShortcutSaving, setIsShortcutSaving] = useState(false);
   // 是否处于快捷键录制模式，录制时拦截下一次按键组合
   const [isShortcutRecording, setIsShortcutRecording] = useState(false);
+  // 快捷键草稿的校验错误，输入时即时提示，为空表示写法有效
+  const [shortcutError, setShortcutError] = useState("");
+  // 切换剪贴板监听的快捷键配置，未设置时为空字符串
+  const [toggleMonitoringShortcut, setToggleMonitoringShortcutState] = useState("");
+  // 切换监听快捷键的编辑草稿，保存前不影响已注册的快捷键
+  const [toggleShortcutDraft, setToggleShortcutDraft] = useState("");
+  // 切换监听快捷键读取/保存过程状态，避免重复提交
+  const [isToggleShortcutLoading, setIsToggleShortcutLoading] = useState(false);
+  // 是否正在为切换监听快捷键录制按键组合
+  const [isToggleShortcutRecording, setIsToggleShortcutRecording] = useState(false);
+  // 切换监听快捷键草稿的校验错误，为空表示写法有效
+  const [toggleShortcutError, setToggleShortcutError] = useState("");
+  // 复制后自动清空剪贴板的延迟秒数，0 表示不自动清空
+  const [clipboardAutoClear, setClipboardAutoClearState] = useState(0);
+  // 自动清空设置读取/保存过程状态，避免重复提交
+  const [isClipboardAutoClearLoading, setIsClipboardAutoClearLoading] = useState(false);
+  // 固定条目超过多少天自动取消固定，0 表示不自动取消
+  const [autoUnpinDays, setAutoUnpinDaysState] = useState(0);
+  // 自动取消固定设置读取/保存过程状态，避免重复提交
+  const [isAutoUnpinDaysLoading, setIsAutoUnpinDaysLoading] = useState(false);
+  // 新捕获条目默认存活的分钟数，0 表示不过期
+  const [itemTtlMinutes, setItemTtlMinutesState] = useState(0);
+  // 条目存活时间设置读取/保存过程状态，避免重复提交
+  const [isItemTtlMinutesLoading, setIsItemTtlMinutesLoading] = useState(false);
+  // 同一内容 1 分钟内最多累加的次数，0 表示不限制
+  const [captureBurstThreshold, setCaptureBurstThresholdState] = useState(0);
+  // 高频捕获上限读取/保存过程状态，避免重复提交
+  const [isCaptureBurstThresholdLoading, setIsCaptureBurstThresholdLoading] = useState(false);
+  // 10 秒内新记录超过该数量后合并更新列表，0 表示逐条更新
+  const [broadcastBatchThreshold, setBroadcastBatchThresholdState] = useState(0);
+  // 合并更新阈值读取/保存过程状态，避免重复提交
+  const [isBroadcastBatchThresholdLoading, setIsBroadcastBatchThresholdLoading] = useState(false);
+  // 后台轮询剪贴板的间隔（毫秒），读取完成前为 null
+  const [pollInterval, setPollIntervalState] = useState(null);
+  // 轮询间隔读取/保存过程状态，避免重复提交
+  const [isPollIntervalLoading, setIsPollIntervalLoading] = useState(false);
+  // 数据存放位置，仅用于展示，读取完成前为 null
+  const [storageLocation, setStorageLocation] = useState(null);
+  // 迁移数据库的目标路径草稿
+  const [dbMoveDraft, setDbMoveDraft] = useState("");
+  // 数据库迁移进行中，避免重复提交
+  const [isDbMoving, setIsDbMoving] = useState(false);
+  // 整个列表的排序方式，默认按最近复制时间
+  const [historySort, setHistorySortState] = useState("recent");
+  // 列表排序方式读取/保存过程状态，避免重复提交
+  const [isHistorySortLoading, setIsHistorySortLoading] = useState(false);
+  // 固定区排序方式，默认按最近复制时间
+  const [pinnedSort, setPinnedSortState] = useState("updated_at");
+  // 固定区排序方式读取/保存过程状态，避免重复提交
+  const [isPinnedSortLoading, setIsPinnedSortLoading] = useState(false);
+  // 编辑后与已有条目重复时保留哪一条，默认保留已有条目
+  const [mergeTextWinner, setMergeTextWinnerState] = useState("existing");
+  // 合并设置读取/保存过程状态，避免重复提交
+  const [isMergeTextWinnerLoading, setIsMergeTextWinnerLoading] = useState(false);
+  // 左键点击托盘图标时的动作，默认打开主窗口
+  const [trayLeftClick, setTrayLeftClickState] = useState("show_window");
+  // 托盘左键动作读取/保存过程状态，避免重复提交
+  const [isTrayLeftClickLoading, setIsTrayLeftClickLoading] = useState(false);
+  // 托盘菜单显示的操作项（按显示顺序）
+  const [trayMenu, setTrayMenuState] = useState(["show", "settings", "quit"]);
+  // 托盘菜单设置读取/保存过程状态，避免重复提交
+  const [isTrayMenuLoading, setIsTrayMenuLoading] = useState(false);
+  // 托盘“最近复制”子菜单显示的条目数，0 表示不显示
+  const [trayRecentCount, setTrayRecentCountState] = useState(8);
+  // 托盘最近条目数读取/保存过程状态，避免重复提交
+  const [isTrayRecentCountLoading, setIsTrayRecentCountLoading] = useState(false);
+  // 导入 / 导出配置使用的 JSON 文本
+  const [settingsJsonDraft, setSettingsJsonDraft] = useState("");
+  // 导入 / 导出配置进行中，避免重复提交
+  const [isSettingsTransferring, setIsSettingsTransferring] = useState(false);
+  // 最近一次导入的结果说明，未导入过时为空
+  const [settingsImportSummary, setSettingsImportSummary] = useState("");
 
   const shortcutDisplay = useMemo(
     () => openWindowShortcut || "未设置",
@@ -41,6 +221,7 @@ export const useSettingsController = () => {
     () => shortcutDraft.trim() !== openWindowShortcut,
     [openWindowShortcut, shortcutDraft],
   );
+  const toggleShortcutDirty = toggleShortcutDraft.trim() !== toggleMonitoringShortcut;
 
   // 仅加载监听状态，供设置窗口初始化使用
   const loadMonitoringStatus = useCallback(async () => {
@@ -61,6 +242,89 @@ export const useSettingsController = () => {
     setIsAutostartLoading(false);
   }, [runAction]);
 
+  // 以后端返回的捕获过滤设置为准刷新本地状态
+  const applyCaptureFilter = useCallback((settings) => {
+    if (typeof settings?.mode !== "string") {
+      return;
+    }
+    setCaptureFilterModeState(settings.mode);
+    setCaptureFilterPattern(settings.pattern ?? "");
+  }, []);
+
+  // 读取捕获过滤设置，供设置页初始化展示
+  const loadCaptureFilter = useCallback(async () => {
+    setIsCaptureFilterLoading(true);
+    applyCaptureFilter(await runAction(() => getCaptureFilter()));
+    setIsCaptureFilterLoading(false);
+  }, [applyCaptureFilter, runAction]);
+
+  // 提交捕获过滤设置，正则原文一并保存，便于切换回自定义模式时无需重新输入
+  const saveCaptureFilter = useCallback(
+    async (mode, pattern) => {
+      setIsCaptureFilterLoading(true);
+      applyCaptureFilter(await runAction(() => setCaptureFilter(mode, pattern || null)));
+      setIsCaptureFilterLoading(false);
+    },
+    [applyCaptureFilter, runAction],
+  );
+
+  // 切换捕获过滤方式：自定义正则需要先填写表达式，由保存按钮提交，其余方式立即生效
+  const handleCaptureFilterModeChange = useCallback(
+    (event) => {
+      const targetMode = event.target.value;
+      setCaptureFilterModeState(targetMode);
+      if (targetMode !== "custom_regex") {
+        saveCaptureFilter(targetMode, captureFilterPattern);
+      }
+    },
+    [captureFilterPattern, saveCaptureFilter],
+  );
+
+  // 保存自定义过滤正则，正则无效时由后端返回错误提示
+  const handleCaptureFilterPatternSave = useCallback(() => {
+    saveCaptureFilter("custom_regex", captureFilterPattern);
+  }, [captureFilterPattern, saveCaptureFilter]);
+
+  // 以后端返回的前缀 / 后缀为准，同时重置草稿
+  const applyPasteWrapper = useCallback((wrapper) => {
+    if (typeof wrapper?.prefix !== "string" || typeof wrapper?.suffix !== "string") {
+      return;
+    }
+    setPasteWrapperState(wrapper);
+    setPasteWrapperDraft(wrapper);
+  }, []);
+
+  // 读取粘贴前缀 / 后缀设置，供设置页初始化展示
+  const loadPasteWrapper = useCallback(async () => {
+    setIsPasteWrapperLoading(true);
+    applyPasteWrapper(await runAction(() => getPasteWrapper()));
+    setIsPasteWrapperLoading(false);
+  }, [applyPasteWrapper, runAction]);
+
+  // 保存粘贴前缀 / 后缀，保留输入中的空格与换行
+  const handlePasteWrapperSave = useCallback(async () => {
+    setIsPasteWrapperLoading(true);
+    applyPasteWrapper(
+      await runAction(() => setPasteWrapper(pasteWrapperDraft.prefix, pasteWrapperDraft.suffix)),
+    );
+    setIsPasteWrapperLoading(false);
+  }, [applyPasteWrapper, pasteWrapperDraft, runAction]);
+
+  // 草稿与已保存的值不同时才允许保存
+  const pasteWrapperDirty =
+    pasteWrapperDraft.prefix !== pasteWrapper.prefix ||
+    pasteWrapperDraft.suffix !== pasteWrapper.suffix;
+
+  // 读取锁屏暂停设置，供设置页初始化展示
+  const loadPauseOnLock = useCallback(async () => {
+    setIsPauseOnLockLoading(true);
+    const enabled = await runAction(() => getPauseOnLock());
+    if (typeof enabled === "boolean") {
+      setPauseOnLockState(enabled);
+    }
+    setIsPauseOnLockLoading(false);
+  }, [runAction]);
+
   // 读取打开剪贴板窗口的快捷键设置，供设置页初始化展示
   const loadOpenWindowShortcut = useCallback(async () => {
     setIsShortcutLoading(true);
@@ -100,6 +364,366 @@ export const useSettingsController = () => {
     setIsShortcutSaving(false);
   }, [runAction]);
 
+  // 读取切换剪贴板监听的快捷键设置，供设置页初始化展示
+  const loadToggleMonitoringShortcut = useCallback(async () => {
+    setIsToggleShortcutLoading(true);
+    const shortcut = await runAction(() => getToggleMonitoringShortcut());
+    if (shortcut !== undefined) {
+      const value = shortcut ? String(shortcut) : "";
+      setToggleMonitoringShortcutState(value);
+      setToggleShortcutDraft(value);
+    }
+    setIsToggleShortcutLoading(false);
+  }, [runAction]);
+
+  // 保存或清空切换监听快捷键（传入空字符串表示清空），与已有快捷键冲突时由后端拒绝
+  const saveToggleMonitoringShortcut = useCallback(
+    async (draft) => {
+      const normalized = draft.trim();
+      setIsToggleShortcutLoading(true);
+      const saved = await runAction(() =>
+        setToggleMonitoringShortcut(normalized ? normalized : null),
+      );
+      if (saved !== undefined) {
+        const value = saved ? String(saved) : "";
+        setToggleMonitoringShortcutState(value);
+        setToggleShortcutDraft(value);
+      }
+      setIsToggleShortcutLoading(false);
+    },
+    [runAction],
+  );
+
+  const handleToggleShortcutSave = useCallback(
+    () => saveToggleMonitoringShortcut(toggleShortcutDraft),
+    [saveToggleMonitoringShortcut, toggleShortcutDraft],
+  );
+
+  const handleToggleShortcutClear = useCallback(
+    () => saveToggleMonitoringShortcut(""),
+    [saveToggleMonitoringShortcut],
+  );
+
+  // 读取复制后自动清空的延迟，供设置页初始化展示
+  const loadClipboardAutoClear = useCallback(async () => {
+    setIsClipboardAutoClearLoading(true);
+    const seconds = await runAction(() => getClipboardAutoClear());
+    if (seconds !== undefined) {
+      setClipboardAutoClearState(typeof seconds === "number" ? seconds : 0);
+    }
+    setIsClipboardAutoClearLoading(false);
+  }, [runAction]);
+
+  // 切换复制后自动清空的延迟，选择 0 时关闭
+  const handleClipboardAutoClearChange = useCallback(
+    async (event) => {
+      const targetSeconds = Number(event.target.value);
+      setIsClipboardAutoClearLoading(true);
+      const saved = await runAction(() =>
+        setClipboardAutoClear(targetSeconds > 0 ? targetSeconds : null),
+      );
+      if (saved !== undefined) {
+        setClipboardAutoClearState(typeof saved === "number" ? saved : 0);
+      }
+      setIsClipboardAutoClearLoading(false);
+    },
+    [runAction],
+  );
+
+  // 读取自动取消固定的天数，供设置页初始化展示
+  const loadAutoUnpinDays = useCallback(async () => {
+    setIsAutoUnpinDaysLoading(true);
+    const days = await runAction(() => getAutoUnpinDays());
+    if (days !== undefined) {
+      setAutoUnpinDaysState(typeof days === "number" ? days : 0);
+    }
+    setIsAutoUnpinDaysLoading(false);
+  }, [runAction]);
+
+  // 切换自动取消固定的天数，选择 0 时不自动取消
+  const handleAutoUnpinDaysChange = useCallback(
+    async (event) => {
+      const targetDays = Number(event.target.value);
+      setIsAutoUnpinDaysLoading(true);
+      const saved = await runAction(() => setAutoUnpinDays(targetDays > 0 ? targetDays : null));
+      if (saved !== undefined) {
+        setAutoUnpinDaysState(typeof saved === "number" ? saved : 0);
+      }
+      setIsAutoUnpinDaysLoading(false);
+    },
+    [runAction],
+  );
+
+  // 读取条目默认存活的分钟数，供设置页初始化展示
+  const loadItemTtlMinutes = useCallback(async () => {
+    setIsItemTtlMinutesLoading(true);
+    const minutes = await runAction(() => getItemTtlMinutes());
+    if (minutes !== undefined) {
+      setItemTtlMinutesState(typeof minutes === "number" ? minutes : 0);
+    }
+    setIsItemTtlMinutesLoading(false);
+  }, [runAction]);
+
+  // 切换条目默认存活的分钟数，选择 0 时不过期
+  const handleItemTtlMinutesChange = useCallback(
+    async (event) => {
+      const targetMinutes = Number(event.target.value);
+      setIsItemTtlMinutesLoading(true);
+      const saved = await runAction(() =>
+        setItemTtlMinutes(targetMinutes > 0 ? targetMinutes : null),
+      );
+      if (saved !== undefined) {
+        setItemTtlMinutesState(typeof saved === "number" ? saved : 0);
+      }
+      setIsItemTtlMinutesLoading(false);
+    },
+    [runAction],
+  );
+
+  // 读取高频重复捕获的计数上限，供设置页初始化展示
+  const loadCaptureBurstThreshold = useCallback(async () => {
+    setIsCaptureBurstThresholdLoading(true);
+    const threshold = await runAction(() => getCaptureBurstThreshold());
+    if (threshold !== undefined) {
+      setCaptureBurstThresholdState(typeof threshold === "number" ? threshold : 0);
+    }
+    setIsCaptureBurstThresholdLoading(false);
+  }, [runAction]);
+
+  // 切换高频重复捕获的计数上限，选择 0 时不限制
+  const handleCaptureBurstThresholdChange = useCallback(
+    async (event) => {
+      const targetThreshold = Number(event.target.value);
+      setIsCaptureBurstThresholdLoading(true);
+      const saved = await runAction(() =>
+        setCaptureBurstThreshold(targetThreshold > 0 ? targetThreshold : null),
+      );
+      if (saved !== undefined) {
+        setCaptureBurstThresholdState(typeof saved === "number" ? saved : 0);
+      }
+      setIsCaptureBurstThresholdLoading(false);
+    },
+    [runAction],
+  );
+
+  // 读取合并更新的频率阈值，供设置页初始化展示
+  const loadBroadcastBatchThreshold = useCallback(async () => {
+    setIsBroadcastBatchThresholdLoading(true);
+    const threshold = await runAction(() => getBroadcastBatchThreshold());
+    if (threshold !== undefined) {
+      setBroadcastBatchThresholdState(typeof threshold === "number" ? threshold : 0);
+    }
+    setIsBroadcastBatchThresholdLoading(false);
+  }, [runAction]);
+
+  // 切换合并更新的频率阈值，选择 0 时逐条更新
+  const handleBroadcastBatchThresholdChange = useCallback(
+    async (event) => {
+      const targetThreshold = Number(event.target.value);
+      setIsBroadcastBatchThresholdLoading(true);
+      const saved = await runAction(() =>
+        setBroadcastBatchThreshold(targetThreshold > 0 ? targetThreshold : null),
+      );
+      if (saved !== undefined) {
+        setBroadcastBatchThresholdState(typeof saved === "number" ? saved : 0);
+      }
+      setIsBroadcastBatchThresholdLoading(false);
+    },
+    [runAction],
+  );
+
+  // 读取后台轮询间隔，供设置页初始化展示
+  const loadPollInterval = useCallback(async () => {
+    setIsPollIntervalLoading(true);
+    const interval = await runAction(() => getPollInterval());
+    if (typeof interval === "number") {
+      setPollIntervalState(interval);
+    }
+    setIsPollIntervalLoading(false);
+  }, [runAction]);
+
+  // 切换后台轮询间隔，保存后下一次轮询即生效
+  const handlePollIntervalChange = useCallback(
+    async (event) => {
+      const targetInterval = Number(event.target.value);
+      setIsPollIntervalLoading(true);
+      const saved = await runAction(() => setPollInterval(targetInterval));
+      if (typeof saved === "number") {
+        setPollIntervalState(saved);
+      }
+      setIsPollIntervalLoading(false);
+    },
+    [runAction],
+  );
+
+  // 读取数据存放位置，供设置页展示数据库所在目录
+  const loadStorageLocation = useCallback(async () => {
+    const location = await runAction(() => getStorageLocation());
+    if (location) {
+      setStorageLocation(location);
+    }
+  }, [runAction]);
+
+  // 迁移数据库到草稿中的路径，成功后刷新展示的存放位置并清空草稿
+  const handleMoveDb = useCallback(async () => {
+    const target = dbMoveDraft.trim();
+    if (!target) {
+      return;
+    }
+    setIsDbMoving(true);
+    const location = await runAction(() => moveDb(target));
+    if (location) {
+      setStorageLocation(location);
+      setDbMoveDraft("");
+    }
+    setIsDbMoving(false);
+  }, [dbMoveDraft, runAction]);
+
+  // 读取整个列表的排序方式，供设置页初始化展示
+  const loadHistorySort = useCallback(async () => {
+    setIsHistorySortLoading(true);
+    const mode = await runAction(() => getHistorySort());
+    if (typeof mode === "string") {
+      setHistorySortState(mode);
+    }
+    setIsHistorySortLoading(false);
+  }, [runAction]);
+
+  // 切换整个列表的排序方式，以后端返回的实际值为准
+  const handleHistorySortChange = useCallback(
+    async (event) => {
+      const targetMode = event.target.value;
+      setIsHistorySortLoading(true);
+      const saved = await runAction(() => setHistorySort(targetMode));
+      if (typeof saved === "string") {
+        setHistorySortState(saved);
+      }
+      setIsHistorySortLoading(false);
+    },
+    [runAction],
+  );
+
+  // 读取固定区排序方式，供设置页初始化展示
+  const loadPinnedSort = useCallback(async () => {
+    setIsPinnedSortLoading(true);
+    const mode = await runAction(() => getPinnedSort());
+    if (typeof mode === "string") {
+      setPinnedSortState(mode);
+    }
+    setIsPinnedSortLoading(false);
+  }, [runAction]);
+
+  // 切换固定区排序方式，以后端返回的实际值为准
+  const handlePinnedSortChange = useCallback(
+    async (event) => {
+      const targetMode = event.target.value;
+      setIsPinnedSortLoading(true);
+      const saved = await runAction(() => setPinnedSort(targetMode));
+      if (typeof saved === "string") {
+        setPinnedSortState(saved);
+      }
+      setIsPinnedSortLoading(false);
+    },
+    [runAction],
+  );
+
+  // 读取合并时保留哪一条的设置，供设置页初始化展示
+  const loadMergeTextWinner = useCallback(async () => {
+    setIsMergeTextWinnerLoading(true);
+    const winner = await runAction(() => getMergeTextWinner());
+    if (typeof winner === "string") {
+      setMergeTextWinnerState(winner);
+    }
+    setIsMergeTextWinnerLoading(false);
+  }, [runAction]);
+
+  // 切换合并时保留哪一条，以后端返回的实际值为准
+  const handleMergeTextWinnerChange = useCallback(
+    async (event) => {
+      const targetWinner = event.target.value;
+      setIsMergeTextWinnerLoading(true);
+      const saved = await runAction(() => setMergeTextWinner(targetWinner));
+      if (typeof saved === "string") {
+        setMergeTextWinnerState(saved);
+      }
+      setIsMergeTextWinnerLoading(false);
+    },
+    [runAction],
+  );
+
+  // 读取托盘左键动作设置，供设置页初始化展示
+  const loadTrayLeftClick = useCallback(async () => {
+    setIsTrayLeftClickLoading(true);
+    const action = await runAction(() => getTrayLeftClick());
+    if (typeof action === "string") {
+      setTrayLeftClickState(action);
+    }
+    setIsTrayLeftClickLoading(false);
+  }, [runAction]);
+
+  // 切换托盘左键动作，以后端返回的实际值为准
+  const handleTrayLeftClickChange = useCallback(
+    async (event) => {
+      const targetAction = event.target.value;
+      setIsTrayLeftClickLoading(true);
+      const saved = await runAction(() => setTrayLeftClick(targetAction));
+      if (typeof saved === "string") {
+        setTrayLeftClickState(saved);
+      }
+      setIsTrayLeftClickLoading(false);
+    },
+    [runAction],
+  );
+
+  // 读取托盘菜单配置，供设置页初始化展示
+  const loadTrayMenu = useCallback(async () => {
+    setIsTrayMenuLoading(true);
+    const actions = await runAction(() => getTrayMenu());
+    if (Array.isArray(actions)) {
+      setTrayMenuState(actions);
+    }
+    setIsTrayMenuLoading(false);
+  }, [runAction]);
+
+  // 显示或隐藏某个托盘菜单项：新显示的项排在退出之前，以后端返回的实际配置为准
+  const handleTrayMenuToggle = useCallback(
+    async (action, enabled) => {
+      const rest = trayMenu.filter((item) => item !== action && item !== "quit");
+      const targetActions = enabled ? [...rest, action, "quit"] : [...rest, "quit"];
+      setIsTrayMenuLoading(true);
+      const saved = await runAction(() => setTrayMenu(targetActions));
+      if (Array.isArray(saved)) {
+        setTrayMenuState(saved);
+      }
+      setIsTrayMenuLoading(false);
+    },
+    [runAction, trayMenu],
+  );
+
+  // 读取托盘“最近复制”子菜单显示的条目数，供设置页初始化展示
+  const loadTrayRecentCount = useCallback(async () => {
+    setIsTrayRecentCountLoading(true);
+    const count = await runAction(() => getTrayRecentCount());
+    if (typeof count === "number") {
+      setTrayRecentCountState(count);
+    }
+    setIsTrayRecentCountLoading(false);
+  }, [runAction]);
+
+  // 切换托盘“最近复制”子菜单显示的条目数，以后端返回的实际值为准
+  const handleTrayRecentCountChange = useCallback(
+    async (event) => {
+      const targetCount = Number(event.target.value);
+      setIsTrayRecentCountLoading(true);
+      const saved = await runAction(() => setTrayRecentCount(targetCount));
+      if (typeof saved === "number") {
+        setTrayRecentCountState(saved);
+      }
+      setIsTrayRecentCountLoading(false);
+    },
+    [runAction],
+  );
+
   // 切换开机自启动开关，失败时回滚到之前状态
   const handleAutostartToggle = useCallback(
     async (event) => {
@@ -118,6 +742,265 @@ export const useSettingsController = () => {
     [runAction],
   );
 
+  // 读取清空后重新记录的设置，供设置页初始化展示
+  const loadResetDedupOnEmpty = useCallback(async () => {
+    setIsResetDedupOnEmptyLoading(true);
+    const enabled = await runAction(() => getResetDedupOnEmpty());
+    if (typeof enabled === "boolean") {
+      setResetDedupOnEmptyState(enabled);
+    }
+    setIsResetDedupOnEmptyLoading(false);
+  }, [runAction]);
+
+  // 切换清空后重新记录的设置，失败时回滚开关状态
+  const handleResetDedupOnEmptyToggle = useCallback(
+    async (event) => {
+      const targetEnabled = event.target.checked;
+      setResetDedupOnEmptyState(targetEnabled);
+      setIsResetDedupOnEmptyLoading(true);
+      const saved = await runAction(() => setResetDedupOnEmpty(targetEnabled));
+      if (typeof saved === "boolean") {
+        setResetDedupOnEmptyState(saved);
+      } else {
+        setResetDedupOnEmptyState(!targetEnabled);
+      }
+      setIsResetDedupOnEmptyLoading(false);
+    },
+    [runAction],
+  );
+
+  // 读取忽略应用内复制的设置，供设置页初始化展示
+  const loadIgnoreOwnWindowCopies = useCallback(async () => {
+    setIsIgnoreOwnWindowCopiesLoading(true);
+    const enabled = await runAction(() => getIgnoreOwnWindowCopies());
+    if (typeof enabled === "boolean") {
+      setIgnoreOwnWindowCopiesState(enabled);
+    }
+    setIsIgnoreOwnWindowCopiesLoading(false);
+  }, [runAction]);
+
+  // 切换忽略应用内复制的设置，失败时回滚开关状态
+  const handleIgnoreOwnWindowCopiesToggle = useCallback(
+    async (event) => {
+      const targetEnabled = event.target.checked;
+      setIgnoreOwnWindowCopiesState(targetEnabled);
+      setIsIgnoreOwnWindowCopiesLoading(true);
+      const saved = await runAction(() => setIgnoreOwnWindowCopies(targetEnabled));
+      if (typeof saved === "boolean") {
+        setIgnoreOwnWindowCopiesState(saved);
+      } else {
+        setIgnoreOwnWindowCopiesState(!targetEnabled);
+      }
+      setIsIgnoreOwnWindowCopiesLoading(false);
+    },
+    [runAction],
+  );
+
+  // 读取捕获时清理排版字符的设置，供设置页初始化展示
+  const loadSanitizeOnCapture = useCallback(async () => {
+    setIsSanitizeOnCaptureLoading(true);
+    const enabled = await runAction(() => getSanitizeOnCapture());
+    if (typeof enabled === "boolean") {
+      setSanitizeOnCaptureState(enabled);
+    }
+    setIsSanitizeOnCaptureLoading(false);
+  }, [runAction]);
+
+  // 切换捕获时清理排版字符的设置，失败时回滚开关状态
+  const handleSanitizeOnCaptureToggle = useCallback(
+    async (event) => {
+      const targetEnabled = event.target.checked;
+      setSanitizeOnCaptureState(targetEnabled);
+      setIsSanitizeOnCaptureLoading(true);
+      const saved = await runAction(() => setSanitizeOnCapture(targetEnabled));
+      if (typeof saved === "boolean") {
+        setSanitizeOnCaptureState(saved);
+      } else {
+        setSanitizeOnCaptureState(!targetEnabled);
+      }
+      setIsSanitizeOnCaptureLoading(false);
+    },
+    [runAction],
+  );
+
+  // 把后端返回的规则列表写回草稿，每行一条
+  const applySensitivePathPatterns = useCallback((patterns) => {
+    if (Array.isArray(patterns)) {
+      setSensitivePathPatternsDraft(patterns.join("\n"));
+    }
+  }, []);
+
+  // 读取敏感路径规则，供设置页初始化展示
+  const loadSensitivePathPatterns = useCallback(async () => {
+    setIsSensitivePathPatternsLoading(true);
+    applySensitivePathPatterns(await runAction(() => getSensitivePathPatterns()));
+    setIsSensitivePathPatternsLoading(false);
+  }, [applySensitivePathPatterns, runAction]);
+
+  // 保存敏感路径规则，传入 null 时恢复内置规则；规则无效时后端返回错误，草稿保持不变
+  const saveSensitivePathPatterns = useCallback(
+    async (patterns) => {
+      setIsSensitivePathPatternsLoading(true);
+      applySensitivePathPatterns(await runAction(() => setSensitivePathPatterns(patterns)));
+      setIsSensitivePathPatternsLoading(false);
+    },
+    [applySensitivePathPatterns, runAction],
+  );
+
+  // 按行拆分草稿后保存，空行由后端忽略
+  const handleSensitivePathPatternsSave = useCallback(() => {
+    saveSensitivePathPatterns(sensitivePathPatternsDraft.split("\n"));
+  }, [saveSensitivePathPatterns, sensitivePathPatternsDraft]);
+
+  // 恢复内置的敏感路径规则
+  const handleSensitivePathPatternsReset = useCallback(() => {
+    saveSensitivePathPatterns(null);
+  }, [saveSensitivePathPatterns]);
+
+  // 把后端返回的排除规则写回草稿，每行一条
+  const applyExclusionPatterns = useCallback((patterns) => {
+    if (Array.isArray(patterns)) {
+      setExclusionPatternsDraft(patterns.join("\n"));
+    }
+  }, []);
+
+  // 读取排除规则，供设置页初始化展示
+  const loadExclusionPatterns = useCallback(async () => {
+    setIsExclusionPatternsLoading(true);
+    applyExclusionPatterns(await runAction(() => getExclusionPatterns()));
+    setIsExclusionPatternsLoading(false);
+  }, [applyExclusionPatterns, runAction]);
+
+  // 按行拆分草稿后保存，空行由后端忽略；规则无效时后端返回错误，草稿保持不变
+  const handleExclusionPatternsSave = useCallback(async () => {
+    setIsExclusionPatternsLoading(true);
+    applyExclusionPatterns(
+      await runAction(() => setExclusionPatterns(exclusionPatternsDraft.split("\n"))),
+    );
+    setIsExclusionPatternsLoading(false);
+  }, [applyExclusionPatterns, exclusionPatternsDraft, runAction]);
+
+  // 把后端返回的屏蔽应用列表写回草稿，每行一个
+  const applySourceBlocklist = useCallback((apps) => {
+    if (Array.isArray(apps)) {
+      setSourceBlocklistDraft(apps.join("\n"));
+    }
+  }, []);
+
+  // 读取屏蔽应用列表，供设置页初始化展示
+  const loadSourceBlocklist = useCallback(async () => {
+    setIsSourceBlocklistLoading(true);
+    applySourceBlocklist(await runAction(() => getSourceBlocklist()));
+    setIsSourceBlocklistLoading(false);
+  }, [applySourceBlocklist, runAction]);
+
+  // 按行拆分草稿后保存，空行与重复项由后端忽略
+  const handleSourceBlocklistSave = useCallback(async () => {
+    setIsSourceBlocklistLoading(true);
+    applySourceBlocklist(
+      await runAction(() => setSourceBlocklist(sourceBlocklistDraft.split("\n"))),
+    );
+    setIsSourceBlocklistLoading(false);
+  }, [applySourceBlocklist, runAction, sourceBlocklistDraft]);
+
+  // 读取敏感路径拦截设置，供设置页初始化展示
+  const loadBlockRiskyCaptures = useCallback(async () => {
+    setIsBlockRiskyCapturesLoading(true);
+    const enabled = await runAction(() => getBlockRiskyCaptures());
+    if (typeof enabled === "boolean") {
+      setBlockRiskyCapturesState(enabled);
+    }
+    setIsBlockRiskyCapturesLoading(false);
+  }, [runAction]);
+
+  // 切换敏感路径拦截设置，失败时回滚开关状态
+  const handleBlockRiskyCapturesToggle = useCallback(
+    async (event) => {
+      const targetEnabled = event.target.checked;
+      setBlockRiskyCapturesState(targetEnabled);
+      setIsBlockRiskyCapturesLoading(true);
+      const saved = await runAction(() => setBlockRiskyCaptures(targetEnabled));
+      if (typeof saved === "boolean") {
+        setBlockRiskyCapturesState(saved);
+      } else {
+        setBlockRiskyCapturesState(!targetEnabled);
+      }
+      setIsBlockRiskyCapturesLoading(false);
+    },
+    [runAction],
+  );
+
+  // 读取快捷键切换设置，供设置页初始化展示
+  const loadShortcutTogglesWindow = useCallback(async () => {
+    setIsShortcutTogglesWindowLoading(true);
+    const enabled = await runAction(() => getShortcutTogglesWindow());
+    if (typeof enabled === "boolean") {
+      setShortcutTogglesWindowState(enabled);
+    }
+    setIsShortcutTogglesWindowLoading(false);
+  }, [runAction]);
+
+  // 切换快捷键切换设置，失败时回滚开关状态
+  const handleShortcutTogglesWindowToggle = useCallback(
+    async (event) => {
+      const targetEnabled = event.target.checked;
+      setShortcutTogglesWindowState(targetEnabled);
+      setIsShortcutTogglesWindowLoading(true);
+      const saved = await runAction(() => setShortcutTogglesWindow(targetEnabled));
+      if (typeof saved === "boolean") {
+        setShortcutTogglesWindowState(saved);
+      } else {
+        setShortcutTogglesWindowState(!targetEnabled);
+      }
+      setIsShortcutTogglesWindowLoading(false);
+    },
+    [runAction],
+  );
+
+  // 读取窗口位置记忆设置，供设置页初始化展示
+  const loadRememberWindowPlacement = useCallback(async () => {
+    setIsRememberWindowPlacementLoading(true);
+    const enabled = await runAction(() => getRememberWindowPlacement());
+    if (typeof enabled === "boolean") {
+      setRememberWindowPlacementState(enabled);
+    }
+    setIsRememberWindowPlacementLoading(false);
+  }, [runAction]);
+
+  // 切换窗口位置记忆设置，失败时回滚开关状态
+  const handleRememberWindowPlacementToggle = useCallback(
+    async (event) => {
+      const targetEnabled = event.target.checked;
+      setRememberWindowPlacementState(targetEnabled);
+      setIsRememberWindowPlacementLoading(true);
+      const saved = await runAction(() => setRememberWindowPlacement(targetEnabled));
+      if (typeof saved === "boolean") {
+        setRememberWindowPlacementState(saved);
+      } else {
+        setRememberWindowPlacementState(!targetEnabled);
+      }
+      setIsRememberWindowPlacementLoading(false);
+    },
+    [runAction],
+  );
+
+  // 切换锁屏暂停设置，失败时回滚开关状态
+  const handlePauseOnLockToggle = useCallback(
+    async (event) => {
+      const targetEnabled = event.target.checked;
+      setPauseOnLockState(targetEnabled);
+      setIsPauseOnLockLoading(true);
+      const saved = await runAction(() => setPauseOnLock(targetEnabled));
+      if (typeof saved === "boolean") {
+        setPauseOnLockState(saved);
+      } else {
+        setPauseOnLockState(!targetEnabled);
+      }
+      setIsPauseOnLockLoading(false);
+    },
+    [runAction],
+  );
+
   // 快捷键录制：监听下一次按键组合，并写入草稿
   useEffect(() => {
     if (!isShortcutRecording) {
@@ -143,6 +1026,202 @@ export const useSettingsController = () => {
     };
   }, [isShortcutRecording]);
 
+  // 切换监听快捷键的录制，流程与打开窗口快捷键一致
+  useEffect(() => {
+    if (!isToggleShortcutRecording) {
+      return;
+    }
+    const handleKeyDown = (event) => {
+      if (event.key === "Escape") {
+        setIsToggleShortcutRecording(false);
+        return;
+      }
+      const shortcut = buildShortcutFromEvent(event);
+      if (!shortcut) {
+        return;
+      }
+      event.preventDefault();
+      event.stopPropagation();
+      setToggleShortcutDraft(shortcut);
+      setIsToggleShortcutRecording(false);
+    };
+    window.addEventListener("keydown", handleKeyDown);
+    return () => {
+      window.removeEventListener("keydown", handleKeyDown);
+    };
+  }, [isToggleShortcutRecording]);
+
+  // 草稿变化时即时校验写法，避免保存时才因注册失败看到难懂的错误；空草稿表示清空，不做校验
+  useEffect(() => {
+    const normalized = shortcutDraft.trim();
+    if (!normalized) {
+      setShortcutError("");
+      return;
+    }
+    let cancelled = false;
+    validateShortcut(normalized)
+      .then(() => {
+        if (!cancelled) {
+          setShortcutError("");
+        }
+      })
+      .catch((error) => {
+        if (!cancelled) {
+          setShortcutError(error?.message ?? String(error));
+        }
+      });
+    return () => {
+      cancelled = true;
+    };
+  }, [shortcutDraft]);
+
+  // 切换监听快捷键草稿的即时校验；与其他快捷键的冲突在保存时由后端检查
+  useEffect(() => {
+    const normalized = toggleShortcutDraft.trim();
+    if (!normalized) {
+      setToggleShortcutError("");
+      return;
+    }
+    let cancelled = false;
+    validateShortcut(normalized)
+      .then(() => {
+        if (!cancelled) {
+          setToggleShortcutError("");
+        }
+      })
+      .catch((error) => {
+        if (!cancelled) {
+          setToggleShortcutError(error?.message ?? String(error));
+        }
+      });
+    return () => {
+      cancelled = true;
+    };
+  }, [toggleShortcutDraft]);
+
+  // 导出全部设置到文本框，便于复制到其他设备
+  const handleExportSettings = useCallback(async () => {
+    setIsSettingsTransferring(true);
+    const json = await runAction(() => exportSettings());
+    if (typeof json === "string") {
+      setSettingsJsonDraft(json);
+      setSettingsImportSummary("");
+    }
+    setIsSettingsTransferring(false);
+  }, [runAction]);
+
+  // 导入文本框中的配置；各项设置由 settings-changed 事件统一刷新
+  const handleImportSettings = useCallback(async () => {
+    const json = settingsJsonDraft.trim();
+    if (!json) {
+      return;
+    }
+    setIsSettingsTransferring(true);
+    const report = await runAction(() => importSettings(json));
+    if (report) {
+      const ignored = report.ignored.length ? `，忽略 ${report.ignored.join("、")}` : "";
+      setSettingsImportSummary(`已导入 ${report.applied.length} 项设置${ignored}`);
+    }
+    setIsSettingsTransferring(false);
+  }, [runAction, settingsJsonDraft]);
+
+  // 读取所有持久化的设置项：初始化与导入配置后共用
+  const reloadSettings = useCallback(() => {
+    loadCaptureFilter();
+    loadPasteWrapper();
+    loadPauseOnLock();
+    loadResetDedupOnEmpty();
+    loadIgnoreOwnWindowCopies();
+    loadSanitizeOnCapture();
+    loadSensitivePathPatterns();
+    loadExclusionPatterns();
+    loadSourceBlocklist();
+    loadBlockRiskyCaptures();
+    loadOpenWindowShortcut();
+    loadToggleMonitoringShortcut();
+    loadShortcutTogglesWindow();
+    loadRememberWindowPlacement();
+    loadHistorySort();
+    loadPinnedSort();
+    loadMergeTextWinner();
+    loadTrayLeftClick();
+    loadTrayMenu();
+    loadTrayRecentCount();
+    loadClipboardAutoClear();
+    loadAutoUnpinDays();
+    loadItemTtlMinutes();
+    loadCaptureBurstThreshold();
+    loadBroadcastBatchThreshold();
+    loadPollInterval();
+  }, [
+    loadAutoUnpinDays,
+    loadBlockRiskyCaptures,
+    loadBroadcastBatchThreshold,
+    loadCaptureBurstThreshold,
+    loadCaptureFilter,
+    loadClipboardAutoClear,
+    loadExclusionPatterns,
+    loadHistorySort,
+    loadIgnoreOwnWindowCopies,
+    loadItemTtlMinutes,
+    loadMergeTextWinner,
+    loadOpenWindowShortcut,
+    loadPasteWrapper,
+    loadPauseOnLock,
+    loadPinnedSort,
+    loadPollInterval,
+    loadRememberWindowPlacement,
+    loadResetDedupOnEmpty,
+    loadSanitizeOnCapture,
+    loadSensitivePathPatterns,
+    loadShortcutTogglesWindow,
+    loadSourceBlocklist,
+    loadToggleMonitoringShortcut,
+    loadTrayLeftClick,
+    loadTrayMenu,
+    loadTrayRecentCount,
+  ]);
+
+  // 导入配置后（可能来自其他窗口）重新读取各项设置，避免显示过期的值
+  useEffect(() => {
+    let unlisten = null;
+    const registerListener = async () => {
+      const stop = await runAction(() => listen("settings-changed", () => reloadSettings()));
+      if (typeof stop === "function") {
+        unlisten = stop;
+      }
+    };
+    registerListener();
+    return () => {
+      if (unlisten) {
+        unlisten();
+      }
+    };
+  }, [reloadSettings, runAction]);
+
+  // 通过托盘菜单或快捷键切换监听时同步开关状态，避免设置页显示过期的值
+  useEffect(() => {
+    let unlisten = null;
+    const registerListener = async () => {
+      const stop = await runAction(() =>
+        listen("clipboard-monitoring-changed", (event) => {
+          if (typeof event.payload === "boolean") {
+            setIsMonitoring(event.payload);
+          }
+        }),
+      );
+      if (typeof stop === "function") {
+        unlisten = stop;
+      }
+    };
+    registerListener();
+    return () => {
+      if (unlisten) {
+        unlisten();
+      }
+    };
+  }, [runAction]);
+
   // 将监听开关同步到后台，确保关闭窗口后仍遵循用户设置
   useEffect(() => {
     if (!isMonitoringReady) {
@@ -155,8 +1234,9 @@ export const useSettingsController = () => {
   useEffect(() => {
     loadMonitoringStatus();
     loadAutostartStatus();
-    loadOpenWindowShortcut();
-  }, [loadAutostartStatus, loadMonitoringStatus, loadOpenWindowShortcut]);
+    reloadSettings();
+    loadStorageLocation();
+  }, [loadAutostartStatus, loadMonitoringStatus, loadStorageLocation, reloadSettings]);
 
   return {
     errorMessage,
@@ -166,6 +1246,45 @@ export const useSettingsController = () => {
     autostartEnabled,
     isAutostartLoading,
     handleAutostartToggle,
+    captureFilterMode,
+    captureFilterPattern,
+    setCaptureFilterPattern,
+    isCaptureFilterLoading,
+    handleCaptureFilterModeChange,
+    handleCaptureFilterPatternSave,
+    pasteWrapperDraft,
+    setPasteWrapperDraft,
+    pasteWrapperDirty,
+    isPasteWrapperLoading,
+    handlePasteWrapperSave,
+    pauseOnLock,
+    isPauseOnLockLoading,
+    handlePauseOnLockToggle,
+    resetDedupOnEmpty,
+    isResetDedupOnEmptyLoading,
+    handleResetDedupOnEmptyToggle,
+    ignoreOwnWindowCopies,
+    isIgnoreOwnWindowCopiesLoading,
+    handleIgnoreOwnWindowCopiesToggle,
+    sanitizeOnCapture,
+    isSanitizeOnCaptureLoading,
+    handleSanitizeOnCaptureToggle,
+    sensitivePathPatternsDraft,
+    setSensitivePathPatternsDraft,
+    isSensitivePathPatternsLoading,
+    handleSensitivePathPatternsSave,
+    handleSensitivePathPatternsReset,
+    exclusionPatternsDraft,
+    setExclusionPatternsDraft,
+    isExclusionPatternsLoading,
+    handleExclusionPatternsSave,
+    sourceBlocklistDraft,
+    setSourceBlocklistDraft,
+    isSourceBlocklistLoading,
+    handleSourceBlocklistSave,
+    blockRiskyCaptures,
+    isBlockRiskyCapturesLoading,
+    handleBlockRiskyCapturesToggle,
     openWindowShortcut,
     shortcutDraft,
     setShortcutDraft,
@@ -175,8 +1294,72 @@ export const useSettingsController = () => {
     setIsShortcutRecording,
     shortcutDisplay,
     shortcutDirty,
+    shortcutError,
     handleShortcutSave,
     handleShortcutClear,
+    toggleMonitoringShortcut,
+    toggleShortcutDraft,
+    setToggleShortcutDraft,
+    isToggleShortcutLoading,
+    isToggleShortcutRecording,
+    setIsToggleShortcutRecording,
+    toggleShortcutDirty,
+    toggleShortcutError,
+    handleToggleShortcutSave,
+    handleToggleShortcutClear,
+    shortcutTogglesWindow,
+    isShortcutTogglesWindowLoading,
+    handleShortcutTogglesWindowToggle,
+    rememberWindowPlacement,
+    isRememberWindowPlacementLoading,
+    handleRememberWindowPlacementToggle,
+    clipboardAutoClear,
+    isClipboardAutoClearLoading,
+    handleClipboardAutoClearChange,
+    autoUnpinDays,
+    isAutoUnpinDaysLoading,
+    handleAutoUnpinDaysChange,
+    itemTtlMinutes,
+    isItemTtlMinutesLoading,
+    handleItemTtlMinutesChange,
+    captureBurstThreshold,
+    isCaptureBurstThresholdLoading,
+    handleCaptureBurstThresholdChange,
+    broadcastBatchThreshold,
+    isBroadcastBatchThresholdLoading,
+    handleBroadcastBatchThresholdChange,
+    pollInterval,
+    isPollIntervalLoading,
+    handlePollIntervalChange,
+    storageLocation,
+    dbMoveDraft,
+    setDbMoveDraft,
+    isDbMoving,
+    handleMoveDb,
+    historySort,
+    isHistorySortLoading,
+    handleHistorySortChange,
+    pinnedSort,
+    isPinnedSortLoading,
+    handlePinnedSortChange,
+    mergeTextWinner,
+    isMergeTextWinnerLoading,
+    handleMergeTextWinnerChange,
+    trayLeftClick,
+    isTrayLeftClickLoading,
+    handleTrayLeftClickChange,
+    trayMenu,
+    isTrayMenuLoading,
+    handleTrayMenuToggle,
+    trayRecentCount,
+    isTrayRecentCountLoading,
+    handleTrayRecentCountChange,
+    settingsJsonDraft,
+    setSettingsJsonDraft,
+    isSettingsTransferring,
+    settingsImportSummary,
+    handleExportSettings,
+    handleImportSettings,
   };
 };
 
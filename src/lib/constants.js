@@ -4,3 +4,99 @@
 export const HISTORY_RETENTION_DAYS = 7;
 // 详情编辑保存节流间隔，避免每次键入都触发数据库写入
 export const DETAIL_SAVE_DELAY = 600;
+// 托盘左键动作的可选项，顺序即设置页下拉框的展示顺序
+export const TRAY_LEFT_CLICK_OPTIONS = [
+  { value: "show_window", label: "打开剪贴板窗口" },
+  { value: "show_menu", label: "弹出托盘菜单" },
+  { value: "paste_last", label: "复制最近一条记录" },
+];
+// 托盘菜单可显示的操作项，顺序即设置页的展示顺序；退出是唯一的退出入口，始终显示
+export const TRAY_MENU_OPTIONS = [
+  { value: "show", label: "打开" },
+  { value: "settings", label: "设置" },
+  { value: "toggle_monitoring", label: "暂停 / 恢复记录" },
+  { value: "paste_last", label: "复制最近一条" },
+  { value: "clear_unpinned", label: "清空未固定条目" },
+  { value: "quit", label: "退出" },
+];
+// 托盘“最近复制”子菜单显示条目数的可选项，0 表示不显示子菜单
+export const TRAY_RECENT_COUNT_OPTIONS = [
+  { value: 0, label: "不显示" },
+  { value: 5, label: "5 条" },
+  { value: 8, label: "8 条" },
+  { value: 12, label: "12 条" },
+  { value: 20, label: "20 条" },
+];
+// 后台捕获过滤方式的可选项，顺序即设置页下拉框的展示顺序
+export const CAPTURE_FILTER_OPTIONS = [
+  { value: "all", label: "记录全部内容" },
+  { value: "urls_only", label: "只记录链接" },
+  { value: "urls_and_code", label: "只记录链接与代码" },
+  { value: "custom_regex", label: "自定义正则" },
+];
+// 整个列表排序方式的可选项，顺序即设置页下拉框的展示顺序
+export const HISTORY_SORT_OPTIONS = [
+  { value: "recent", label: "按最近复制时间" },
+  { value: "manual", label: "手动排序" },
+];
+// 固定区排序方式的可选项，顺序即设置页下拉框的展示顺序
+export const PINNED_SORT_OPTIONS = [
+  { value: "updated_at", label: "按最近复制时间" },
+  { value: "pinned_at", label: "按固定时间" },
+];
+// 编辑后与已有条目重复时保留哪一条的可选项，顺序即设置页下拉框的展示顺序
+export const MERGE_TEXT_WINNER_OPTIONS = [
+  { value: "existing", label: "保留已有条目" },
+  { value: "edited", label: "保留编辑的条目" },
+];
+// 复制后自动清空剪贴板的可选延迟（秒），0 表示不自动清空
+export const CLIPBOARD_AUTO_CLEAR_OPTIONS = [
+  { value: 0, label: "不自动清空" },
+  { value: 15, label: "15 秒后" },
+  { value: 30, label: "30 秒后" },
+  { value: 60, label: "1 分钟后" },
+  { value: 120, label: "2 分钟后" },
+];
+
+// 固定条目自动取消固定的可选天数，0 表示不自动取消
+export const AUTO_UNPIN_DAYS_OPTIONS = [
+  { value: 0, label: "不自动取消" },
+  { value: 1, label: "1 天后" },
+  { value: 7, label: "7 天后" },
+  { value: 30, label: "30 天后" },
+  { value: 90, label: "90 天后" },
+];
+
+// 条目默认存活时间的可选分钟数，0 表示不过期；固定的条目不受影响
+export const ITEM_TTL_MINUTES_OPTIONS = [
+  { value: 0, label: "不过期" },
+  { value: 10, label: "10 分钟后" },
+  { value: 60, label: "1 小时后" },
+  { value: 480, label: "8 小时后" },
+  { value: 1440, label: "1 天后" },
+];
+
+// 高频重复捕获的计数上限选项（1 分钟内），0 表示不限制
+export const CAPTURE_BURST_THRESHOLD_OPTIONS = [
+  { value: 0, label: "不限制" },
+  { value: 5, label: "最多 5 次" },
+  { value: 10, label: "最多 10 次" },
+  { value: 20, label: "最多 20 次" },
+];
+
+// 合并广播的频率阈值选项（10 秒内的新记录数），0 表示逐条更新
+export const BROADCAST_BATCH_THRESHOLD_OPTIONS = [
+  { value: 0, label: "逐条更新" },
+  { value: 3, label: "超过 3 条" },
+  { value: 5, label: "超过 5 条" },
+  { value: 10, label: "超过 10 条" },
+];
+
+// 后台轮询剪贴板的间隔选项（毫秒），默认 900 毫秒；能检测剪贴板变化的系统不按该间隔读取
+export const POLL_INTERVAL_OPTIONS = [
+  { value: 300, label: "300 毫秒" },
+  { value: 500, label: "500 毫秒" },
+  { value: 900, label: "900 毫秒（默认）" },
+  { value: 2000, label: "2 秒" },
+  { value: 5000, label: "5 秒" },
+];
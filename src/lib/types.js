@@ -10,6 +10,17 @@
  * @property {string} updatedAt 最近更新时间（ISO-8601 字符串）。
  * @property {boolean} pinned 是否固定（固定条目不会被上限清理）。
  * @property {number} count 命中/复制次数（用于快速识别常用内容）。
+ * @property {number | null} position 手动排序位置（越小越靠前），未手动排序时为 null；非固定条目仅在手动排序模式下生效。
+ * @property {string | null} pinnedAt 最近一次被固定的时间（ISO-8601 字符串），未固定时为 null。
+ * @property {string | null} script 后端识别的主要文字体系（如 cjk、latin），无法识别时为 null。
+ * @property {ContentType | null} contentType 后端识别的结构化内容类型，普通文本为 null。
+ * @property {boolean} sanitized 捕获时是否清理过排版字符（保存的文本与复制的原文不同）。
+ * @property {boolean} risky 捕获时内容像是指向敏感文件（私钥、凭据、.env 等）的路径。
+ * @property {string | null} expiresAt 到期后自动删除的时间（RFC3339），不会过期时为 null。
+ * @property {"text" | "image"} kind 条目内容的种类；图片条目的 text 只是“图片 宽×高”的说明。
+ * @property {string | null} sourceApp 最近一次捕获时处于前台的应用，无法判断时为 null。
+ * @property {string | null} note 用户为条目填写的备注，未填写时为 null。
+ * @property {string[]} tags 条目的标签，按名称排序。
  */
 
 /**
@@ -35,5 +46,60 @@
  * @property {string | null | undefined} mergedId 若发生合并，被删除的旧条目 id；否则为 null/undefined。
  */
 
+/**
+ * 后端按保留天数清理条目后广播的事件 payload。
+ * @typedef {Object} ClipboardPrunedPayload
+ * @property {string[]} ids 被清理删除的条目 id 列表。
+ */
+
+/**
+ * 左键点击托盘图标时的动作：打开主窗口 / 弹出托盘菜单 / 把最近一条记录写回剪贴板。
+ * @typedef {"show_window" | "show_menu" | "paste_last"} TrayLeftClickAction
+ */
+
+/**
+ * 托盘菜单中可以显示的操作项。
+ * @typedef {"show" | "settings" | "toggle_monitoring" | "paste_last" | "clear_unpinned" | "quit"} TrayMenuAction
+ */
+
+/**
+ * 整个历史列表的排序方式：按最近复制时间 / 手动排序。
+ * @typedef {"recent" | "manual"} HistorySortMode
+ */
+
+/**
+ * 固定区的排序方式：按最近复制时间 / 按固定时间。
+ * @typedef {"updated_at" | "pinned_at"} PinnedSortMode
+ */
+
+/**
+ * 编辑后文本与已有条目重复时保留哪一条：已有条目 / 被编辑的条目。
+ * @typedef {"existing" | "edited"} MergeTextWinner
+ */
+
+/**
+ * 可格式化的结构化内容类型。
+ * @typedef {"json" | "xml"} ContentType
+ */
+
+/**
+ * 后台捕获过滤方式：全部 / 只记录链接 / 链接与代码 / 自定义正则。
+ * @typedef {"all" | "urls_only" | "urls_and_code" | "custom_regex"} CaptureFilterMode
+ */
+
+/**
+ * 捕获过滤设置。
+ * @typedef {Object} CaptureFilterSettings
+ * @property {CaptureFilterMode} mode 过滤方式。
+ * @property {string | null} pattern 自定义正则原文，仅在 custom_regex 模式下生效。
+ */
+
+/**
+ * 写回剪贴板时包裹在条目文本两侧的前缀与后缀。
+ * @typedef {Object} PasteWrapper
+ * @property {string} prefix 前缀，空字符串表示不添加。
+ * @property {string} suffix 后缀，空字符串表示不添加。
+ */
+
 export {};
 
@@ -78,3 +78,54 @@ export const setClipboardMonitoring = async (enabled) =>
  * @returns {Promise<void>}
  */
 export const markClipboardSkip = async (text) => invokeCommand("mark_clipboard_skip", { text });
+
+/**
+ * 把条目写回系统剪贴板：默认按设置添加前缀 / 后缀，raw 为 true 时写入原文。
+ * 后端会同时标记跳过，避免 watcher 重复计数。
+ * @param {string} id
+ * @param {boolean} [raw]
+ * @returns {Promise<string>} 实际写入剪贴板的文本
+ */
+export const copyItemToClipboard = async (id, raw = false) =>
+  invokeCommand("copy_item_to_clipboard", { id, raw });
+
+/**
+ * 把图片条目的原图写回系统剪贴板，条目不是图片时返回错误。
+ * @param {string} id
+ * @returns {Promise<void>}
+ */
+export const copyImageItemToClipboard = async (id) =>
+  invokeCommand("copy_image_item_to_clipboard", { id });
+
+/**
+ * 读取图片条目的缩略图：历史列表不附带缩略图，条目显示时再按需加载。
+ * @param {string} id
+ * @returns {Promise<string | null>} PNG 的 data URL，文本条目为 null
+ */
+export const getItemThumbnail = async (id) => invokeCommand("get_item_thumbnail", { id });
+
+/**
+ * 立即记录当前系统剪贴板内容，不受监听开关影响；剪贴板为空时返回 null。
+ * @returns {Promise<import("../lib/types").ClipboardItem | null>}
+ */
+export const captureNow = async () => invokeCommand("capture_now");
+
+/**
+ * 把当前全部条目标记为已读，用于未读角标计数。
+ * @returns {Promise<void>}
+ */
+export const markAllSeen = async () => invokeCommand("mark_all_seen");
+
+/**
+ * 领取冷启动时通过条目链接传入的待定位条目 id，没有时返回 null。
+ * @returns {Promise<string | null>}
+ */
+export const takePendingFocusItem = async () => invokeCommand("take_pending_focus_item");
+
+/**
+ * 按指定格式美化条目文本，返回美化后的内容，不修改已保存的条目。
+ * @param {string} id
+ * @param {import("../lib/types").ContentType} kind
+ * @returns {Promise<string>}
+ */
+export const formatItem = async (id, kind) => invokeCommand("format_item", { id, kind });
@@ -24,11 +24,14 @@ const formatInvokeErrorMessage = (error) => {
 };
 
 // 统一封装 Tauri invoke：成功直接返回结果；失败抛出 Error，便于上层统一读取 error.message。
+// 后端命令返回 { code, message } 形式的错误，code 同时挂到抛出的 Error 上，需要区分错误种类时按 code 判断而不是匹配文案。
 export const invokeCommand = async (commandName, args) => {
   try {
     return await invoke(commandName, args);
   } catch (error) {
-    throw new Error(formatInvokeErrorMessage(error));
+    const wrapped = new Error(formatInvokeErrorMessage(error));
+    wrapped.code = typeof error?.code === "string" ? error.code : "unknown";
+    throw wrapped;
   }
 };
 
@@ -16,12 +16,189 @@ export const getAutostartStatus = async () => invokeCommand("get_autostart_statu
 export const setAutostartEnabled = async (enabled) =>
   invokeCommand("set_autostart_enabled", { enabled });
 
+/**
+ * 读取后台捕获过滤设置。
+ * @returns {Promise<import("../lib/types").CaptureFilterSettings>}
+ */
+export const getCaptureFilter = async () => invokeCommand("get_capture_filter");
+
+/**
+ * 更新后台捕获过滤设置，自定义正则模式下 pattern 不能为空。
+ * @param {import("../lib/types").CaptureFilterMode} mode
+ * @param {string | null} pattern
+ * @returns {Promise<import("../lib/types").CaptureFilterSettings>}
+ */
+export const setCaptureFilter = async (mode, pattern) =>
+  invokeCommand("set_capture_filter", { mode, pattern });
+
+/**
+ * 读取写回剪贴板时添加的前缀 / 后缀。
+ * @returns {Promise<import("../lib/types").PasteWrapper>}
+ */
+export const getPasteWrapper = async () => invokeCommand("get_paste_wrapper");
+
+/**
+ * 设置写回剪贴板时添加的前缀 / 后缀，空字符串表示不添加。
+ * @param {string} prefix
+ * @param {string} suffix
+ * @returns {Promise<import("../lib/types").PasteWrapper>}
+ */
+export const setPasteWrapper = async (prefix, suffix) =>
+  invokeCommand("set_paste_wrapper", { prefix, suffix });
+
+/**
+ * 读取锁屏期间是否自动暂停记录剪贴板。
+ * @returns {Promise<boolean>}
+ */
+export const getPauseOnLock = async () => invokeCommand("get_pause_on_lock");
+
+/**
+ * 设置锁屏期间是否自动暂停记录剪贴板。
+ * @param {boolean} enabled
+ * @returns {Promise<boolean>}
+ */
+export const setPauseOnLock = async (enabled) => invokeCommand("set_pause_on_lock", { enabled });
+
+/**
+ * 读取剪贴板被清空后是否重新记录之前出现过的内容。
+ * @returns {Promise<boolean>}
+ */
+export const getResetDedupOnEmpty = async () => invokeCommand("get_reset_dedup_on_empty");
+
+/**
+ * 设置剪贴板被清空后是否重新记录之前出现过的内容。
+ * @param {boolean} enabled
+ * @returns {Promise<boolean>}
+ */
+export const setResetDedupOnEmpty = async (enabled) =>
+  invokeCommand("set_reset_dedup_on_empty", { enabled });
+
+/**
+ * 读取是否忽略在应用自身窗口内复制的内容。
+ * @returns {Promise<boolean>}
+ */
+export const getIgnoreOwnWindowCopies = async () => invokeCommand("get_ignore_own_window_copies");
+
+/**
+ * 设置是否忽略在应用自身窗口内复制的内容。
+ * @param {boolean} enabled
+ * @returns {Promise<boolean>}
+ */
+export const setIgnoreOwnWindowCopies = async (enabled) =>
+  invokeCommand("set_ignore_own_window_copies", { enabled });
+
+/**
+ * 读取捕获时是否清理排版字符（弯引号、不换行空格、零宽字符）。
+ * @returns {Promise<boolean>}
+ */
+export const getSanitizeOnCapture = async () => invokeCommand("get_sanitize_on_capture");
+
+/**
+ * 设置捕获时是否清理排版字符，只影响之后捕获的内容。
+ * @param {boolean} enabled
+ * @returns {Promise<boolean>}
+ */
+export const setSanitizeOnCapture = async (enabled) =>
+  invokeCommand("set_sanitize_on_capture", { enabled });
+
+/**
+ * 读取敏感路径规则（正则原文列表），未自定义时为内置规则。
+ * @returns {Promise<string[]>}
+ */
+export const getSensitivePathPatterns = async () => invokeCommand("get_sensitive_path_patterns");
+
+/**
+ * 更新敏感路径规则；传入 null 恢复内置规则，传入空数组表示不检查。
+ * @param {string[] | null} patterns
+ * @returns {Promise<string[]>}
+ */
+export const setSensitivePathPatterns = async (patterns) =>
+  invokeCommand("set_sensitive_path_patterns", { patterns });
+
+/**
+ * 读取排除规则（正则原文列表），匹配任一规则的内容不写入历史。
+ * @returns {Promise<string[]>}
+ */
+export const getExclusionPatterns = async () => invokeCommand("get_exclusion_patterns");
+
+/**
+ * 更新排除规则；传入空数组表示不排除，任一规则无效时整体不保存。
+ * @param {string[]} patterns
+ * @returns {Promise<string[]>}
+ */
+export const setExclusionPatterns = async (patterns) =>
+  invokeCommand("set_exclusion_patterns", { patterns });
+
+/**
+ * 读取来源应用屏蔽列表（macOS 为 bundle id，Windows / Linux 为进程名）。
+ * @returns {Promise<string[]>}
+ */
+export const getSourceBlocklist = async () => invokeCommand("get_source_blocklist");
+
+/**
+ * 更新来源应用屏蔽列表，传入空数组表示不屏蔽。
+ * @param {string[]} apps
+ * @returns {Promise<string[]>}
+ */
+export const setSourceBlocklist = async (apps) => invokeCommand("set_source_blocklist", { apps });
+
+/**
+ * 读取是否拦截（不写入历史）指向敏感文件的路径。
+ * @returns {Promise<boolean>}
+ */
+export const getBlockRiskyCaptures = async () => invokeCommand("get_block_risky_captures");
+
+/**
+ * 设置是否拦截指向敏感文件的路径，关闭时照常写入并提醒。
+ * @param {boolean} enabled
+ * @returns {Promise<boolean>}
+ */
+export const setBlockRiskyCaptures = async (enabled) =>
+  invokeCommand("set_block_risky_captures", { enabled });
+
+/**
+ * 读取全局快捷键是否在显示 / 隐藏主窗口之间切换。
+ * @returns {Promise<boolean>}
+ */
+export const getShortcutTogglesWindow = async () => invokeCommand("get_shortcut_toggles_window");
+
+/**
+ * 设置全局快捷键是否在显示 / 隐藏主窗口之间切换。
+ * @param {boolean} enabled
+ * @returns {Promise<boolean>}
+ */
+export const setShortcutTogglesWindow = async (enabled) =>
+  invokeCommand("set_shortcut_toggles_window", { enabled });
+
+/**
+ * 读取是否按显示器布局分别记住主窗口位置。
+ * @returns {Promise<boolean>}
+ */
+export const getRememberWindowPlacement = async () =>
+  invokeCommand("get_remember_window_placement");
+
+/**
+ * 设置是否按显示器布局分别记住主窗口位置。
+ * @param {boolean} enabled
+ * @returns {Promise<boolean>}
+ */
+export const setRememberWindowPlacement = async (enabled) =>
+  invokeCommand("set_remember_window_placement", { enabled });
+
 /**
  * 读取打开主窗口的快捷键配置。
  * @returns {Promise<string | null>}
  */
 export const getOpenWindowShortcut = async () => invokeCommand("get_open_window_shortcut");
 
+/**
+ * 校验快捷键写法，写法不合法时抛出带具体原因的错误。
+ * @param {string} accelerator
+ * @returns {Promise<void>}
+ */
+export const validateShortcut = async (accelerator) =>
+  invokeCommand("validate_shortcut", { accelerator });
+
 /**
  * 更新打开主窗口的快捷键配置（传 null 表示清空）。
  * @param {string | null} shortcut
@@ -30,6 +207,212 @@ export const getOpenWindowShortcut = async () => invokeCommand("get_open_window_
 export const setOpenWindowShortcut = async (shortcut) =>
   invokeCommand("set_open_window_shortcut", { shortcut });
 
+/**
+ * 读取切换剪贴板监听的快捷键配置。
+ * @returns {Promise<string | null>}
+ */
+export const getToggleMonitoringShortcut = async () =>
+  invokeCommand("get_toggle_monitoring_shortcut");
+
+/**
+ * 更新切换剪贴板监听的快捷键配置（传 null 表示清空），与已有快捷键冲突时抛出错误。
+ * @param {string | null} shortcut
+ * @returns {Promise<string | null>}
+ */
+export const setToggleMonitoringShortcut = async (shortcut) =>
+  invokeCommand("set_toggle_monitoring_shortcut", { shortcut });
+
+/**
+ * 读取复制后自动清空剪贴板的延迟秒数，未开启时为 null。
+ * @returns {Promise<number | null>}
+ */
+export const getClipboardAutoClear = async () => invokeCommand("get_clipboard_auto_clear");
+
+/**
+ * 设置复制后自动清空剪贴板的延迟秒数（传 null 关闭）。
+ * @param {number | null} seconds
+ * @returns {Promise<number | null>}
+ */
+export const setClipboardAutoClear = async (seconds) =>
+  invokeCommand("set_clipboard_auto_clear", { seconds });
+
+/**
+ * 读取固定条目自动取消固定的天数，未开启时为 null。
+ * @returns {Promise<number | null>}
+ */
+export const getAutoUnpinDays = async () => invokeCommand("get_auto_unpin_days");
+
+/**
+ * 设置固定条目自动取消固定的天数（传 null 关闭）。
+ * @param {number | null} days
+ * @returns {Promise<number | null>}
+ */
+export const setAutoUnpinDays = async (days) => invokeCommand("set_auto_unpin_days", { days });
+
+/**
+ * 读取新捕获条目默认存活的分钟数，未开启时为 null。
+ * @returns {Promise<number | null>}
+ */
+export const getItemTtlMinutes = async () => invokeCommand("get_item_ttl_minutes");
+
+/**
+ * 设置新捕获条目默认存活的分钟数（传 null 关闭），只影响之后捕获或取消固定的条目。
+ * @param {number | null} minutes
+ * @returns {Promise<number | null>}
+ */
+export const setItemTtlMinutes = async (minutes) =>
+  invokeCommand("set_item_ttl_minutes", { minutes });
+
+/**
+ * 读取同一内容短时间内重复捕获时的计数上限，未开启时为 null。
+ * @returns {Promise<number | null>}
+ */
+export const getCaptureBurstThreshold = async () => invokeCommand("get_capture_burst_threshold");
+
+/**
+ * 设置同一内容短时间内重复捕获时的计数上限（传 null 关闭）。
+ * @param {number | null} threshold
+ * @returns {Promise<number | null>}
+ */
+export const setCaptureBurstThreshold = async (threshold) =>
+  invokeCommand("set_capture_burst_threshold", { threshold });
+
+/**
+ * 读取后台记录改为合并更新的频率阈值（10 秒内的新记录数），未开启时为 null。
+ * @returns {Promise<number | null>}
+ */
+export const getBroadcastBatchThreshold = async () =>
+  invokeCommand("get_broadcast_batch_threshold");
+
+/**
+ * 设置后台记录改为合并更新的频率阈值（传 null 关闭）。
+ * @param {number | null} threshold
+ * @returns {Promise<number | null>}
+ */
+export const setBroadcastBatchThreshold = async (threshold) =>
+  invokeCommand("set_broadcast_batch_threshold", { threshold });
+
+/**
+ * 读取后台轮询剪贴板的间隔（毫秒）。
+ * @returns {Promise<number>}
+ */
+export const getPollInterval = async () => invokeCommand("get_poll_interval");
+
+/**
+ * 设置后台轮询剪贴板的间隔（传 null 恢复默认值），下一次轮询即生效。
+ * @param {number | null} intervalMs
+ * @returns {Promise<number>}
+ */
+export const setPollInterval = async (intervalMs) =>
+  invokeCommand("set_poll_interval", { intervalMs });
+
+/**
+ * 读取整个历史列表的排序方式。
+ * @returns {Promise<import("../lib/types").HistorySortMode>}
+ */
+export const getHistorySort = async () => invokeCommand("get_history_sort");
+
+/**
+ * 更新整个历史列表的排序方式，后端会广播 history-sort-changed 通知所有窗口。
+ * @param {import("../lib/types").HistorySortMode} mode
+ * @returns {Promise<import("../lib/types").HistorySortMode>}
+ */
+export const setHistorySort = async (mode) => invokeCommand("set_history_sort", { mode });
+
+/**
+ * 读取固定区的排序方式。
+ * @returns {Promise<import("../lib/types").PinnedSortMode>}
+ */
+export const getPinnedSort = async () => invokeCommand("get_pinned_sort");
+
+/**
+ * 更新固定区的排序方式，后端会广播 pinned-sort-changed 通知所有窗口。
+ * @param {import("../lib/types").PinnedSortMode} mode
+ * @returns {Promise<import("../lib/types").PinnedSortMode>}
+ */
+export const setPinnedSort = async (mode) => invokeCommand("set_pinned_sort", { mode });
+
+/**
+ * 读取编辑后与已有条目重复时保留哪一条。
+ * @returns {Promise<import("../lib/types").MergeTextWinner>}
+ */
+export const getMergeTextWinner = async () => invokeCommand("get_merge_text_winner");
+
+/**
+ * 更新编辑后与已有条目重复时保留哪一条。
+ * @param {import("../lib/types").MergeTextWinner} winner
+ * @returns {Promise<import("../lib/types").MergeTextWinner>}
+ */
+export const setMergeTextWinner = async (winner) =>
+  invokeCommand("set_merge_text_winner", { winner });
+
+/**
+ * 读取左键点击托盘图标时的动作。
+ * @returns {Promise<import("../lib/types").TrayLeftClickAction>}
+ */
+export const getTrayLeftClick = async () => invokeCommand("get_tray_left_click");
+
+/**
+ * 更新左键点击托盘图标时的动作，返回后端实际保存的值。
+ * @param {import("../lib/types").TrayLeftClickAction} action
+ * @returns {Promise<import("../lib/types").TrayLeftClickAction>}
+ */
+export const setTrayLeftClick = async (action) =>
+  invokeCommand("set_tray_left_click", { action });
+
+/**
+ * 读取托盘菜单显示的操作项（按显示顺序）。
+ * @returns {Promise<import("../lib/types").TrayMenuAction[]>}
+ */
+export const getTrayMenu = async () => invokeCommand("get_tray_menu");
+
+/**
+ * 更新托盘菜单显示的操作项并立即生效，返回后端实际保存的配置（始终包含退出）。
+ * @param {import("../lib/types").TrayMenuAction[]} actions
+ * @returns {Promise<import("../lib/types").TrayMenuAction[]>}
+ */
+export const setTrayMenu = async (actions) => invokeCommand("set_tray_menu", { actions });
+
+/**
+ * 读取托盘“最近复制”子菜单显示的条目数，0 表示不显示子菜单。
+ * @returns {Promise<number>}
+ */
+export const getTrayRecentCount = async () => invokeCommand("get_tray_recent_count");
+
+/**
+ * 设置托盘“最近复制”子菜单显示的条目数并立即重建菜单，返回后端实际保存的值。
+ * @param {number} count
+ * @returns {Promise<number>}
+ */
+export const setTrayRecentCount = async (count) =>
+  invokeCommand("set_tray_recent_count", { count });
+
+/**
+ * 读取数据存放位置（数据库路径、所在目录、是否便携模式）。
+ * @returns {Promise<{ dbPath: string, dataDir: string, portable: boolean }>}
+ */
+export const getStorageLocation = async () => invokeCommand("get_storage_location");
+
+/**
+ * 把数据库迁移到新的位置（目录或完整文件路径），校验成功后才会删除原文件。
+ * @param {string} newPath
+ * @returns {Promise<{ dbPath: string, dataDir: string, portable: boolean }>}
+ */
+export const moveDb = async (newPath) => invokeCommand("move_db", { newPath });
+
+/**
+ * 导出全部设置为 JSON 文本（含导出时实际生效的配置，仅供查看）。
+ * @returns {Promise<string>}
+ */
+export const exportSettings = async () => invokeCommand("export_settings");
+
+/**
+ * 导入由 exportSettings 导出的 JSON，返回实际写入与被忽略的设置键。
+ * @param {string} json
+ * @returns {Promise<{ applied: string[], ignored: string[] }>}
+ */
+export const importSettings = async (json) => invokeCommand("import_settings", { json });
+
 /**
  * 打开或聚焦设置窗口（由后端统一创建，避免前端多窗口逻辑分散）。
  * @returns {Promise<void>}
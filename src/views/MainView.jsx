@@ -20,6 +20,8 @@ import { useClipboardController } from "../hooks/useClipboardController";
 import { CopyIcon } from "../components/icons/CopyIcon";
 import { ConfirmClearDialog } from "../components/ConfirmClearDialog";
 import { CopyToast } from "../components/CopyToast";
+import { ItemThumbnail } from "../components/ItemThumbnail";
+import { RiskyCaptureToast } from "../components/RiskyCaptureToast";
 
 // 主窗口视图：只负责 UI 结构与交互绑定，业务状态与后端交互交给 controller hook。
 // 这样未来改 UI 布局不会影响数据逻辑，改逻辑也不必在 1000+ 行的 JSX 中穿梭。
@@ -41,6 +43,8 @@ export const MainView = () => {
     isConfirmOpen,
     isCopyToastOpen,
     setIsCopyToastOpen,
+    riskyCapture,
+    setRiskyCapture,
     requestClear,
     cancelClear,
     confirmClear,
@@ -48,6 +52,8 @@ export const MainView = () => {
     handleDetailChange,
     flushDetailPersist,
     handleCopy,
+    handleCopyFormatted,
+    handleCaptureNow,
     handleWrite,
     openSettingsWindow,
     handleOpenLink,
@@ -131,6 +137,14 @@ export const MainView = () => {
                 flexWrap="wrap"
                 sx={{ width: "100%" }}
               >
+                <Button
+                  variant="outlined"
+                  size="small"
+                  onClick={handleCaptureNow}
+                  sx={{ px: 1.25, minWidth: 0 }}
+                >
+                  立即记录
+                </Button>
                 <Button
                   variant="outlined"
                   size="small"
@@ -158,6 +172,9 @@ export const MainView = () => {
                 onChange={(event) => setQuery(event.target.value)}
                 inputRef={searchInputRef}
               />
+              <Button variant="outlined" size="small" onClick={handleCaptureNow}>
+                立即记录
+              </Button>
               <Button variant="outlined" size="small" onClick={() => requestClear("history")}>
                 清空历史
               </Button>
@@ -225,6 +242,19 @@ export const MainView = () => {
                           borderBottom: "1px solid rgba(15, 23, 42, 0.06)",
                         }}
                       >
+                        {item.kind === "image" ? (
+                          <ItemThumbnail
+                            item={item}
+                            sx={{
+                              width: 48,
+                              height: 48,
+                              mr: 1,
+                              flexShrink: 0,
+                              objectFit: "cover",
+                              borderRadius: 1,
+                            }}
+                          />
+                        ) : null}
                         <ListItemText
                           primary={item.text}
                           secondary={`${new Intl.DateTimeFormat("zh-CN", {
@@ -234,7 +264,7 @@ export const MainView = () => {
                             minute: "2-digit",
                           }).format(new Date(item.updatedAt))}${
                             item.count > 1 ? ` · ${item.count} 次` : ""
-                          }`}
+                          }${item.sourceApp ? ` · 来自 ${item.sourceApp}` : ""}`}
                           primaryTypographyProps={{
                             noWrap: true,
                             sx: { fontWeight: 500 },
@@ -295,16 +325,24 @@ export const MainView = () => {
                 </Typography>
                 {selectedItem?.pinned ? <Chip label="已固定" size="small" color="secondary" /> : null}
               </Stack>
-              <TextField
-                size="small"
-                multiline
-                minRows={6}
-                placeholder="请选择条目"
-                value={selectedItem?.text ?? ""}
-                onChange={handleDetailChange}
-                onBlur={flushDetailPersist}
-                disabled={!selectedItem}
-              />
+              {/* 图片条目只展示缩略图，正文是尺寸说明，不提供编辑 */}
+              {selectedItem?.kind === "image" ? (
+                <ItemThumbnail
+                  item={selectedItem}
+                  sx={{ alignSelf: "flex-start", maxWidth: "100%", borderRadius: 1 }}
+                />
+              ) : (
+                <TextField
+                  size="small"
+                  multiline
+                  minRows={6}
+                  placeholder="请选择条目"
+                  value={selectedItem?.text ?? ""}
+                  onChange={handleDetailChange}
+                  onBlur={flushDetailPersist}
+                  disabled={!selectedItem}
+                />
+              )}
               <Stack direction="row" spacing={1} flexWrap="wrap">
                 <Button
                   variant="contained"
@@ -314,6 +352,16 @@ export const MainView = () => {
                 >
                   复制
                 </Button>
+                {/* 仅当内容被识别为 JSON/XML 时才提供格式化复制 */}
+                {selectedItem?.contentType ? (
+                  <Button
+                    variant="outlined"
+                    size="small"
+                    onClick={() => handleCopyFormatted(selectedItem)}
+                  >
+                    格式化复制
+                  </Button>
+                ) : null}
                 {/* 仅当当前内容是可识别链接时才显示打开入口，避免无效操作 */}
                 {canOpenLink ? (
                   <Button
@@ -382,6 +430,7 @@ export const MainView = () => {
       />
 
       <CopyToast open={isCopyToastOpen} onClose={() => setIsCopyToastOpen(false)} />
+      <RiskyCaptureToast capture={riskyCapture} onClose={() => setRiskyCapture(null)} />
     </>
   );
 };
@@ -2,6 +2,9 @@ import {
   Alert,
   Box,
   Button,
+  Checkbox,
+  FormControlLabel,
+  MenuItem,
   Paper,
   Stack,
   Switch,
@@ -9,6 +12,21 @@ import {
   Typography,
 } from "@mui/material";
 import { useSettingsController } from "../hooks/useSettingsController";
+import {
+  AUTO_UNPIN_DAYS_OPTIONS,
+  BROADCAST_BATCH_THRESHOLD_OPTIONS,
+  CAPTURE_BURST_THRESHOLD_OPTIONS,
+  CAPTURE_FILTER_OPTIONS,
+  CLIPBOARD_AUTO_CLEAR_OPTIONS,
+  ITEM_TTL_MINUTES_OPTIONS,
+  HISTORY_SORT_OPTIONS,
+  MERGE_TEXT_WINNER_OPTIONS,
+  PINNED_SORT_OPTIONS,
+  POLL_INTERVAL_OPTIONS,
+  TRAY_LEFT_CLICK_OPTIONS,
+  TRAY_MENU_OPTIONS,
+  TRAY_RECENT_COUNT_OPTIONS,
+} from "../lib/constants";
 
 // 设置窗口视图：聚焦渲染设置项 UI，所有状态与系统交互交给 controller hook 处理。
 
@@ -21,6 +39,45 @@ export const SettingsView = () => {
     autostartEnabled,
     isAutostartLoading,
     handleAutostartToggle,
+    captureFilterMode,
+    captureFilterPattern,
+    setCaptureFilterPattern,
+    isCaptureFilterLoading,
+    handleCaptureFilterModeChange,
+    handleCaptureFilterPatternSave,
+    pasteWrapperDraft,
+    setPasteWrapperDraft,
+    pasteWrapperDirty,
+    isPasteWrapperLoading,
+    handlePasteWrapperSave,
+    pauseOnLock,
+    isPauseOnLockLoading,
+    handlePauseOnLockToggle,
+    resetDedupOnEmpty,
+    isResetDedupOnEmptyLoading,
+    handleResetDedupOnEmptyToggle,
+    ignoreOwnWindowCopies,
+    isIgnoreOwnWindowCopiesLoading,
+    handleIgnoreOwnWindowCopiesToggle,
+    sanitizeOnCapture,
+    isSanitizeOnCaptureLoading,
+    handleSanitizeOnCaptureToggle,
+    sensitivePathPatternsDraft,
+    setSensitivePathPatternsDraft,
+    isSensitivePathPatternsLoading,
+    handleSensitivePathPatternsSave,
+    handleSensitivePathPatternsReset,
+    exclusionPatternsDraft,
+    setExclusionPatternsDraft,
+    isExclusionPatternsLoading,
+    handleExclusionPatternsSave,
+    sourceBlocklistDraft,
+    setSourceBlocklistDraft,
+    isSourceBlocklistLoading,
+    handleSourceBlocklistSave,
+    blockRiskyCaptures,
+    isBlockRiskyCapturesLoading,
+    handleBlockRiskyCapturesToggle,
     openWindowShortcut,
     shortcutDraft,
     setShortcutDraft,
@@ -30,8 +87,72 @@ export const SettingsView = () => {
     setIsShortcutRecording,
     shortcutDisplay,
     shortcutDirty,
+    shortcutError,
     handleShortcutSave,
     handleShortcutClear,
+    toggleMonitoringShortcut,
+    toggleShortcutDraft,
+    setToggleShortcutDraft,
+    isToggleShortcutLoading,
+    isToggleShortcutRecording,
+    setIsToggleShortcutRecording,
+    toggleShortcutDirty,
+    toggleShortcutError,
+    handleToggleShortcutSave,
+    handleToggleShortcutClear,
+    shortcutTogglesWindow,
+    isShortcutTogglesWindowLoading,
+    handleShortcutTogglesWindowToggle,
+    rememberWindowPlacement,
+    isRememberWindowPlacementLoading,
+    handleRememberWindowPlacementToggle,
+    clipboardAutoClear,
+    isClipboardAutoClearLoading,
+    handleClipboardAutoClearChange,
+    autoUnpinDays,
+    isAutoUnpinDaysLoading,
+    handleAutoUnpinDaysChange,
+    itemTtlMinutes,
+    isItemTtlMinutesLoading,
+    handleItemTtlMinutesChange,
+    captureBurstThreshold,
+    isCaptureBurstThresholdLoading,
+    handleCaptureBurstThresholdChange,
+    broadcastBatchThreshold,
+    isBroadcastBatchThresholdLoading,
+    handleBroadcastBatchThresholdChange,
+    pollInterval,
+    isPollIntervalLoading,
+    handlePollIntervalChange,
+    storageLocation,
+    dbMoveDraft,
+    setDbMoveDraft,
+    isDbMoving,
+    handleMoveDb,
+    settingsJsonDraft,
+    setSettingsJsonDraft,
+    isSettingsTransferring,
+    settingsImportSummary,
+    handleExportSettings,
+    handleImportSettings,
+    historySort,
+    isHistorySortLoading,
+    handleHistorySortChange,
+    pinnedSort,
+    isPinnedSortLoading,
+    handlePinnedSortChange,
+    mergeTextWinner,
+    isMergeTextWinnerLoading,
+    handleMergeTextWinnerChange,
+    trayLeftClick,
+    isTrayLeftClickLoading,
+    handleTrayLeftClickChange,
+    trayMenu,
+    isTrayMenuLoading,
+    handleTrayMenuToggle,
+    trayRecentCount,
+    isTrayRecentCountLoading,
+    handleTrayRecentCountChange,
   } = useSettingsController();
 
   return (
@@ -82,6 +203,413 @@ export const SettingsView = () => {
         </Stack>
       </Paper>
 
+      {/* 捕获过滤设置：只记录符合条件的内容，例如浏览资料时只收集链接，而无需关闭监听 */}
+      <Paper
+        variant="outlined"
+        sx={{
+          p: 1.5,
+          borderRadius: 1.5,
+          display: "flex",
+          flexDirection: "column",
+          gap: 1,
+        }}
+      >
+        <Stack direction="row" spacing={1} alignItems="center" justifyContent="space-between">
+          <Box sx={{ minWidth: 0 }}>
+            <Typography variant="subtitle2" sx={{ fontWeight: 600 }}>
+              记录范围
+            </Typography>
+            <Typography variant="caption" sx={{ color: "text.secondary" }}>
+              不符合条件的内容不会进入历史
+            </Typography>
+          </Box>
+          <TextField
+            select
+            size="small"
+            value={captureFilterMode}
+            onChange={handleCaptureFilterModeChange}
+            disabled={isCaptureFilterLoading}
+            sx={{ minWidth: 160 }}
+          >
+            {CAPTURE_FILTER_OPTIONS.map((option) => (
+              <MenuItem key={option.value} value={option.value}>
+                {option.label}
+              </MenuItem>
+            ))}
+          </TextField>
+        </Stack>
+        {captureFilterMode === "custom_regex" ? (
+          <Stack direction="row" spacing={1} alignItems="center">
+            <TextField
+              size="small"
+              fullWidth
+              placeholder="例如：^https?://|TODO"
+              value={captureFilterPattern}
+              onChange={(event) => setCaptureFilterPattern(event.target.value)}
+              disabled={isCaptureFilterLoading}
+            />
+            <Button
+              variant="contained"
+              size="small"
+              onClick={handleCaptureFilterPatternSave}
+              disabled={isCaptureFilterLoading || !captureFilterPattern.trim()}
+            >
+              保存
+            </Button>
+          </Stack>
+        ) : null}
+      </Paper>
+
+      {/* 粘贴格式设置：复制历史条目时统一在两侧添加前缀 / 后缀，例如结尾换行或代码反引号 */}
+      <Paper
+        variant="outlined"
+        sx={{
+          p: 1.5,
+          borderRadius: 1.5,
+          display: "flex",
+          flexDirection: "column",
+          gap: 1,
+        }}
+      >
+        <Stack direction="row" spacing={1} alignItems="center" justifyContent="space-between">
+          <Box sx={{ minWidth: 0 }}>
+            <Typography variant="subtitle2" sx={{ fontWeight: 600 }}>
+              复制时添加前后缀
+            </Typography>
+            <Typography variant="caption" sx={{ color: "text.secondary" }}>
+              只影响写入剪贴板的内容，历史记录保持原文
+            </Typography>
+          </Box>
+          <Button
+            variant="contained"
+            size="small"
+            onClick={handlePasteWrapperSave}
+            disabled={isPasteWrapperLoading || !pasteWrapperDirty}
+          >
+            保存
+          </Button>
+        </Stack>
+        <Stack direction="row" spacing={1}>
+          <TextField
+            size="small"
+            fullWidth
+            multiline
+            maxRows={3}
+            label="前缀"
+            value={pasteWrapperDraft.prefix}
+            onChange={(event) =>
+              setPasteWrapperDraft((prev) => ({ ...prev, prefix: event.target.value }))
+            }
+            disabled={isPasteWrapperLoading}
+          />
+          <TextField
+            size="small"
+            fullWidth
+            multiline
+            maxRows={3}
+            label="后缀"
+            value={pasteWrapperDraft.suffix}
+            onChange={(event) =>
+              setPasteWrapperDraft((prev) => ({ ...prev, suffix: event.target.value }))
+            }
+            disabled={isPasteWrapperLoading}
+          />
+        </Stack>
+      </Paper>
+
+      {/* 锁屏暂停开关：锁屏期间不记录剪贴板，避免记录锁屏界面自动填充的内容 */}
+      <Paper
+        variant="outlined"
+        sx={{
+          p: 1.5,
+          borderRadius: 1.5,
+          display: "flex",
+          flexDirection: "column",
+          gap: 1,
+        }}
+      >
+        <Stack direction="row" spacing={1} alignItems="center" justifyContent="space-between">
+          <Box sx={{ minWidth: 0 }}>
+            <Typography variant="subtitle2" sx={{ fontWeight: 600 }}>
+              锁屏时暂停记录
+            </Typography>
+            <Typography variant="caption" sx={{ color: "text.secondary" }}>
+              锁屏期间复制的内容不会进入历史，解锁后自动恢复
+            </Typography>
+          </Box>
+          <Stack direction="row" spacing={1} alignItems="center">
+            <Switch
+              size="small"
+              checked={pauseOnLock}
+              onChange={handlePauseOnLockToggle}
+              color="secondary"
+              disabled={isPauseOnLockLoading}
+            />
+            <Typography variant="caption" sx={{ color: "text.secondary" }}>
+              {isPauseOnLockLoading ? "读取中..." : pauseOnLock ? "已开启" : "已关闭"}
+            </Typography>
+          </Stack>
+        </Stack>
+      </Paper>
+
+      {/* 清空后重新记录：剪贴板被清空后再次复制相同内容时仍计入历史 */}
+      <Paper
+        variant="outlined"
+        sx={{
+          p: 1.5,
+          borderRadius: 1.5,
+          display: "flex",
+          flexDirection: "column",
+          gap: 1,
+        }}
+      >
+        <Stack direction="row" spacing={1} alignItems="center" justifyContent="space-between">
+          <Box sx={{ minWidth: 0 }}>
+            <Typography variant="subtitle2" sx={{ fontWeight: 600 }}>
+              清空后重新记录
+            </Typography>
+            <Typography variant="caption" sx={{ color: "text.secondary" }}>
+              剪贴板被清空后，再次复制之前的内容也会计入历史
+            </Typography>
+          </Box>
+          <Stack direction="row" spacing={1} alignItems="center">
+            <Switch
+              size="small"
+              checked={resetDedupOnEmpty}
+              onChange={handleResetDedupOnEmptyToggle}
+              color="secondary"
+              disabled={isResetDedupOnEmptyLoading}
+            />
+            <Typography variant="caption" sx={{ color: "text.secondary" }}>
+              {isResetDedupOnEmptyLoading
+                ? "读取中..."
+                : resetDedupOnEmpty
+                  ? "已开启"
+                  : "已关闭"}
+            </Typography>
+          </Stack>
+        </Stack>
+      </Paper>
+
+      {/* 忽略应用内复制：在本应用窗口中选中复制的文本不写入历史 */}
+      <Paper
+        variant="outlined"
+        sx={{
+          p: 1.5,
+          borderRadius: 1.5,
+          display: "flex",
+          flexDirection: "column",
+          gap: 1,
+        }}
+      >
+        <Stack direction="row" spacing={1} alignItems="center" justifyContent="space-between">
+          <Box sx={{ minWidth: 0 }}>
+            <Typography variant="subtitle2" sx={{ fontWeight: 600 }}>
+              忽略应用内复制
+            </Typography>
+            <Typography variant="caption" sx={{ color: "text.secondary" }}>
+              在本应用窗口中选中并复制的文本不计入历史
+            </Typography>
+          </Box>
+          <Stack direction="row" spacing={1} alignItems="center">
+            <Switch
+              size="small"
+              checked={ignoreOwnWindowCopies}
+              onChange={handleIgnoreOwnWindowCopiesToggle}
+              color="secondary"
+              disabled={isIgnoreOwnWindowCopiesLoading}
+            />
+            <Typography variant="caption" sx={{ color: "text.secondary" }}>
+              {isIgnoreOwnWindowCopiesLoading
+                ? "读取中..."
+                : ignoreOwnWindowCopies
+                  ? "已开启"
+                  : "已关闭"}
+            </Typography>
+          </Stack>
+        </Stack>
+      </Paper>
+
+      {/* 清理排版字符：从文档或网页复制的弯引号、不换行空格等在捕获时换成普通字符 */}
+      <Paper
+        variant="outlined"
+        sx={{
+          p: 1.5,
+          borderRadius: 1.5,
+          display: "flex",
+          flexDirection: "column",
+          gap: 1,
+        }}
+      >
+        <Stack direction="row" spacing={1} alignItems="center" justifyContent="space-between">
+          <Box sx={{ minWidth: 0 }}>
+            <Typography variant="subtitle2" sx={{ fontWeight: 600 }}>
+              清理排版字符
+            </Typography>
+            <Typography variant="caption" sx={{ color: "text.secondary" }}>
+              捕获时把弯引号换成直引号、不换行空格换成普通空格，并去掉零宽字符
+            </Typography>
+          </Box>
+          <Stack direction="row" spacing={1} alignItems="center">
+            <Switch
+              size="small"
+              checked={sanitizeOnCapture}
+              onChange={handleSanitizeOnCaptureToggle}
+              color="secondary"
+              disabled={isSanitizeOnCaptureLoading}
+            />
+            <Typography variant="caption" sx={{ color: "text.secondary" }}>
+              {isSanitizeOnCaptureLoading ? "读取中..." : sanitizeOnCapture ? "已开启" : "已关闭"}
+            </Typography>
+          </Stack>
+        </Stack>
+      </Paper>
+
+      {/* 敏感路径提醒：复制到私钥、凭据、.env 等文件的路径时提示，可选择不写入历史 */}
+      <Paper
+        variant="outlined"
+        sx={{
+          p: 1.5,
+          borderRadius: 1.5,
+          display: "flex",
+          flexDirection: "column",
+          gap: 1,
+        }}
+      >
+        <Stack direction="row" spacing={1} alignItems="center" justifyContent="space-between">
+          <Box sx={{ minWidth: 0 }}>
+            <Typography variant="subtitle2" sx={{ fontWeight: 600 }}>
+              敏感路径提醒
+            </Typography>
+            <Typography variant="caption" sx={{ color: "text.secondary" }}>
+              复制的路径匹配下列规则时提醒，开启拦截后不写入历史
+            </Typography>
+          </Box>
+          <Stack direction="row" spacing={1} alignItems="center">
+            <Switch
+              size="small"
+              checked={blockRiskyCaptures}
+              onChange={handleBlockRiskyCapturesToggle}
+              color="secondary"
+              disabled={isBlockRiskyCapturesLoading}
+            />
+            <Typography variant="caption" sx={{ color: "text.secondary" }}>
+              {isBlockRiskyCapturesLoading ? "读取中..." : blockRiskyCaptures ? "拦截" : "仅提醒"}
+            </Typography>
+          </Stack>
+        </Stack>
+        <TextField
+          size="small"
+          fullWidth
+          multiline
+          minRows={3}
+          placeholder="每行一条正则，路径分隔符统一按 / 书写"
+          value={sensitivePathPatternsDraft}
+          onChange={(event) => setSensitivePathPatternsDraft(event.target.value)}
+          disabled={isSensitivePathPatternsLoading}
+        />
+        <Stack direction="row" spacing={1} justifyContent="flex-end">
+          <Button
+            variant="text"
+            size="small"
+            onClick={handleSensitivePathPatternsReset}
+            disabled={isSensitivePathPatternsLoading}
+          >
+            恢复默认
+          </Button>
+          <Button
+            variant="contained"
+            size="small"
+            onClick={handleSensitivePathPatternsSave}
+            disabled={isSensitivePathPatternsLoading}
+          >
+            保存
+          </Button>
+        </Stack>
+      </Paper>
+
+      {/* 排除规则：匹配任一正则的内容（如 API Key）不写入历史 */}
+      <Paper
+        variant="outlined"
+        sx={{
+          p: 1.5,
+          borderRadius: 1.5,
+          display: "flex",
+          flexDirection: "column",
+          gap: 1,
+        }}
+      >
+        <Box sx={{ minWidth: 0 }}>
+          <Typography variant="subtitle2" sx={{ fontWeight: 600 }}>
+            排除规则
+          </Typography>
+          <Typography variant="caption" sx={{ color: "text.secondary" }}>
+            复制的内容匹配任一规则时不写入历史，适合排除密钥、令牌等
+          </Typography>
+        </Box>
+        <TextField
+          size="small"
+          fullWidth
+          multiline
+          minRows={2}
+          placeholder="每行一条正则，例如 ^sk-[A-Za-z0-9]{20,}$"
+          value={exclusionPatternsDraft}
+          onChange={(event) => setExclusionPatternsDraft(event.target.value)}
+          disabled={isExclusionPatternsLoading}
+        />
+        <Stack direction="row" spacing={1} justifyContent="flex-end">
+          <Button
+            variant="contained"
+            size="small"
+            onClick={handleExclusionPatternsSave}
+            disabled={isExclusionPatternsLoading}
+          >
+            保存
+          </Button>
+        </Stack>
+      </Paper>
+
+      {/* 屏蔽来源应用：在密码管理器等应用中复制的内容不写入历史 */}
+      <Paper
+        variant="outlined"
+        sx={{
+          p: 1.5,
+          borderRadius: 1.5,
+          display: "flex",
+          flexDirection: "column",
+          gap: 1,
+        }}
+      >
+        <Box sx={{ minWidth: 0 }}>
+          <Typography variant="subtitle2" sx={{ fontWeight: 600 }}>
+            屏蔽来源应用
+          </Typography>
+          <Typography variant="caption" sx={{ color: "text.secondary" }}>
+            复制时前台是下列应用则不写入历史；Wayland 下无法识别前台应用
+          </Typography>
+        </Box>
+        <TextField
+          size="small"
+          fullWidth
+          multiline
+          minRows={2}
+          placeholder="每行一个：macOS 填 bundle id，Windows / Linux 填进程名（如 1Password.exe）"
+          value={sourceBlocklistDraft}
+          onChange={(event) => setSourceBlocklistDraft(event.target.value)}
+          disabled={isSourceBlocklistLoading}
+        />
+        <Stack direction="row" spacing={1} justifyContent="flex-end">
+          <Button
+            variant="contained"
+            size="small"
+            onClick={handleSourceBlocklistSave}
+            disabled={isSourceBlocklistLoading}
+          >
+            保存
+          </Button>
+        </Stack>
+      </Paper>
+
       {/* 打开剪贴板窗口快捷键设置，方便用户快速唤起主窗口 */}
       <Paper
         variant="outlined"
@@ -120,7 +648,9 @@ export const SettingsView = () => {
               variant="contained"
               size="small"
               onClick={handleShortcutSave}
-              disabled={isShortcutLoading || isShortcutSaving || !shortcutDirty}
+              disabled={
+                isShortcutLoading || isShortcutSaving || !shortcutDirty || Boolean(shortcutError)
+              }
             >
               保存
             </Button>
@@ -146,13 +676,16 @@ export const SettingsView = () => {
           value={shortcutDraft}
           onChange={(event) => setShortcutDraft(event.target.value)}
           disabled={isShortcutLoading || isShortcutSaving}
+          error={Boolean(shortcutError)}
           helperText={
-            isShortcutRecording ? "请直接按下组合键，按 Esc 取消录制" : `当前生效：${shortcutDisplay}`
+            isShortcutRecording
+              ? "请直接按下组合键，按 Esc 取消录制"
+              : shortcutError || `当前生效：${shortcutDisplay}`
           }
         />
       </Paper>
 
-      {/* 剪贴板监听开关，控制后台是否持续记录 */}
+      {/* 切换剪贴板监听快捷键：在任意界面暂停或恢复记录，不必打开托盘菜单 */}
       <Paper
         variant="outlined"
         sx={{
@@ -163,29 +696,705 @@ export const SettingsView = () => {
           gap: 1,
         }}
       >
-        <Stack direction="row" spacing={1} alignItems="center" justifyContent="space-between">
+        <Stack
+          direction={{ xs: "column", md: "row" }}
+          spacing={1}
+          alignItems={{ xs: "flex-start", md: "center" }}
+          justifyContent="space-between"
+        >
           <Box sx={{ minWidth: 0 }}>
             <Typography variant="subtitle2" sx={{ fontWeight: 600 }}>
-              剪贴板监听
+              暂停 / 恢复记录快捷键
             </Typography>
             <Typography variant="caption" sx={{ color: "text.secondary" }}>
-              关闭后将不再自动记录剪贴板
+              每按一次在记录与暂停之间切换，托盘图标会同步显示当前状态
             </Typography>
           </Box>
-          <Stack direction="row" spacing={1} alignItems="center">
-            <Switch
+          <Stack direction="row" spacing={1} alignItems="center" flexWrap="wrap">
+            <Button
+              variant="outlined"
               size="small"
-              checked={isMonitoring}
-              onChange={(event) => setIsMonitoring(event.target.checked)}
-              color="secondary"
-              disabled={!isMonitoringReady}
-            />
-            <Typography variant="caption" sx={{ color: "text.secondary" }}>
-              {isMonitoringReady ? (isMonitoring ? "已开启" : "已关闭") : "读取中..."}
-            </Typography>
+              onClick={() => setIsToggleShortcutRecording((prev) => !prev)}
+              disabled={isToggleShortcutLoading}
+            >
+              {isToggleShortcutRecording ? "等待按键..." : "录制"}
+            </Button>
+            <Button
+              variant="contained"
+              size="small"
+              onClick={handleToggleShortcutSave}
+              disabled={
+                isToggleShortcutLoading || !toggleShortcutDirty || Boolean(toggleShortcutError)
+              }
+            >
+              保存
+            </Button>
+            <Button
+              variant="text"
+              size="small"
+              color="secondary"
+              onClick={handleToggleShortcutClear}
+              disabled={
+                isToggleShortcutLoading || (!toggleShortcutDraft && !toggleMonitoringShortcut)
+              }
+            >
+              清空
+            </Button>
+          </Stack>
+        </Stack>
+        <TextField
+          size="small"
+          fullWidth
+          placeholder="例如：Ctrl+Shift+P 或 Command+Shift+P"
+          value={toggleShortcutDraft}
+          onChange={(event) => setToggleShortcutDraft(event.target.value)}
+          disabled={isToggleShortcutLoading}
+          error={Boolean(toggleShortcutError)}
+          helperText={
+            isToggleShortcutRecording
+              ? "请直接按下组合键，按 Esc 取消录制"
+              : toggleShortcutError || `当前生效：${toggleMonitoringShortcut || "未设置"}`
+          }
+        />
+      </Paper>
+
+      {/* 快捷键切换窗口：主窗口已在前台时再按一次快捷键会隐藏窗口 */}
+      <Paper
+        variant="outlined"
+        sx={{
+          p: 1.5,
+          borderRadius: 1.5,
+          display: "flex",
+          flexDirection: "column",
+          gap: 1,
+        }}
+      >
+        <Stack direction="row" spacing={1} alignItems="center" justifyContent="space-between">
+          <Box sx={{ minWidth: 0 }}>
+            <Typography variant="subtitle2" sx={{ fontWeight: 600 }}>
+              快捷键切换窗口
+            </Typography>
+            <Typography variant="caption" sx={{ color: "text.secondary" }}>
+              主窗口已在前台时，再按一次快捷键会隐藏窗口
+            </Typography>
+          </Box>
+          <Stack direction="row" spacing={1} alignItems="center">
+            <Switch
+              size="small"
+              checked={shortcutTogglesWindow}
+              onChange={handleShortcutTogglesWindowToggle}
+              color="secondary"
+              disabled={isShortcutTogglesWindowLoading}
+            />
+            <Typography variant="caption" sx={{ color: "text.secondary" }}>
+              {isShortcutTogglesWindowLoading
+                ? "读取中..."
+                : shortcutTogglesWindow
+                  ? "已开启"
+                  : "已关闭"}
+            </Typography>
           </Stack>
         </Stack>
       </Paper>
+
+      {/* 窗口位置记忆设置，接入或拔出显示器后恢复对应布局下的窗口位置 */}
+      <Paper
+        variant="outlined"
+        sx={{
+          p: 1.5,
+          borderRadius: 1.5,
+          display: "flex",
+          flexDirection: "column",
+          gap: 1,
+        }}
+      >
+        <Stack direction="row" spacing={1} alignItems="center" justifyContent="space-between">
+          <Box sx={{ minWidth: 0 }}>
+            <Typography variant="subtitle2" sx={{ fontWeight: 600 }}>
+              记住窗口位置
+            </Typography>
+            <Typography variant="caption" sx={{ color: "text.secondary" }}>
+              按显示器布局分别记住主窗口位置，新的显示器布局下居中显示
+            </Typography>
+          </Box>
+          <Stack direction="row" spacing={1} alignItems="center">
+            <Switch
+              size="small"
+              checked={rememberWindowPlacement}
+              onChange={handleRememberWindowPlacementToggle}
+              color="secondary"
+              disabled={isRememberWindowPlacementLoading}
+            />
+            <Typography variant="caption" sx={{ color: "text.secondary" }}>
+              {isRememberWindowPlacementLoading
+                ? "读取中..."
+                : rememberWindowPlacement
+                  ? "已开启"
+                  : "已关闭"}
+            </Typography>
+          </Stack>
+        </Stack>
+      </Paper>
+
+      {/* 托盘左键动作设置，决定点击托盘图标时的默认行为 */}
+      <Paper
+        variant="outlined"
+        sx={{
+          p: 1.5,
+          borderRadius: 1.5,
+          display: "flex",
+          flexDirection: "column",
+          gap: 1,
+        }}
+      >
+        <Stack direction="row" spacing={1} alignItems="center" justifyContent="space-between">
+          <Box sx={{ minWidth: 0 }}>
+            <Typography variant="subtitle2" sx={{ fontWeight: 600 }}>
+              托盘左键点击
+            </Typography>
+            <Typography variant="caption" sx={{ color: "text.secondary" }}>
+              左键点击托盘图标时执行的操作
+            </Typography>
+          </Box>
+          <TextField
+            select
+            size="small"
+            value={trayLeftClick}
+            onChange={handleTrayLeftClickChange}
+            disabled={isTrayLeftClickLoading}
+            sx={{ minWidth: 160 }}
+          >
+            {TRAY_LEFT_CLICK_OPTIONS.map((option) => (
+              <MenuItem key={option.value} value={option.value}>
+                {option.label}
+              </MenuItem>
+            ))}
+          </TextField>
+        </Stack>
+      </Paper>
+
+      {/* 托盘菜单项设置，勾选的操作按勾选顺序显示在托盘菜单中 */}
+      <Paper
+        variant="outlined"
+        sx={{
+          p: 1.5,
+          borderRadius: 1.5,
+          display: "flex",
+          flexDirection: "column",
+          gap: 1,
+        }}
+      >
+        <Box sx={{ minWidth: 0 }}>
+          <Typography variant="subtitle2" sx={{ fontWeight: 600 }}>
+            托盘菜单
+          </Typography>
+          <Typography variant="caption" sx={{ color: "text.secondary" }}>
+            勾选要显示的操作，新勾选的项排在退出之前
+          </Typography>
+        </Box>
+        <Stack direction="row" flexWrap="wrap" columnGap={1.5}>
+          {TRAY_MENU_OPTIONS.map((option) => (
+            <FormControlLabel
+              key={option.value}
+              control={
+                <Checkbox
+                  size="small"
+                  checked={trayMenu.includes(option.value)}
+                  onChange={(event) => handleTrayMenuToggle(option.value, event.target.checked)}
+                  disabled={isTrayMenuLoading || option.value === "quit"}
+                />
+              }
+              label={<Typography variant="body2">{option.label}</Typography>}
+            />
+          ))}
+        </Stack>
+        <Stack direction="row" spacing={1} alignItems="center" justifyContent="space-between">
+          <Box sx={{ minWidth: 0 }}>
+            <Typography variant="body2">最近复制</Typography>
+            <Typography variant="caption" sx={{ color: "text.secondary" }}>
+              在托盘菜单中列出最近的条目，点击即可复制，固定的条目排在前面
+            </Typography>
+          </Box>
+          <TextField
+            select
+            size="small"
+            value={trayRecentCount}
+            onChange={handleTrayRecentCountChange}
+            disabled={isTrayRecentCountLoading}
+            sx={{ minWidth: 160 }}
+          >
+            {TRAY_RECENT_COUNT_OPTIONS.map((option) => (
+              <MenuItem key={option.value} value={option.value}>
+                {option.label}
+              </MenuItem>
+            ))}
+          </TextField>
+        </Stack>
+      </Paper>
+
+      {/* 整个列表排序方式设置，手动排序时新复制的内容插入到非固定条目最前面 */}
+      <Paper
+        variant="outlined"
+        sx={{
+          p: 1.5,
+          borderRadius: 1.5,
+          display: "flex",
+          flexDirection: "column",
+          gap: 1,
+        }}
+      >
+        <Stack direction="row" spacing={1} alignItems="center" justifyContent="space-between">
+          <Box sx={{ minWidth: 0 }}>
+            <Typography variant="subtitle2" sx={{ fontWeight: 600 }}>
+              历史列表排序
+            </Typography>
+            <Typography variant="caption" sx={{ color: "text.secondary" }}>
+              手动排序时再次复制不会改变条目位置，新内容出现在最前面
+            </Typography>
+          </Box>
+          <TextField
+            select
+            size="small"
+            value={historySort}
+            onChange={handleHistorySortChange}
+            disabled={isHistorySortLoading}
+            sx={{ minWidth: 160 }}
+          >
+            {HISTORY_SORT_OPTIONS.map((option) => (
+              <MenuItem key={option.value} value={option.value}>
+                {option.label}
+              </MenuItem>
+            ))}
+          </TextField>
+        </Stack>
+      </Paper>
+
+      {/* 固定区排序方式设置，决定固定条目在未手动排序时的先后顺序 */}
+      <Paper
+        variant="outlined"
+        sx={{
+          p: 1.5,
+          borderRadius: 1.5,
+          display: "flex",
+          flexDirection: "column",
+          gap: 1,
+        }}
+      >
+        <Stack direction="row" spacing={1} alignItems="center" justifyContent="space-between">
+          <Box sx={{ minWidth: 0 }}>
+            <Typography variant="subtitle2" sx={{ fontWeight: 600 }}>
+              固定条目排序
+            </Typography>
+            <Typography variant="caption" sx={{ color: "text.secondary" }}>
+              按固定时间排序时，再次复制固定内容不会改变其位置
+            </Typography>
+          </Box>
+          <TextField
+            select
+            size="small"
+            value={pinnedSort}
+            onChange={handlePinnedSortChange}
+            disabled={isPinnedSortLoading}
+            sx={{ minWidth: 160 }}
+          >
+            {PINNED_SORT_OPTIONS.map((option) => (
+              <MenuItem key={option.value} value={option.value}>
+                {option.label}
+              </MenuItem>
+            ))}
+          </TextField>
+        </Stack>
+      </Paper>
+
+      {/* 编辑合并设置：编辑后的文本与已有条目重复时，决定保留哪一条（计数与标签都会合并） */}
+      <Paper
+        variant="outlined"
+        sx={{
+          p: 1.5,
+          borderRadius: 1.5,
+          display: "flex",
+          flexDirection: "column",
+          gap: 1,
+        }}
+      >
+        <Stack direction="row" spacing={1} alignItems="center" justifyContent="space-between">
+          <Box sx={{ minWidth: 0 }}>
+            <Typography variant="subtitle2" sx={{ fontWeight: 600 }}>
+              编辑后内容重复时
+            </Typography>
+            <Typography variant="caption" sx={{ color: "text.secondary" }}>
+              两条记录会合并为一条，复制次数与标签都会保留
+            </Typography>
+          </Box>
+          <TextField
+            select
+            size="small"
+            value={mergeTextWinner}
+            onChange={handleMergeTextWinnerChange}
+            disabled={isMergeTextWinnerLoading}
+            sx={{ minWidth: 160 }}
+          >
+            {MERGE_TEXT_WINNER_OPTIONS.map((option) => (
+              <MenuItem key={option.value} value={option.value}>
+                {option.label}
+              </MenuItem>
+            ))}
+          </TextField>
+        </Stack>
+      </Paper>
+
+      {/* 复制后自动清空剪贴板设置，适合复制密码等敏感内容后避免残留 */}
+      <Paper
+        variant="outlined"
+        sx={{
+          p: 1.5,
+          borderRadius: 1.5,
+          display: "flex",
+          flexDirection: "column",
+          gap: 1,
+        }}
+      >
+        <Stack direction="row" spacing={1} alignItems="center" justifyContent="space-between">
+          <Box sx={{ minWidth: 0 }}>
+            <Typography variant="subtitle2" sx={{ fontWeight: 600 }}>
+              复制后自动清空
+            </Typography>
+            <Typography variant="caption" sx={{ color: "text.secondary" }}>
+              从本应用复制的内容到期后清空，期间复制了其他内容则不清空
+            </Typography>
+          </Box>
+          <TextField
+            select
+            size="small"
+            value={clipboardAutoClear}
+            onChange={handleClipboardAutoClearChange}
+            disabled={isClipboardAutoClearLoading}
+            sx={{ minWidth: 160 }}
+          >
+            {CLIPBOARD_AUTO_CLEAR_OPTIONS.map((option) => (
+              <MenuItem key={option.value} value={option.value}>
+                {option.label}
+              </MenuItem>
+            ))}
+          </TextField>
+        </Stack>
+      </Paper>
+
+      {/* 条目存活时间：临时复制的内容到期后自动删除，固定的条目不会过期 */}
+      <Paper
+        variant="outlined"
+        sx={{
+          p: 1.5,
+          borderRadius: 1.5,
+          display: "flex",
+          flexDirection: "column",
+          gap: 1,
+        }}
+      >
+        <Stack direction="row" spacing={1} alignItems="center" justifyContent="space-between">
+          <Box sx={{ minWidth: 0 }}>
+            <Typography variant="subtitle2" sx={{ fontWeight: 600 }}>
+              条目自动过期
+            </Typography>
+            <Typography variant="caption" sx={{ color: "text.secondary" }}>
+              新复制或取消固定的条目到期后自动删除，固定的条目不会过期
+            </Typography>
+          </Box>
+          <TextField
+            select
+            size="small"
+            value={itemTtlMinutes}
+            onChange={handleItemTtlMinutesChange}
+            disabled={isItemTtlMinutesLoading}
+            sx={{ minWidth: 160 }}
+          >
+            {ITEM_TTL_MINUTES_OPTIONS.map((option) => (
+              <MenuItem key={option.value} value={option.value}>
+                {option.label}
+              </MenuItem>
+            ))}
+          </TextField>
+        </Stack>
+      </Paper>
+
+      {/* 自动取消固定：临时固定的条目超过设定天数后回到普通列表，条目本身保留 */}
+      <Paper
+        variant="outlined"
+        sx={{
+          p: 1.5,
+          borderRadius: 1.5,
+          display: "flex",
+          flexDirection: "column",
+          gap: 1,
+        }}
+      >
+        <Stack direction="row" spacing={1} alignItems="center" justifyContent="space-between">
+          <Box sx={{ minWidth: 0 }}>
+            <Typography variant="subtitle2" sx={{ fontWeight: 600 }}>
+              自动取消固定
+            </Typography>
+            <Typography variant="caption" sx={{ color: "text.secondary" }}>
+              固定超过设定天数的条目自动回到普通列表，不会被删除
+            </Typography>
+          </Box>
+          <TextField
+            select
+            size="small"
+            value={autoUnpinDays}
+            onChange={handleAutoUnpinDaysChange}
+            disabled={isAutoUnpinDaysLoading}
+            sx={{ minWidth: 160 }}
+          >
+            {AUTO_UNPIN_DAYS_OPTIONS.map((option) => (
+              <MenuItem key={option.value} value={option.value}>
+                {option.label}
+              </MenuItem>
+            ))}
+          </TextField>
+        </Stack>
+      </Paper>
+
+      {/* 高频重复捕获的计数上限，避免卡住的应用反复写入同一内容刷高使用次数 */}
+      <Paper
+        variant="outlined"
+        sx={{
+          p: 1.5,
+          borderRadius: 1.5,
+          display: "flex",
+          flexDirection: "column",
+          gap: 1,
+        }}
+      >
+        <Stack direction="row" spacing={1} alignItems="center" justifyContent="space-between">
+          <Box sx={{ minWidth: 0 }}>
+            <Typography variant="subtitle2" sx={{ fontWeight: 600 }}>
+              高频重复捕获
+            </Typography>
+            <Typography variant="caption" sx={{ color: "text.secondary" }}>
+              同一内容 1 分钟内反复出现时，超过上限的次数不再累加
+            </Typography>
+          </Box>
+          <TextField
+            select
+            size="small"
+            value={captureBurstThreshold}
+            onChange={handleCaptureBurstThresholdChange}
+            disabled={isCaptureBurstThresholdLoading}
+            sx={{ minWidth: 160 }}
+          >
+            {CAPTURE_BURST_THRESHOLD_OPTIONS.map((option) => (
+              <MenuItem key={option.value} value={option.value}>
+                {option.label}
+              </MenuItem>
+            ))}
+          </TextField>
+        </Stack>
+      </Paper>
+
+      {/* 合并更新设置：脚本循环复制等高频写入时，按批次刷新列表，避免界面被大量更新拖慢 */}
+      <Paper
+        variant="outlined"
+        sx={{
+          p: 1.5,
+          borderRadius: 1.5,
+          display: "flex",
+          flexDirection: "column",
+          gap: 1,
+        }}
+      >
+        <Stack direction="row" spacing={1} alignItems="center" justifyContent="space-between">
+          <Box sx={{ minWidth: 0 }}>
+            <Typography variant="subtitle2" sx={{ fontWeight: 600 }}>
+              合并更新列表
+            </Typography>
+            <Typography variant="caption" sx={{ color: "text.secondary" }}>
+              10 秒内新记录超过所选数量时，改为每 2 秒合并刷新一次列表
+            </Typography>
+          </Box>
+          <TextField
+            select
+            size="small"
+            value={broadcastBatchThreshold}
+            onChange={handleBroadcastBatchThresholdChange}
+            disabled={isBroadcastBatchThresholdLoading}
+            sx={{ minWidth: 160 }}
+          >
+            {BROADCAST_BATCH_THRESHOLD_OPTIONS.map((option) => (
+              <MenuItem key={option.value} value={option.value}>
+                {option.label}
+              </MenuItem>
+            ))}
+          </TextField>
+        </Stack>
+      </Paper>
+
+      {/* 剪贴板监听开关，控制后台是否持续记录 */}
+      <Paper
+        variant="outlined"
+        sx={{
+          p: 1.5,
+          borderRadius: 1.5,
+          display: "flex",
+          flexDirection: "column",
+          gap: 1,
+        }}
+      >
+        <Stack direction="row" spacing={1} alignItems="center" justifyContent="space-between">
+          <Box sx={{ minWidth: 0 }}>
+            <Typography variant="subtitle2" sx={{ fontWeight: 600 }}>
+              剪贴板监听
+            </Typography>
+            <Typography variant="caption" sx={{ color: "text.secondary" }}>
+              关闭后将不再自动记录剪贴板
+            </Typography>
+          </Box>
+          <Stack direction="row" spacing={1} alignItems="center">
+            <Switch
+              size="small"
+              checked={isMonitoring}
+              onChange={(event) => setIsMonitoring(event.target.checked)}
+              color="secondary"
+              disabled={!isMonitoringReady}
+            />
+            <Typography variant="caption" sx={{ color: "text.secondary" }}>
+              {isMonitoringReady ? (isMonitoring ? "已开启" : "已关闭") : "读取中..."}
+            </Typography>
+          </Stack>
+        </Stack>
+      </Paper>
+
+      {/* 轮询间隔设置：调慢可省电，调快可更及时地记录复制内容 */}
+      <Paper
+        variant="outlined"
+        sx={{
+          p: 1.5,
+          borderRadius: 1.5,
+          display: "flex",
+          flexDirection: "column",
+          gap: 1,
+        }}
+      >
+        <Stack direction="row" spacing={1} alignItems="center" justifyContent="space-between">
+          <Box sx={{ minWidth: 0 }}>
+            <Typography variant="subtitle2" sx={{ fontWeight: 600 }}>
+              检查剪贴板间隔
+            </Typography>
+            <Typography variant="caption" sx={{ color: "text.secondary" }}>
+              无法检测剪贴板变化的系统（如 Linux）按该间隔检查，越长越省电
+            </Typography>
+          </Box>
+          <TextField
+            select
+            size="small"
+            value={pollInterval ?? ""}
+            onChange={handlePollIntervalChange}
+            disabled={isPollIntervalLoading || pollInterval === null}
+            sx={{ minWidth: 160 }}
+          >
+            {POLL_INTERVAL_OPTIONS.map((option) => (
+              <MenuItem key={option.value} value={option.value}>
+                {option.label}
+              </MenuItem>
+            ))}
+          </TextField>
+        </Stack>
+      </Paper>
+
+      {/* 数据存放位置，便携模式下数据库位于可执行文件旁 */}
+      <Paper
+        variant="outlined"
+        sx={{
+          p: 1.5,
+          borderRadius: 1.5,
+          display: "flex",
+          flexDirection: "column",
+          gap: 1,
+        }}
+      >
+        <Box sx={{ minWidth: 0 }}>
+          <Typography variant="subtitle2" sx={{ fontWeight: 600 }}>
+            数据存放位置
+            {storageLocation?.portable ? "（便携模式）" : ""}
+          </Typography>
+          <Typography
+            variant="caption"
+            sx={{ color: "text.secondary", wordBreak: "break-all" }}
+          >
+            {storageLocation ? storageLocation.dbPath : "读取中..."}
+          </Typography>
+        </Box>
+        {storageLocation && !storageLocation.portable ? (
+          <Stack direction="row" spacing={1} alignItems="center">
+            <TextField
+              size="small"
+              fullWidth
+              placeholder="新的目录或数据库文件路径"
+              value={dbMoveDraft}
+              onChange={(event) => setDbMoveDraft(event.target.value)}
+              disabled={isDbMoving}
+            />
+            <Button
+              variant="contained"
+              size="small"
+              onClick={handleMoveDb}
+              disabled={isDbMoving || !dbMoveDraft.trim()}
+              sx={{ flexShrink: 0 }}
+            >
+              {isDbMoving ? "迁移中..." : "迁移"}
+            </Button>
+          </Stack>
+        ) : null}
+      </Paper>
+
+      {/* 导入 / 导出配置，便于在多台设备间同步设置 */}
+      <Paper
+        variant="outlined"
+        sx={{
+          p: 1.5,
+          borderRadius: 1.5,
+          display: "flex",
+          flexDirection: "column",
+          gap: 1,
+        }}
+      >
+        <Box sx={{ minWidth: 0 }}>
+          <Typography variant="subtitle2" sx={{ fontWeight: 600 }}>
+            导入 / 导出配置
+          </Typography>
+          <Typography variant="caption" sx={{ color: "text.secondary" }}>
+            {settingsImportSummary || "导出后复制文本保存，导入时粘贴到下方；未包含的设置保持不变"}
+          </Typography>
+        </Box>
+        <TextField
+          size="small"
+          fullWidth
+          multiline
+          minRows={3}
+          maxRows={8}
+          placeholder="配置 JSON"
+          value={settingsJsonDraft}
+          onChange={(event) => setSettingsJsonDraft(event.target.value)}
+          disabled={isSettingsTransferring}
+        />
+        <Stack direction="row" spacing={1} justifyContent="flex-end">
+          <Button
+            variant="outlined"
+            size="small"
+            onClick={handleExportSettings}
+            disabled={isSettingsTransferring}
+          >
+            导出
+          </Button>
+          <Button
+            variant="contained"
+            size="small"
+            onClick={handleImportSettings}
+            disabled={isSettingsTransferring || !settingsJsonDraft.trim()}
+          >
+            {isSettingsTransferring ? "处理中..." : "导入"}
+          </Button>
+        </Stack>
+      </Paper>
     </Stack>
   );
 };